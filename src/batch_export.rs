@@ -0,0 +1,279 @@
+//! Headless batch exports driven by a jobs file (`--jobs`)
+//!
+//! Each job is exported with the same ffmpeg_export functions the GUI uses,
+//! without burned-in subtitles. Jobs run on a small pool of worker threads.
+
+use crate::ffmpeg_export;
+use serde::Deserialize;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// One entry of the jobs file
+#[derive(Debug, Deserialize)]
+pub struct Job {
+    pub input: String,
+    pub start: JobTime,
+    pub end: JobTime,
+    pub output: String,
+    /// Inferred from the output extension when missing
+    #[serde(default)]
+    pub format: Option<JobFormat>,
+}
+
+/// A job time: plain seconds, or any format accepted by `--clip-start`
+/// (HH:MM:SS:FF frame counts use the input's frame rate)
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum JobTime {
+    Seconds(f64),
+    Text(String),
+}
+
+impl JobTime {
+    /// Time in milliseconds; `fps` is the frame rate for HH:MM:SS:FF (30 when None)
+    fn to_ms(&self, fps: Option<f32>) -> Result<f32, String> {
+        match self {
+            JobTime::Seconds(secs) if *secs < 0.0 => {
+                Err(format!("Timestamp cannot be negative: {}", secs))
+            }
+            JobTime::Seconds(secs) => Ok((*secs * 1000.0) as f32),
+            JobTime::Text(text) => crate::parse_timestamp(text, fps),
+        }
+    }
+
+    fn as_text(&self) -> Option<&str> {
+        match self {
+            JobTime::Seconds(_) => None,
+            JobTime::Text(text) => Some(text),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum JobFormat {
+    Video,
+    Gif,
+    Audio,
+}
+
+impl JobFormat {
+    /// Guess the format from an output path's extension (video by default)
+    fn from_output_path(output: &str) -> Self {
+        match output_extension(output) {
+            Some(ext) if ext.eq_ignore_ascii_case("gif") => JobFormat::Gif,
+            Some(ext) if ffmpeg_export::AudioFormat::from_extension(ext).is_some() => {
+                JobFormat::Audio
+            }
+            _ => JobFormat::Video,
+        }
+    }
+}
+
+/// Extension of an output path, if it has one
+fn output_extension(output: &str) -> Option<&str> {
+    Path::new(output).extension().and_then(|ext| ext.to_str())
+}
+
+/// Audio encoding for an output path's extension (MP3 when it isn't an audio one)
+fn audio_format_for(output: &str) -> ffmpeg_export::AudioFormat {
+    output_extension(output)
+        .and_then(ffmpeg_export::AudioFormat::from_extension)
+        .unwrap_or_default()
+}
+
+/// Parse the contents of a jobs file (a JSON array of jobs)
+fn parse_jobs(json: &str) -> Result<Vec<Job>, String> {
+    serde_json::from_str(json).map_err(|e| format!("Invalid jobs file: {}", e))
+}
+
+/// Number of worker threads: the requested count, at least 1 and at most the CPU count
+fn worker_count(requested: usize, job_count: usize) -> usize {
+    let cpus = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    requested.clamp(1, cpus).min(job_count.max(1))
+}
+
+/// Export a single job
+fn run_job(job: &Job) -> Result<(), String> {
+    if !Path::new(&job.input).exists() {
+        return Err(format!("Input file not found: {}", job.input));
+    }
+    let fps = crate::timestamp_fps(Some(&job.input), &[job.start.as_text(), job.end.as_text()]);
+    let start_ms = job.start.to_ms(fps)?;
+    let end_ms = job.end.to_ms(fps)?;
+    if start_ms >= end_ms {
+        return Err(format!(
+            "start must be less than end ({} >= {})",
+            start_ms, end_ms
+        ));
+    }
+
+    let start_secs = start_ms / 1000.0;
+    let end_secs = end_ms / 1000.0;
+    let format = job
+        .format
+        .unwrap_or_else(|| JobFormat::from_output_path(&job.output));
+
+    match format {
+        JobFormat::Video => ffmpeg_export::export_clip(
+            &job.input,
+            &job.output,
+            start_secs,
+            end_secs,
+            None,
+            ffmpeg_export::SubtitleMode::None,
+            None,
+            0,
+            &ffmpeg_export::ExportOptions::default(),
+            |_| {},
+        ),
+        JobFormat::Gif => ffmpeg_export::export_gif(
+            &job.input,
+            &job.output,
+            start_secs,
+            end_secs,
+            None,
+            false,
+            None,
+            0,
+            &ffmpeg_export::GifOptions::default(),
+            |_| {},
+        ),
+        JobFormat::Audio => ffmpeg_export::export_audio(
+            &job.input,
+            &job.output,
+            start_secs,
+            end_secs,
+            None,
+            audio_format_for(&job.output),
+            &ffmpeg_export::CancelHandle::default(),
+        ),
+    }
+}
+
+/// Run every job in the jobs file, printing per-job status and a summary
+///
+/// # Returns
+/// * `Ok(true)` if every job succeeded, `Ok(false)` if any failed
+/// * `Err(String)` if the jobs file couldn't be read
+pub fn run_jobs_file(jobs_path: &str, concurrency: usize) -> Result<bool, String> {
+    let json = std::fs::read_to_string(jobs_path)
+        .map_err(|e| format!("Failed to read jobs file {}: {}", jobs_path, e))?;
+    let jobs = parse_jobs(&json)?;
+    if jobs.is_empty() {
+        println!("No jobs in {}", jobs_path);
+        return Ok(true);
+    }
+
+    let workers = worker_count(concurrency, jobs.len());
+    println!("Running {} job(s) with {} worker(s)", jobs.len(), workers);
+
+    let started = Instant::now();
+    let next_job = AtomicUsize::new(0);
+    let failures: Mutex<Vec<(usize, String)>> = Mutex::new(Vec::new());
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                loop {
+                    let index = next_job.fetch_add(1, Ordering::SeqCst);
+                    let Some(job) = jobs.get(index) else {
+                        break;
+                    };
+                    let job_started = Instant::now();
+                    match run_job(job) {
+                        Ok(()) => println!(
+                            "[{}/{}] OK     {} ({:.1}s)",
+                            index + 1,
+                            jobs.len(),
+                            job.output,
+                            job_started.elapsed().as_secs_f32()
+                        ),
+                        Err(e) => {
+                            eprintln!(
+                                "[{}/{}] FAILED {}: {}",
+                                index + 1,
+                                jobs.len(),
+                                job.output,
+                                e
+                            );
+                            if let Ok(mut failures) = failures.lock() {
+                                failures.push((index, e));
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    let mut failures = failures.into_inner().unwrap_or_default();
+    failures.sort_by_key(|(index, _)| *index);
+    println!(
+        "Finished in {:.1}s: {} succeeded, {} failed",
+        started.elapsed().as_secs_f32(),
+        jobs.len() - failures.len(),
+        failures.len()
+    );
+    for (index, error) in &failures {
+        eprintln!("  job {} ({}): {}", index + 1, jobs[*index].output, error);
+    }
+
+    Ok(failures.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffmpeg_export::AudioFormat;
+
+    #[test]
+    fn test_parse_jobs() {
+        let jobs = parse_jobs(
+            r#"[
+                {"input": "a.mkv", "start": 1.5, "end": "00:01:00", "output": "a.mp4"},
+                {"input": "b.mkv", "start": "00:10", "end": 20, "output": "b.out", "format": "gif"}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].start.to_ms(None).unwrap(), 1500.0);
+        assert_eq!(jobs[0].end.to_ms(None).unwrap(), 60000.0);
+        assert_eq!(jobs[0].format, None);
+        assert_eq!(jobs[1].format, Some(JobFormat::Gif));
+
+        assert!(parse_jobs(r#"[{"input": "a.mkv"}]"#).is_err());
+        assert!(JobTime::Seconds(-1.0).to_ms(None).is_err());
+
+        // Frame counts follow the input's frame rate
+        let frames = JobTime::Text("00:00:01:12".to_string());
+        assert_eq!(frames.to_ms(Some(24.0)).unwrap(), 1500.0);
+        assert_eq!(frames.to_ms(None).unwrap(), 1400.0);
+    }
+
+    #[test]
+    fn test_format_from_output_path() {
+        assert_eq!(JobFormat::from_output_path("clip.GIF"), JobFormat::Gif);
+        assert_eq!(JobFormat::from_output_path("clip.mp3"), JobFormat::Audio);
+        assert_eq!(JobFormat::from_output_path("clip.FLAC"), JobFormat::Audio);
+        assert_eq!(JobFormat::from_output_path("clip.m4a"), JobFormat::Audio);
+        assert_eq!(JobFormat::from_output_path("clip.mp4"), JobFormat::Video);
+        assert_eq!(JobFormat::from_output_path("clip"), JobFormat::Video);
+
+        assert_eq!(audio_format_for("clip.wav"), AudioFormat::Wav);
+        assert_eq!(audio_format_for("clip.m4a"), AudioFormat::Aac);
+        assert_eq!(audio_format_for("clip.ogg"), AudioFormat::Mp3);
+    }
+
+    #[test]
+    fn test_worker_count_bounds() {
+        assert_eq!(worker_count(0, 5), 1);
+        assert_eq!(worker_count(4, 1), 1);
+        let cpus = std::thread::available_parallelism().unwrap().get();
+        assert!(worker_count(10_000, 10_000) <= cpus);
+    }
+}
@@ -0,0 +1,265 @@
+use crate::theme::OneDarkExt;
+use crate::video_player::ClockTime;
+use crate::AppState;
+use gpui::{
+    div, prelude::*, Context, Entity, IntoElement, MouseButton, Render, ScrollHandle, Window,
+};
+use gpui_component::ActiveTheme;
+use gpui_component::input::{Input, InputState};
+
+/// Bookmarks tab listing the markers saved for the current file
+pub struct BookmarksTab {
+    rename_input: Entity<InputState>,
+    renaming: Option<usize>, // Index of the bookmark whose name is being edited
+    scroll_handle: ScrollHandle,
+}
+
+impl BookmarksTab {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let rename_input = cx.new(|cx| InputState::new(window, cx).placeholder("Bookmark name"));
+
+        Self {
+            rename_input,
+            renaming: None,
+            scroll_handle: ScrollHandle::new(),
+        }
+    }
+
+    /// Format a bookmark time as MM:SS.mmm
+    fn format_time(time_ms: u64) -> String {
+        format!(
+            "{:02}:{:02}.{:03}",
+            time_ms / 60_000,
+            (time_ms / 1000) % 60,
+            time_ms % 1000
+        )
+    }
+
+    /// Seek the video player to a bookmark
+    fn seek_to(&self, time_ms: u64, cx: &mut Context<Self>) {
+        let video_player = cx.global::<AppState>().video_player.clone();
+        if let Ok(player) = video_player.lock() {
+            let clock_time = ClockTime::from_nseconds(time_ms * 1_000_000);
+            if let Err(e) = player.seek(clock_time) {
+                crate::notify_error(cx, format!("Failed to seek to bookmark: {}", e));
+            }
+        };
+    }
+
+    /// Start editing a bookmark's name
+    fn start_rename(&mut self, idx: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(name) = cx.global::<AppState>().markers.get(idx).map(|m| m.name.clone()) else {
+            return;
+        };
+        self.rename_input.update(cx, |input, cx| {
+            input.set_value(name, window, cx);
+        });
+        self.renaming = Some(idx);
+        cx.notify();
+    }
+
+    /// Apply the edited name (empty names are ignored)
+    fn finish_rename(&mut self, cx: &mut Context<Self>) {
+        let Some(idx) = self.renaming.take() else {
+            return;
+        };
+        let name = self.rename_input.read(cx).value().trim().to_string();
+        if !name.is_empty() {
+            cx.update_global::<AppState, _>(|state, _| {
+                if let Some(marker) = state.markers.get_mut(idx) {
+                    marker.name = name;
+                }
+            });
+            crate::save_markers(cx);
+        }
+        cx.notify();
+    }
+
+    /// Remove a bookmark
+    fn remove(&mut self, idx: usize, cx: &mut Context<Self>) {
+        cx.update_global::<AppState, _>(|state, _| {
+            if idx < state.markers.len() {
+                state.markers.remove(idx);
+            }
+        });
+        crate::save_markers(cx);
+        self.renaming = None;
+        cx.notify();
+    }
+
+    /// Set the clip to run from a bookmark to the next one (or the end of the video)
+    fn clip_from(&self, idx: usize, cx: &mut Context<Self>) {
+        let app_state = cx.global::<AppState>();
+        let Some(start_ms) = app_state.markers.get(idx).map(|m| m.time_ms) else {
+            return;
+        };
+        let end_ms = match app_state.markers.get(idx + 1) {
+            Some(next) => next.time_ms,
+            None => app_state
+                .video_player
+                .lock()
+                .ok()
+                .and_then(|player| player.get_position_duration())
+                .map(|(_, duration)| duration.nseconds() / 1_000_000)
+                .unwrap_or(0),
+        };
+        if end_ms <= start_ms {
+            return;
+        }
+
+        if let Some(unified_window_entity) = app_state.unified_window_entity.clone() {
+            unified_window_entity.update(cx, |unified_window, app_cx| {
+                let controls_entity = unified_window.controls.clone();
+                controls_entity.update(app_cx, |controls, cx| {
+                    controls.set_clip_times(start_ms, end_ms, cx);
+                });
+            });
+        }
+    }
+}
+
+impl Render for BookmarksTab {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let markers = cx.global::<AppState>().markers.clone();
+
+        // The list may have changed under us (cleared from the controls, new file)
+        if self.renaming.is_some_and(|idx| idx >= markers.len()) {
+            self.renaming = None;
+        }
+        let renaming = self.renaming;
+
+        let theme = cx.theme();
+        let border_variant_color = theme.border_variant();
+        let hover_bg = theme.element_hover();
+        let text_color = theme.text();
+        let text_muted_color = theme.text_muted();
+
+        // Small text button used for the row actions
+        let action_button = move |label: &'static str| {
+            div()
+                .px_2()
+                .py_1()
+                .rounded_md()
+                .cursor_pointer()
+                .text_xs()
+                .text_color(text_muted_color)
+                .hover(move |style| style.bg(hover_bg))
+                .child(label)
+        };
+
+        div()
+            .w_full()
+            .flex_1()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .when(markers.is_empty(), |this| {
+                this.child(
+                    div()
+                        .px_2()
+                        .py_1()
+                        .text_sm()
+                        .text_color(text_muted_color)
+                        .child("No bookmarks yet. Use Add Marker to bookmark the current time."),
+                )
+            })
+            .child(
+                div()
+                    .id("bookmarks-scroll")
+                    .w_full()
+                    .flex_1()
+                    .flex()
+                    .flex_col()
+                    .overflow_y_scroll()
+                    .track_scroll(&self.scroll_handle)
+                    .children(markers.into_iter().enumerate().map(|(idx, marker)| {
+                        let time_ms = marker.time_ms;
+                        let is_renaming = renaming == Some(idx);
+
+                        div()
+                            .w_full()
+                            .flex()
+                            .flex_row()
+                            .items_center()
+                            .gap_2()
+                            .px_2()
+                            .py_1()
+                            .border_b_1()
+                            .border_color(border_variant_color)
+                            .child(
+                                div()
+                                    .cursor_pointer()
+                                    .text_xs()
+                                    .text_color(text_muted_color)
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(move |this, _, _, cx| {
+                                            this.seek_to(time_ms, cx);
+                                        }),
+                                    )
+                                    .child(Self::format_time(time_ms)),
+                            )
+                            .when(is_renaming, |this| {
+                                this.child(
+                                    div()
+                                        .flex_1()
+                                        .on_key_down(cx.listener(
+                                            |this, event: &gpui::KeyDownEvent, _, cx| {
+                                                match event.keystroke.key.as_str() {
+                                                    "enter" => this.finish_rename(cx),
+                                                    "escape" => {
+                                                        this.renaming = None;
+                                                        cx.notify();
+                                                    }
+                                                    _ => {}
+                                                }
+                                            },
+                                        ))
+                                        .child(Input::new(&self.rename_input)),
+                                )
+                            })
+                            .when(!is_renaming, |this| {
+                                this.child(
+                                    div()
+                                        .flex_1()
+                                        .cursor_pointer()
+                                        .text_sm()
+                                        .text_color(text_color)
+                                        .on_mouse_down(
+                                            MouseButton::Left,
+                                            cx.listener(move |this, _, _, cx| {
+                                                this.seek_to(time_ms, cx);
+                                            }),
+                                        )
+                                        .child(marker.name),
+                                )
+                            })
+                            .child(
+                                action_button(if is_renaming { "Save" } else { "Rename" })
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(move |this, _, window, cx| {
+                                            if is_renaming {
+                                                this.finish_rename(cx);
+                                            } else {
+                                                this.start_rename(idx, window, cx);
+                                            }
+                                        }),
+                                    ),
+                            )
+                            .child(action_button("Clip").on_mouse_down(
+                                MouseButton::Left,
+                                cx.listener(move |this, _, _, cx| {
+                                    this.clip_from(idx, cx);
+                                }),
+                            ))
+                            .child(action_button("Remove").on_mouse_down(
+                                MouseButton::Left,
+                                cx.listener(move |this, _, _, cx| {
+                                    this.remove(idx, cx);
+                                }),
+                            ))
+                    })),
+            )
+    }
+}
@@ -3,6 +3,7 @@
 //! This module handles loading and saving user preferences to a config file.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -11,6 +12,70 @@ use std::path::PathBuf;
 pub struct Config {
     /// The name of the selected theme.
     pub theme_name: Option<String>,
+    /// Lead-in/lead-out padding in milliseconds used by "Clip with padding".
+    pub clip_padding_ms: Option<u64>,
+    /// Whether to dry-run the export filter chain before a full export.
+    pub filter_preflight: Option<bool>,
+    /// Max UI refresh rate in fps (0 = uncapped).
+    pub render_fps_cap: Option<u32>,
+    /// Whether Play Clip pauses at the clip end.
+    pub stop_at_clip_end: Option<bool>,
+    /// Subtitle list row spacing ("Compact", "Normal" or "Comfortable").
+    pub subtitle_list_density: Option<String>,
+    /// Subtitle list text size ("Small", "Normal" or "Large").
+    pub subtitle_list_font_size: Option<String>,
+    /// Whether exports resize on the GPU when ffmpeg has a hardware scaler
+    /// (subtitles are still burned in on the CPU).
+    pub hardware_scaling: Option<bool>,
+    /// How long the on-screen display stays up in milliseconds (0 = off).
+    pub osd_duration_ms: Option<u64>,
+    /// Whether the on-screen display shows the time and duration.
+    pub osd_show_time: Option<bool>,
+    /// Whether the on-screen display shows the file name.
+    pub osd_show_title: Option<bool>,
+    /// Whether the on-screen display shows the playback speed.
+    pub osd_show_speed: Option<bool>,
+    /// ffmpeg executable to run (unset = `ffmpeg` from PATH).
+    pub ffmpeg_path: Option<String>,
+    /// ffprobe executable to run (unset = `ffprobe` from PATH).
+    pub ffprobe_path: Option<String>,
+    /// Default save folder per export format ("video", "gif", "audio").
+    pub export_dirs: Option<HashMap<String, String>>,
+    /// Encoding of subtitle text, e.g. "windows-1251" (unset = auto-detect).
+    pub subtitle_charset: Option<String>,
+    /// Whether the window hides its titlebar and window buttons.
+    pub borderless_window: Option<bool>,
+    /// Whether subtitles are displayed at startup (the last checkbox state).
+    pub display_subtitles: Option<bool>,
+    /// Position and size of the main window when the app was last closed.
+    pub window_bounds: Option<SavedWindowBounds>,
+}
+
+/// A window's position and size in logical pixels.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct SavedWindowBounds {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Whether the window was maximized (x/y/width/height are then its restore size).
+    #[serde(default)]
+    pub maximized: bool,
+}
+
+impl SavedWindowBounds {
+    /// Smallest saved size that is restored; anything smaller falls back to the default.
+    pub const MIN_WIDTH: f32 = 480.0;
+    pub const MIN_HEIGHT: f32 = 320.0;
+
+    /// Whether the saved bounds are sane enough to open a window with.
+    pub fn is_usable(&self) -> bool {
+        [self.x, self.y, self.width, self.height]
+            .iter()
+            .all(|v| v.is_finite())
+            && self.width >= Self::MIN_WIDTH
+            && self.height >= Self::MIN_HEIGHT
+    }
 }
 
 impl Config {
@@ -43,3 +108,81 @@ impl Config {
         Ok(())
     }
 }
+
+/// Settings remembered for a single video file, keyed by its path.
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct FileSettings {
+    /// Named bookmarks (also used as chapter markers), sorted by time.
+    #[serde(default)]
+    pub bookmarks: Vec<crate::Marker>,
+}
+
+impl FileSettings {
+    /// Get the per-file settings path, next to the main config file.
+    fn store_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|p| p.join("asve").join("files.json"))
+    }
+
+    /// Load all per-file settings, returns an empty map if not found or on error.
+    fn load_all() -> HashMap<String, FileSettings> {
+        Self::store_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Load the settings for a video file, returns default if none were saved.
+    pub fn load(video_path: &str) -> Self {
+        Self::load_all().remove(video_path).unwrap_or_default()
+    }
+
+    /// Save the settings for a video file, keeping other files' settings.
+    pub fn save(&self, video_path: &str) -> Result<(), std::io::Error> {
+        if let Some(path) = Self::store_path() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut all = Self::load_all();
+            all.insert(video_path.to_string(), self.clone());
+            let contents = serde_json::to_string_pretty(&all)?;
+            fs::write(path, contents)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saved_window_bounds_usable() {
+        let bounds = SavedWindowBounds {
+            x: -1200.0,
+            y: 40.0,
+            width: 1260.0,
+            height: 720.0,
+            maximized: false,
+        };
+        assert!(bounds.is_usable());
+        assert!(
+            !SavedWindowBounds {
+                width: 100.0,
+                ..bounds
+            }
+            .is_usable()
+        );
+        assert!(
+            !SavedWindowBounds {
+                y: f32::NAN,
+                ..bounds
+            }
+            .is_usable()
+        );
+
+        // Older settings files have no "maximized" key
+        let parsed: SavedWindowBounds =
+            serde_json::from_str(r#"{"x": 10, "y": 20, "width": 800, "height": 600}"#).unwrap();
+        assert!(!parsed.maximized);
+    }
+}
@@ -1,7 +1,14 @@
 use crate::theme::OneDarkExt;
-use gpui::{div, prelude::*, px, Context, Entity, IntoElement, MouseButton, Render, Window};
+use gpui::{
+    anchored, canvas, deferred, div, fill, img, point, prelude::*, px, relative, size, svg, Bounds,
+    Context, Corner, Entity, IntoElement, ModifiersChangedEvent, MouseButton, MouseDownEvent,
+    MouseMoveEvent, ObjectFit, Pixels, Point, Render, Window,
+};
 use gpui_component::ActiveTheme;
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::font_utils;
 use crate::video_player::ClockTime;
@@ -30,6 +37,25 @@ impl SelectItem for FontName {
     }
 }
 
+/// An audio track in the audio track selector
+#[derive(Debug, Clone, PartialEq)]
+struct AudioTrackItem {
+    id: i64, // mpv track id (1-based)
+    label: String,
+}
+
+impl SelectItem for AudioTrackItem {
+    type Value = i64;
+
+    fn title(&self) -> gpui::SharedString {
+        self.label.clone().into()
+    }
+
+    fn value(&self) -> &Self::Value {
+        &self.id
+    }
+}
+
 impl From<String> for FontName {
     fn from(s: String) -> Self {
         FontName(s)
@@ -45,32 +71,72 @@ impl From<FontName> for String {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ExportFormat {
     Video,
+    WebM,
     Gif,
     Audio,
+    ContactSheet,
+    Frame,
 }
 
 impl ExportFormat {
+    pub const ALL: [ExportFormat; 6] = [
+        ExportFormat::Video,
+        ExportFormat::WebM,
+        ExportFormat::Gif,
+        ExportFormat::Audio,
+        ExportFormat::ContactSheet,
+        ExportFormat::Frame,
+    ];
+
     fn next(&self) -> Self {
         match self {
-            ExportFormat::Video => ExportFormat::Gif,
+            ExportFormat::Video => ExportFormat::WebM,
+            ExportFormat::WebM => ExportFormat::Gif,
             ExportFormat::Gif => ExportFormat::Audio,
-            ExportFormat::Audio => ExportFormat::Video,
+            ExportFormat::Audio => ExportFormat::ContactSheet,
+            ExportFormat::ContactSheet => ExportFormat::Frame,
+            ExportFormat::Frame => ExportFormat::Video,
         }
     }
 
-    fn as_str(&self) -> &'static str {
+    /// Short name, also the key for the format's save folder in the settings
+    pub fn as_str(&self) -> &'static str {
         match self {
             ExportFormat::Video => "video",
+            ExportFormat::WebM => "webm",
             ExportFormat::Gif => "gif",
             ExportFormat::Audio => "audio",
+            ExportFormat::ContactSheet => "sheet",
+            ExportFormat::Frame => "frame",
+        }
+    }
+
+    /// Name shown in menus
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportFormat::Video => "Video",
+            ExportFormat::WebM => "WebM",
+            ExportFormat::Gif => "GIF",
+            ExportFormat::Audio => "Audio",
+            ExportFormat::ContactSheet => "Contact Sheet",
+            ExportFormat::Frame => "Frame",
         }
     }
 
-    fn file_extension(&self) -> &'static str {
+    /// Video clips encoded by `export_clip` (the codecs follow the extension)
+    fn is_video(&self) -> bool {
+        matches!(self, ExportFormat::Video | ExportFormat::WebM)
+    }
+
+    /// Suffix for the default output file name; audio follows the chosen codec
+    fn file_extension(&self, audio_format: crate::ffmpeg_export::AudioFormat) -> String {
         match self {
-            ExportFormat::Video => "_clip.mp4",
-            ExportFormat::Gif => "_clip.gif",
-            ExportFormat::Audio => "_clip.mp3",
+            ExportFormat::Video => "_clip.mp4".to_string(),
+            ExportFormat::WebM => "_clip.webm".to_string(),
+            ExportFormat::Gif => "_clip.gif".to_string(),
+            ExportFormat::Audio => format!("_clip.{}", audio_format.extension()),
+            ExportFormat::ContactSheet => "_sheet.png".to_string(),
+            ExportFormat::Frame => "_frame.png".to_string(),
         }
     }
 }
@@ -78,6 +144,10 @@ impl ExportFormat {
 /// Controls window with play/pause/stop buttons and video scrubber
 pub struct ControlsWindow {
     slider_state: Option<Entity<SliderState>>,
+    slider_step: f32, // Scrubber step in seconds the slider was built with (one frame)
+    coarse_scrub: bool, // Alt is held: scrubber drags jump in coarse steps
+    mute_while_scrubbing: bool, // Silence the audio blips while the scrubber is dragged
+    scrubbing: bool,  // A scrubber drag has muted the audio, to restore on release
     display_subtitles_enabled: bool,
     pub clip_start_input: Entity<InputState>,
     pub clip_end_input: Entity<InputState>,
@@ -89,20 +159,73 @@ pub struct ControlsWindow {
     // Subtitle styling controls
     subtitle_font_select: Entity<SelectState<Vec<FontName>>>,
     subtitle_font_size_slider: Entity<SliderState>,
+    volume_slider: Entity<SliderState>, // Playback volume (0-100)
+    adjust_sliders: Vec<(Adjustment, Entity<SliderState>)>, // Playback picture adjustments
+    adjust_open: bool, // Whether the Adjust panel is expanded
+    audio_track_select: Entity<SelectState<Vec<AudioTrackItem>>>,
+    audio_tracks_file: Option<String>, // File whose audio tracks are listed in the selector
+    chapters: Vec<(f64, String)>, // Embedded chapters of the loaded file as (start secs, title)
+    chapters_file: Option<String>, // File the chapters were read from
     subtitle_bold_enabled: bool,
     subtitle_italic_enabled: bool,
+    subtitle_background_enabled: bool,
+    subtitle_background_opacity_slider: Entity<SliderState>,
+    subtitle_title_safe_enabled: bool,
+    subtitle_title_safe_slider: Entity<SliderState>,
+    subtitle_outline_width_slider: Entity<SliderState>,
+    subtitle_color_input: Entity<InputState>, // Hex text color, applied once it's valid
+    subtitle_color_invalid: bool,             // The typed color isn't valid hex
     export_format: ExportFormat,
+    export_quality: crate::ffmpeg_export::ExportQuality, // Video export CRF/preset
+    audio_format: crate::ffmpeg_export::AudioFormat, // Codec for audio exports
+    output_scale: crate::ffmpeg_export::OutputScale, // Video export resolution
     current_position: f32,
     duration: f32,
     is_playing: bool,
     pub clip_start: Option<f32>, // stored in milliseconds
     pub clip_end: Option<f32>,   // stored in milliseconds
     is_exporting: bool,
+    export_progress: f32, // Fraction of the running export written so far
+    last_export: Option<(ExportJob, PathBuf)>, // Settings and output of the last export, for Repeat
+    export_report: Option<String>, // What the last export wrote (file, duration, size)
+    clip_queue: Vec<QueuedClip>, // Clips waiting for Export All, failed ones first
+    queue_position: Option<(usize, usize)>, // While Export All runs: (clip number, total clips)
+    exporting_clip: Option<QueuedClip>, // The queued clip being exported, put back if it fails
+    export_cancel: Option<crate::ffmpeg_export::CancelHandle>, // Stops the running export
     is_playing_clip: bool,
     clip_playback_end: Option<f32>, // milliseconds - when to stop during clip playback
     last_seek_time: Option<f32>,    // milliseconds - video time when user clicked "Play Clip"
-    last_render_time: Instant,      // For rate limiting renders to 30 FPS
-    loop_enabled: bool,             // When true, loop back to clip start instead of pausing
+    last_render_time: Instant,      // For rate limiting renders to the refresh rate cap
+    loop_enabled: bool,             // Loop exported GIFs forever
+    ab_loop_enabled: bool, // Play Clip loops back to clip start instead of pausing at the end
+    loop_file: bool,       // Playback restarts from the beginning when the file ends
+    clip_loop_seek_pending: bool, // Seeked back to clip start; position still reads past the end
+    loop_a: Option<f32>,            // milliseconds - start of the review loop (not the clip)
+    loop_b: Option<f32>,            // milliseconds - end of the review loop
+    loop_seek_pending: bool,        // Seeked back to A; position still reads past B
+    marker_name_input: Entity<InputState>, // Name for the next marker dropped at the playhead
+    embed_chapters: bool,           // When true, markers inside the clip are embedded as chapters
+    subtitle_chapters: bool, // When true, each subtitle in the clip starts a chapter
+    output_fps_input: Entity<InputState>, // Output frame rate for video exports (empty = source)
+    intro_hold_input: Entity<InputState>, // Seconds to hold the first frame of video exports
+    target_size_input: Entity<InputState>, // Two-pass target size in MB (empty = use quality)
+    sheet_cols_input: Entity<InputState>, // Contact sheet columns (empty = 4)
+    sheet_rows_input: Entity<InputState>, // Contact sheet rows (empty = 4)
+    gif_fps_input: Entity<InputState>,    // GIF frame rate (empty = 10)
+    gif_width_input: Entity<InputState>,  // GIF width in pixels (empty = 480)
+    export_gif_audio: bool, // When true, GIF exports also write the clip's audio as an mp3
+    tonemap_hdr: bool, // When true, HDR sources are tone-mapped to SDR on export
+    copy_metadata: bool, // When true, video exports keep the source's metadata tags and chapters
+    whole_captions: bool, // When true, exports widen the range so no caption is cut off
+    soft_subtitles: bool, // When true, video exports mux the subtitles as a selectable stream
+    use_gpu_encoder: bool, // When true, MP4 exports use a hardware H.264 encoder if ffmpeg has one
+    copy_metadata_selective: bool, // When true, only the title and language tags are kept
+    thumbnail_key: Option<(String, u64, u64)>, // Video path and clip range the strip was built for
+    thumbnails: Vec<Option<PathBuf>>, // Extracted strip images, None while still pending
+    thumbnail_generation: Arc<AtomicU64>, // Bumped on each range change to cancel stale jobs
+    slider_bounds: Arc<Mutex<Option<Bounds<Pixels>>>>, // Scrubber bounds from the last paint
+    hover_preview: Option<HoverPreview>, // Frame preview shown while hovering the scrubber
+    hover_generation: Arc<AtomicU64>, // Bumped on each hovered time change to debounce extraction
 }
 
 impl ControlsWindow {
@@ -156,12 +279,44 @@ impl ControlsWindow {
     pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
         // Slider will be created once we know the video duration
 
-        // Subtitle display will start as disabled (false)
+        // Subtitle display starts in the state remembered from the last session
+        let display_subtitles_enabled = cx.global::<AppState>().display_subtitles;
 
         // Create time input fields for clip start and end with mask pattern
         // Format: HH:MM:SS.mmm where 9 = digit only
         let clip_start_input = cx.new(|cx| InputState::new(window, cx).mask_pattern("99:99:99.999"));
         let clip_end_input = cx.new(|cx| InputState::new(window, cx).mask_pattern("99:99:99.999"));
+        let marker_name_input = cx.new(|cx| InputState::new(window, cx).placeholder("Marker name"));
+        let output_fps_input = cx.new(|cx| InputState::new(window, cx).placeholder("Source fps"));
+        let intro_hold_input = cx.new(|cx| InputState::new(window, cx).placeholder("Hold 0s"));
+        let target_size_input = cx.new(|cx| InputState::new(window, cx).placeholder("Target MB"));
+        let sheet_cols_input = cx.new(|cx| InputState::new(window, cx).placeholder("4 cols"));
+        let sheet_rows_input = cx.new(|cx| InputState::new(window, cx).placeholder("4 rows"));
+        let gif_fps_input = cx.new(|cx| InputState::new(window, cx).placeholder("10 fps"));
+        let gif_width_input = cx.new(|cx| InputState::new(window, cx).placeholder("480 px"));
+        let subtitle_color = cx.global::<AppState>().subtitle_settings.color.clone();
+        let subtitle_color_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("#FFFFFF")
+                .default_value(subtitle_color)
+        });
+
+        // Apply typed subtitle colors once they're valid hex; anything else is
+        // flagged and leaves the current color alone
+        cx.subscribe(&subtitle_color_input, |this, state, event: &InputEvent, cx| {
+            if let InputEvent::Change = event {
+                let value = state.read(cx).value().to_string();
+                match crate::ffmpeg_export::normalize_hex_color(&value) {
+                    Some(color) => {
+                        this.subtitle_color_invalid = false;
+                        this.set_subtitle_color(&color, cx);
+                    }
+                    None => this.subtitle_color_invalid = !value.trim().is_empty(),
+                }
+                cx.notify();
+            }
+        })
+        .detach();
 
         // Subscribe to clip start input changes
         cx.subscribe(&clip_start_input, |this, state, event: &InputEvent, cx| {
@@ -255,14 +410,112 @@ impl ControlsWindow {
                     });
                     if let Ok(player) = video_player.lock() {
                         if let Err(e) = player.set_subtitle_font(&font_name_str) {
-                            eprintln!("Failed to set subtitle font: {}", e);
+                            crate::notify_error(cx, format!("Failed to set subtitle font: {}", e));
+                        }
+                    };
+                }
+            },
+        )
+        .detach();
+
+        // Create subtitle background box opacity slider (0-100%)
+        let subtitle_background_opacity_slider = cx.new(|_cx| {
+            SliderState::new()
+                .min(0.0)
+                .max(100.0)
+                .step(5.0)
+                .default_value(60.0)
+        });
+
+        // Subscribe to background opacity changes
+        cx.subscribe(
+            &subtitle_background_opacity_slider,
+            |this, _, event: &SliderEvent, cx| {
+                let SliderEvent::Change(value) = event;
+                let opacity = value.end() as f64 / 100.0;
+                let app_state = cx.global::<AppState>();
+                let video_player = app_state.video_player.clone();
+                cx.update_global::<AppState, _>(|state, _| {
+                    state.subtitle_settings.background_opacity = opacity;
+                });
+                if this.subtitle_background_enabled {
+                    if let Ok(player) = video_player.lock() {
+                        if let Err(e) = player.set_subtitle_background(true, opacity) {
+                            crate::notify_error(
+                                cx,
+                                format!("Failed to set subtitle background opacity: {}", e),
+                            );
+                        }
+                    };
+                }
+                cx.notify();
+            },
+        )
+        .detach();
+
+        // Create title-safe inset slider (0-20% of the frame)
+        let subtitle_title_safe_slider = cx.new(|_cx| {
+            SliderState::new()
+                .min(0.0)
+                .max(20.0)
+                .step(1.0)
+                .default_value(5.0)
+        });
+
+        // Subscribe to title-safe inset changes
+        cx.subscribe(
+            &subtitle_title_safe_slider,
+            |this, _, event: &SliderEvent, cx| {
+                let SliderEvent::Change(value) = event;
+                let inset = value.end() as f64 / 100.0;
+                let app_state = cx.global::<AppState>();
+                let video_player = app_state.video_player.clone();
+                cx.update_global::<AppState, _>(|state, _| {
+                    state.subtitle_settings.title_safe_inset = inset;
+                });
+                if this.subtitle_title_safe_enabled {
+                    if let Ok(player) = video_player.lock() {
+                        if let Err(e) = player.set_subtitle_safe_area(true, inset) {
+                            crate::notify_error(
+                                cx,
+                                format!("Failed to set subtitle title-safe inset: {}", e),
+                            );
                         }
                     };
                 }
+                cx.notify();
+            },
+        )
+        .detach();
+
+        // Create subtitle outline width slider, starting from the current setting
+        let outline_width = cx.global::<AppState>().subtitle_settings.outline_width;
+        let subtitle_outline_width_slider = cx.new(|_cx| {
+            SliderState::new()
+                .min(0.0)
+                .max(MAX_SUBTITLE_OUTLINE_WIDTH)
+                .step(0.5)
+                .default_value(outline_width as f32)
+        });
+
+        // Subscribe to outline width changes
+        cx.subscribe(
+            &subtitle_outline_width_slider,
+            |_this, _, event: &SliderEvent, cx| {
+                let SliderEvent::Change(value) = event;
+                let width = value.end() as f64;
+                cx.update_global::<AppState, _>(|state, _| {
+                    state.subtitle_settings.outline_width = width;
+                });
+                apply_subtitle_outline(cx);
+                cx.notify();
             },
         )
         .detach();
 
+        // The player starts with mpv's own outline, which doesn't match the export default
+        apply_subtitle_outline(cx);
+
         // Create subtitle font size slider (20-100)
         let subtitle_font_size_slider = cx.new(|_cx| {
             SliderState::new()
@@ -285,16 +538,79 @@ impl ControlsWindow {
                 });
                 if let Ok(player) = video_player.lock() {
                     if let Err(e) = player.set_subtitle_font_size(size as f64) {
-                        eprintln!("Failed to set subtitle font size: {}", e);
+                        crate::notify_error(cx, format!("Failed to set subtitle font size: {}", e));
                     }
                 };
             },
         )
         .detach();
 
+        // Create playback volume slider (0-100), starting at the remembered volume
+        let volume = cx.global::<AppState>().volume;
+        let volume_slider = cx.new(|_cx| {
+            SliderState::new()
+                .min(0.0)
+                .max(100.0)
+                .step(1.0)
+                .default_value(volume as f32)
+        });
+
+        // Subscribe to volume changes
+        cx.subscribe(&volume_slider, |_this, _, event: &SliderEvent, cx| {
+            let SliderEvent::Change(value) = event;
+            let volume = value.end() as f64;
+            let video_player = cx.global::<AppState>().video_player.clone();
+            cx.update_global::<AppState, _>(|state, _| {
+                state.volume = volume;
+            });
+            if let Ok(player) = video_player.lock() {
+                if let Err(e) = player.set_volume(volume) {
+                    crate::notify_error(cx, format!("Failed to set volume: {}", e));
+                }
+            };
+        })
+        .detach();
+
+        // Picture adjustment sliders (-100..100), starting at the values already in use
+        let adjustments = cx.global::<AppState>().adjustments;
+        let adjust_sliders = Adjustment::ALL
+            .into_iter()
+            .map(|adjustment| {
+                let slider = cx.new(|_cx| {
+                    SliderState::new()
+                        .min(-100.0)
+                        .max(100.0)
+                        .step(1.0)
+                        .default_value(adjustment.value(&adjustments) as f32)
+                });
+                cx.subscribe(&slider, move |_this, _, event: &SliderEvent, cx| {
+                    let SliderEvent::Change(value) = event;
+                    Self::set_adjustment(adjustment, value.end() as i64, cx);
+                })
+                .detach();
+                (adjustment, slider)
+            })
+            .collect();
+
+        // Create audio track selector; it's filled once mpv has read the file's tracks
+        let audio_track_select = cx.new(|cx| SelectState::new(Vec::new(), None, window, cx));
+        cx.subscribe(
+            &audio_track_select,
+            |_this, _, event: &SelectEvent<Vec<AudioTrackItem>>, cx| {
+                if let SelectEvent::Confirm(Some(track_id)) = event {
+                    Self::select_audio_track(*track_id, cx);
+                }
+            },
+        )
+        .detach();
+
         Self {
             slider_state: None,
-            display_subtitles_enabled: false,
+            slider_step: 0.0,
+            coarse_scrub: false,
+            mute_while_scrubbing: false,
+            scrubbing: false,
+            display_subtitles_enabled,
             clip_start_input,
             clip_end_input,
             clip_start_error: false,
@@ -303,21 +619,337 @@ impl ControlsWindow {
             pending_end_value: None,
             subtitle_font_select,
             subtitle_font_size_slider,
+            volume_slider,
+            adjust_sliders,
+            adjust_open: false,
+            audio_track_select,
+            audio_tracks_file: None,
+            chapters: Vec::new(),
+            chapters_file: None,
             subtitle_bold_enabled: false,
             subtitle_italic_enabled: false,
+            subtitle_background_enabled: false,
+            subtitle_background_opacity_slider,
+            subtitle_title_safe_enabled: false,
+            subtitle_title_safe_slider,
+            subtitle_outline_width_slider,
+            subtitle_color_input,
+            subtitle_color_invalid: false,
             export_format: ExportFormat::Video,
+            export_quality: crate::ffmpeg_export::ExportQuality::default(),
+            audio_format: crate::ffmpeg_export::AudioFormat::default(),
+            output_scale: crate::ffmpeg_export::OutputScale::default(),
             current_position: 0.0,
             duration: 0.0,
             is_playing: false,
             clip_start: None,
             clip_end: None,
             is_exporting: false,
+            export_progress: 0.0,
+            last_export: None,
+            export_report: None,
+            clip_queue: Vec::new(),
+            queue_position: None,
+            exporting_clip: None,
+            export_cancel: None,
             is_playing_clip: false,
             clip_playback_end: None,
             last_seek_time: None,
             last_render_time: Instant::now(),
             loop_enabled: false,
+            ab_loop_enabled: false,
+            loop_file: false,
+            clip_loop_seek_pending: false,
+            loop_a: None,
+            loop_b: None,
+            loop_seek_pending: false,
+            marker_name_input,
+            embed_chapters: false,
+            subtitle_chapters: false,
+            output_fps_input,
+            intro_hold_input,
+            target_size_input,
+            sheet_cols_input,
+            sheet_rows_input,
+            gif_fps_input,
+            gif_width_input,
+            export_gif_audio: false,
+            tonemap_hdr: false,
+            copy_metadata: false,
+            whole_captions: false,
+            soft_subtitles: false,
+            use_gpu_encoder: false,
+            copy_metadata_selective: false,
+            thumbnail_key: None,
+            thumbnails: Vec::new(),
+            thumbnail_generation: Arc::new(AtomicU64::new(0)),
+            slider_bounds: Arc::new(Mutex::new(None)),
+            hover_preview: None,
+            hover_generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Rebuild the thumbnail strip when the clip range (or video) changes
+    ///
+    /// Extraction is debounced so dragging a bound doesn't spawn an ffmpeg per
+    /// frame, and each job checks the generation counter between frames so a
+    /// newer range supersedes it.
+    fn update_thumbnail_strip(&mut self, cx: &mut Context<Self>) {
+        let file_path = cx.global::<AppState>().file_path.clone();
+        let key = match (file_path, self.clip_start, self.clip_end) {
+            (Some(path), Some(start), Some(end)) if end > start => {
+                Some((path, start as u64, end as u64))
+            }
+            _ => None,
+        };
+
+        if key == self.thumbnail_key {
+            return;
+        }
+        self.thumbnail_key = key.clone();
+
+        let generation = self.thumbnail_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let Some((video_path, start_ms, end_ms)) = key else {
+            self.thumbnails.clear();
+            return;
+        };
+
+        let times = crate::thumbnail_strip::strip_times_ms(
+            start_ms,
+            end_ms,
+            crate::thumbnail_strip::STRIP_CELLS,
+        );
+        self.thumbnails = vec![None; times.len()];
+
+        let generation_counter = self.thumbnail_generation.clone();
+        cx.spawn(async move |this, cx| {
+            cx.background_executor()
+                .timer(Duration::from_millis(300))
+                .await;
+
+            for (idx, time_ms) in times.into_iter().enumerate() {
+                if generation_counter.load(Ordering::SeqCst) != generation {
+                    return;
+                }
+
+                let video_path = video_path.clone();
+                let result = cx
+                    .background_executor()
+                    .spawn(async move {
+                        crate::thumbnail_strip::cached_thumbnail(&video_path, time_ms)
+                    })
+                    .await;
+
+                match result {
+                    Ok(path) => {
+                        let updated = this.update(cx, |this, cx| {
+                            if this.thumbnail_generation.load(Ordering::SeqCst) == generation {
+                                if let Some(slot) = this.thumbnails.get_mut(idx) {
+                                    *slot = Some(path);
+                                }
+                                cx.notify();
+                            }
+                        });
+                        if updated.is_err() {
+                            return;
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to extract clip thumbnail: {}", e),
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Show the frame preview for the scrubber position under the mouse
+    ///
+    /// Like the thumbnail strip, extraction is debounced and superseded by
+    /// newer hovers, so sweeping across the scrubber only extracts a frame
+    /// where the mouse settles.
+    fn update_hover_preview(&mut self, mouse: Point<Pixels>, cx: &mut Context<Self>) {
+        let Some(bounds) = self.slider_bounds.lock().ok().and_then(|bounds| *bounds) else {
+            return;
+        };
+        let Some(time_ms) = hover_time_ms(
+            f32::from(mouse.x),
+            f32::from(bounds.origin.x),
+            f32::from(bounds.size.width),
+            self.duration,
+        ) else {
+            return;
+        };
+        let position = point(mouse.x - px(HOVER_PREVIEW_WIDTH / 2.0), bounds.origin.y - px(4.0));
+
+        if let Some(preview) = self.hover_preview.as_mut() {
+            if preview.time_ms == time_ms {
+                preview.position = position;
+                cx.notify();
+                return;
+            }
+        }
+
+        // Keep the previous frame up until the new one is ready
+        let thumbnail = self
+            .hover_preview
+            .take()
+            .and_then(|preview| preview.thumbnail);
+        self.hover_preview = Some(HoverPreview {
+            time_ms,
+            position,
+            thumbnail,
+        });
+        cx.notify();
+
+        let Some(video_path) = cx.global::<AppState>().file_path.clone() else {
+            return;
+        };
+        let generation = self.hover_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let generation_counter = self.hover_generation.clone();
+        cx.spawn(async move |this, cx| {
+            cx.background_executor()
+                .timer(Duration::from_millis(HOVER_PREVIEW_DEBOUNCE_MS))
+                .await;
+            if generation_counter.load(Ordering::SeqCst) != generation {
+                return;
+            }
+
+            let result = cx
+                .background_executor()
+                .spawn(async move {
+                    crate::thumbnail_strip::cached_thumbnail(&video_path, time_ms)
+                })
+                .await;
+
+            let _ = this.update(cx, |this, cx| {
+                if this.hover_generation.load(Ordering::SeqCst) != generation {
+                    return;
+                }
+                if let Some(preview) = this.hover_preview.as_mut() {
+                    // Without a frame the popup still shows the time
+                    preview.thumbnail = match result {
+                        Ok(path) => Some(path),
+                        Err(e) => {
+                            eprintln!("Failed to extract hover thumbnail: {}", e);
+                            None
+                        }
+                    };
+                    cx.notify();
+                }
+            });
+        })
+        .detach();
+    }
+
+    /// Hide the scrubber frame preview and drop any pending extraction
+    fn clear_hover_preview(&mut self, cx: &mut Context<Self>) {
+        self.hover_generation.fetch_add(1, Ordering::SeqCst);
+        if self.hover_preview.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    /// Parse the output fps input; empty or invalid values mean "use the source fps"
+    fn parse_output_fps(value: &str) -> Option<f32> {
+        value
+            .trim()
+            .parse::<f32>()
+            .ok()
+            .filter(|fps| fps.is_finite() && *fps > 0.0)
+    }
+
+    /// Parse the intro hold input; empty, zero or invalid values disable the hold
+    fn parse_intro_hold(value: &str) -> Option<f32> {
+        value
+            .trim()
+            .parse::<f32>()
+            .ok()
+            .filter(|secs| secs.is_finite() && *secs > 0.0)
+            .map(|secs| secs.min(MAX_INTRO_HOLD_SECS))
+    }
+
+    /// Parse the target size input in MB; empty, zero or invalid values encode by quality
+    fn parse_target_size(value: &str) -> Option<u32> {
+        value.trim().parse::<u32>().ok().filter(|mb| *mb > 0)
+    }
+
+    /// Parse a contact sheet rows/columns input; empty or invalid values mean 4
+    fn parse_grid_size(value: &str) -> u32 {
+        value
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|size| (1..=MAX_SHEET_GRID).contains(size))
+            .unwrap_or(4)
+    }
+
+    /// Parse a GIF frame rate or width input; empty means `default`
+    ///
+    /// Values outside `range` are an error rather than being clamped, so the
+    /// export doesn't silently come out different from what was typed.
+    fn parse_gif_setting(
+        value: &str,
+        default: u32,
+        range: std::ops::RangeInclusive<u32>,
+        name: &str,
+    ) -> Result<u32, String> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(default);
         }
+        value
+            .parse::<u32>()
+            .ok()
+            .filter(|v| range.contains(v))
+            .ok_or_else(|| {
+                format!(
+                    "GIF {} must be between {} and {}",
+                    name,
+                    range.start(),
+                    range.end()
+                )
+            })
+    }
+
+    /// GIF frame rate and width from the GIF settings inputs
+    fn gif_settings(&self, cx: &gpui::App) -> Result<(u32, u32), String> {
+        let fps = Self::parse_gif_setting(
+            &self.gif_fps_input.read(cx).value(),
+            crate::ffmpeg_export::DEFAULT_GIF_FPS,
+            crate::ffmpeg_export::GIF_FPS_RANGE,
+            "frame rate",
+        )?;
+        let width = Self::parse_gif_setting(
+            &self.gif_width_input.read(cx).value(),
+            crate::ffmpeg_export::DEFAULT_GIF_WIDTH,
+            crate::ffmpeg_export::GIF_WIDTH_RANGE,
+            "width",
+        )?;
+        Ok((fps, width))
+    }
+
+    /// Drop a named marker at the current playhead position
+    ///
+    /// Uses the marker name input if filled in, otherwise "Marker N".
+    fn add_marker(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let time_ms = (self.current_position * 1000.0) as u64;
+        let typed_name = self.marker_name_input.read(cx).value().trim().to_string();
+
+        cx.update_global::<AppState, _>(|state, _| {
+            let name = if typed_name.is_empty() {
+                format!("Marker {}", state.markers.len() + 1)
+            } else {
+                typed_name
+            };
+            // Keep markers sorted so chapters come out in order
+            let insert_at = state.markers.partition_point(|m| m.time_ms <= time_ms);
+            state.markers.insert(insert_at, crate::Marker { time_ms, name });
+        });
+        crate::save_markers(cx);
+
+        self.marker_name_input.update(cx, |input, cx| {
+            input.set_value("", window, cx);
+        });
+        cx.notify();
     }
 
     /// Handle display subtitles checkbox toggle
@@ -326,17 +958,30 @@ impl ControlsWindow {
 
         let app_state = cx.global::<AppState>();
         let video_player = app_state.video_player.clone();
-        let selected_track = app_state.selected_subtitle_track.map(|t| t as i32);
+        let selected_track = app_state.selected_subtitle_track_id();
         cx.update_global::<AppState, _>(|state, _| {
             state.display_subtitles = checked;
         });
         if let Ok(player) = video_player.lock() {
             if let Err(e) = player.set_subtitle_display(checked, selected_track) {
-                eprintln!("Failed to set subtitle display: {}", e);
+                crate::notify_error(cx, format!("Failed to set subtitle display: {}", e));
             }
         } else {
-            eprintln!("Failed to lock video player for subtitle display toggle");
+            crate::notify_error(
+                cx,
+                "Failed to lock video player for subtitle display toggle",
+            );
         };
+
+        // Remember the choice for the next launch
+        let mut config = crate::config::Config::load();
+        config.display_subtitles = Some(checked);
+        if let Err(e) = config.save() {
+            crate::notify_error(
+                cx,
+                format!("Failed to save subtitle display setting: {}", e),
+            );
+        }
         cx.notify();
     }
 
@@ -351,7 +996,7 @@ impl ControlsWindow {
         });
         if let Ok(player) = video_player.lock() {
             if let Err(e) = player.set_subtitle_bold(enabled) {
-                eprintln!("Failed to set subtitle bold: {}", e);
+                crate::notify_error(cx, format!("Failed to set subtitle bold: {}", e));
             }
         };
         cx.notify();
@@ -368,7 +1013,111 @@ impl ControlsWindow {
         });
         if let Ok(player) = video_player.lock() {
             if let Err(e) = player.set_subtitle_italic(enabled) {
-                eprintln!("Failed to set subtitle italic: {}", e);
+                crate::notify_error(cx, format!("Failed to set subtitle italic: {}", e));
+            }
+        };
+        cx.notify();
+    }
+
+    /// Handle subtitle background box checkbox toggle
+    fn toggle_subtitle_background(
+        &mut self,
+        enabled: bool,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.subtitle_background_enabled = enabled;
+
+        let app_state = cx.global::<AppState>();
+        let video_player = app_state.video_player.clone();
+        let opacity = app_state.subtitle_settings.background_opacity;
+        cx.update_global::<AppState, _>(|state, _| {
+            state.subtitle_settings.background_box = enabled;
+        });
+        if let Ok(player) = video_player.lock() {
+            if let Err(e) = player.set_subtitle_background(enabled, opacity) {
+                crate::notify_error(cx, format!("Failed to set subtitle background: {}", e));
+            }
+        };
+        cx.notify();
+    }
+
+    /// Set the subtitle text color (normalized hex) for playback and burned-in exports
+    fn set_subtitle_color(&mut self, color: &str, cx: &mut Context<Self>) {
+        cx.update_global::<AppState, _>(|state, _| {
+            state.subtitle_settings.color = color.to_string();
+        });
+        let video_player = cx.global::<AppState>().video_player.clone();
+        if let Ok(player) = video_player.lock() {
+            if let Err(e) = player.set_subtitle_color(color) {
+                crate::notify_error(cx, format!("Failed to set subtitle color: {}", e));
+            }
+        };
+        cx.notify();
+    }
+
+    /// Pick a subtitle text color swatch, showing its hex in the color field
+    fn pick_subtitle_color(&mut self, color: &str, window: &mut Window, cx: &mut Context<Self>) {
+        self.subtitle_color_input.update(cx, |input, cx| {
+            input.set_value(color.to_string(), window, cx);
+        });
+        self.subtitle_color_invalid = false;
+        self.set_subtitle_color(color, cx);
+    }
+
+    /// Move subtitles to a numpad-style position (1-9) for playback and burned-in exports
+    fn set_subtitle_alignment(&mut self, alignment: u8, cx: &mut Context<Self>) {
+        cx.update_global::<AppState, _>(|state, _| {
+            state.subtitle_settings.alignment = alignment;
+        });
+        let video_player = cx.global::<AppState>().video_player.clone();
+        if let Ok(player) = video_player.lock() {
+            if let Err(e) = player.set_subtitle_alignment(alignment) {
+                crate::notify_error(cx, format!("Failed to set subtitle alignment: {}", e));
+            }
+        };
+        cx.notify();
+    }
+
+    /// Pick the subtitle outline color (one of `SUBTITLE_OUTLINE_COLORS`)
+    fn set_subtitle_outline_color(&mut self, color: &str, cx: &mut Context<Self>) {
+        cx.update_global::<AppState, _>(|state, _| {
+            state.subtitle_settings.outline_color = color.to_string();
+        });
+        apply_subtitle_outline(cx);
+        cx.notify();
+    }
+
+    /// Handle subtitle shadow checkbox toggle
+    fn toggle_subtitle_shadow(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        cx.update_global::<AppState, _>(|state, _| {
+            state.subtitle_settings.shadow = enabled;
+        });
+        apply_subtitle_outline(cx);
+        cx.notify();
+    }
+
+    /// Handle title-safe region checkbox toggle
+    fn toggle_subtitle_title_safe(
+        &mut self,
+        enabled: bool,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.subtitle_title_safe_enabled = enabled;
+
+        let app_state = cx.global::<AppState>();
+        let video_player = app_state.video_player.clone();
+        let inset = app_state.subtitle_settings.title_safe_inset;
+        cx.update_global::<AppState, _>(|state, _| {
+            state.subtitle_settings.title_safe = enabled;
+        });
+        if let Ok(player) = video_player.lock() {
+            if let Err(e) = player.set_subtitle_safe_area(enabled, inset) {
+                crate::notify_error(
+                    cx,
+                    format!("Failed to set subtitle title-safe region: {}", e),
+                );
             }
         };
         cx.notify();
@@ -377,6 +1126,8 @@ impl ControlsWindow {
     fn update_position_from_player(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let app_state = cx.global::<AppState>();
         let video_player = app_state.video_player.clone();
+        let fps = app_state.source_video_fps;
+        let file_path = app_state.file_path.clone();
 
         if let Ok(player) = video_player.lock() {
             if let Some((position, duration)) = player.get_position_duration() {
@@ -384,36 +1135,63 @@ impl ControlsWindow {
                 self.current_position = position.nseconds() as f32 / 1_000_000_000.0;
                 self.duration = duration.nseconds() as f32 / 1_000_000_000.0;
 
-                // Create the slider if we don't have one yet and we have a valid duration
-                if self.slider_state.is_none() && self.duration > 0.0 {
+                // mpv has read the chapter list by the time the duration is known
+                if self.duration > 0.0 && self.chapters_file != file_path {
+                    self.chapters = player.list_chapters();
+                    self.chapters_file = file_path;
+                }
+
+                // Step one frame at a time so dragging lands on exact frames
+                let step = scrubber_step(fps);
+
+                // Create the slider once we have a valid duration, and again whenever the
+                // frame rate (and with it the step) changes
+                if self.duration > 0.0 && (self.slider_state.is_none() || self.slider_step != step)
+                {
                     let slider_state = cx.new(|_cx| {
                         SliderState::new()
                             .min(0.0)
                             .max(self.duration)
-                            .step(0.1)
-                            .default_value(0.0)
+                            .step(step)
+                            .default_value(self.current_position)
                     });
 
                     // Subscribe to slider events
-                    cx.subscribe(&slider_state, |_this, _, event: &SliderEvent, cx| {
-                        let SliderEvent::Change(value) = event;
-                        let position_secs = value.end();
+                    cx.subscribe_in(
+                        &slider_state,
+                        window,
+                        |this, slider, event: &SliderEvent, window, cx| {
+                            let SliderEvent::Change(value) = event;
+                            this.start_scrub_mute(cx);
 
-                        // Seek the video
-                        let app_state = cx.global::<AppState>();
-                        let video_player = app_state.video_player.clone();
+                            // Seek the video
+                            let app_state = cx.global::<AppState>();
+                            let video_player = app_state.video_player.clone();
+                            let position_secs = scrub_position(
+                                value.end(),
+                                app_state.source_video_fps,
+                                this.duration,
+                                this.coarse_scrub,
+                            );
 
-                        if let Ok(player) = video_player.lock() {
-                            let nanos = (position_secs * 1_000_000_000.0) as u64;
-                            let clock_time = ClockTime::from_nseconds(nanos);
-                            if let Err(e) = player.seek(clock_time) {
-                                eprintln!("Failed to seek: {}", e);
-                            }
-                        };
-                    })
+                            if let Ok(player) = video_player.lock() {
+                                let nanos = (position_secs * 1_000_000_000.0) as u64;
+                                let clock_time = ClockTime::from_nseconds(nanos);
+                                if let Err(e) = player.seek(clock_time) {
+                                    crate::notify_error(cx, format!("Failed to seek: {}", e));
+                                }
+                            };
+
+                            // Keep the thumb on the position actually sought to
+                            slider.update(cx, |state, cx| {
+                                state.set_value(SliderValue::Single(position_secs), window, cx);
+                            });
+                        },
+                    )
                     .detach();
 
                     self.slider_state = Some(slider_state);
+                    self.slider_step = step;
                 }
 
                 // Update slider position if it exists
@@ -423,17 +1201,352 @@ impl ControlsWindow {
                     });
                 }
             }
+
+            // Pausing can stop between frame boundaries; land exactly on the nearest
+            // frame so the time readout and the picture agree
+            let was_playing = self.is_playing;
             self.is_playing = player.is_playing();
+            if was_playing && !self.is_playing {
+                if let Some(frame_secs) = snap_to_frame(self.current_position, fps) {
+                    let nanos = (frame_secs as f64 * 1_000_000_000.0) as u64;
+                    if let Err(e) = player.seek_exact(ClockTime::from_nseconds(nanos)) {
+                        crate::notify_error(cx, format!("Failed to snap to frame: {}", e));
+                    }
+                    self.current_position = frame_secs;
+                }
+            }
         };
     }
 
-    fn format_time(seconds: f32) -> String {
-        let total_secs = seconds as u64;
+    /// Pause if playing, otherwise play (Play/Pause button and space)
+    ///
+    /// `is_playing` is left for the next frame's poll of the player, which also
+    /// snaps a pause onto a frame boundary.
+    pub fn toggle_play(&mut self, cx: &mut Context<Self>) {
+        let video_player = cx.global::<AppState>().video_player.clone();
+        if let Ok(player) = video_player.lock() {
+            if player.is_playing() {
+                if let Err(e) = player.pause() {
+                    crate::notify_error(cx, format!("Failed to pause: {}", e));
+                }
+            } else if let Err(e) = player.play() {
+                crate::notify_error(cx, format!("Failed to play: {}", e));
+            }
+        };
+        cx.notify();
+    }
+
+    /// Pause and rewind to the start of the file (Stop button)
+    ///
+    /// Unlike `VideoPlayer::stop`, the file stays loaded. Clip playback is
+    /// cancelled so the clip end check can't act on it later.
+    pub fn stop(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let video_player = cx.global::<AppState>().video_player.clone();
+        if let Ok(player) = video_player.lock() {
+            if let Err(e) = player.pause() {
+                crate::notify_error(cx, format!("Failed to pause: {}", e));
+            }
+            if let Err(e) = player.seek_exact(ClockTime::from_nseconds(0)) {
+                crate::notify_error(cx, format!("Failed to seek to start: {}", e));
+            }
+        };
+        self.is_playing = false;
+        self.is_playing_clip = false;
+        self.clip_playback_end = None;
+
+        // Show position 0 right away instead of waiting for the seek to land
+        self.current_position = 0.0;
+        if let Some(slider) = &self.slider_state {
+            slider.update(cx, |state, cx| {
+                state.set_value(SliderValue::Single(0.0), window, cx);
+            });
+        }
+        cx.notify();
+    }
+
+    /// Mute the audio for a scrubber drag, if muting while scrubbing is on
+    fn start_scrub_mute(&mut self, cx: &mut Context<Self>) {
+        if !self.mute_while_scrubbing || self.scrubbing {
+            return;
+        }
+        let video_player = cx.global::<AppState>().video_player.clone();
+        if let Ok(player) = video_player.lock() {
+            if let Err(e) = player.set_muted(true) {
+                crate::notify_error(cx, format!("Failed to mute while scrubbing: {}", e));
+                return;
+            }
+        };
+        self.scrubbing = true;
+    }
+
+    /// Restore the audio when a scrubber drag ends
+    ///
+    /// The mute state is put back to the user's own (`AppState::muted`), so a
+    /// file that was muted before the drag stays muted.
+    fn end_scrub_mute(&mut self, cx: &mut Context<Self>) {
+        if !self.scrubbing {
+            return;
+        }
+        self.scrubbing = false;
+        let app_state = cx.global::<AppState>();
+        let muted = app_state.muted;
+        let video_player = app_state.video_player.clone();
+        if let Ok(player) = video_player.lock() {
+            if let Err(e) = player.set_muted(muted) {
+                crate::notify_error(
+                    cx,
+                    format!("Failed to restore audio after scrubbing: {}", e),
+                );
+            }
+        };
+    }
+
+    /// Step one frame forward or back (playback stays paused)
+    pub fn step_frame(&mut self, forward: bool, cx: &mut Context<Self>) {
+        let video_player = cx.global::<AppState>().video_player.clone();
+        if let Ok(player) = video_player.lock() {
+            let result = if forward {
+                player.frame_step_forward()
+            } else {
+                player.frame_step_backward()
+            };
+            if let Err(e) = result {
+                crate::notify_error(cx, format!("Failed to step frame: {}", e));
+            }
+        };
+        // Frame stepping isn't clip playback; don't let the clip end check pause on it
+        self.is_playing_clip = false;
+        self.clip_playback_end = None;
+        cx.notify();
+    }
+
+    /// Turn whole-file looping on or off
+    fn set_loop_file(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        let video_player = cx.global::<AppState>().video_player.clone();
+        let result = match video_player.lock() {
+            Ok(player) => player.set_loop(enabled),
+            Err(_) => return,
+        };
+        match result {
+            Ok(()) => self.loop_file = enabled,
+            Err(e) => crate::notify_error(cx, format!("Failed to set looping: {}", e)),
+        }
+        cx.notify();
+    }
+
+    /// Turn deinterlacing on or off for playback and exports
+    fn set_deinterlace(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        let video_player = cx.global::<AppState>().video_player.clone();
+        let result = match video_player.lock() {
+            Ok(player) => player.set_deinterlace(enabled),
+            Err(_) => return,
+        };
+        match result {
+            Ok(()) => cx.update_global::<AppState, _>(|state, _| state.deinterlace = enabled),
+            Err(e) => crate::notify_error(cx, format!("Failed to set deinterlacing: {}", e)),
+        }
+        cx.notify();
+    }
+
+    /// Apply a picture adjustment to playback and remember it
+    fn set_adjustment(adjustment: Adjustment, value: i64, cx: &mut Context<Self>) {
+        cx.update_global::<AppState, _>(|state, _| {
+            adjustment.set_value(&mut state.adjustments, value);
+        });
+        let video_player = cx.global::<AppState>().video_player.clone();
+        let result = match video_player.lock() {
+            Ok(player) => adjustment.apply(&player, value),
+            Err(_) => return,
+        };
+        if let Err(e) = result {
+            crate::notify_error(cx, format!("Failed to set {}: {}", adjustment.name(), e));
+        }
+        cx.notify();
+    }
+
+    /// Put every picture adjustment back to 0
+    fn reset_adjustments(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        for (adjustment, slider) in self.adjust_sliders.clone() {
+            slider.update(cx, |state, cx| {
+                state.set_value(SliderValue::Single(0.0), window, cx);
+            });
+            Self::set_adjustment(adjustment, 0, cx);
+        }
+    }
+
+    /// Jump to the previous (`delta` < 0) or next chapter
+    fn seek_chapter(&mut self, delta: i32, cx: &mut Context<Self>) {
+        if self.chapters.is_empty() {
+            return;
+        }
+        let video_player = cx.global::<AppState>().video_player.clone();
+        if let Ok(player) = video_player.lock() {
+            if let Err(e) = player.seek_chapter(delta) {
+                crate::notify_error(cx, format!("Failed to seek to chapter: {}", e));
+            }
+        };
+        self.is_playing_clip = false;
+        self.clip_playback_end = None;
+        cx.notify();
+    }
+
+    /// Seek to the start of a chapter picked from the scrubber
+    fn seek_to_chapter_start(&mut self, start_secs: f64, cx: &mut Context<Self>) {
+        let video_player = cx.global::<AppState>().video_player.clone();
+        if let Ok(player) = video_player.lock() {
+            let nanos = (start_secs.max(0.0) * 1_000_000_000.0) as u64;
+            if let Err(e) = player.seek(ClockTime::from_nseconds(nanos)) {
+                crate::notify_error(cx, format!("Failed to seek to chapter: {}", e));
+            }
+        };
+        self.is_playing_clip = false;
+        self.clip_playback_end = None;
+        cx.notify();
+    }
+
+    /// Play an audio track (mpv track id) and use it for exports
+    fn select_audio_track(track_id: i64, cx: &mut Context<Self>) {
+        cx.update_global::<AppState, _>(|state, _| {
+            state.selected_audio_track = Some((track_id - 1).max(0) as usize);
+        });
+        let video_player = cx.global::<AppState>().video_player.clone();
+        if let Ok(player) = video_player.lock() {
+            if let Err(e) = player.set_audio_track(track_id as i32) {
+                crate::notify_error(cx, format!("Failed to set audio track: {}", e));
+            }
+        };
+    }
+
+    /// Fill the audio track selector for a newly loaded file, picking its first track
+    fn refresh_audio_tracks(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let app_state = cx.global::<AppState>();
+        let file_path = app_state.file_path.clone();
+        if file_path.is_none() || self.audio_tracks_file == file_path {
+            return;
+        }
+
+        // Empty until mpv has read the tracks (or when the file has no audio)
+        let tracks = match app_state.video_player.lock() {
+            Ok(player) => player.list_audio_tracks(),
+            Err(_) => return,
+        };
+        let Some(&(first_track_id, _)) = tracks.first() else {
+            return;
+        };
+
+        let items: Vec<AudioTrackItem> = tracks
+            .into_iter()
+            .map(|(id, label)| AudioTrackItem { id, label })
+            .collect();
+        self.audio_track_select.update(cx, |state, cx| {
+            state.set_items(items, window, cx);
+            state.set_selected_index(Some(IndexPath::new(0)), window, cx);
+        });
+        self.audio_tracks_file = file_path;
+        Self::select_audio_track(first_track_id, cx);
+    }
+
+    /// Save the frame on screen as a PNG, asking where to put it
+    fn take_screenshot(&mut self, cx: &mut Context<Self>) {
+        let app_state = cx.global::<AppState>();
+        let video_player = app_state.video_player.clone();
+        let Some(input_path) = app_state.file_path.as_ref().map(PathBuf::from) else {
+            crate::notify_error(cx, "Screenshot error: no video loaded");
+            return;
+        };
+
+        let directory = input_path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf();
+        let stem = input_path
+            .file_stem()
+            .and_then(|n| n.to_str())
+            .unwrap_or("video");
+        let default_filename = screenshot_filename(stem, self.current_position);
+        let path_receiver = cx.prompt_for_new_path(&directory, Some(&default_filename));
+
+        cx.spawn(async move |_this, cx| {
+            if let Ok(Ok(Some(output_path))) = path_receiver.await {
+                let result = match video_player.lock() {
+                    Ok(player) => player
+                        .screenshot(&output_path.to_string_lossy())
+                        .map_err(|e| e.to_string()),
+                    Err(_) => Err("Failed to lock video player".to_string()),
+                };
+                match result {
+                    Ok(()) => println!("Screenshot saved: {}", output_path.display()),
+                    Err(e) => {
+                        let _ = cx.update(|cx| {
+                            crate::notify_error(cx, format!("Failed to save screenshot: {}", e));
+                        });
+                    }
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Seek by `delta_secs` from the current position (arrow keys)
+    pub fn seek_by(&mut self, delta_secs: f64, cx: &mut Context<Self>) {
+        let video_player = cx.global::<AppState>().video_player.clone();
+        if let Ok(player) = video_player.lock() {
+            if let Err(e) = player.seek_relative(delta_secs) {
+                crate::notify_error(cx, format!("Failed to seek: {}", e));
+            }
+        };
+        // Jumping around cancels clip playback, so the clip end check can't pause later
+        self.is_playing_clip = false;
+        self.clip_playback_end = None;
+        cx.notify();
+    }
+
+    /// Handle an `a`/`b` key press for the review loop at the current position
+    pub fn press_loop_point(&mut self, point: LoopPoint, cx: &mut Context<Self>) {
+        let playhead_ms = self.current_position * 1000.0;
+        (self.loop_a, self.loop_b) = ab_loop_press(self.loop_a, self.loop_b, point, playhead_ms);
+        self.loop_seek_pending = false;
+        match (self.loop_a, self.loop_b) {
+            (Some(a), Some(b)) => println!(
+                "Looping {} - {}",
+                Self::format_time_ms(a),
+                Self::format_time_ms(b)
+            ),
+            (Some(a), None) => println!("Loop A set at {}", Self::format_time_ms(a)),
+            _ => println!("Loop cleared"),
+        }
+        cx.notify();
+    }
+
+    /// Clear the review loop
+    pub fn clear_ab_loop(&mut self, cx: &mut Context<Self>) {
+        if self.loop_a.is_some() || self.loop_b.is_some() {
+            self.loop_a = None;
+            self.loop_b = None;
+            self.loop_seek_pending = false;
+            println!("Loop cleared");
+            cx.notify();
+        }
+    }
+
+    fn format_time(seconds: f32) -> String {
+        let total_secs = seconds as u64;
         let mins = total_secs / 60;
         let secs = total_secs % 60;
         format!("{:02}:{:02}", mins, secs)
     }
 
+    /// Format a paused position with millisecond precision and a frame counter
+    /// so that each frame step produces a visible change
+    fn format_time_precise(seconds: f32, fps: f32) -> String {
+        let total_ms = (seconds * 1000.0) as u64;
+        let mins = total_ms / 60_000;
+        let secs = (total_ms / 1000) % 60;
+        let ms = total_ms % 1000;
+        let frame = (seconds * fps).round() as u64;
+        format!("{:02}:{:02}.{:03} (frame {})", mins, secs, ms, frame)
+    }
+
     fn format_time_ms(milliseconds: f32) -> String {
         let total_ms = milliseconds as u64;
         let total_secs = total_ms / 1000;
@@ -524,6 +1637,64 @@ impl ControlsWindow {
         cx.notify();
     }
 
+    /// Set the clip start to the playhead ("Set Start" button and the `i` key)
+    pub fn mark_clip_start(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let current_time_ms = self.current_position * 1000.0;
+
+        self.clip_start = Some(current_time_ms);
+
+        // Update the input field
+        let formatted = Self::format_time_ms(current_time_ms);
+        self.clip_start_input.update(cx, |input, cx| {
+            input.set_value(formatted, window, cx);
+        });
+
+        // Check if this violates the constraint and set error state
+        let has_error = self
+            .clip_end
+            .map(|end| current_time_ms >= end)
+            .unwrap_or(false);
+        self.clip_start_error = has_error;
+        if !has_error {
+            self.clip_end_error = false;
+            // Update clip_playback_end if playing
+            if self.is_playing_clip {
+                self.clip_playback_end = self.clip_end;
+            }
+        }
+
+        cx.notify();
+    }
+
+    /// Set the clip end to the playhead ("Set End" button and the `o` key)
+    pub fn mark_clip_end(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let current_time_ms = self.current_position * 1000.0;
+
+        self.clip_end = Some(current_time_ms);
+
+        // Update the input field
+        let formatted = Self::format_time_ms(current_time_ms);
+        self.clip_end_input.update(cx, |input, cx| {
+            input.set_value(formatted, window, cx);
+        });
+
+        // Check if this violates the constraint and set error state
+        let has_error = self
+            .clip_start
+            .map(|start| current_time_ms <= start)
+            .unwrap_or(false);
+        self.clip_end_error = has_error;
+        if !has_error {
+            self.clip_start_error = false;
+            // Update clip_playback_end if playing
+            if self.is_playing_clip {
+                self.clip_playback_end = Some(current_time_ms);
+            }
+        }
+
+        cx.notify();
+    }
+
     /// Check if there's a valid clip (start and end times set with start < end)
     pub fn has_valid_clip(&self, cx: &Context<Self>) -> bool {
         let start_ms = Self::parse_masked_time_ms(&self.clip_start_input.read(cx).value())
@@ -534,50 +1705,65 @@ impl ControlsWindow {
         start_ms.is_some() && end_ms.is_some() && start_ms.unwrap() < end_ms.unwrap()
     }
 
-    fn handle_export_click(&mut self, cx: &mut Context<Self>) {
+    /// Capture the clip and the export settings as they are now, with the
+    /// folder and file name the output defaults to
+    fn build_export_job(&self, cx: &mut Context<Self>) -> Option<(ExportJob, PathBuf, String)> {
         // Try to get times from input fields first, fall back to stored values
         let clip_start_ms =
-            Self::parse_masked_time_ms(&self.clip_start_input.read(cx).value())
-                .or(self.clip_start)
-                .unwrap_or_else(|| {
-                    eprintln!("Export error: clip start not set");
-                    0.0
-                });
+            Self::parse_masked_time_ms(&self.clip_start_input.read(cx).value()).or(self.clip_start);
+        let Some(clip_start_ms) = clip_start_ms else {
+            crate::notify_error(cx, "Export error: clip start not set");
+            return None;
+        };
 
-        let clip_end_ms = Self::parse_masked_time_ms(&self.clip_end_input.read(cx).value())
-            .or(self.clip_end)
-            .unwrap_or_else(|| {
-                eprintln!("Export error: clip end not set");
-                0.0
-            });
+        let clip_end_ms =
+            Self::parse_masked_time_ms(&self.clip_end_input.read(cx).value()).or(self.clip_end);
+        let Some(clip_end_ms) = clip_end_ms else {
+            crate::notify_error(cx, "Export error: clip end not set");
+            return None;
+        };
 
         if clip_start_ms >= clip_end_ms {
-            eprintln!("Export error: clip start must be before clip end");
-            return;
+            crate::notify_error(cx, "Export error: clip start must be before clip end");
+            return None;
         }
 
-        // Convert milliseconds to seconds for ffmpeg
-        let clip_start = clip_start_ms / 1000.0;
-        let clip_end = clip_end_ms / 1000.0;
+        let (gif_fps, gif_width) = match self.gif_settings(cx) {
+            Ok(settings) => settings,
+            Err(e) if self.export_format == ExportFormat::Gif => {
+                crate::notify_error(cx, e);
+                return None;
+            }
+            Err(_) => (
+                crate::ffmpeg_export::DEFAULT_GIF_FPS,
+                crate::ffmpeg_export::DEFAULT_GIF_WIDTH,
+            ),
+        };
 
         // Get the input file path from AppState
-        let app_state = cx.global::<AppState>();
-        let input_path = match &app_state.file_path {
-            Some(path) => path.clone(),
-            None => {
-                eprintln!("Export error: no input file loaded");
-                return;
-            }
+        let Some(input_path) = cx.global::<AppState>().file_path.clone() else {
+            crate::notify_error(cx, "Export error: no input file loaded");
+            return None;
         };
+        let app_state = cx.global::<AppState>();
 
         // Get the current export format
         let export_format = self.export_format;
 
-        // Generate default output filename and directory
+        // Generate default output filename and directory: the folder chosen for
+        // this format in the settings, falling back to the source's folder
         let input_path_buf = std::path::PathBuf::from(&input_path);
-        let directory = input_path_buf
-            .parent()
-            .unwrap_or_else(|| std::path::Path::new("."));
+        let directory = app_state
+            .export_dirs
+            .get(export_format.as_str())
+            .map(std::path::PathBuf::from)
+            .filter(|dir| dir.is_dir())
+            .unwrap_or_else(|| {
+                input_path_buf
+                    .parent()
+                    .unwrap_or_else(|| std::path::Path::new("."))
+                    .to_path_buf()
+            });
 
         // Use appropriate file extension based on export format
         let default_filename = input_path_buf
@@ -585,10 +1771,7 @@ impl ControlsWindow {
             .and_then(|n| n.to_str())
             .unwrap_or("video")
             .to_string()
-            + export_format.file_extension();
-
-        // Prompt for save location
-        let path_receiver = cx.prompt_for_new_path(directory, Some(&default_filename));
+            + &export_format.file_extension(self.audio_format);
 
         // Get subtitle settings from AppState
         let subtitle_settings = app_state.subtitle_settings.clone();
@@ -596,91 +1779,1170 @@ impl ControlsWindow {
         let selected_subtitle_track = app_state.selected_subtitle_track;
         let source_video_width = app_state.source_video_width;
         let loop_enabled = self.loop_enabled;
+        let export_gif_audio = self.export_gif_audio;
+        let filter_preflight = app_state.filter_preflight;
+        let hardware_scaling = app_state.hardware_scaling;
+        let audio_track = app_state.selected_audio_track;
+
+        // Tone mapping is opt-in; without it HDR footage comes out washed out
+        let tonemap_hdr = app_state.source_is_hdr && self.tonemap_hdr;
+        let warn_hdr =
+            app_state.source_is_hdr && !tonemap_hdr && export_format != ExportFormat::Audio;
+
+        // A subtitle file opened from disk is burned in (or muxed) from the file itself
+        let external_subtitle_file = app_state.unified_window_entity.as_ref().and_then(
+            |unified_window| {
+                unified_window
+                    .read(cx)
+                    .subtitles
+                    .read(cx)
+                    .selected_external_subtitle(cx)
+            },
+        );
+
+        // Encoding options for video exports; empty fields keep the source's values
+        let export_options = crate::ffmpeg_export::ExportOptions {
+            output_fps: Self::parse_output_fps(&self.output_fps_input.read(cx).value()),
+            intro_hold_secs: Self::parse_intro_hold(&self.intro_hold_input.read(cx).value()),
+            quality: self.export_quality,
+            target_size_mb: Self::parse_target_size(&self.target_size_input.read(cx).value()),
+            output_scale: self.output_scale,
+            tonemap_hdr,
+            deinterlace: app_state.deinterlace,
+            copy_metadata: match (self.copy_metadata, self.copy_metadata_selective) {
+                (false, _) => crate::ffmpeg_export::MetadataCopy::None,
+                (true, true) => crate::ffmpeg_export::MetadataCopy::TitleLanguage,
+                (true, false) => crate::ffmpeg_export::MetadataCopy::All,
+            },
+            audio_track,
+            // The opened file is read in the encoding picked for subtitles
+            subtitle_charset: external_subtitle_file
+                .as_ref()
+                .and(app_state.subtitle_charset.clone()),
+            subtitle_file: external_subtitle_file,
+            subtitle_offset_ms: app_state.subtitle_offset_ms,
+            ..Default::default()
+        };
+
+        // Grid size for contact sheets
+        let sheet_options = crate::ffmpeg_export::ContactSheetOptions {
+            cols: Self::parse_grid_size(&self.sheet_cols_input.read(cx).value()),
+            rows: Self::parse_grid_size(&self.sheet_rows_input.read(cx).value()),
+            tonemap_hdr,
+            ..Default::default()
+        };
+
+        // Subtitles edited on the Clip tab are burned in instead of the selected track
+        let custom_subtitle_srt = if app_state.custom_subtitle_mode {
+            app_state
+                .unified_window_entity
+                .as_ref()
+                .map(|unified_window| {
+                    unified_window.read(cx).subtitles.read(cx).custom_subtitle_srt(cx)
+                })
+                .filter(|srt| !srt.trim().is_empty())
+        } else {
+            None
+        };
+
+        // Subtitles as they're shown, with the timing offset applied; they decide
+        // the whole-caption bounds and the subtitle chapters
+        let subtitle_entries = if (self.whole_captions && display_subtitles)
+            || (self.subtitle_chapters && export_format.is_video())
+        {
+            let entries = match &custom_subtitle_srt {
+                Some(srt) => crate::subtitle_extractor::parse_srt(srt),
+                None => app_state
+                    .unified_window_entity
+                    .as_ref()
+                    .map(|unified_window| {
+                        unified_window.read(cx).subtitles.read(cx).subtitle_entries.clone()
+                    })
+                    .unwrap_or_default(),
+            };
+            offset_subtitle_entries(&entries, app_state.subtitle_offset_ms)
+        } else {
+            Vec::new()
+        };
+
+        // Widen the export range so burned-in captions aren't cut off mid-display
+        // (the clip bounds in the UI are left alone). A frame is taken at the clip start.
+        let (clip_start_ms, clip_end_ms) = if self.whole_captions
+            && display_subtitles
+            && !matches!(export_format, ExportFormat::Audio | ExportFormat::Frame)
+        {
+            let (start_ms, end_ms) =
+                expand_to_whole_subtitles(clip_start_ms, clip_end_ms, &subtitle_entries);
+            if (start_ms, end_ms) != (clip_start_ms, clip_end_ms) {
+                println!(
+                    "Exporting {} - {} to include whole captions (clip is {} - {})",
+                    Self::format_time_ms(start_ms),
+                    Self::format_time_ms(end_ms),
+                    Self::format_time_ms(clip_start_ms),
+                    Self::format_time_ms(clip_end_ms)
+                );
+            }
+            (start_ms, end_ms)
+        } else {
+            (clip_start_ms, clip_end_ms)
+        };
+
+        // Convert milliseconds to seconds for ffmpeg
+        let clip_start = clip_start_ms / 1000.0;
+        let clip_end = clip_end_ms / 1000.0;
+
+        // Markers and/or subtitles inside the clip become chapters (video exports only)
+        let mut chapter_markers = Vec::new();
+        if export_format.is_video() {
+            if self.embed_chapters {
+                chapter_markers.extend(app_state.markers.iter().cloned());
+            }
+            // Keeping the metadata keeps the source's chapters that fall inside the clip
+            if self.copy_metadata && !self.copy_metadata_selective {
+                chapter_markers.extend(crate::ffmpeg_export::source_chapter_markers(
+                    &self.chapters,
+                    clip_start_ms as u64,
+                    clip_end_ms as u64,
+                ));
+            }
+            if self.subtitle_chapters {
+                chapter_markers.extend(crate::ffmpeg_export::subtitle_chapter_markers(
+                    &subtitle_entries,
+                    clip_start_ms as u64,
+                    clip_end_ms as u64,
+                ));
+            }
+            chapter_markers.sort_by_key(|marker: &crate::Marker| marker.time_ms);
+        }
+        let hold_ms = (export_options.intro_hold_secs.unwrap_or(0.0) * 1000.0) as u64;
+        let chapters_metadata = crate::ffmpeg_export::format_chapters_metadata(
+            &chapter_markers,
+            clip_start_ms as u64,
+            clip_end_ms as u64,
+            hold_ms,
+        );
+
+        let job = ExportJob {
+            input_path,
+            export_format,
+            clip_start,
+            clip_end,
+            subtitle_settings,
+            display_subtitles,
+            soft_subtitles: self.soft_subtitles,
+            use_gpu_encoder: self.use_gpu_encoder && export_format == ExportFormat::Video,
+            selected_subtitle_track,
+            source_video_width,
+            loop_enabled,
+            gif_fps,
+            gif_width,
+            audio_format: self.audio_format,
+            export_gif_audio,
+            filter_preflight,
+            hardware_scaling,
+            audio_track,
+            tonemap_hdr,
+            export_options,
+            sheet_options,
+            custom_subtitle_srt,
+            chapters_metadata,
+        };
+
+        if warn_hdr {
+            crate::notify_error(
+                cx,
+                "Exporting HDR video without tone-mapping; colors will look washed out",
+            );
+        }
+
+        Some((job, directory, default_filename))
+    }
+
+    fn handle_export_click(&mut self, cx: &mut Context<Self>) {
+        let Some((job, directory, default_filename)) = self.build_export_job(cx) else {
+            return;
+        };
+
+        // Prompt for save location
+        let path_receiver = cx.prompt_for_new_path(&directory, Some(&default_filename));
 
         cx.spawn(async move |this, cx| {
             if let Ok(Ok(Some(output_path))) = path_receiver.await {
-                let output_path_str = output_path.to_string_lossy().to_string();
-
-                // Set exporting state
                 this.update(cx, |this, cx| {
-                    this.is_exporting = true;
-                    cx.notify();
+                    this.run_export(job, output_path, cx);
                 })
                 .ok();
+            }
+        })
+        .detach();
+    }
 
-                // Run export on background thread
-                let input_path_clone = input_path.clone();
-                let output_path_str_clone = output_path_str.clone();
-                let subtitle_settings_clone = subtitle_settings.clone();
+    /// Add the current clip, with the current export settings, to the queue
+    fn add_to_queue(&mut self, cx: &mut Context<Self>) {
+        let Some((job, directory, default_filename)) = self.build_export_job(cx) else {
+            return;
+        };
+        self.clip_queue.push(QueuedClip {
+            job,
+            default_path: directory.join(default_filename),
+            error: None,
+        });
+        cx.notify();
+    }
 
-                let export_result = cx
-                    .background_executor()
-                    .spawn(async move {
-                        match export_format {
-                            ExportFormat::Gif => {
-                                // Export as GIF with subtitle settings
-                                crate::ffmpeg_export::export_gif(
+    /// Remove a clip from the queue before it's exported
+    fn remove_from_queue(&mut self, index: usize, cx: &mut Context<Self>) {
+        if index < self.clip_queue.len() {
+            self.clip_queue.remove(index);
+            cx.notify();
+        }
+    }
+
+    /// Export every queued clip, one after another, to numbered files
+    /// (`movie_clip_1.mp4`, `movie_clip_2.mp4`, ...) without any dialogs
+    ///
+    /// Clips that failed last time are tried again.
+    fn export_all(&mut self, cx: &mut Context<Self>) {
+        if self.is_exporting || self.clip_queue.is_empty() {
+            return;
+        }
+        for clip in &mut self.clip_queue {
+            clip.error = None;
+        }
+        self.queue_position = Some((0, self.clip_queue.len()));
+        self.run_next_queued(cx);
+    }
+
+    /// Start the next queued export, or finish the queue run
+    fn run_next_queued(&mut self, cx: &mut Context<Self>) {
+        let Some((done, total)) = self.queue_position else {
+            return;
+        };
+        let Some(index) = self.clip_queue.iter().position(|clip| clip.error.is_none()) else {
+            println!(
+                "Export queue finished ({} clips, {} failed)",
+                total,
+                self.clip_queue.len()
+            );
+            self.queue_position = None;
+            cx.notify();
+            return;
+        };
+        let clip = self.clip_queue.remove(index);
+        self.exporting_clip = Some(clip.clone());
+        let number = done + 1;
+        self.queue_position = Some((number, total));
+        let output_path = queued_output_path(&clip.default_path, number, |path| path.exists());
+        self.run_export(clip.job, output_path, cx);
+    }
+
+    /// Export the last clip again with the same settings, to the next free
+    /// numbered filename (e.g. `movie_clip_2.mp4`), without any dialogs
+    pub fn repeat_last_export(&mut self, cx: &mut Context<Self>) {
+        if self.is_exporting {
+            return;
+        }
+        let Some((job, last_output_path)) = self.last_export.clone() else {
+            crate::notify_error(cx, "Nothing to repeat: no export has run yet");
+            return;
+        };
+        let output_path = next_numbered_path(&last_output_path, |path| path.exists());
+        self.run_export(job, output_path, cx);
+    }
+
+    /// Kill the running export's ffmpeg process; the partial output is deleted
+    /// once the export task sees it stop
+    pub fn cancel_export(&mut self, cx: &mut Context<Self>) {
+        if let Some(cancel) = &self.export_cancel {
+            println!("Cancelling export...");
+            cancel.cancel();
+        }
+        cx.notify();
+    }
+
+    /// Run an export in the background and report the result
+    fn run_export(&mut self, job: ExportJob, output_path: PathBuf, cx: &mut Context<Self>) {
+        self.last_export = Some((job.clone(), output_path.clone()));
+        self.is_exporting = true;
+        self.export_progress = 0.0;
+        self.export_report = None;
+        let cancel = crate::ffmpeg_export::CancelHandle::default();
+        self.export_cancel = Some(cancel.clone());
+        cx.notify();
+
+        // ffmpeg reports progress on the export thread; show it here until the export ends
+        let progress_bits = Arc::new(AtomicU32::new(0));
+        let progress_bits_for_ui = progress_bits.clone();
+        cx.spawn(async move |this, cx| {
+            loop {
+                cx.background_executor()
+                    .timer(Duration::from_millis(100))
+                    .await;
+                let still_exporting = this.update(cx, |this, cx| {
+                    if this.is_exporting {
+                        this.export_progress =
+                            f32::from_bits(progress_bits_for_ui.load(Ordering::Relaxed));
+                        cx.notify();
+                    }
+                    this.is_exporting
+                });
+                if !matches!(still_exporting, Ok(true)) {
+                    break;
+                }
+            }
+        })
+        .detach();
+        let report_progress =
+            move |fraction: f32| progress_bits.store(fraction.to_bits(), Ordering::Relaxed);
+
+        let ExportJob {
+            input_path,
+            export_format,
+            clip_start,
+            clip_end,
+            subtitle_settings,
+            display_subtitles,
+            soft_subtitles,
+            use_gpu_encoder,
+            selected_subtitle_track,
+            source_video_width,
+            loop_enabled,
+            gif_fps,
+            gif_width,
+            audio_format,
+            export_gif_audio,
+            filter_preflight,
+            hardware_scaling,
+            audio_track,
+            tonemap_hdr,
+            export_options,
+            sheet_options,
+            custom_subtitle_srt,
+            chapters_metadata,
+        } = job;
+
+        // Soft subtitles replace burning them in for video exports
+        let subtitle_mode = if soft_subtitles {
+            crate::ffmpeg_export::SubtitleMode::Soft
+        } else if display_subtitles {
+            crate::ffmpeg_export::SubtitleMode::Burn
+        } else {
+            crate::ffmpeg_export::SubtitleMode::None
+        };
+        let output_path_str = output_path.to_string_lossy().to_string();
+
+        // A held intro frame makes video exports that much longer than the clip
+        let hold_secs = match export_format {
+            ExportFormat::Video => export_options.intro_hold_secs.unwrap_or(0.0),
+            _ => 0.0,
+        };
+        let expected_duration = clip_end - clip_start + hold_secs;
+
+        cx.spawn(async move |this, cx| {
+            // Run export on background thread
+            let input_path_clone = input_path.clone();
+            let output_path_str_clone = output_path_str.clone();
+            let subtitle_settings_clone = subtitle_settings.clone();
+
+            // Companion mp3 written next to a GIF export
+            let gif_audio_path =
+                (export_format == ExportFormat::Gif && export_gif_audio).then(|| {
+                    output_path
+                        .with_extension("mp3")
+                        .to_string_lossy()
+                        .to_string()
+                });
+            let cancel_clone = cancel.clone();
+
+            // Temp files handed to ffmpeg, removed once the export is done:
+            // chapters for its ffmetadata input
+            let chapters_file = chapters_metadata.as_ref().map(|_| {
+                std::env::temp_dir().join(format!("asve_chapters_{}.txt", std::process::id()))
+            });
+            let chapters_file_clone = chapters_file.clone();
+            // Custom clip subtitles, read by the subtitles filter from a file
+            let custom_subtitles_file = custom_subtitle_srt.as_ref().map(|_| {
+                std::env::temp_dir()
+                    .join(format!("asve_custom_subtitles_{}.srt", std::process::id()))
+            });
+            let custom_subtitles_file_clone = custom_subtitles_file.clone();
+
+            let export_result = cx
+                .background_executor()
+                .spawn(async move {
+                    let chapters_path = match (chapters_metadata, chapters_file_clone) {
+                        (Some(metadata), Some(path)) => {
+                            std::fs::write(&path, metadata)
+                                .map_err(|e| format!("Failed to write chapters file: {}", e))?;
+                            Some(path.to_string_lossy().to_string())
+                        }
+                        _ => None,
+                    };
+                    // The custom subtitles are written as UTF-8
+                    let (subtitle_file, subtitle_charset) =
+                        match (custom_subtitle_srt, custom_subtitles_file_clone) {
+                            (Some(srt), Some(path)) => {
+                                std::fs::write(&path, srt).map_err(|e| {
+                                    format!("Failed to write custom subtitles file: {}", e)
+                                })?;
+                                (Some(path.to_string_lossy().to_string()), None)
+                            }
+                            _ => (
+                                export_options.subtitle_file.clone(),
+                                export_options.subtitle_charset.clone(),
+                            ),
+                        };
+                    let export_options = crate::ffmpeg_export::ExportOptions {
+                        chapters_path,
+                        subtitle_file: subtitle_file.clone(),
+                        subtitle_charset: subtitle_charset.clone(),
+                        hardware_encoder: if use_gpu_encoder {
+                            crate::ffmpeg_export::detect_hw_encoder()
+                        } else {
+                            None
+                        },
+                        cancel: cancel_clone.clone(),
+                        ..export_options
+                    };
+
+                    // Catch filter errors (bad font name, path escaping) in half a
+                    // second instead of after the whole export has run
+                    if filter_preflight {
+                        let vf_filter = match export_format {
+                            // Hardware scaling is left out: it falls back on its own
+                            ExportFormat::Gif => Some(crate::ffmpeg_export::gif_video_filter(
+                                &input_path_clone,
+                                Some(&subtitle_settings_clone),
+                                display_subtitles,
+                                selected_subtitle_track,
+                                source_video_width,
+                                &crate::ffmpeg_export::GifOptions {
+                                    fps: gif_fps,
+                                    width: gif_width,
+                                    deinterlace: export_options.deinterlace,
+                                    tonemap_hdr,
+                                    subtitle_file: subtitle_file.clone(),
+                                    subtitle_charset: subtitle_charset.clone(),
+                                    subtitle_offset_ms: export_options.subtitle_offset_ms,
+                                    ..Default::default()
+                                },
+                            )),
+                            ExportFormat::Video | ExportFormat::WebM => {
+                                let subtitle_filter = crate::ffmpeg_export::clip_subtitle_filter(
                                     &input_path_clone,
-                                    &output_path_str_clone,
-                                    clip_start,
-                                    clip_end,
-                                    if display_subtitles {
-                                        Some(&subtitle_settings_clone)
-                                    } else {
-                                        None
-                                    },
-                                    display_subtitles,
+                                    Some(&subtitle_settings_clone),
+                                    subtitle_mode == crate::ffmpeg_export::SubtitleMode::Burn,
                                     selected_subtitle_track,
                                     source_video_width,
-                                    loop_enabled,
+                                    &export_options,
+                                )?;
+                                crate::ffmpeg_export::clip_video_filter(
+                                    subtitle_filter.as_ref(),
+                                    input_path_clone.ends_with(".ts"),
+                                    &export_options,
                                 )
                             }
-                            ExportFormat::Audio => {
-                                // Audio export - no-op for now
-                                Ok(())
-                            }
-                            ExportFormat::Video => {
-                                // Export as video (MP4)
-                                crate::ffmpeg_export::export_clip(
-                                    &input_path_clone,
-                                    &output_path_str_clone,
-                                    clip_start,
-                                    clip_end,
-                                    if display_subtitles {
-                                        Some(&subtitle_settings_clone)
+                            // A single frame fails about as fast as the test would
+                            ExportFormat::Audio
+                            | ExportFormat::ContactSheet
+                            | ExportFormat::Frame => None,
+                        };
+
+                        // Nothing to test when the stream is copied without filtering
+                        if let Some(vf_filter) = vf_filter {
+                            crate::ffmpeg_export::dry_run_filter(
+                                &input_path_clone,
+                                clip_start,
+                                &vf_filter,
+                            )?;
+                        }
+                    }
+
+                    match export_format {
+                        ExportFormat::Gif => {
+                            let gif_options = crate::ffmpeg_export::GifOptions {
+                                fps: gif_fps,
+                                width: gif_width,
+                                loop_gif: loop_enabled,
+                                deinterlace: export_options.deinterlace,
+                                tonemap_hdr,
+                                hw_scaler: if hardware_scaling {
+                                    crate::ffmpeg_export::detect_hw_scaler()
+                                } else {
+                                    None
+                                },
+                                subtitle_file,
+                                subtitle_charset,
+                                subtitle_offset_ms: export_options.subtitle_offset_ms,
+                                cancel: cancel_clone,
+                            };
+
+                            // Export as GIF with subtitle settings
+                            crate::ffmpeg_export::export_gif(
+                                &input_path_clone,
+                                &output_path_str_clone,
+                                clip_start,
+                                clip_end,
+                                if display_subtitles {
+                                    Some(&subtitle_settings_clone)
+                                } else {
+                                    None
+                                },
+                                display_subtitles,
+                                selected_subtitle_track,
+                                source_video_width,
+                                &gif_options,
+                                &report_progress,
+                            )
+                        }
+                        ExportFormat::Audio => crate::ffmpeg_export::export_audio(
+                            &input_path_clone,
+                            &output_path_str_clone,
+                            clip_start,
+                            clip_end,
+                            audio_track,
+                            audio_format,
+                            &cancel_clone,
+                        ),
+                        ExportFormat::ContactSheet => crate::ffmpeg_export::export_contact_sheet(
+                            &input_path_clone,
+                            &output_path_str_clone,
+                            clip_start,
+                            clip_end,
+                            &crate::ffmpeg_export::ContactSheetOptions {
+                                cancel: cancel_clone,
+                                ..sheet_options
+                            },
+                        ),
+                        // The frame at the clip start
+                        ExportFormat::Frame => crate::ffmpeg_export::export_frame(
+                            &input_path_clone,
+                            &output_path_str_clone,
+                            clip_start,
+                            crate::ffmpeg_export::ImageFormat::from_output_path(
+                                &output_path_str_clone,
+                            ),
+                            Some(&subtitle_settings_clone),
+                            display_subtitles,
+                            selected_subtitle_track,
+                            source_video_width,
+                            &export_options,
+                        ),
+                        ExportFormat::Video | ExportFormat::WebM => {
+                            // Export as video (MP4, or VP9/Opus for WebM)
+                            crate::ffmpeg_export::export_clip(
+                                &input_path_clone,
+                                &output_path_str_clone,
+                                clip_start,
+                                clip_end,
+                                if display_subtitles {
+                                    Some(&subtitle_settings_clone)
+                                } else {
+                                    None
+                                },
+                                subtitle_mode,
+                                selected_subtitle_track,
+                                source_video_width,
+                                &crate::ffmpeg_export::ExportOptions {
+                                    hw_scaler: if hardware_scaling {
+                                        crate::ffmpeg_export::detect_hw_scaler()
                                     } else {
                                         None
                                     },
-                                    display_subtitles,
-                                    selected_subtitle_track,
-                                    source_video_width,
-                                )
+                                    ..export_options.clone()
+                                },
+                                &report_progress,
+                            )
+                        }
+                    }
+                })
+                .await;
+
+            for path in chapters_file.iter().chain(&custom_subtitles_file) {
+                remove_temp_file(path);
+            }
+
+            // Handle result and reset exporting state
+            let output_name = output_path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| output_path_str.clone());
+            let mut report = None;
+            let mut failure = None;
+            match export_result {
+                // Cancelled: remove the partial output instead of reporting a failure.
+                // ffmpeg has exited by now, so Windows no longer holds the file open.
+                _ if cancel.is_cancelled() => {
+                    for path in std::iter::once(output_path_str.clone()).chain(gif_audio_path) {
+                        if Path::new(&path).exists() {
+                            if let Err(e) = std::fs::remove_file(&path) {
+                                eprintln!("Failed to remove partial export {}: {}", path, e);
                             }
                         }
-                    })
-                    .await;
+                    }
+                    println!("Export cancelled: {}", output_path_str);
+                }
+                // A still image has no duration or streams to check
+                Ok(()) if export_format == ExportFormat::ContactSheet => {
+                    println!("Contact sheet exported: {}", output_path_str);
+                    report = Some(format!("Saved {}", output_name));
+                }
+                Ok(()) if export_format == ExportFormat::Frame => {
+                    println!("Frame exported: {}", output_path_str);
+                    report = Some(format!("Saved {}", output_name));
+                }
+                Ok(()) => {
+                    // ffmpeg can succeed and still write garbage, so check the
+                    // output's duration and streams against the source
+                    let input_path_for_probe = input_path.clone();
+                    let output_path_for_probe = output_path_str.clone();
+                    let validation = cx
+                        .background_executor()
+                        .spawn(async move {
+                            let source = crate::ffmpeg_export::probe_media(&input_path_for_probe)?;
+                            let output = crate::ffmpeg_export::probe_media(&output_path_for_probe)?;
+                            let warnings = crate::ffmpeg_export::validate_export(
+                                &output,
+                                expected_duration,
+                                export_format != ExportFormat::Audio,
+                                source.has_audio && export_format != ExportFormat::Gif,
+                            );
+                            Ok::<_, String>((output, warnings))
+                        })
+                        .await;
 
-                // Handle result and reset exporting state
-                match export_result {
-                    Ok(()) => {
-                        println!("Export completed successfully: {}", output_path_str);
+                    match validation {
+                        Ok((output, warnings)) => {
+                            println!(
+                                "Export completed successfully: {} ({:.2}s, {})",
+                                output_path_str,
+                                output.duration_secs,
+                                crate::ffmpeg_export::format_file_size(output.size_bytes)
+                            );
+                            report = Some(format!(
+                                "Saved {} ({:.2}s, {})",
+                                output_name,
+                                output.duration_secs,
+                                crate::ffmpeg_export::format_file_size(output.size_bytes)
+                            ));
+                            let _ = cx.update(|cx| {
+                                for warning in warnings {
+                                    crate::notify_error(cx, format!("Export warning: {}", warning));
+                                }
+                            });
+                        }
+                        Err(e) => {
+                            println!("Export completed: {}", output_path_str);
+                            report = Some(format!("Saved {}", output_name));
+                            let _ = cx.update(|cx| {
+                                crate::notify_error(
+                                    cx,
+                                    format!("Export warning: could not check the output: {}", e),
+                                );
+                            });
+                        }
                     }
-                    Err(e) => {
-                        eprintln!("Export failed: {}", e);
+
+                    // GIFs have no sound, so the audio is written next to it
+                    // afterwards and reported on its own
+                    if let Some(audio_path) = gif_audio_path {
+                        let input_path_for_audio = input_path.clone();
+                        let audio_path_clone = audio_path.clone();
+                        let cancel_for_audio = cancel.clone();
+                        let audio_result = cx
+                            .background_executor()
+                            .spawn(async move {
+                                crate::ffmpeg_export::export_audio(
+                                    &input_path_for_audio,
+                                    &audio_path_clone,
+                                    clip_start,
+                                    clip_end,
+                                    audio_track,
+                                    crate::ffmpeg_export::AudioFormat::Mp3,
+                                    &cancel_for_audio,
+                                )
+                            })
+                            .await;
+                        let audio_report = match audio_result {
+                            // Cancelled while writing the audio: the GIF stays
+                            _ if cancel.is_cancelled() => {
+                                if Path::new(&audio_path).exists() {
+                                    if let Err(e) = std::fs::remove_file(&audio_path) {
+                                        eprintln!(
+                                            "Failed to remove partial export {}: {}",
+                                            audio_path, e
+                                        );
+                                    }
+                                }
+                                "audio cancelled".to_string()
+                            }
+                            Ok(()) => {
+                                println!("Audio exported alongside GIF: {}", audio_path);
+                                let size = std::fs::metadata(&audio_path)
+                                    .map(|metadata| metadata.len())
+                                    .unwrap_or(0);
+                                format!(
+                                    "audio saved as {} ({})",
+                                    Path::new(&audio_path)
+                                        .file_name()
+                                        .map(|name| name.to_string_lossy().to_string())
+                                        .unwrap_or_else(|| audio_path.clone()),
+                                    crate::ffmpeg_export::format_file_size(size)
+                                )
+                            }
+                            Err(e) => {
+                                let _ = cx.update(|cx| {
+                                    crate::notify_error(cx, format!("Audio export failed: {}", e));
+                                });
+                                "audio failed".to_string()
+                            }
+                        };
+                        report = report.map(|report| format!("{}; {}", report, audio_report));
                     }
                 }
-
-                this.update(cx, |this, cx| {
-                    this.is_exporting = false;
-                    cx.notify();
-                })
-                .ok();
+                Err(e) => {
+                    let _ = cx.update(|cx| {
+                        crate::notify_error(cx, format!("Export failed: {}", e));
+                    });
+                    failure = Some(e);
+                }
             }
+
+            let cancelled = cancel.is_cancelled();
+            this.update(cx, |this, cx| {
+                this.is_exporting = false;
+                this.export_cancel = None;
+                this.export_report = report;
+                // A queued clip that failed (or was cancelled) goes back in the
+                // queue, after the ones that failed before it
+                if let Some(mut clip) = this.exporting_clip.take() {
+                    if failure.is_some() || cancelled {
+                        clip.error = failure;
+                        let at = this
+                            .clip_queue
+                            .iter()
+                            .take_while(|clip| clip.error.is_some())
+                            .count();
+                        this.clip_queue.insert(at, clip);
+                    }
+                }
+                // Cancelling stops Export All; the clips not started yet stay queued
+                if cancelled {
+                    this.queue_position = None;
+                } else {
+                    this.run_next_queued(cx);
+                }
+                cx.notify();
+            })
+            .ok();
         })
         .detach();
     }
 }
 
+/// Everything needed to run an export, captured when Export is clicked so
+/// that it can be repeated later
+#[derive(Clone)]
+struct ExportJob {
+    input_path: String,
+    export_format: ExportFormat,
+    clip_start: f32, // seconds, after any whole-caption widening
+    clip_end: f32,
+    subtitle_settings: crate::SubtitleSettings,
+    display_subtitles: bool,
+    soft_subtitles: bool,
+    use_gpu_encoder: bool,
+    selected_subtitle_track: Option<usize>,
+    source_video_width: u32,
+    loop_enabled: bool,
+    gif_fps: u32,
+    gif_width: u32,
+    audio_format: crate::ffmpeg_export::AudioFormat,
+    export_gif_audio: bool,
+    filter_preflight: bool,
+    hardware_scaling: bool,
+    audio_track: Option<usize>,
+    tonemap_hdr: bool,
+    export_options: crate::ffmpeg_export::ExportOptions,
+    sheet_options: crate::ffmpeg_export::ContactSheetOptions,
+    custom_subtitle_srt: Option<String>,
+    chapters_metadata: Option<String>,
+}
+
+/// Playback picture adjustment with a slider in the Adjust panel
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Adjustment {
+    Brightness,
+    Contrast,
+    Saturation,
+    Gamma,
+}
+
+impl Adjustment {
+    const ALL: [Adjustment; 4] = [
+        Adjustment::Brightness,
+        Adjustment::Contrast,
+        Adjustment::Saturation,
+        Adjustment::Gamma,
+    ];
+
+    /// Name shown above the slider
+    fn name(&self) -> &'static str {
+        match self {
+            Adjustment::Brightness => "Brightness",
+            Adjustment::Contrast => "Contrast",
+            Adjustment::Saturation => "Saturation",
+            Adjustment::Gamma => "Gamma",
+        }
+    }
+
+    fn value(&self, adjustments: &crate::VideoAdjustments) -> i64 {
+        match self {
+            Adjustment::Brightness => adjustments.brightness,
+            Adjustment::Contrast => adjustments.contrast,
+            Adjustment::Saturation => adjustments.saturation,
+            Adjustment::Gamma => adjustments.gamma,
+        }
+    }
+
+    fn set_value(&self, adjustments: &mut crate::VideoAdjustments, value: i64) {
+        match self {
+            Adjustment::Brightness => adjustments.brightness = value,
+            Adjustment::Contrast => adjustments.contrast = value,
+            Adjustment::Saturation => adjustments.saturation = value,
+            Adjustment::Gamma => adjustments.gamma = value,
+        }
+    }
+
+    /// Set the matching mpv property on the player
+    fn apply(
+        &self,
+        player: &crate::video_player::VideoPlayer,
+        value: i64,
+    ) -> Result<(), crate::video_player::VideoPlayerError> {
+        match self {
+            Adjustment::Brightness => player.set_brightness(value),
+            Adjustment::Contrast => player.set_contrast(value),
+            Adjustment::Saturation => player.set_saturation(value),
+            Adjustment::Gamma => player.set_gamma(value),
+        }
+    }
+}
+
+/// Frame preview shown above the scrubber while hovering it
+#[derive(Clone)]
+struct HoverPreview {
+    time_ms: u64,               // Hovered time, snapped to HOVER_PREVIEW_STEP_MS
+    position: Point<Pixels>,    // Bottom-left corner of the popup, in window coordinates
+    thumbnail: Option<PathBuf>, // None until extracted, or when extraction failed
+}
+
+/// A clip waiting in the export queue
+#[derive(Clone)]
+struct QueuedClip {
+    job: ExportJob,
+    default_path: PathBuf, // Output path before numbering (e.g. `movie_clip.mp4`)
+    error: Option<String>, // Why its last export failed; skipped until the next Export All
+}
+
+/// Delete a temp file written for an export, if it was written
+fn remove_temp_file(path: &Path) {
+    if path.exists() {
+        if let Err(e) = std::fs::remove_file(path) {
+            eprintln!("Failed to remove temp file {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Output path of the `number`th clip of an Export All run, e.g. `movie_clip_2.mp4`
+///
+/// Taken names move on to the next free number instead of being overwritten.
+fn queued_output_path(
+    default_path: &Path,
+    number: usize,
+    exists: impl Fn(&Path) -> bool,
+) -> PathBuf {
+    let stem = default_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("clip");
+    let extension = default_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| format!(".{}", ext))
+        .unwrap_or_default();
+    let path = default_path.with_file_name(format!("{}_{}{}", stem, number, extension));
+    if exists(&path) {
+        next_numbered_path(&path, exists)
+    } else {
+        path
+    }
+}
+
+/// Default screenshot file name: `<stem>_frame_<ms>.png`
+///
+/// The position keeps names unique when grabbing many frames from one video.
+fn screenshot_filename(stem: &str, position_secs: f32) -> String {
+    let position_ms = (position_secs.max(0.0) * 1000.0).round() as u64;
+    format!("{}_frame_{}.png", stem, position_ms)
+}
+
+/// The first `<stem>_N.<ext>` path after `path` that doesn't exist yet
+///
+/// A trailing `_N` on the stem is treated as an earlier number, so repeating
+/// from `clip_2.mp4` gives `clip_3.mp4` rather than `clip_2_2.mp4`.
+fn next_numbered_path(path: &Path, exists: impl Fn(&Path) -> bool) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("clip");
+    let (base, first) = match stem.rsplit_once('_') {
+        Some((base, number)) if !base.is_empty() => match number.parse::<u32>() {
+            Ok(number) => (base, number + 1),
+            Err(_) => (stem, 2),
+        },
+        _ => (stem, 2),
+    };
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| format!(".{}", ext))
+        .unwrap_or_default();
+
+    (first..)
+        .map(|number| path.with_file_name(format!("{}_{}{}", base, number, extension)))
+        .find(|candidate| !exists(candidate))
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Widen a clip range so it fully includes every subtitle crossing its bounds
+///
+/// Repeats until stable, since pulling the start back to one subtitle can
+/// land inside another overlapping subtitle.
+fn expand_to_whole_subtitles(
+    start_ms: f32,
+    end_ms: f32,
+    entries: &[crate::subtitle_extractor::SubtitleEntry],
+) -> (f32, f32) {
+    let (mut start_ms, mut end_ms) = (start_ms, end_ms);
+    loop {
+        let mut changed = false;
+        for entry in entries {
+            let (entry_start, entry_end) = (entry.start_ms as f32, entry.end_ms as f32);
+            if entry_start < start_ms && start_ms < entry_end {
+                start_ms = entry_start;
+                changed = true;
+            }
+            if entry_start < end_ms && end_ms < entry_end {
+                end_ms = entry_end;
+                changed = true;
+            }
+        }
+        if !changed {
+            return (start_ms, end_ms);
+        }
+    }
+}
+
+/// Subtitle entries shifted by the timing offset, clamped at zero
+fn offset_subtitle_entries(
+    entries: &[crate::subtitle_extractor::SubtitleEntry],
+    offset_ms: i64,
+) -> Vec<crate::subtitle_extractor::SubtitleEntry> {
+    entries
+        .iter()
+        .map(|entry| crate::subtitle_extractor::SubtitleEntry {
+            start_ms: entry.start_ms.saturating_add_signed(offset_ms),
+            end_ms: entry.end_ms.saturating_add_signed(offset_ms),
+            text: entry.text.clone(),
+        })
+        .collect()
+}
+
+/// Below this window width the control groups stack vertically
+const COMPACT_CONTROLS_WIDTH: f32 = 900.0;
+
+/// From this window width the clip time inputs get wider
+const WIDE_CONTROLS_WIDTH: f32 = 1800.0;
+
+/// Largest number of contact sheet rows or columns
+const MAX_SHEET_GRID: u32 = 10;
+
+/// Longest first-frame hold accepted for video exports, in seconds
+const MAX_INTRO_HOLD_SECS: f32 = 30.0;
+
+/// Thickest subtitle outline the slider goes up to
+const MAX_SUBTITLE_OUTLINE_WIDTH: f32 = 6.0;
+
+/// Text colors offered as swatches in the subtitle panel, as (hex, RGB)
+const SUBTITLE_TEXT_COLORS: [(&str, u32); 8] = [
+    ("#FFFFFF", 0xFFFFFF),
+    ("#FFFF00", 0xFFFF00),
+    ("#00FFFF", 0x00FFFF),
+    ("#00FF00", 0x00FF00),
+    ("#FF00FF", 0xFF00FF),
+    ("#FF8000", 0xFF8000),
+    ("#FF0000", 0xFF0000),
+    ("#C0C0C0", 0xC0C0C0),
+];
+
+/// Outline colors offered as swatches in the subtitle panel, as (hex, RGB)
+const SUBTITLE_OUTLINE_COLORS: [(&str, u32); 4] = [
+    ("#000000", 0x000000),
+    ("#404040", 0x404040),
+    ("#FFFFFF", 0xFFFFFF),
+    ("#1A237E", 0x1A237E),
+];
+
+/// Send the outline settings in `AppState` to the player
+fn apply_subtitle_outline(cx: &mut gpui::App) {
+    let app_state = cx.global::<AppState>();
+    let settings = app_state.subtitle_settings.clone();
+    let video_player = app_state.video_player.clone();
+    if let Ok(player) = video_player.lock() {
+        if let Err(e) = player.set_subtitle_outline(
+            &settings.outline_color,
+            settings.outline_width,
+            settings.shadow,
+        ) {
+            crate::notify_error(cx, format!("Failed to set subtitle outline: {}", e));
+        }
+    };
+}
+
+/// Scrubber step in seconds: one frame at the given frame rate
+///
+/// Falls back to 0.1s when the frame rate is unknown or implausible.
+fn scrubber_step(fps: f32) -> f32 {
+    if fps.is_finite() && (1.0..=240.0).contains(&fps) {
+        1.0 / fps
+    } else {
+        0.1
+    }
+}
+
+/// The frame boundary nearest to `position_secs`, or None when it's already on one
+/// (within a millisecond) or the frame rate is unknown
+fn snap_to_frame(position_secs: f32, fps: f32) -> Option<f32> {
+    if !(fps.is_finite() && (1.0..=240.0).contains(&fps)) {
+        return None;
+    }
+    let frame_secs = (position_secs * fps).round() / fps;
+    ((frame_secs - position_secs).abs() >= 0.001).then_some(frame_secs)
+}
+
+/// Size of the scrubber hover preview popup's frame (16:9, letterboxed)
+const HOVER_PREVIEW_WIDTH: f32 = 160.0;
+const HOVER_PREVIEW_HEIGHT: f32 = 90.0;
+
+/// Hovered times snap to this many milliseconds so nearby hovers share a cached frame
+const HOVER_PREVIEW_STEP_MS: u64 = 1000;
+
+/// How long the mouse has to rest on a time before its frame is extracted
+const HOVER_PREVIEW_DEBOUNCE_MS: u64 = 150;
+
+/// Height of the waveform strip under the scrubber
+const WAVEFORM_HEIGHT: f32 = 24.0;
+
+/// Width of each waveform bar, including a 1px gap
+const WAVEFORM_BAR_WIDTH: f32 = 2.0;
+
+/// Level of each of `columns` waveform bars, resampling the peaks to the strip width
+///
+/// Each bar takes the loudest peak it covers, or the nearest one when there
+/// are more bars than peaks.
+fn waveform_columns(peaks: &[f32], columns: usize) -> Vec<f32> {
+    if peaks.is_empty() {
+        return Vec::new();
+    }
+    (0..columns)
+        .map(|column| {
+            let start = column * peaks.len() / columns;
+            let end = ((column + 1) * peaks.len() / columns).max(start + 1);
+            peaks[start..end].iter().copied().fold(0.0, f32::max)
+        })
+        .collect()
+}
+
+/// Video time under the mouse on the scrubber, in milliseconds
+///
+/// Snapped to `HOVER_PREVIEW_STEP_MS`; None when the scrubber has no width
+/// or the duration isn't known yet.
+fn hover_time_ms(mouse_x: f32, left: f32, width: f32, duration_secs: f32) -> Option<u64> {
+    if width <= 0.0 || duration_secs <= 0.0 {
+        return None;
+    }
+    let fraction = ((mouse_x - left) / width).clamp(0.0, 1.0);
+    let time_ms = (fraction * duration_secs * 1000.0) as u64;
+    Some(time_ms / HOVER_PREVIEW_STEP_MS * HOVER_PREVIEW_STEP_MS)
+}
+
+/// Coarse scrubber steps across the whole video when Alt is held
+const COARSE_SCRUB_STEPS: f32 = 1000.0;
+
+/// Where a scrubber drag at `value` seconds should seek to
+///
+/// Drags land on single frames; with `coarse` (Alt held) they snap to about a
+/// thousandth of the duration instead, never finer than a frame, so fast drags
+/// across long videos issue fewer distinct seeks.
+fn scrub_position(value: f32, fps: f32, duration: f32, coarse: bool) -> f32 {
+    let frame = scrubber_step(fps);
+    let step = if coarse {
+        frame.max(duration / COARSE_SCRUB_STEPS)
+    } else {
+        frame
+    };
+    ((value / step).round() * step).clamp(0.0, duration.max(0.0))
+}
+
+/// Decide whether clip playback has reached its end and should pause (or loop)
+///
+/// It takes a moment for the video player to actually update its position when we seek.
+/// This means that for a small amount of time, the current position might be
+/// well ahead or behind the actual position the user is seeking to.
+///
+/// It appears to almost always be less than a millisecond or two,
+/// but potentially more than one frame.
+///
+/// To prevent automatically pausing when the user is trying to play a clip
+/// (because for a moment we think we're past our desired pause point),
+/// we store the time in milliseconds the user was AT when they hit the "play clip" button.
+/// If the video player is reporting it's still at that time, don't auto pause.
+fn should_pause(current_ms: f32, end_ms: f32, last_seek_ms: Option<f32>) -> bool {
+    let past_seek_time = last_seek_ms.is_none_or(|seek_ms| (current_ms - seek_ms).abs() > 0.1);
+    past_seek_time && current_ms >= end_ms
+}
+
+/// Ends of the review loop set with the `a` and `b` keys
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LoopPoint {
+    A,
+    B,
+}
+
+/// New (A, B) review loop after pressing `a` or `b` at the playhead
+///
+/// Pressing either key once both ends are set clears the loop. B has to come
+/// after A, so B without A (or before it) is ignored, and moving A past B
+/// drops B.
+fn ab_loop_press(
+    loop_a: Option<f32>,
+    loop_b: Option<f32>,
+    point: LoopPoint,
+    playhead_ms: f32,
+) -> (Option<f32>, Option<f32>) {
+    if loop_a.is_some() && loop_b.is_some() {
+        return (None, None);
+    }
+    match point {
+        LoopPoint::A => (Some(playhead_ms), loop_b.filter(|&b| b > playhead_ms)),
+        LoopPoint::B => match loop_a {
+            Some(a) if playhead_ms > a => (Some(a), Some(playhead_ms)),
+            _ => (loop_a, None),
+        },
+    }
+}
+
 impl Render for ControlsWindow {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         // Apply any pending input values (queued when window wasn't available)
@@ -695,6 +2957,10 @@ impl Render for ControlsWindow {
             });
         }
 
+        if cx.global::<AppState>().has_video_loaded {
+            self.refresh_audio_tracks(window, cx);
+        }
+
         // Check if a video is loaded
         let app_state = cx.global::<AppState>();
         let has_video_loaded = app_state.has_video_loaded;
@@ -709,35 +2975,33 @@ impl Render for ControlsWindow {
                 if t.is_playing_clip {
                     if let Some(end_time_ms) = t.clip_playback_end {
                         let current_time_ms = t.current_position * 1000.0;
-                        // it takes a moment for the video player to actually update its position when we seek
-                        // this means that for a small amount of time, the current_time_ms might be
-                        // well ahead or behind or the actual position the user is seeking to.
-                        //
-                        // it appears to almost always (on this computer) be less than a millisecond or two,
-                        // but potentially more than one frame.
-                        //
-                        // to prevent automatically pausing when the user is trying to play a clip
-                        // (because for a moment we think we're past our desired pause point),
-                        // we store the time in milliseconds the user was AT when they hit the "play clip" button
-                        // if the video player is reporting it's still in the same millisecond or +/- 1 ms, don't auto pause
-                        let past_seek_time = t
-                            .last_seek_time
-                            .map_or(true, |seek_time| (current_time_ms - seek_time).abs() > 0.1);
-                        if past_seek_time && current_time_ms >= end_time_ms {
+                        // After looping, wait for the seek to land before the end
+                        // check can fire again (otherwise it loops on every frame)
+                        if t.clip_loop_seek_pending && current_time_ms < end_time_ms {
+                            t.clip_loop_seek_pending = false;
+                        }
+                        if !t.clip_loop_seek_pending
+                            && should_pause(current_time_ms, end_time_ms, t.last_seek_time)
+                        {
                             let app_state = cx.global::<AppState>();
                             let video_player = app_state.video_player.clone();
 
-                            if t.loop_enabled {
+                            // Turning the loop off mid-pass lets this pass finish and stop
+                            if t.ab_loop_enabled {
                                 // Loop back to clip start
                                 if let Some(start_ms) = t.clip_start {
                                     if let Ok(player) = video_player.lock() {
                                         let nanos = (start_ms * 1_000_000.0) as u64;
                                         let clock_time = ClockTime::from_nseconds(nanos);
                                         if let Err(e) = player.seek(clock_time) {
-                                            eprintln!("Failed to seek to clip start for loop: {}", e);
+                                            crate::notify_error(
+                                                cx,
+                                                format!("Failed to seek to clip start: {}", e),
+                                            );
                                         }
                                         // Update last_seek_time to prevent immediate re-trigger
                                         t.last_seek_time = Some(current_time_ms);
+                                        t.clip_loop_seek_pending = true;
                                     };
                                 }
                             } else {
@@ -745,33 +3009,67 @@ impl Render for ControlsWindow {
                                 t.is_playing_clip = false;
                                 t.clip_playback_end = None;
 
-                                // Pause the player
-                                if let Ok(player) = video_player.lock() {
-                                    println!("Pausing because of the clip playback end check");
-                                    if let Err(e) = player.pause() {
-                                        eprintln!("Failed to pause after clip playback: {}", e);
-                                    }
-                                };
+                                // Pause the player, unless the user wants playback to carry on
+                                // past the clip (looping above still takes priority)
+                                if app_state.stop_at_clip_end {
+                                    if let Ok(player) = video_player.lock() {
+                                        println!("Pausing because of the clip playback end check");
+                                        if let Err(e) = player.pause() {
+                                            crate::notify_error(
+                                                cx,
+                                                format!(
+                                                    "Failed to pause after clip playback: {}",
+                                                    e
+                                                ),
+                                            );
+                                        }
+                                    };
+                                }
                             }
                         }
                     }
                 }
 
-                // Rate limit renders to 30 FPS (33.33ms per frame)
-                const FRAME_DURATION_MS: u128 = 33; // 1000ms / 30fps ≈ 33.33ms
-                let now = Instant::now();
-                let elapsed = now.duration_since(t.last_render_time).as_millis();
-
-                if elapsed >= FRAME_DURATION_MS {
-                    t.last_render_time = now;
-                    // Request another render on next frame to create continuous updates
-                    cx.notify();
+                // Review loop: jump back to A at B (Play Clip takes priority)
+                if let (Some(loop_a), Some(loop_b)) = (t.loop_a, t.loop_b) {
+                    let current_time_ms = t.current_position * 1000.0;
+                    // Same as the clip loop: wait for the seek back to A to land
+                    if t.loop_seek_pending && current_time_ms < loop_b {
+                        t.loop_seek_pending = false;
+                    }
+                    if t.is_playing
+                        && !t.is_playing_clip
+                        && !t.loop_seek_pending
+                        && should_pause(current_time_ms, loop_b, t.last_seek_time)
+                    {
+                        let video_player = cx.global::<AppState>().video_player.clone();
+                        if let Ok(player) = video_player.lock() {
+                            let nanos = (loop_a * 1_000_000.0) as u64;
+                            if let Err(e) = player.seek(ClockTime::from_nseconds(nanos)) {
+                                crate::notify_error(
+                                    cx,
+                                    format!("Failed to seek to loop start: {}", e),
+                                );
+                            }
+                            t.last_seek_time = Some(current_time_ms);
+                            t.loop_seek_pending = true;
+                        };
+                    }
                 }
+
+                // Rate limit renders to the configured refresh rate cap
+                crate::notify_rate_limited(&mut t.last_render_time, cx);
             });
         }
 
         // Slider is updated in update_position_from_player
 
+        self.update_thumbnail_strip(cx);
+        let thumbnails = self.thumbnails.clone();
+        let hover_preview = self.hover_preview.clone();
+        let slider_bounds = self.slider_bounds.clone();
+        let waveform = cx.global::<AppState>().waveform.clone();
+
         let current_time = self.current_position;
         let duration = if self.duration > 0.0 {
             self.duration
@@ -779,6 +3077,24 @@ impl Render for ControlsWindow {
             100.0
         };
 
+        // Coarse time during playback, frame-accurate time while paused
+        let current_time_label = if self.is_playing {
+            Self::format_time(current_time)
+        } else {
+            Self::format_time_precise(current_time, cx.global::<AppState>().source_video_fps)
+        };
+
+        // Review loop ends, shown next to the time
+        let loop_label = match (self.loop_a, self.loop_b) {
+            (Some(a), Some(b)) => Some(format!(
+                "Loop {} - {}",
+                Self::format_time(a / 1000.0),
+                Self::format_time(b / 1000.0)
+            )),
+            (Some(a), None) => Some(format!("Loop {} -", Self::format_time(a / 1000.0))),
+            _ => None,
+        };
+
         let theme = cx.theme();
         // Pre-capture colors for closures
         let hover_bg = theme.element_hover();
@@ -790,10 +3106,49 @@ impl Render for ControlsWindow {
         let border_variant_color = theme.border_variant();
         let surface_bg = theme.surface_background();
         let error_color = theme.error(); // For input error borders
+        let marker_color = theme.warning(); // For chapter markers on the scrubber
+        let markers = cx.global::<AppState>().markers.clone();
+        let chapter_tick_color = theme.text_muted(); // For the file's own chapters
+        let chapters = self.chapters.clone();
+        let has_chapters = !chapters.is_empty();
+        let source_is_hdr = cx.global::<AppState>().source_is_hdr;
+        let deinterlace = cx.global::<AppState>().deinterlace;
 
         // Capture error states for styling
         let clip_start_error = self.clip_start_error;
         let clip_end_error = self.clip_end_error;
+        let gif_fps_error = Self::parse_gif_setting(
+            &self.gif_fps_input.read(cx).value(),
+            crate::ffmpeg_export::DEFAULT_GIF_FPS,
+            crate::ffmpeg_export::GIF_FPS_RANGE,
+            "frame rate",
+        )
+        .is_err();
+        let gif_width_error = Self::parse_gif_setting(
+            &self.gif_width_input.read(cx).value(),
+            crate::ffmpeg_export::DEFAULT_GIF_WIDTH,
+            crate::ffmpeg_export::GIF_WIDTH_RANGE,
+            "width",
+        )
+        .is_err();
+        // GIF settings input, outlined in red while its value is out of range
+        let gif_input = |input: &Entity<InputState>, width: f32, invalid: bool| {
+            div()
+                .w(px(width))
+                .rounded_md()
+                .when(invalid, |this| this.border_1().border_color(error_color))
+                .child(Input::new(input).xsmall())
+        };
+
+        // Narrow windows stack the control groups and drop the volume slider;
+        // wide ones give the clip inputs more room
+        let window_width = f32::from(window.bounds().size.width);
+        let compact = window_width < COMPACT_CONTROLS_WIDTH;
+        let clip_input_width = if window_width >= WIDE_CONTROLS_WIDTH {
+            px(140.0)
+        } else {
+            px(100.0)
+        };
 
         div()
             .flex()
@@ -817,22 +3172,328 @@ impl Render for ControlsWindow {
                             .w_full()
                             .text_sm()
                             .text_color(text_color)
-                            .child(Self::format_time(current_time))
+                            .items_center()
+                            .child(
+                                div()
+                                    .flex()
+                                    .flex_row()
+                                    .gap_3()
+                                    .child(current_time_label)
+                                    .when_some(loop_label, |this, label| {
+                                        this.child(div().text_color(text_muted_color).child(label))
+                                    }),
+                            )
+                            // Marker controls: name input, add, and clear
+                            .when(has_video_loaded, |this| {
+                                this.child(
+                                    div()
+                                        .flex()
+                                        .flex_row()
+                                        .items_center()
+                                        .gap_2()
+                                        .child(
+                                            div().w(px(120.0)).child(
+                                                Input::new(&self.marker_name_input).xsmall(),
+                                            ),
+                                        )
+                                        .child(
+                                            div()
+                                                .px_2()
+                                                .py_1()
+                                                .bg(hover_bg)
+                                                .rounded_md()
+                                                .cursor_pointer()
+                                                .text_xs()
+                                                .text_color(text_color)
+                                                .hover(move |style| style.bg(bg))
+                                                .on_mouse_down(
+                                                    MouseButton::Left,
+                                                    cx.listener(|this, _, window, cx| {
+                                                        this.add_marker(window, cx);
+                                                    }),
+                                                )
+                                                .child("Add Marker"),
+                                        )
+                                        .when(!markers.is_empty(), |this| {
+                                            this.child(
+                                                div()
+                                                    .px_2()
+                                                    .py_1()
+                                                    .rounded_md()
+                                                    .cursor_pointer()
+                                                    .text_xs()
+                                                    .text_color(text_muted_color)
+                                                    .hover(move |style| style.bg(hover_bg))
+                                                    .on_mouse_down(
+                                                        MouseButton::Left,
+                                                        cx.listener(|_, _, _, cx| {
+                                                            cx.update_global::<AppState, _>(
+                                                                |state, _| state.markers.clear(),
+                                                            );
+                                                            crate::save_markers(cx);
+                                                            cx.notify();
+                                                        }),
+                                                    )
+                                                    .child(format!(
+                                                        "Clear {} marker{}",
+                                                        markers.len(),
+                                                        if markers.len() == 1 { "" } else { "s" }
+                                                    )),
+                                            )
+                                        }),
+                                )
+                            })
                             .child(Self::format_time(duration)),
                     )
-                    // Slider (only shown when video is loaded)
+                    // Slider (only shown when video is loaded), with markers drawn over it
                     .when_some(self.slider_state.as_ref(), |this, slider_state| {
-                        this.child(Slider::new(slider_state).horizontal())
+                        this.child(
+                            div()
+                                .id("scrubber")
+                                .relative()
+                                .w_full()
+                                // Frame preview of the hovered time
+                                .on_mouse_move(cx.listener(
+                                    |this, event: &MouseMoveEvent, _, cx| {
+                                        this.update_hover_preview(event.position, cx);
+                                    },
+                                ))
+                                .on_hover(cx.listener(|this, hovered: &bool, _, cx| {
+                                    if !*hovered {
+                                        this.clear_hover_preview(cx);
+                                    }
+                                }))
+                                // Alt switches dragging to coarse steps, even mid-drag
+                                .capture_any_mouse_down(cx.listener(
+                                    |this, event: &MouseDownEvent, _, _| {
+                                        this.coarse_scrub = event.modifiers.alt;
+                                    },
+                                ))
+                                .on_modifiers_changed(cx.listener(
+                                    |this, event: &ModifiersChangedEvent, _, _| {
+                                        this.coarse_scrub = event.modifiers.alt;
+                                    },
+                                ))
+                                // The drag ends wherever the button is released
+                                .on_mouse_up(
+                                    MouseButton::Left,
+                                    cx.listener(|this, _, _, cx| this.end_scrub_mute(cx)),
+                                )
+                                .on_mouse_up_out(
+                                    MouseButton::Left,
+                                    cx.listener(|this, _, _, cx| this.end_scrub_mute(cx)),
+                                )
+                                .child(Slider::new(slider_state).horizontal())
+                                .children(markers.iter().map(|marker| {
+                                    let fraction =
+                                        (marker.time_ms as f32 / 1000.0 / duration).clamp(0.0, 1.0);
+                                    div()
+                                        .absolute()
+                                        .top_0()
+                                        .bottom_0()
+                                        .left(relative(fraction))
+                                        .w(px(2.0))
+                                        .bg(marker_color)
+                                }))
+                                // Embedded chapters: short ticks along the bottom, click to seek
+                                .children(chapters.into_iter().map(|(start_secs, _)| {
+                                    let fraction = (start_secs as f32 / duration).clamp(0.0, 1.0);
+                                    div()
+                                        .absolute()
+                                        .bottom_0()
+                                        .h(relative(0.5))
+                                        .left(relative(fraction))
+                                        .w(px(3.0))
+                                        .bg(chapter_tick_color)
+                                        .cursor_pointer()
+                                        .on_mouse_down(
+                                            MouseButton::Left,
+                                            cx.listener(move |this, _, _, cx| {
+                                                // Keep the slider from seeking to the click too
+                                                cx.stop_propagation();
+                                                this.seek_to_chapter_start(start_secs, cx);
+                                            }),
+                                        )
+                                }))
+                                // Records the scrubber bounds for mapping hovers to times
+                                .child(
+                                    canvas(
+                                        move |bounds, _, _| {
+                                            if let Ok(mut slider_bounds) = slider_bounds.lock() {
+                                                *slider_bounds = Some(bounds);
+                                            }
+                                        },
+                                        |_, _, _, _| {},
+                                    )
+                                    .absolute()
+                                    .size_full(),
+                                )
+                                .when_some(hover_preview, |this, preview| {
+                                    this.child(deferred(
+                                        anchored()
+                                            .position(preview.position)
+                                            .anchor(Corner::BottomLeft)
+                                            .snap_to_window()
+                                            .child(
+                                                div()
+                                                    .w(px(HOVER_PREVIEW_WIDTH))
+                                                    .flex()
+                                                    .flex_col()
+                                                    .items_center()
+                                                    .gap_1()
+                                                    .p_1()
+                                                    .rounded_md()
+                                                    .bg(surface_bg)
+                                                    .border_1()
+                                                    .border_color(border_variant_color)
+                                                    .text_xs()
+                                                    .text_color(text_color)
+                                                    .when_some(preview.thumbnail, |el, path| {
+                                                        el.child(
+                                                            img(path)
+                                                                .w_full()
+                                                                .h(px(HOVER_PREVIEW_HEIGHT))
+                                                                .object_fit(ObjectFit::Contain),
+                                                        )
+                                                    })
+                                                    .child(Self::format_time(
+                                                        preview.time_ms as f32 / 1000.0,
+                                                    )),
+                                            ),
+                                    ))
+                                }),
+                        )
+                    })
+                    // Audio waveform with the playhead; a flat line for files without audio
+                    .when(self.slider_state.is_some(), |this| {
+                        let waveform_color = text_muted_color;
+                        this.child(
+                            div()
+                                .relative()
+                                .w_full()
+                                .h(px(WAVEFORM_HEIGHT))
+                                .child(
+                                    canvas(
+                                        |_, _, _| {},
+                                        move |bounds, _, window, _| {
+                                            let height = f32::from(bounds.size.height);
+                                            let width = f32::from(bounds.size.width);
+                                            let columns = (width / WAVEFORM_BAR_WIDTH) as usize;
+                                            let levels = waveform_columns(&waveform, columns);
+                                            if levels.is_empty() {
+                                                let line = Bounds::new(
+                                                    point(
+                                                        bounds.origin.x,
+                                                        bounds.origin.y + px(height / 2.0),
+                                                    ),
+                                                    size(bounds.size.width, px(1.0)),
+                                                );
+                                                window.paint_quad(fill(line, waveform_color));
+                                                return;
+                                            }
+                                            for (column, level) in levels.into_iter().enumerate() {
+                                                let bar_height = (level * height).max(1.0);
+                                                let x = column as f32 * WAVEFORM_BAR_WIDTH;
+                                                let bar = Bounds::new(
+                                                    point(
+                                                        bounds.origin.x + px(x),
+                                                        bounds.origin.y
+                                                            + px((height - bar_height) / 2.0),
+                                                    ),
+                                                    size(
+                                                        px(WAVEFORM_BAR_WIDTH - 1.0),
+                                                        px(bar_height),
+                                                    ),
+                                                );
+                                                window.paint_quad(fill(bar, waveform_color));
+                                            }
+                                        },
+                                    )
+                                    .size_full(),
+                                )
+                                .child(
+                                    div()
+                                        .absolute()
+                                        .top_0()
+                                        .bottom_0()
+                                        .left(relative((current_time / duration).clamp(0.0, 1.0)))
+                                        .w(px(1.0))
+                                        .bg(text_color),
+                                ),
+                        )
+                    })
+                    // Adjust panel: playback-only brightness/contrast/saturation/gamma
+                    .when(self.adjust_open && has_video_loaded, |this| {
+                        this.child(
+                            div()
+                                .flex()
+                                .flex_row()
+                                .items_end()
+                                .gap_3()
+                                .children(self.adjust_sliders.iter().map(|(adjustment, slider)| {
+                                    div()
+                                        .flex_1()
+                                        .flex()
+                                        .flex_col()
+                                        .gap_1()
+                                        .child(div().text_xs().text_color(text_muted_color).child(
+                                            format!(
+                                                "{}: {:.0}",
+                                                adjustment.name(),
+                                                slider.read(cx).value().end()
+                                            ),
+                                        ))
+                                        .child(Slider::new(slider))
+                                }))
+                                .child(
+                                    div()
+                                        .px_2()
+                                        .py_1()
+                                        .bg(hover_bg)
+                                        .rounded_md()
+                                        .cursor_pointer()
+                                        .text_xs()
+                                        .text_color(text_color)
+                                        .hover(move |style| style.bg(bg))
+                                        .on_mouse_down(
+                                            MouseButton::Left,
+                                            cx.listener(|this, _, window, cx| {
+                                                this.reset_adjustments(window, cx);
+                                            }),
+                                        )
+                                        .child("Reset"),
+                                ),
+                        )
+                    })
+                    // Thumbnail strip previewing the selected clip range
+                    .when(!thumbnails.is_empty(), |this| {
+                        this.child(div().flex().flex_row().gap_1().w_full().children(
+                            thumbnails.into_iter().map(|thumbnail| {
+                                div()
+                                    .flex_1()
+                                    .h(px(48.0))
+                                    .bg(bg)
+                                    .rounded_sm()
+                                    .overflow_hidden()
+                                    .when_some(thumbnail, |this, path| {
+                                        this.child(
+                                            img(path).size_full().object_fit(ObjectFit::Cover),
+                                        )
+                                    })
+                            }),
+                        ))
                     }),
             )
             // Button controls section
             .child(
                 div()
                     .flex()
+                    .flex_wrap()
                     .items_center()
                     .justify_between()
                     .gap_4()
                     .w_full()
+                    .when(compact, |el| el.flex_col().items_start())
                     // Left side: Clip start/end buttons and inputs
                     .child(
                         div()
@@ -852,7 +3513,7 @@ impl Render for ControlsWindow {
                                             .flex()
                                             .flex_col()
                                             .gap_1()
-                                            .w(px(100.0))
+                                            .w(clip_input_width)
                                             .child(
                                                 div()
                                                     .border_1()
@@ -879,40 +3540,7 @@ impl Render for ControlsWindow {
                                                     .on_mouse_down(
                                                         MouseButton::Left,
                                                         cx.listener(|this, _, window, cx| {
-                                                            let current_time_ms =
-                                                                this.current_position * 1000.0;
-
-                                                            this.clip_start = Some(current_time_ms);
-
-                                                            // Update the input field
-                                                            let formatted = Self::format_time_ms(
-                                                                current_time_ms,
-                                                            );
-                                                            this.clip_start_input.update(
-                                                                cx,
-                                                                |input, cx| {
-                                                                    input.set_value(
-                                                                        formatted, window, cx,
-                                                                    );
-                                                                },
-                                                            );
-
-                                                            // Check if this violates the constraint and set error state
-                                                            let has_error = this
-                                                                .clip_end
-                                                                .map(|end| current_time_ms >= end)
-                                                                .unwrap_or(false);
-                                                            this.clip_start_error = has_error;
-                                                            if !has_error {
-                                                                this.clip_end_error = false;
-                                                                // Update clip_playback_end if playing
-                                                                if this.is_playing_clip {
-                                                                    this.clip_playback_end =
-                                                                        this.clip_end;
-                                                                }
-                                                            }
-
-                                                            cx.notify();
+                                                            this.mark_clip_start(window, cx);
                                                         }),
                                                     )
                                                     .child("Set Start"),
@@ -924,7 +3552,7 @@ impl Render for ControlsWindow {
                                             .flex()
                                             .flex_col()
                                             .gap_1()
-                                            .w(px(100.0))
+                                            .w(clip_input_width)
                                             .child(
                                                 div()
                                                     .border_1()
@@ -951,40 +3579,7 @@ impl Render for ControlsWindow {
                                                     .on_mouse_down(
                                                         MouseButton::Left,
                                                         cx.listener(|this, _, window, cx| {
-                                                            let current_time_ms =
-                                                                this.current_position * 1000.0;
-
-                                                            this.clip_end = Some(current_time_ms);
-
-                                                            // Update the input field
-                                                            let formatted = Self::format_time_ms(
-                                                                current_time_ms,
-                                                            );
-                                                            this.clip_end_input.update(
-                                                                cx,
-                                                                |input, cx| {
-                                                                    input.set_value(
-                                                                        formatted, window, cx,
-                                                                    );
-                                                                },
-                                                            );
-
-                                                            // Check if this violates the constraint and set error state
-                                                            let has_error = this
-                                                                .clip_start
-                                                                .map(|start| current_time_ms <= start)
-                                                                .unwrap_or(false);
-                                                            this.clip_end_error = has_error;
-                                                            if !has_error {
-                                                                this.clip_start_error = false;
-                                                                // Update clip_playback_end if playing
-                                                                if this.is_playing_clip {
-                                                                    this.clip_playback_end =
-                                                                        Some(current_time_ms);
-                                                                }
-                                                            }
-
-                                                            cx.notify();
+                                                            this.mark_clip_end(window, cx);
                                                         }),
                                                     )
                                                     .child("Set End"),
@@ -1007,6 +3602,11 @@ impl Render for ControlsWindow {
                                         let is_valid =
                                             duration.is_some() && duration.unwrap() > 0.0;
                                         let loop_enabled = self.loop_enabled;
+                                        // Export All shows which clip it's on, e.g. "Exporting 2/5"
+                                        let queue_label = self
+                                            .queue_position
+                                            .map(|(number, total)| format!(" {}/{}", number, total))
+                                            .unwrap_or_default();
 
                                         div()
                                             .flex()
@@ -1060,6 +3660,140 @@ impl Render for ControlsWindow {
                                                                 self.export_format.as_str().to_uppercase()
                                                             )),
                                                     )
+                                                    // Quality button - cycles the video CRF/preset
+                                                    .when(
+                                                        self.export_format == ExportFormat::Video,
+                                                        |this| {
+                                                            this.child(
+                                                                div()
+                                                                    .px_2()
+                                                                    .py_1()
+                                                                    .bg(hover_bg)
+                                                                    .rounded_md()
+                                                                    .cursor_pointer()
+                                                                    .text_xs()
+                                                                    .text_color(text_color)
+                                                                    .hover(move |style| style.bg(bg))
+                                                                    .on_mouse_down(
+                                                                        MouseButton::Left,
+                                                                        cx.listener(|this, _, _, cx| {
+                                                                            this.export_quality =
+                                                                                this.export_quality.next();
+                                                                            cx.notify();
+                                                                        }),
+                                                                    )
+                                                                    .child(self.export_quality.name()),
+                                                            )
+                                                        },
+                                                    )
+                                                    // Audio format button - cycles the audio codec
+                                                    .when(
+                                                        self.export_format == ExportFormat::Audio,
+                                                        |this| {
+                                                            this.child(
+                                                                div()
+                                                                    .px_2()
+                                                                    .py_1()
+                                                                    .bg(hover_bg)
+                                                                    .rounded_md()
+                                                                    .cursor_pointer()
+                                                                    .text_xs()
+                                                                    .text_color(text_color)
+                                                                    .hover(move |style| style.bg(bg))
+                                                                    .on_mouse_down(
+                                                                        MouseButton::Left,
+                                                                        cx.listener(|this, _, _, cx| {
+                                                                            this.audio_format =
+                                                                                this.audio_format.next();
+                                                                            cx.notify();
+                                                                        }),
+                                                                    )
+                                                                    .child(self.audio_format.name()),
+                                                            )
+                                                        },
+                                                    )
+                                                    // Resolution button - cycles the output height
+                                                    .when(self.export_format.is_video(), |this| {
+                                                        this.child(
+                                                            div()
+                                                                .px_2()
+                                                                .py_1()
+                                                                .bg(hover_bg)
+                                                                .rounded_md()
+                                                                .cursor_pointer()
+                                                                .text_xs()
+                                                                .text_color(text_color)
+                                                                .hover(move |style| style.bg(bg))
+                                                                .on_mouse_down(
+                                                                    MouseButton::Left,
+                                                                    cx.listener(|this, _, _, cx| {
+                                                                        this.output_scale =
+                                                                            this.output_scale.next();
+                                                                        cx.notify();
+                                                                    }),
+                                                                )
+                                                                .child(self.output_scale.name()),
+                                                        )
+                                                    })
+                                                    // Output frame rate (video exports only)
+                                                    .when(
+                                                        self.export_format.is_video(),
+                                                        |this| {
+                                                            this.child(
+                                                                div().w(px(80.0)).child(
+                                                                    Input::new(&self.output_fps_input)
+                                                                        .xsmall(),
+                                                                ),
+                                                            )
+                                                            .child(
+                                                                div().w(px(70.0)).child(
+                                                                    Input::new(&self.intro_hold_input)
+                                                                        .xsmall(),
+                                                                ),
+                                                            )
+                                                            .child(
+                                                                div().w(px(80.0)).child(
+                                                                    Input::new(&self.target_size_input)
+                                                                        .xsmall(),
+                                                                ),
+                                                            )
+                                                        },
+                                                    )
+                                                    // Contact sheet grid size
+                                                    .when(
+                                                        self.export_format
+                                                            == ExportFormat::ContactSheet,
+                                                        |this| {
+                                                            this.child(
+                                                                div().w(px(60.0)).child(
+                                                                    Input::new(&self.sheet_cols_input)
+                                                                        .xsmall(),
+                                                                ),
+                                                            )
+                                                            .child(
+                                                                div().w(px(60.0)).child(
+                                                                    Input::new(&self.sheet_rows_input)
+                                                                        .xsmall(),
+                                                                ),
+                                                            )
+                                                        },
+                                                    )
+                                                    // GIF frame rate and width
+                                                    .when(
+                                                        self.export_format == ExportFormat::Gif,
+                                                        |this| {
+                                                            this.child(gif_input(
+                                                                &self.gif_fps_input,
+                                                                60.0,
+                                                                gif_fps_error,
+                                                            ))
+                                                            .child(gif_input(
+                                                                &self.gif_width_input,
+                                                                70.0,
+                                                                gif_width_error,
+                                                            ))
+                                                        },
+                                                    )
                                                     .child(
                                                         div()
                                                             .px_3()
@@ -1108,26 +3842,436 @@ impl Render for ControlsWindow {
                                                                     }
                                                                 }),
                                                             )
-                                                            .child(if self.is_exporting {
-                                                                "Exporting..."
+                                                            // Audio and contact sheets don't report progress
+                                                            .child(if self.is_exporting
+                                                                && self.export_progress > 0.0
+                                                            {
+                                                                format!(
+                                                                    "Exporting{} {:.0}%",
+                                                                    queue_label,
+                                                                    self.export_progress * 100.0
+                                                                )
+                                                            } else if self.is_exporting {
+                                                                format!("Exporting{}...", queue_label)
                                                             } else {
-                                                                "Export"
+                                                                "Export".to_string()
+                                                            })
+                                                            // Progress bar along the bottom edge
+                                                            .when(self.is_exporting, |this| {
+                                                                this.relative().child(
+                                                                    div()
+                                                                        .absolute()
+                                                                        .left_0()
+                                                                        .bottom_0()
+                                                                        .h(px(2.0))
+                                                                        .w(relative(self.export_progress))
+                                                                        .bg(list_active_bg),
+                                                                )
                                                             }),
+                                                    )
+                                                    // Stop the running export
+                                                    .when(self.is_exporting, |this| {
+                                                        this.child(
+                                                            div()
+                                                                .px_3()
+                                                                .py_1()
+                                                                .rounded_md()
+                                                                .text_xs()
+                                                                .bg(hover_bg)
+                                                                .cursor_pointer()
+                                                                .text_color(text_color)
+                                                                .hover(move |style| style.bg(bg))
+                                                                .on_mouse_down(
+                                                                    MouseButton::Left,
+                                                                    cx.listener(|this, _, _, cx| {
+                                                                        this.cancel_export(cx);
+                                                                    }),
+                                                                )
+                                                                .child("Cancel"),
+                                                        )
+                                                    })
+                                                    // Repeat the last export to a new numbered file
+                                                    .child({
+                                                        let can_repeat = self.last_export.is_some()
+                                                            && !self.is_exporting;
+                                                        div()
+                                                            .px_3()
+                                                            .py_1()
+                                                            .rounded_md()
+                                                            .text_xs()
+                                                            .when(can_repeat, |this| {
+                                                                this.bg(hover_bg)
+                                                                    .cursor_pointer()
+                                                                    .text_color(text_color)
+                                                                    .hover(move |style| style.bg(bg))
+                                                            })
+                                                            .when(!can_repeat, |this| {
+                                                                this.bg(bg)
+                                                                    .cursor_not_allowed()
+                                                                    .text_color(text_disabled_color)
+                                                            })
+                                                            .on_mouse_down(
+                                                                MouseButton::Left,
+                                                                cx.listener(|this, _, _, cx| {
+                                                                    this.repeat_last_export(cx);
+                                                                }),
+                                                            )
+                                                            .child("Repeat")
+                                                    })
+                                                    // Queue the clip for Export All
+                                                    .child(
+                                                        div()
+                                                            .px_3()
+                                                            .py_1()
+                                                            .rounded_md()
+                                                            .text_xs()
+                                                            .when(is_valid, |this| {
+                                                                this.bg(hover_bg)
+                                                                    .cursor_pointer()
+                                                                    .text_color(text_color)
+                                                                    .hover(move |style| style.bg(bg))
+                                                            })
+                                                            .when(!is_valid, |this| {
+                                                                this.bg(bg)
+                                                                    .cursor_not_allowed()
+                                                                    .text_color(text_disabled_color)
+                                                            })
+                                                            .on_mouse_down(
+                                                                MouseButton::Left,
+                                                                cx.listener(move |this, _, _, cx| {
+                                                                    if is_valid {
+                                                                        this.add_to_queue(cx);
+                                                                    }
+                                                                }),
+                                                            )
+                                                            .child("Add to queue"),
+                                                    )
+                                                    .when(
+                                                        !self.clip_queue.is_empty()
+                                                            && !self.is_exporting,
+                                                        |this| {
+                                                            this.child(
+                                                                div()
+                                                                    .px_3()
+                                                                    .py_1()
+                                                                    .rounded_md()
+                                                                    .text_xs()
+                                                                    .bg(hover_bg)
+                                                                    .cursor_pointer()
+                                                                    .text_color(text_color)
+                                                                    .hover(move |style| style.bg(bg))
+                                                                    .on_mouse_down(
+                                                                        MouseButton::Left,
+                                                                        cx.listener(|this, _, _, cx| {
+                                                                            this.export_all(cx);
+                                                                        }),
+                                                                    )
+                                                                    .child(format!(
+                                                                        "Export All ({})",
+                                                                        self.clip_queue.len()
+                                                                    )),
+                                                            )
+                                                        },
+                                                    )
+                                                    // What the last export wrote
+                                                    .when_some(
+                                                        self.export_report
+                                                            .clone()
+                                                            .filter(|_| !self.is_exporting),
+                                                        |this, report| {
+                                                            this.child(
+                                                                div()
+                                                                    .text_xs()
+                                                                    .text_color(text_disabled_color)
+                                                                    .child(report),
+                                                            )
+                                                        },
                                                     ),
                                             )
-                                            // Right: Loop checkbox (small)
+                                            // Right: Loop and chapters checkboxes (small)
                                             .child(
-                                                Checkbox::new("loop-checkbox")
-                                                    .label("Loop")
-                                                    .checked(loop_enabled)
-                                                    .disabled(!is_valid)
-                                                    .on_click(cx.listener(|this, checked, _, cx| {
-                                                        this.loop_enabled = *checked;
-                                                        cx.notify();
-                                                    })),
+                                                div()
+                                                    .flex()
+                                                    .flex_col()
+                                                    .gap_1()
+                                                    .when(
+                                                        self.export_format == ExportFormat::Gif,
+                                                        |this| {
+                                                            this.child(
+                                                                Checkbox::new("loop-checkbox")
+                                                                    .label("Loop")
+                                                                    .checked(loop_enabled)
+                                                                    .disabled(!is_valid)
+                                                                    .on_click(cx.listener(
+                                                                        |this, checked, _, cx| {
+                                                                            this.loop_enabled =
+                                                                                *checked;
+                                                                            cx.notify();
+                                                                        },
+                                                                    )),
+                                                            )
+                                                        },
+                                                    )
+                                                    // GIF exports: also write the audio as an mp3
+                                                    .when(
+                                                        self.export_format == ExportFormat::Gif,
+                                                        |this| {
+                                                            this.child(
+                                                                Checkbox::new("gif-audio-checkbox")
+                                                                    .label("+ Audio")
+                                                                    .checked(self.export_gif_audio)
+                                                                    .disabled(!is_valid)
+                                                                    .on_click(cx.listener(
+                                                                        |this, checked, _, cx| {
+                                                                            this.export_gif_audio =
+                                                                                *checked;
+                                                                            cx.notify();
+                                                                        },
+                                                                    )),
+                                                            )
+                                                        },
+                                                    )
+                                                    // HDR sources: opt-in tone mapping to SDR
+                                                    .when(
+                                                        source_is_hdr
+                                                            && self.export_format
+                                                                != ExportFormat::Audio,
+                                                        |this| {
+                                                            this.child(
+                                                                Checkbox::new("tonemap-checkbox")
+                                                                    .label("Tone-map HDR")
+                                                                    .checked(self.tonemap_hdr)
+                                                                    .disabled(!is_valid)
+                                                                    .on_click(cx.listener(
+                                                                        |this, checked, _, cx| {
+                                                                            this.tonemap_hdr =
+                                                                                *checked;
+                                                                            cx.notify();
+                                                                        },
+                                                                    )),
+                                                            )
+                                                        },
+                                                    )
+                                                    // Deinterlace playback and video/GIF exports
+                                                    .when(
+                                                        self.export_format.is_video()
+                                                            || self.export_format
+                                                                == ExportFormat::Gif,
+                                                        |this| {
+                                                            this.child(
+                                                                Checkbox::new(
+                                                                    "deinterlace-checkbox",
+                                                                )
+                                                                .label("Deinterlace")
+                                                                .checked(deinterlace)
+                                                                .on_click(cx.listener(
+                                                                    |this, checked, _, cx| {
+                                                                        this.set_deinterlace(
+                                                                            *checked, cx,
+                                                                        );
+                                                                    },
+                                                                )),
+                                                            )
+                                                        },
+                                                    )
+                                                    .child(
+                                                        Checkbox::new("chapters-checkbox")
+                                                            .label("Chapters")
+                                                            .checked(self.embed_chapters)
+                                                            .disabled(
+                                                                markers.is_empty()
+                                                                    || !self
+                                                                        .export_format
+                                                                        .is_video(),
+                                                            )
+                                                            .on_click(cx.listener(|this, checked, _, cx| {
+                                                                this.embed_chapters = *checked;
+                                                                cx.notify();
+                                                            })),
+                                                    )
+                                                    // Video exports: a chapter per subtitle line
+                                                    .when(
+                                                        self.export_format.is_video(),
+                                                        |this| {
+                                                            this.child(
+                                                                Checkbox::new(
+                                                                    "subtitle-chapters-checkbox",
+                                                                )
+                                                                .label("Subtitle chapters")
+                                                                .checked(self.subtitle_chapters)
+                                                                .disabled(!is_valid)
+                                                                .on_click(cx.listener(
+                                                                    |this, checked, _, cx| {
+                                                                        this.subtitle_chapters =
+                                                                            *checked;
+                                                                        cx.notify();
+                                                                    },
+                                                                )),
+                                                            )
+                                                        },
+                                                    )
+                                                    // Video exports: keep source tags and chapters
+                                                    .when(
+                                                        self.export_format.is_video(),
+                                                        |this| {
+                                                            this.child(
+                                                                Checkbox::new("metadata-checkbox")
+                                                                    .label("Keep metadata")
+                                                                    .checked(self.copy_metadata)
+                                                                    .disabled(!is_valid)
+                                                                    .on_click(cx.listener(
+                                                                        |this, checked, _, cx| {
+                                                                            this.copy_metadata =
+                                                                                *checked;
+                                                                            cx.notify();
+                                                                        },
+                                                                    )),
+                                                            )
+                                                        },
+                                                    )
+                                                    .when(
+                                                        self.export_format.is_video()
+                                                            && self.copy_metadata,
+                                                        |this| {
+                                                            this.child(
+                                                                Checkbox::new(
+                                                                    "metadata-selective-checkbox",
+                                                                )
+                                                                .label("Title/language only")
+                                                                .checked(
+                                                                    self.copy_metadata_selective,
+                                                                )
+                                                                .disabled(!is_valid)
+                                                                .on_click(cx.listener(
+                                                                    |this, checked, _, cx| {
+                                                                        this.copy_metadata_selective =
+                                                                            *checked;
+                                                                        cx.notify();
+                                                                    },
+                                                                )),
+                                                            )
+                                                        },
+                                                    )
+                                                    // Video exports: mux subtitles as a stream
+                                                    .when(
+                                                        self.export_format.is_video(),
+                                                        |this| {
+                                                            this.child(
+                                                                Checkbox::new("soft-subs-checkbox")
+                                                                    .label("Soft subtitles")
+                                                                    .checked(self.soft_subtitles)
+                                                                    .disabled(!is_valid)
+                                                                    .on_click(cx.listener(
+                                                                        |this, checked, _, cx| {
+                                                                            this.soft_subtitles =
+                                                                                *checked;
+                                                                            cx.notify();
+                                                                        },
+                                                                    )),
+                                                            )
+                                                        },
+                                                    )
+                                                    // MP4 exports: encode on the GPU when possible
+                                                    .when(
+                                                        self.export_format == ExportFormat::Video,
+                                                        |this| {
+                                                            this.child(
+                                                                Checkbox::new(
+                                                                    "gpu-encoder-checkbox",
+                                                                )
+                                                                .label("Use GPU encoder")
+                                                                .checked(self.use_gpu_encoder)
+                                                                .disabled(!is_valid)
+                                                                .on_click(cx.listener(
+                                                                    |this, checked, _, cx| {
+                                                                        this.use_gpu_encoder =
+                                                                            *checked;
+                                                                        cx.notify();
+                                                                    },
+                                                                )),
+                                                            )
+                                                        },
+                                                    )
+                                                    // Burned-in subtitles: don't cut captions off
+                                                    .when(
+                                                        !matches!(
+                                                            self.export_format,
+                                                            ExportFormat::Audio
+                                                                | ExportFormat::Frame
+                                                        ),
+                                                        |this| {
+                                                            this.child(
+                                                                Checkbox::new(
+                                                                    "whole-captions-checkbox",
+                                                                )
+                                                                .label("Whole captions")
+                                                                .checked(self.whole_captions)
+                                                                .disabled(!is_valid)
+                                                                .on_click(cx.listener(
+                                                                    |this, checked, _, cx| {
+                                                                        this.whole_captions =
+                                                                            *checked;
+                                                                        cx.notify();
+                                                                    },
+                                                                )),
+                                                            )
+                                                        },
+                                                    ),
                                             )
                                     }),
-                            ), // Display total clip length and export button (always visible, greyed out if invalid)
+                            ) // Display total clip length and export button (always visible, greyed out if invalid)
+                            // Queued clips, each with a button to take it out again
+                            .when(!self.clip_queue.is_empty(), |this| {
+                                this.child(
+                                    div()
+                                        .flex()
+                                        .flex_col()
+                                        .gap_1()
+                                        .text_xs()
+                                        .text_color(text_muted_color)
+                                        .children(self.clip_queue.iter().enumerate().map(
+                                            |(index, clip)| {
+                                                div()
+                                                    .flex()
+                                                    .flex_row()
+                                                    .items_center()
+                                                    .gap_2()
+                                                    .child(format!(
+                                                        "{}. {} - {} {}",
+                                                        index + 1,
+                                                        Self::format_time_ms(
+                                                            clip.job.clip_start * 1000.0
+                                                        ),
+                                                        Self::format_time_ms(
+                                                            clip.job.clip_end * 1000.0
+                                                        ),
+                                                        clip.job.export_format.name()
+                                                    ))
+                                                    .when(clip.error.is_some(), |this| {
+                                                        this.child(
+                                                            div()
+                                                                .text_color(error_color)
+                                                                .child("failed"),
+                                                        )
+                                                    })
+                                                    .child(
+                                                        div()
+                                                            .px_1()
+                                                            .rounded_md()
+                                                            .cursor_pointer()
+                                                            .hover(move |style| style.bg(hover_bg))
+                                                            .on_mouse_down(
+                                                                MouseButton::Left,
+                                                                cx.listener(move |this, _, _, cx| {
+                                                                    this.remove_from_queue(index, cx);
+                                                                }),
+                                                            )
+                                                            .child("×"),
+                                                    )
+                                            },
+                                        )),
+                                )
+                            }),
                     )
                     // Center: Play/pause and Play Clip buttons
                     .child(
@@ -1137,7 +4281,43 @@ impl Render for ControlsWindow {
                             .gap_2()
                             .child(
                                 div()
-                                    .px_6()
+                                    .px_6()
+                                    .py_3()
+                                    .bg(hover_bg)
+                                    .rounded_md()
+                                    .cursor_pointer()
+                                    .text_color(text_color)
+                                    .hover(move |style| style.bg(bg))
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(|this, _, _, cx| {
+                                            this.toggle_play(cx);
+                                        }),
+                                    )
+                                    .child(if self.is_playing { "Pause" } else { "Play" }),
+                            )
+                            // Stop: pause and rewind to the start
+                            .child(
+                                div()
+                                    .px_3()
+                                    .py_3()
+                                    .bg(hover_bg)
+                                    .rounded_md()
+                                    .cursor_pointer()
+                                    .text_color(text_color)
+                                    .hover(move |style| style.bg(bg))
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(|this, _, window, cx| {
+                                            this.stop(window, cx);
+                                        }),
+                                    )
+                                    .child("Stop"),
+                            )
+                            // Frame step buttons (also `,` and `.`)
+                            .child(
+                                div()
+                                    .px_3()
                                     .py_3()
                                     .bg(hover_bg)
                                     .rounded_md()
@@ -1147,22 +4327,94 @@ impl Render for ControlsWindow {
                                     .on_mouse_down(
                                         MouseButton::Left,
                                         cx.listener(|this, _, _, cx| {
-                                            let app_state = cx.global::<AppState>();
-                                            let video_player = app_state.video_player.clone();
-                                            if let Ok(player) = video_player.lock() {
-                                                if this.is_playing {
-                                                    if let Err(e) = player.pause() {
-                                                        eprintln!("Failed to pause: {}", e);
-                                                    }
-                                                } else {
-                                                    if let Err(e) = player.play() {
-                                                        eprintln!("Failed to play: {}", e);
-                                                    }
-                                                }
-                                            };
+                                            this.step_frame(false, cx);
                                         }),
                                     )
-                                    .child(if self.is_playing { "Pause" } else { "Play" }),
+                                    .child("<"),
+                            )
+                            .child(
+                                div()
+                                    .px_3()
+                                    .py_3()
+                                    .bg(hover_bg)
+                                    .rounded_md()
+                                    .cursor_pointer()
+                                    .text_color(text_color)
+                                    .hover(move |style| style.bg(bg))
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(|this, _, _, cx| {
+                                            this.step_frame(true, cx);
+                                        }),
+                                    )
+                                    .child(">"),
+                            )
+                            // Previous/next chapter (disabled for files without chapters)
+                            .child(
+                                div()
+                                    .px_3()
+                                    .py_3()
+                                    .rounded_md()
+                                    .when(has_chapters, |this| {
+                                        this.bg(hover_bg)
+                                            .cursor_pointer()
+                                            .text_color(text_color)
+                                            .hover(move |style| style.bg(bg))
+                                    })
+                                    .when(!has_chapters, |this| {
+                                        this.bg(bg)
+                                            .cursor_not_allowed()
+                                            .text_color(text_disabled_color)
+                                    })
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(|this, _, _, cx| {
+                                            this.seek_chapter(-1, cx);
+                                        }),
+                                    )
+                                    .child("Prev Chapter"),
+                            )
+                            .child(
+                                div()
+                                    .px_3()
+                                    .py_3()
+                                    .rounded_md()
+                                    .when(has_chapters, |this| {
+                                        this.bg(hover_bg)
+                                            .cursor_pointer()
+                                            .text_color(text_color)
+                                            .hover(move |style| style.bg(bg))
+                                    })
+                                    .when(!has_chapters, |this| {
+                                        this.bg(bg)
+                                            .cursor_not_allowed()
+                                            .text_color(text_disabled_color)
+                                    })
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(|this, _, _, cx| {
+                                            this.seek_chapter(1, cx);
+                                        }),
+                                    )
+                                    .child("Next Chapter"),
+                            )
+                            // Save the current frame as an image
+                            .child(
+                                div()
+                                    .px_3()
+                                    .py_3()
+                                    .bg(hover_bg)
+                                    .rounded_md()
+                                    .cursor_pointer()
+                                    .text_color(text_color)
+                                    .hover(move |style| style.bg(bg))
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(|this, _, _, cx| {
+                                            this.take_screenshot(cx);
+                                        }),
+                                    )
+                                    .child("Screenshot"),
                             )
                             .child({
                                 let start_ms = Self::parse_masked_time_ms(
@@ -1218,16 +4470,26 @@ impl Render for ControlsWindow {
                                                             ClockTime::from_nseconds(nanos);
 
                                                         if let Err(e) = player.seek(clock_time) {
-                                                            eprintln!(
-                                                                "Failed to seek to clip start: {}",
-                                                                e
+                                                            crate::notify_error(
+                                                                cx,
+                                                                format!(
+                                                                    "Failed to seek to clip start: {}",
+                                                                    e
+                                                                ),
                                                             );
                                                         } else if let Err(e) = player.play() {
-                                                            eprintln!("Failed to play clip: {}", e);
+                                                            crate::notify_error(
+                                                                cx,
+                                                                format!(
+                                                                    "Failed to play clip: {}",
+                                                                    e
+                                                                ),
+                                                            );
                                                         } else {
                                                             // Set up clip playback mode
                                                             this.is_playing_clip = true;
                                                             this.clip_playback_end = Some(end);
+                                                            this.clip_loop_seek_pending = false;
                                                             this.last_seek_time = Some(
                                                                 this.current_position * 1000.0,
                                                             );
@@ -1238,6 +4500,128 @@ impl Render for ControlsWindow {
                                         }),
                                     )
                                     .child("Play Clip")
+                            })
+                            // A-B loop toggle: Play Clip repeats the clip until it's turned off
+                            .child(
+                                div()
+                                    .px_3()
+                                    .py_3()
+                                    .rounded_md()
+                                    .cursor_pointer()
+                                    .text_color(text_color)
+                                    .bg(if self.ab_loop_enabled {
+                                        list_active_bg
+                                    } else {
+                                        hover_bg
+                                    })
+                                    .hover(move |style| style.bg(bg))
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(|this, _, _, cx| {
+                                            this.ab_loop_enabled = !this.ab_loop_enabled;
+                                            cx.notify();
+                                        }),
+                                    )
+                                    .child("Loop Clip"),
+                            )
+                            // Whole-file loop: mpv restarts the file at its end
+                            .child(
+                                div()
+                                    .px_3()
+                                    .py_3()
+                                    .rounded_md()
+                                    .cursor_pointer()
+                                    .text_color(text_color)
+                                    .bg(if self.loop_file { list_active_bg } else { hover_bg })
+                                    .hover(move |style| style.bg(bg))
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(|this, _, _, cx| {
+                                            this.set_loop_file(!this.loop_file, cx);
+                                        }),
+                                    )
+                                    .child("Loop"),
+                            )
+                            // Shows or hides the picture adjustment sliders
+                            .child(
+                                div()
+                                    .px_3()
+                                    .py_3()
+                                    .rounded_md()
+                                    .cursor_pointer()
+                                    .text_color(text_color)
+                                    .bg(if self.adjust_open { list_active_bg } else { hover_bg })
+                                    .hover(move |style| style.bg(bg))
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(|this, _, _, cx| {
+                                            this.adjust_open = !this.adjust_open;
+                                            cx.notify();
+                                        }),
+                                    )
+                                    .child("Adjust"),
+                            )
+                            // Mute toggle and volume slider
+                            .child({
+                                let muted = cx.global::<AppState>().muted;
+                                div()
+                                    .p_2()
+                                    .rounded_md()
+                                    .cursor_pointer()
+                                    .hover(move |style| style.bg(hover_bg))
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(|_this, _, _, cx| {
+                                            let video_player =
+                                                cx.global::<AppState>().video_player.clone();
+                                            let result = match video_player.lock() {
+                                                Ok(player) => player.toggle_mute(),
+                                                Err(_) => return,
+                                            };
+                                            match result {
+                                                Ok(muted) => {
+                                                    cx.update_global::<AppState, _>(|state, _| {
+                                                        state.muted = muted;
+                                                    });
+                                                    cx.notify();
+                                                }
+                                                Err(e) => crate::notify_error(
+                                                    cx,
+                                                    format!("Failed to toggle mute: {}", e),
+                                                ),
+                                            }
+                                        }),
+                                    )
+                                    .child(
+                                        svg()
+                                            .path(if muted {
+                                                "icons/volume-x.svg"
+                                            } else {
+                                                "icons/volume.svg"
+                                            })
+                                            .size(px(16.0))
+                                            .text_color(text_color),
+                                    )
+                            })
+                            .when(!compact, |el| {
+                                el.child(div().w(px(100.0)).child(Slider::new(&self.volume_slider)))
+                                    .child(
+                                        Checkbox::new("scrub-mute-checkbox")
+                                            .label("Mute while scrubbing")
+                                            .checked(self.mute_while_scrubbing)
+                                            .on_click(cx.listener(|this, checked, _, cx| {
+                                                this.mute_while_scrubbing = *checked;
+                                                cx.notify();
+                                            })),
+                                    )
+                            })
+                            // Audio track picker, once the file's tracks are known
+                            .when(self.audio_tracks_file.is_some(), |el| {
+                                el.child(
+                                    div()
+                                        .w(px(160.0))
+                                        .child(Select::new(&self.audio_track_select)),
+                                )
                             }),
                     )
                     // Right side: Display subtitles checkbox and styling controls
@@ -1245,6 +4629,13 @@ impl Render for ControlsWindow {
                         let display_subtitles_enabled = self.display_subtitles_enabled;
                         let subtitle_bold_enabled = self.subtitle_bold_enabled;
                         let subtitle_italic_enabled = self.subtitle_italic_enabled;
+                        let subtitle_background_enabled = self.subtitle_background_enabled;
+                        let subtitle_title_safe_enabled = self.subtitle_title_safe_enabled;
+                        let subtitle_settings = &cx.global::<AppState>().subtitle_settings;
+                        let outline_color = subtitle_settings.outline_color.clone();
+                        let text_color_hex = subtitle_settings.color.clone();
+                        let subtitle_alignment = subtitle_settings.alignment;
+                        let subtitle_shadow_enabled = subtitle_settings.shadow;
 
                         div()
                             .flex()
@@ -1255,7 +4646,7 @@ impl Render for ControlsWindow {
                             .border_1()
                             .border_color(border_variant_color)
                             .rounded(px(4.))
-                            .min_w(px(250.0))
+                            .when(!compact, |el| el.min_w(px(250.0)))
                             // Top row: Display subtitles, Bold, Italic checkboxes
                             .child(
                                 div()
@@ -1332,8 +4723,473 @@ impl Render for ControlsWindow {
                                             .pt_neg_1(), //this moves the "size: x" and slider below it up ever so slightly to be even with the font dropdown
                                     ),
                             )
+                            // Text color: a grid of swatches and a hex field
+                            .child(
+                                div()
+                                    .flex()
+                                    .items_center()
+                                    .gap_2()
+                                    .child(
+                                        // Two rows of four 14px swatches
+                                        div()
+                                            .flex()
+                                            .flex_wrap()
+                                            .w(px(68.0))
+                                            .gap_1()
+                                            .children(SUBTITLE_TEXT_COLORS.iter().map(
+                                                |&(hex, rgb)| {
+                                                    let selected =
+                                                        text_color_hex.eq_ignore_ascii_case(hex);
+                                                    div()
+                                                        .size(px(14.0))
+                                                        .rounded(px(3.0))
+                                                        .cursor_pointer()
+                                                        .bg(gpui::rgb(rgb))
+                                                        .border_1()
+                                                        .border_color(if selected {
+                                                            text_color
+                                                        } else {
+                                                            border_variant_color
+                                                        })
+                                                        .on_mouse_down(
+                                                            MouseButton::Left,
+                                                            cx.listener(move |this, _, window, cx| {
+                                                                this.pick_subtitle_color(
+                                                                    hex, window, cx,
+                                                                );
+                                                            }),
+                                                        )
+                                                },
+                                            )),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex_1()
+                                            .rounded_md()
+                                            .when(self.subtitle_color_invalid, |this| {
+                                                this.border_1().border_color(error_color)
+                                            })
+                                            .child(Input::new(&self.subtitle_color_input).xsmall()),
+                                    ),
+                            )
+                            // Background box toggle and opacity slider
+                            .child(
+                                div()
+                                    .flex()
+                                    .items_center()
+                                    .gap_2()
+                                    .child(
+                                        Checkbox::new("subtitle-background-checkbox")
+                                            .label("Background")
+                                            .checked(subtitle_background_enabled)
+                                            .on_click(cx.listener(|this, checked, window, cx| {
+                                                this.toggle_subtitle_background(
+                                                    *checked, window, cx,
+                                                );
+                                            })),
+                                    )
+                                    .when(subtitle_background_enabled, |this| {
+                                        this.child(
+                                            div()
+                                                .flex_1()
+                                                .flex()
+                                                .flex_col()
+                                                .gap_1()
+                                                .child(
+                                                    div()
+                                                        .text_xs()
+                                                        .text_color(text_muted_color)
+                                                        .child(format!(
+                                                            "Opacity: {:.0}%",
+                                                            self.subtitle_background_opacity_slider
+                                                                .read(cx)
+                                                                .value()
+                                                                .end()
+                                                        )),
+                                                )
+                                                .child(Slider::new(
+                                                    &self.subtitle_background_opacity_slider,
+                                                )),
+                                        )
+                                    }),
+                            )
+                            // Outline color swatches, width slider and shadow toggle
+                            // (the background box replaces the outline)
+                            .when(!subtitle_background_enabled, |this| {
+                                this.child(
+                                    div()
+                                        .flex()
+                                        .items_center()
+                                        .gap_2()
+                                        .child(div().flex().gap_1().children(
+                                            SUBTITLE_OUTLINE_COLORS.iter().map(|&(hex, rgb)| {
+                                                let selected =
+                                                    outline_color.eq_ignore_ascii_case(hex);
+                                                div()
+                                                    .size(px(14.0))
+                                                    .rounded(px(3.0))
+                                                    .cursor_pointer()
+                                                    .bg(gpui::rgb(rgb))
+                                                    .border_1()
+                                                    .border_color(if selected {
+                                                        text_color
+                                                    } else {
+                                                        border_variant_color
+                                                    })
+                                                    .on_mouse_down(
+                                                        MouseButton::Left,
+                                                        cx.listener(move |this, _, _, cx| {
+                                                            this.set_subtitle_outline_color(
+                                                                hex, cx,
+                                                            );
+                                                        }),
+                                                    )
+                                            }),
+                                        ))
+                                        .child(
+                                            div()
+                                                .flex_1()
+                                                .flex()
+                                                .flex_col()
+                                                .gap_1()
+                                                .child(
+                                                    div()
+                                                        .text_xs()
+                                                        .text_color(text_muted_color)
+                                                        .child(format!(
+                                                            "Outline: {:.1}",
+                                                            self.subtitle_outline_width_slider
+                                                                .read(cx)
+                                                                .value()
+                                                                .end()
+                                                        )),
+                                                )
+                                                .child(Slider::new(
+                                                    &self.subtitle_outline_width_slider,
+                                                )),
+                                        )
+                                        .child(
+                                            Checkbox::new("subtitle-shadow-checkbox")
+                                                .label("Shadow")
+                                                .checked(subtitle_shadow_enabled)
+                                                .on_click(cx.listener(|this, checked, _, cx| {
+                                                    this.toggle_subtitle_shadow(*checked, cx);
+                                                })),
+                                        ),
+                                )
+                            })
+                            // Title-safe region toggle and inset slider
+                            .child(
+                                div()
+                                    .flex()
+                                    .items_center()
+                                    .gap_2()
+                                    .child(
+                                        Checkbox::new("subtitle-title-safe-checkbox")
+                                            .label("Title-safe")
+                                            .checked(subtitle_title_safe_enabled)
+                                            .on_click(cx.listener(|this, checked, window, cx| {
+                                                this.toggle_subtitle_title_safe(
+                                                    *checked, window, cx,
+                                                );
+                                            })),
+                                    )
+                                    .when(subtitle_title_safe_enabled, |this| {
+                                        this.child(
+                                            div()
+                                                .flex_1()
+                                                .flex()
+                                                .flex_col()
+                                                .gap_1()
+                                                .child(
+                                                    div()
+                                                        .text_xs()
+                                                        .text_color(text_muted_color)
+                                                        .child(format!(
+                                                            "Inset: {:.0}%",
+                                                            self.subtitle_title_safe_slider
+                                                                .read(cx)
+                                                                .value()
+                                                                .end()
+                                                        )),
+                                                )
+                                                .child(Slider::new(
+                                                    &self.subtitle_title_safe_slider,
+                                                )),
+                                        )
+                                    }),
+                            )
+                            // 3x3 position picker, laid out like the numpad alignments
+                            .child(
+                                div()
+                                    .flex()
+                                    .items_center()
+                                    .gap_2()
+                                    .child(
+                                        div()
+                                            .text_xs()
+                                            .text_color(text_muted_color)
+                                            .child("Position"),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .flex_wrap()
+                                            .w(px(40.0))
+                                            .gap(px(2.0))
+                                            .children([7u8, 8, 9, 4, 5, 6, 1, 2, 3].map(
+                                                |alignment| {
+                                                    div()
+                                                        .size(px(12.0))
+                                                        .rounded(px(2.0))
+                                                        .cursor_pointer()
+                                                        .border_1()
+                                                        .border_color(border_variant_color)
+                                                        .bg(if alignment == subtitle_alignment {
+                                                            list_active_bg
+                                                        } else {
+                                                            hover_bg
+                                                        })
+                                                        .hover(move |style| style.bg(bg))
+                                                        .on_mouse_down(
+                                                            MouseButton::Left,
+                                                            cx.listener(move |this, _, _, cx| {
+                                                                this.set_subtitle_alignment(
+                                                                    alignment, cx,
+                                                                );
+                                                            }),
+                                                        )
+                                                },
+                                            )),
+                                    ),
+                            )
                             .pb_neg_1()
                     }),
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queued_output_path() {
+        let none_exist = |_: &Path| false;
+        assert_eq!(
+            queued_output_path(Path::new("/out/movie_clip.mp4"), 1, none_exist),
+            PathBuf::from("/out/movie_clip_1.mp4")
+        );
+        assert_eq!(
+            queued_output_path(Path::new("/out/movie_sheet.png"), 5, none_exist),
+            PathBuf::from("/out/movie_sheet_5.png")
+        );
+
+        // An earlier export's file isn't overwritten
+        let taken = |path: &Path| path == Path::new("/out/movie_clip_2.mp4");
+        assert_eq!(
+            queued_output_path(Path::new("/out/movie_clip.mp4"), 2, taken),
+            PathBuf::from("/out/movie_clip_3.mp4")
+        );
+    }
+
+    #[test]
+    fn test_next_numbered_path() {
+        let none_exist = |_: &Path| false;
+        assert_eq!(
+            next_numbered_path(Path::new("/out/movie_clip.mp4"), none_exist),
+            PathBuf::from("/out/movie_clip_2.mp4")
+        );
+        assert_eq!(
+            next_numbered_path(Path::new("/out/movie_clip_2.mp4"), none_exist),
+            PathBuf::from("/out/movie_clip_3.mp4")
+        );
+
+        // Skips numbers that are already taken
+        let taken = |path: &Path| path == Path::new("/out/a_2.gif");
+        assert_eq!(
+            next_numbered_path(Path::new("/out/a.gif"), taken),
+            PathBuf::from("/out/a_3.gif")
+        );
+    }
+
+    #[test]
+    fn test_screenshot_filename() {
+        assert_eq!(screenshot_filename("movie", 83.5), "movie_frame_83500.png");
+        assert_eq!(screenshot_filename("movie", 0.0), "movie_frame_0.png");
+    }
+
+    #[test]
+    fn test_parse_grid_size() {
+        assert_eq!(ControlsWindow::parse_grid_size("6"), 6);
+        assert_eq!(ControlsWindow::parse_grid_size(""), 4);
+        assert_eq!(ControlsWindow::parse_grid_size("0"), 4);
+        assert_eq!(ControlsWindow::parse_grid_size("99"), 4);
+    }
+
+    #[test]
+    fn test_parse_gif_setting() {
+        let parse = |value| ControlsWindow::parse_gif_setting(value, 10, 1..=30, "frame rate");
+        assert_eq!(parse(""), Ok(10));
+        assert_eq!(parse(" 24 "), Ok(24));
+        assert_eq!(parse("30"), Ok(30));
+        assert!(parse("0").is_err());
+        assert!(parse("31").is_err());
+        assert!(parse("fast").is_err());
+    }
+
+    #[test]
+    fn test_parse_target_size() {
+        assert_eq!(ControlsWindow::parse_target_size(" 8 "), Some(8));
+        assert_eq!(ControlsWindow::parse_target_size(""), None);
+        assert_eq!(ControlsWindow::parse_target_size("0"), None);
+        assert_eq!(ControlsWindow::parse_target_size("8.5"), None);
+    }
+
+    #[test]
+    fn test_parse_intro_hold() {
+        assert_eq!(ControlsWindow::parse_intro_hold("1.5"), Some(1.5));
+        assert_eq!(ControlsWindow::parse_intro_hold(""), None);
+        assert_eq!(ControlsWindow::parse_intro_hold("0"), None);
+        assert_eq!(ControlsWindow::parse_intro_hold("-2"), None);
+        assert_eq!(ControlsWindow::parse_intro_hold("600"), Some(MAX_INTRO_HOLD_SECS));
+    }
+
+    #[test]
+    fn test_ab_loop_press() {
+        // a, then b after it, then a third press clears
+        let (a, b) = ab_loop_press(None, None, LoopPoint::A, 1000.0);
+        assert_eq!((a, b), (Some(1000.0), None));
+        let (a, b) = ab_loop_press(a, b, LoopPoint::B, 4000.0);
+        assert_eq!((a, b), (Some(1000.0), Some(4000.0)));
+        assert_eq!(ab_loop_press(a, b, LoopPoint::A, 2000.0), (None, None));
+
+        // B without A, or before A, is ignored
+        assert_eq!(ab_loop_press(None, None, LoopPoint::B, 4000.0), (None, None));
+        assert_eq!(
+            ab_loop_press(Some(5000.0), None, LoopPoint::B, 4000.0),
+            (Some(5000.0), None)
+        );
+    }
+
+    fn entry(start_ms: u64, end_ms: u64) -> crate::subtitle_extractor::SubtitleEntry {
+        crate::subtitle_extractor::SubtitleEntry {
+            start_ms,
+            end_ms,
+            text: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_expand_to_whole_subtitles() {
+        let entries = vec![entry(1000, 3000), entry(5000, 6000), entry(9000, 12000)];
+        // Both bounds cut a caption
+        assert_eq!(
+            expand_to_whole_subtitles(2000.0, 10000.0, &entries),
+            (1000.0, 12000.0)
+        );
+        // Bounds on caption edges or in gaps are left alone
+        assert_eq!(
+            expand_to_whole_subtitles(3000.0, 7000.0, &entries),
+            (3000.0, 7000.0)
+        );
+    }
+
+    #[test]
+    fn test_expand_to_whole_subtitles_follows_overlaps() {
+        let entries = vec![entry(1000, 4000), entry(3000, 6000)];
+        assert_eq!(
+            expand_to_whole_subtitles(5000.0, 8000.0, &entries),
+            (1000.0, 8000.0)
+        );
+    }
+
+    #[test]
+    fn test_offset_subtitle_entries() {
+        let entries = vec![entry(1000, 3000), entry(5000, 6000)];
+        let later = offset_subtitle_entries(&entries, 1500);
+        assert_eq!((later[0].start_ms, later[0].end_ms), (2500, 4500));
+        let earlier = offset_subtitle_entries(&entries, -2000);
+        assert_eq!((earlier[0].start_ms, earlier[0].end_ms), (0, 1000));
+        assert_eq!((earlier[1].start_ms, earlier[1].end_ms), (3000, 4000));
+    }
+
+    #[test]
+    fn test_scrubber_step_is_one_frame() {
+        assert_eq!(scrubber_step(25.0), 0.04);
+        assert!((scrubber_step(59.94) - 0.016683).abs() < 1e-6);
+        assert_eq!(scrubber_step(0.0), 0.1);
+        assert_eq!(scrubber_step(f32::NAN), 0.1);
+    }
+
+    #[test]
+    fn test_snap_to_frame() {
+        // 25fps frames are 40ms apart
+        assert_eq!(snap_to_frame(1.013, 25.0), Some(1.0));
+        assert_eq!(snap_to_frame(1.027, 25.0), Some(1.04));
+        assert_eq!(snap_to_frame(1.0002, 25.0), None);
+        assert_eq!(snap_to_frame(1.013, 0.0), None);
+    }
+
+    #[test]
+    fn test_scrub_position_frames_unless_coarse() {
+        // A 2000s video at 25fps scrubs in 0.04s frames, or 2s steps with Alt
+        assert!((scrub_position(101.31, 25.0, 2000.0, false) - 101.32).abs() < 1e-3);
+        assert_eq!(scrub_position(101.3, 25.0, 2000.0, true), 102.0);
+        // Short videos never step finer than a frame
+        assert!((scrub_position(1.01, 25.0, 10.0, true) - 1.0).abs() < 1e-6);
+        assert_eq!(scrub_position(2001.0, 25.0, 2000.0, false), 2000.0);
+    }
+
+    #[test]
+    fn test_waveform_columns() {
+        let peaks = [0.1, 0.5, 0.2, 0.8];
+        // Fewer bars than peaks take the loudest peak they cover
+        assert_eq!(waveform_columns(&peaks, 2), vec![0.5, 0.8]);
+        // More bars than peaks repeat the nearest one
+        assert_eq!(
+            waveform_columns(&peaks, 8),
+            vec![0.1, 0.1, 0.5, 0.5, 0.2, 0.2, 0.8, 0.8]
+        );
+        assert!(waveform_columns(&[], 100).is_empty());
+        assert!(waveform_columns(&peaks, 0).is_empty());
+    }
+
+    #[test]
+    fn test_hover_time_ms() {
+        // A 100px scrubber at x=50 over a 60s video
+        assert_eq!(hover_time_ms(100.0, 50.0, 100.0, 60.0), Some(30_000));
+        // Snapped down to whole seconds so nearby hovers reuse a frame
+        assert_eq!(hover_time_ms(100.5, 50.0, 100.0, 60.0), Some(30_000));
+        // Outside the scrubber clamps to its ends
+        assert_eq!(hover_time_ms(0.0, 50.0, 100.0, 60.0), Some(0));
+        assert_eq!(hover_time_ms(500.0, 50.0, 100.0, 60.0), Some(60_000));
+        assert_eq!(hover_time_ms(100.0, 50.0, 0.0, 60.0), None);
+        assert_eq!(hover_time_ms(100.0, 50.0, 100.0, 0.0), None);
+    }
+
+    #[test]
+    fn test_should_pause_at_and_after_end() {
+        assert!(!should_pause(4999.0, 5000.0, None));
+        assert!(should_pause(5000.0, 5000.0, None));
+        assert!(should_pause(5016.0, 5000.0, Some(1000.0)));
+    }
+
+    #[test]
+    fn test_should_pause_ignores_stale_position_right_after_seek() {
+        // Play Clip pressed while sitting past the clip end: the player still
+        // reports the old position until the seek to the clip start lands
+        assert!(!should_pause(8000.0, 5000.0, Some(8000.0)));
+        assert!(!should_pause(8000.05, 5000.0, Some(8000.0)));
+    }
+
+    #[test]
+    fn test_should_pause_once_position_moves_after_seek() {
+        // Position briefly ahead of the seek target still counts as moved on
+        assert!(should_pause(8000.5, 5000.0, Some(8000.0)));
+        // Position behind the seek point (the seek landed at the clip start)
+        assert!(!should_pause(1000.0, 5000.0, Some(8000.0)));
+        // ...and playback then reaching the end
+        assert!(should_pause(5001.0, 5000.0, Some(8000.0)));
+    }
+}
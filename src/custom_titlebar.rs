@@ -9,15 +9,6 @@ use gpui_component::button::{Button, ButtonVariants};
 use gpui_component::menu::DropdownMenu;
 use gpui_component::{ActiveTheme, Theme};
 
-#[cfg(target_os = "windows")]
-use raw_window_handle::HasWindowHandle;
-#[cfg(target_os = "windows")]
-use windows::Win32::Foundation::*;
-#[cfg(target_os = "windows")]
-use windows::Win32::UI::Input::KeyboardAndMouse::ReleaseCapture;
-#[cfg(target_os = "windows")]
-use windows::Win32::UI::WindowsAndMessaging::*;
-
 pub struct CustomTitlebar {
     title: SharedString,
 }
@@ -33,32 +24,6 @@ impl CustomTitlebar {
         self.title = title.into();
     }
 
-    #[cfg(target_os = "windows")]
-    fn start_window_drag(window: &mut Window) {
-        // Get the raw window handle (HWND)
-        if let Ok(handle) = window.window_handle() {
-            if let raw_window_handle::RawWindowHandle::Win32(win32_handle) = handle.as_raw() {
-                unsafe {
-                    let hwnd = HWND(win32_handle.hwnd.get() as _);
-                    // Release mouse capture to allow Windows to handle dragging
-                    let _ = ReleaseCapture();
-                    // Send WM_NCLBUTTONDOWN with HTCAPTION to start window dragging
-                    SendMessageW(
-                        hwnd,
-                        WM_NCLBUTTONDOWN,
-                        Some(WPARAM(HTCAPTION as usize)),
-                        Some(LPARAM(0)),
-                    );
-                }
-            }
-        }
-    }
-
-    #[cfg(not(target_os = "windows"))]
-    fn start_window_drag(_window: &mut Window) {
-        // No-op on non-Windows platforms
-    }
-
     /// Render the theme palette button with dropdown theme picker (Windows only)
     #[cfg(target_os = "windows")]
     fn render_theme_menu(&self, cx: &mut Context<Self>) -> impl IntoElement {
@@ -140,7 +105,7 @@ impl Render for CustomTitlebar {
                         this.on_mouse_down(
                             MouseButton::Left,
                             cx.listener(|_, _, window, _| {
-                                Self::start_window_drag(window);
+                                crate::platform::start_window_drag(window);
                             }),
                         )
                     })
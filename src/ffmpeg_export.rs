@@ -2,106 +2,207 @@
 //!
 //! This module uses the system ffmpeg CLI to export video clips.
 
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
-use std::process::Command;
-
-/// Get list of supported video file extensions
-pub fn get_video_extensions() -> Vec<&'static str> {
-    vec![
-        "mp4", "avi", "mov", "mkv", "wmv", "flv", "webm", "m4v", "ts",
-    ]
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+use std::time::SystemTime;
+
+/// ffmpeg executable chosen in the settings (empty = `ffmpeg` from PATH)
+static FFMPEG_PATH: RwLock<String> = RwLock::new(String::new());
+
+/// ffprobe executable chosen in the settings (empty = `ffprobe` from PATH)
+static FFPROBE_PATH: RwLock<String> = RwLock::new(String::new());
+
+/// External command-line tools the app runs
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tool {
+    Ffmpeg,
+    Ffprobe,
 }
 
-/// Get video framerate using ffprobe
-fn get_video_fps(input_path: &str) -> Result<f32, String> {
-    let output = Command::new("ffprobe")
-        .arg("-v")
-        .arg("error")
-        .arg("-select_streams")
-        .arg("v:0")
-        .arg("-show_entries")
-        .arg("stream=r_frame_rate")
-        .arg("-of")
-        .arg("default=noprint_wrappers=1:nokey=1")
-        .arg(input_path)
-        .output()
-        .map_err(|e| format!("Failed to execute ffprobe: {}", e))?;
+impl Tool {
+    pub const ALL: [Tool; 2] = [Self::Ffmpeg, Self::Ffprobe];
 
-    if !output.status.success() {
-        return Ok(30.0); // Default to 30fps if detection fails
+    /// Executable name, looked up on PATH when no path is set
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Ffmpeg => "ffmpeg",
+            Self::Ffprobe => "ffprobe",
+        }
+    }
+
+    fn path_setting(self) -> &'static RwLock<String> {
+        match self {
+            Self::Ffmpeg => &FFMPEG_PATH,
+            Self::Ffprobe => &FFPROBE_PATH,
+        }
     }
 
-    let fps_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    /// Use this executable for the tool (None goes back to the PATH lookup)
+    pub fn set_path(self, path: Option<String>) {
+        if let Ok(mut setting) = self.path_setting().write() {
+            *setting = path.unwrap_or_default();
+        }
+    }
 
-    // Parse fraction format (e.g., "30/1" or "2997/100")
-    if let Some((num_str, den_str)) = fps_str.split_once('/') {
-        let numerator: f32 = num_str.parse().unwrap_or(30.0);
-        let denominator: f32 = den_str.parse().unwrap_or(1.0);
-        if denominator > 0.0 {
-            return Ok(numerator / denominator);
+    /// The executable run for this tool
+    pub fn path(self) -> String {
+        match self.path_setting().read() {
+            Ok(setting) if !setting.is_empty() => setting.clone(),
+            _ => self.name().to_string(),
         }
     }
 
-    // Try parsing as simple float
-    fps_str.parse::<f32>().or(Ok(30.0))
+    /// Start building a command that runs this tool
+    pub fn command(self) -> Command {
+        Command::new(self.path())
+    }
+
+    /// Error message for when this tool's command couldn't be started
+    pub fn spawn_error(self, e: &std::io::Error) -> String {
+        spawn_error_message(self, &self.path(), e)
+    }
 }
 
-/// Get video resolution (width, height) using ffprobe
-pub fn get_video_resolution(input_path: &str) -> Result<(u32, u32), String> {
-    let output = Command::new("ffprobe")
-        .arg("-v")
-        .arg("error")
-        .arg("-select_streams")
-        .arg("v:0")
-        .arg("-show_entries")
-        .arg("stream=width,height")
-        .arg("-of")
-        .arg("csv=p=0")
-        .arg(input_path)
+/// How to get ffmpeg, shown when it can't be found
+const FFMPEG_INSTALL_HINT: &str = "Install FFmpeg (e.g. `brew install ffmpeg` or your package \
+    manager) or pick the executables in Preferences > FFmpeg Location.";
+
+/// Error message for a tool run from `path` that couldn't be started
+///
+/// A missing executable gets a hint on how to install it instead of the raw OS error.
+fn spawn_error_message(tool: Tool, path: &str, e: &std::io::Error) -> String {
+    if e.kind() != std::io::ErrorKind::NotFound {
+        return format!("Failed to execute {}: {}", path, e);
+    }
+    if path == tool.name() {
+        format!("{} not found on PATH. {}", tool.name(), FFMPEG_INSTALL_HINT)
+    } else {
+        format!("{} not found at {}. {}", tool.name(), path, FFMPEG_INSTALL_HINT)
+    }
+}
+
+/// Start building an ffmpeg command (uses the configured executable)
+pub fn ffmpeg_command() -> Command {
+    Tool::Ffmpeg.command()
+}
+
+/// Start building an ffprobe command (uses the configured executable)
+pub fn ffprobe_command() -> Command {
+    Tool::Ffprobe.command()
+}
+
+/// Check that an executable really is the given tool by running `-version`
+///
+/// Returns the version line (e.g. "ffmpeg version 7.1 Copyright ...").
+pub fn check_tool(tool: Tool, path: &str) -> Result<String, String> {
+    let output = Command::new(path)
+        .arg("-version")
         .output()
-        .map_err(|e| format!("Failed to execute ffprobe: {}", e))?;
+        .map_err(|e| spawn_error_message(tool, path, &e))?;
 
     if !output.status.success() {
-        return Ok((1920, 1080)); // Default to 1080p if detection fails
+        return Err(format!("{} -version failed", path));
     }
 
-    let resolution_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    parse_version_line(tool, &String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| format!("{} doesn't look like {}", path, tool.name()))
+}
+
+/// Find the "<tool> version ..." line in `-version` output
+fn parse_version_line(tool: Tool, output: &str) -> Option<String> {
+    let prefix = format!("{} version ", tool.name());
+    output
+        .lines()
+        .find(|line| line.starts_with(&prefix))
+        .map(|line| line.trim().to_string())
+}
+
+/// The tools that can't be run with their current paths (checked with `-version`)
+pub fn missing_tools() -> Vec<Tool> {
+    Tool::ALL
+        .into_iter()
+        .filter(|&tool| match check_tool(tool, &tool.path()) {
+            Ok(version) => {
+                println!("Found {}", version);
+                false
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                true
+            }
+        })
+        .collect()
+}
 
-    // Parse "width,height" format
-    if let Some((width_str, height_str)) = resolution_str.split_once(',') {
-        let width: u32 = width_str.parse().expect("Failed to parse width");
-        let height: u32 = height_str.parse().expect("Failed to parse height");
-        return Ok((width, height));
+/// Startup warning for tools that can't be run, or None when they all work
+pub fn missing_tools_message(missing: &[Tool]) -> Option<String> {
+    let names: Vec<&str> = missing.iter().map(|tool| tool.name()).collect();
+    if names.is_empty() {
+        return None;
     }
+    Some(format!(
+        "{} could not be run, so exports, subtitles and file details won't work. {}",
+        names.join(" and "),
+        FFMPEG_INSTALL_HINT
+    ))
+}
+
+/// Get list of supported video file extensions
+pub fn get_video_extensions() -> Vec<&'static str> {
+    vec![
+        "mp4", "avi", "mov", "mkv", "wmv", "flv", "webm", "m4v", "ts",
+    ]
+}
+
+/// Get video framerate from the cached ffprobe results (30fps if detection fails)
+pub fn get_video_fps(input_path: &str) -> Result<f32, String> {
+    Ok(probe_file(input_path)
+        .ok()
+        .and_then(|probe| probe.fps())
+        .unwrap_or(30.0))
+}
+
+/// Get video resolution (width, height) from the cached ffprobe results
+/// (1080p if detection fails)
+pub fn get_video_resolution(input_path: &str) -> Result<(u32, u32), String> {
+    Ok(probe_file(input_path)
+        .ok()
+        .and_then(|probe| probe.resolution())
+        .unwrap_or((1920, 1080)))
+}
 
-    Ok((1920, 1080))
+/// Whether an ffprobe `field_order` value describes interlaced fields
+fn is_interlaced_field_order(field_order: &str) -> bool {
+    matches!(field_order, "tt" | "bb" | "tb" | "bt")
 }
 
+/// Whether an ffprobe `color_transfer` value is an HDR transfer function
+fn is_hdr_transfer(transfer: &str) -> bool {
+    matches!(transfer, "smpte2084" | "arib-std-b67")
+}
+
+/// Filter chain that tone-maps HDR (PQ/HLG, BT.2020) down to SDR BT.709
+///
+/// Linearizes with zscale, tone-maps with the Hable curve (keeps highlight
+/// detail without crushing midtones) and converts back to BT.709 primaries.
+const HDR_TONEMAP_FILTER: &str = "zscale=t=linear:npl=100,format=gbrpf32le,zscale=p=bt709,\
+    tonemap=tonemap=hable:desat=0,zscale=t=bt709:m=bt709:r=tv,format=yuv420p";
+
 /// Check if file needs advanced audio re-encoding based on channel layout
 fn check_if_advanced_audio_reencoding_needed(input_path: &str) -> Result<Option<String>, String> {
-    let output = Command::new("ffprobe")
-        .arg("-v")
-        .arg("error")
-        .arg("-select_streams")
-        .arg("a:0")
-        .arg("-show_entries")
-        .arg("stream=channel_layout")
-        .arg("-of")
-        .arg("default=noprint_wrappers=1:nokey=1")
-        .arg(input_path)
-        .output()
-        .map_err(|e| format!("Failed to execute ffprobe: {}", e))?;
-
-    if !output.status.success() {
+    let Ok(probe) = probe_file(input_path) else {
         return Ok(None);
-    }
-
-    let layout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    };
 
-    if layout.is_empty() || layout == "stereo" || layout == "mono" {
-        Ok(None)
-    } else {
-        Ok(Some(layout))
+    match probe.channel_layout() {
+        None | Some("") | Some("stereo") | Some("mono") => Ok(None),
+        Some(layout) => Ok(Some(layout.to_string())),
     }
 }
 
@@ -181,328 +282,2230 @@ fn get_audio_codec_args(input_path: &str) -> Result<Vec<String>, String> {
     Ok(vec!["-c:a".to_string(), "copy".to_string()])
 }
 
-/// Export a video clip from start_secs to end_secs using ffmpeg CLI
+/// Build the `subtitles=` filter that burns in a subtitle track with the user's styling
 ///
 /// # Arguments
-/// * `input_path` - Path to the input video file
-/// * `output_path` - Path where the output clip should be saved
-/// * `start_secs` - Start time in seconds
-/// * `end_secs` - End time in seconds
-/// * `subtitle_settings` - Optional subtitle settings (font, size, bold, italic, color)
-/// * `display_subtitles` - Whether to include burned-in subtitles in the output
-/// * `subtitle_track` - Optional subtitle track index to burn in
-/// * `source_video_width` - Width of the video as displayed in the player (for subtitle scaling)
-///
-/// # Returns
-/// * `Ok(())` on success
-/// * `Err(String)` with error message on failure
-pub fn export_clip(
+/// * `input_path` - Path to the input video file (subtitles are read from it)
+/// * `settings` - Subtitle styling (font, size, bold, italic, color)
+/// * `subtitle_track` - 1-based subtitle track index
+/// * `subtitle_file` - Standalone subtitle file to burn in instead of the track
+/// * `charset` - Encoding of `subtitle_file`, or None to let ffmpeg decide
+/// * `output_width` - Width of the exported video (for font scaling)
+/// * `source_video_width` - Width of the video as displayed in the player (for font scaling)
+fn build_subtitle_filter(
     input_path: &str,
-    output_path: &str,
-    start_secs: f32,
-    end_secs: f32,
-    subtitle_settings: Option<&crate::SubtitleSettings>,
-    display_subtitles: bool,
-    subtitle_track: Option<usize>,
+    settings: &crate::SubtitleSettings,
+    subtitle_track: usize,
+    subtitle_file: Option<&str>,
+    charset: Option<&str>,
+    output_width: u32,
     source_video_width: u32,
-) -> Result<(), String> {
-    // Calculate duration
-    let duration = end_secs - start_secs;
+) -> String {
+    // Subtract 1 because FFmpeg's si parameter is 0-based, but our track indices are 1-based
+    let track_idx = subtitle_track.saturating_sub(1);
+
+    // Scale font size proportionally to output resolution vs source resolution
+    // The subtitle_settings.font_size is calibrated for the player display at source_video_width
+    // We need to scale it down/up based on the output resolution
+    let scale_factor = output_width as f64 / source_video_width as f64;
+    let scaled_font_size = (settings.font_size * scale_factor) as i32;
+
+    println!(
+        "[subtitle_filter] Subtitle font scaling: source_width={}, output_width={}, scale_factor={:.3}, original_size={:.1}, scaled_size={}",
+        source_video_width, output_width, scale_factor, settings.font_size, scaled_font_size
+    );
 
-    // Format timestamps for ffmpeg
-    let start_time = format!("{}", start_secs);
-    let duration_time = format!("{}", duration);
+    let bgr_color = ass_bgr_color(&settings.color);
+
+    // Escape the subtitle source path for FFmpeg filter
+    // Need to escape: \ ' : [ ] , ;
+    let escaped_path = subtitle_file
+        .unwrap_or(input_path)
+        .replace("\\", "\\\\")
+        .replace("'", "\\'")
+        .replace(":", "\\:")
+        .replace("[", "\\[")
+        .replace("]", "\\]")
+        .replace(",", "\\,")
+        .replace(";", "\\;");
+
+    // A standalone file has a single track, so there's no stream index to pick,
+    // but its text may need decoding (streams of the video are always UTF-8)
+    let stream_index = match (subtitle_file, charset) {
+        (Some(_), Some(charset)) => format!(":charenc={}", charset),
+        (Some(_), None) => String::new(),
+        (None, _) => format!(":si={}", track_idx),
+    };
 
-    // Detect video metadata using ffprobe
-    let fps = get_video_fps(input_path).unwrap_or(30.0);
-    let frame_count = (duration * fps).trunc() as u32;
+    let mut filter = format!(
+        "subtitles={}{}:force_style=FontName={}\\,FontSize={}\\,Bold={}\\,Italic={}\\,PrimaryColour=&H{}",
+        escaped_path,
+        stream_index,
+        settings.font_family,
+        scaled_font_size,
+        if settings.bold { -1 } else { 0 },
+        if settings.italic { -1 } else { 0 },
+        bgr_color
+    );
 
-    // Get audio codec arguments based on file analysis
-    let audio_args = get_audio_codec_args(input_path)?;
+    // The background box is drawn with the outline color, so it replaces the outline
+    if settings.background_box {
+        filter.push_str(&background_box_style(settings.background_opacity));
+    } else {
+        filter.push_str(&outline_style(
+            &settings.outline_color,
+            settings.outline_width * scale_factor,
+            settings.shadow,
+        ));
+    }
 
-    // Check if input is a .ts file for special handling
-    let is_ts_file = input_path.ends_with(".ts");
+    filter.push_str(&format!("\\,Alignment={}", settings.alignment.clamp(1, 9)));
+    if settings.title_safe {
+        filter.push_str(&title_safe_style(settings.title_safe_inset));
+    } else {
+        filter.push_str(&format!("\\,MarginV={}", DEFAULT_MARGIN));
+    }
 
-    // Get the output video resolution (which is the same as source for video exports)
-    let (output_video_width, _output_video_height) = get_video_resolution(input_path)?;
+    filter
+}
 
-    // Build subtitle filter if needed
-    let subtitle_filter = if display_subtitles && subtitle_track.is_some() {
-        if let Some(settings) = subtitle_settings {
-            // Subtract 1 because FFmpeg's si parameter is 0-based, but our track indices are 1-based
-            let track_idx = subtitle_track.unwrap().saturating_sub(1);
+/// Normalize a hex color to uppercase "#RRGGBB" (or "#AARRGGBB" when it has alpha)
+///
+/// Accepts "#RGB", "#RRGGBB" and mpv's alpha-first "#AARRGGBB", with or without
+/// the '#'. Returns None for anything else.
+pub fn normalize_hex_color(input: &str) -> Option<String> {
+    let hex = input.trim().trim_start_matches('#');
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let hex = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 | 8 => hex.to_string(),
+        _ => return None,
+    };
+    Some(format!("#{}", hex.to_uppercase()))
+}
 
-            // Scale font size proportionally to output resolution vs source resolution
-            // The subtitle_settings.font_size is calibrated for the player display at source_video_width
-            // We need to scale it down/up based on the output resolution
-            let scale_factor = output_video_width as f64 / source_video_width as f64;
-            let scaled_font_size = (settings.font_size * scale_factor) as i32;
+/// Convert a hex color to the AABBGGRR order ASS styles use (without the &H prefix)
+///
+/// ASS alpha is inverted from mpv's (00 is opaque), so "#80FF0000" becomes "7F0000FF".
+/// Invalid colors fall back to opaque white.
+fn ass_bgr_color(hex: &str) -> String {
+    let Some(color) = normalize_hex_color(hex) else {
+        return "00FFFFFF".to_string();
+    };
+    let color = &color[1..];
+    let (alpha, rgb) = match color.len() {
+        8 => (255 - u8::from_str_radix(&color[0..2], 16).unwrap_or(255), &color[2..]),
+        _ => (0, color),
+    };
+    format!("{:02X}{}{}{}", alpha, &rgb[4..6], &rgb[2..4], &rgb[0..2])
+}
 
-            println!(
-                "[export_clip] Subtitle font scaling: source_width={}, output_width={}, scale_factor={:.3}, original_size={:.1}, scaled_size={}",
-                source_video_width, output_video_width, scale_factor, settings.font_size, scaled_font_size
-            );
+/// Build the force_style fields for an outline (and optional drop shadow) around subtitles
+///
+/// `width` is in script units like the font size; a width of 0 turns the outline off.
+fn outline_style(color: &str, width: f64, shadow: bool) -> String {
+    format!(
+        "\\,BorderStyle=1\\,OutlineColour=&H{}\\,Outline={:.1}\\,Shadow={}",
+        ass_bgr_color(color),
+        width.max(0.0),
+        if shadow { 2 } else { 0 }
+    )
+}
 
-            // Convert hex color to FFmpeg format (remove # and convert to BGR format for ASS)
-            let color = settings.color.trim_start_matches('#');
-            // FFmpeg ASS uses BGR format with &H prefix, so we need to reverse RGB to BGR
-            let bgr_color = if color.len() == 6 {
-                format!("{}{}{}", &color[4..6], &color[2..4], &color[0..2])
-            } else {
-                color.to_string()
-            };
-
-            // Escape the input path for FFmpeg filter
-            // Need to escape: \ ' : [ ] , ;
-            let escaped_path = input_path
-                .replace("\\", "\\\\")
-                .replace("'", "\\'")
-                .replace(":", "\\:")
-                .replace("[", "\\[")
-                .replace("]", "\\]")
-                .replace(",", "\\,")
-                .replace(";", "\\;");
-
-            Some(format!(
-                "subtitles={}:si={}:force_style=FontName={}\\,FontSize={}\\,Bold={}\\,Italic={}\\,PrimaryColour=&H{}",
-                escaped_path,
-                track_idx,
-                settings.font_family,
-                scaled_font_size,
-                if settings.bold { -1 } else { 0 },
-                if settings.italic { -1 } else { 0 },
-                bgr_color
-            ))
-        } else {
-            None
-        }
-    } else {
-        None
-    };
+/// Script resolution libass uses for text subtitles converted by ffmpeg
+const DEFAULT_PLAY_RES: (f64, f64) = (384.0, 288.0);
 
-    // Build ffmpeg command matching atci clipper for maximum speed
-    // Key optimization: -ss BEFORE -i for fast seeking
-    let mut cmd = Command::new("ffmpeg");
+/// Margin (in script units) ffmpeg gives converted text subtitles
+const DEFAULT_MARGIN: f64 = 10.0;
 
-    cmd.arg("-ss").arg(&start_time).arg("-i").arg(input_path);
+/// Build the force_style margins that keep subtitles inside the title-safe region
+///
+/// Margins are in script units, which libass scales to the output frame, so
+/// an inset fraction of the script resolution stays the same fraction of the
+/// output however the clip is scaled. MarginV is measured from whichever edge
+/// the subtitle is aligned to, so top-aligned subtitles get pushed down too.
+fn title_safe_style(inset: f64) -> String {
+    let inset = inset.clamp(0.0, 0.5);
+    let (play_res_x, play_res_y) = DEFAULT_PLAY_RES;
+    let margin_h = (DEFAULT_MARGIN + play_res_x * inset).round() as i32;
+    let margin_v = (DEFAULT_MARGIN + play_res_y * inset).round() as i32;
+    format!(
+        "\\,MarginL={}\\,MarginR={}\\,MarginV={}",
+        margin_h, margin_h, margin_v
+    )
+}
 
-    // When using subtitles, we need to use copyts and -to instead of -t
-    let has_subtitles = subtitle_filter.is_some();
+/// Build the force_style fields for an opaque background box behind subtitles
+///
+/// ASS colors are `&HAABBGGRR` where alpha `00` is opaque and `FF` is fully
+/// transparent, so the opacity is inverted. libass draws the box with the
+/// outline color (and its shadow with the back color), so both are set.
+fn background_box_style(opacity: f64) -> String {
+    let alpha = 255 - (opacity.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "\\,BorderStyle=3\\,OutlineColour=&H{:02X}000000\\,BackColour=&H{:02X}000000",
+        alpha, alpha
+    )
+}
 
-    if has_subtitles {
-        cmd.arg("-copyts");
+/// Optional encoding settings for `export_clip`
+///
+/// The defaults keep the source's properties, matching the behavior before
+/// these options existed.
+#[derive(Clone, Debug, Default)]
+pub struct ExportOptions {
+    /// Output frame rate, or None to keep the source frame rate
+    pub output_fps: Option<f32>,
+    /// ffmetadata file whose chapters are embedded in the output
+    pub chapters_path: Option<String>,
+    /// Tone-map HDR sources to SDR (only set for HDR sources)
+    pub tonemap_hdr: bool,
+    /// Deinterlace with yadif before anything else is applied
+    pub deinterlace: bool,
+    /// Which of the source's metadata tags to carry over to the clip
+    pub copy_metadata: MetadataCopy,
+    /// Subtitle file used instead of the selected track (custom clip subtitles or an opened file)
+    pub subtitle_file: Option<String>,
+    /// Encoding of `subtitle_file`, or None to let ffmpeg decide
+    pub subtitle_charset: Option<String>,
+    /// Index among the source's audio streams, or None for ffmpeg's default pick
+    pub audio_track: Option<usize>,
+    /// Seconds to hold the first frame before the clip starts moving (audio waits too)
+    pub intro_hold_secs: Option<f32>,
+    /// x264 speed/quality trade-off
+    pub quality: ExportQuality,
+    /// Target-size mode: a two-pass encode aiming for this many MB, replacing `quality`
+    /// (and the hardware encoder)
+    pub target_size_mb: Option<u32>,
+    /// Output resolution (never larger than the source)
+    pub output_scale: OutputScale,
+    /// GPU H.264 encoder used instead of libx264 (falls back to libx264 if it fails)
+    pub hardware_encoder: Option<HardwareEncoder>,
+    /// Hardware filter for the `output_scale` resize (falls back to `scale` if it fails);
+    /// burned-in subtitles are still drawn on the CPU
+    pub hw_scaler: Option<HwScaler>,
+    /// Shift of burned-in subtitles in ms (positive = later), like the player's delay
+    pub subtitle_offset_ms: i64,
+    /// Lets another thread stop the encode
+    pub cancel: CancelHandle,
+}
+
+/// Shared handle to a running export's ffmpeg process, used to cancel it
+#[derive(Clone, Debug, Default)]
+pub struct CancelHandle {
+    child: Arc<Mutex<Option<Child>>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelHandle {
+    /// Kill the running ffmpeg process, and any started after this
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        if let Ok(mut child) = self.child.lock() {
+            if let Some(child) = child.as_mut() {
+                if let Err(e) = child.kill() {
+                    eprintln!("Failed to kill ffmpeg: {}", e);
+                }
+            }
+        }
     }
 
-    if is_ts_file {
-        // For TS files: use vsync cfr
-        if has_subtitles {
-            // Use -to with absolute endpoint when subtitles are enabled
-            cmd.arg("-to").arg(format!("{}", end_secs));
-        } else {
-            cmd.arg("-t").arg(&duration_time);
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// x264 encoding quality for `export_clip`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ExportQuality {
+    /// CRF 28 with the ultrafast preset: quick, but blocky
+    #[default]
+    Fast,
+    /// CRF 23 with the medium preset (x264's defaults)
+    Balanced,
+    /// CRF 18 with the slow preset: close to transparent, slowest to encode
+    HighQuality,
+}
+
+impl ExportQuality {
+    pub fn next(&self) -> Self {
+        match self {
+            ExportQuality::Fast => ExportQuality::Balanced,
+            ExportQuality::Balanced => ExportQuality::HighQuality,
+            ExportQuality::HighQuality => ExportQuality::Fast,
         }
+    }
 
-        // Add subtitle filter if present, otherwise just format
-        if let Some(ref sub_filter) = subtitle_filter {
-            cmd.arg("-vf").arg(format!("{},format=yuv420p", sub_filter));
-        } else {
-            cmd.arg("-vf").arg("format=yuv420p");
-        }
-
-        cmd.arg("-c:v")
-            .arg("libx264")
-            .arg("-profile:v")
-            .arg("baseline")
-            .arg("-level")
-            .arg("3.1")
-            .arg("-pix_fmt")
-            .arg("yuv420p")
-            .arg("-vsync")
-            .arg("cfr");
-    } else {
-        // For non-TS files: use double seek and frame count (when no subtitles)
-        // or use -to (when subtitles are enabled)
-        if has_subtitles {
-            // Use -to with absolute endpoint when subtitles are enabled
-            cmd.arg("-to")
-                .arg(format!("{}", end_secs))
-                .arg("-frames:v")
-                .arg(frame_count.to_string());
-        } else {
-            cmd.arg("-ss")
-                .arg("00:00:00.001")
-                .arg("-t")
-                .arg(&duration_time)
-                .arg("-frames:v")
-                .arg(frame_count.to_string());
+    /// Name shown on the quality button
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportQuality::Fast => "Fast",
+            ExportQuality::Balanced => "Balanced",
+            ExportQuality::HighQuality => "High Quality",
         }
+    }
 
-        // Add subtitle filter if present
-        if let Some(ref sub_filter) = subtitle_filter {
-            cmd.arg("-vf").arg(sub_filter);
+    /// The x264 CRF and preset
+    fn crf_preset(&self) -> (u32, &'static str) {
+        match self {
+            ExportQuality::Fast => (28, "ultrafast"),
+            ExportQuality::Balanced => (23, "medium"),
+            ExportQuality::HighQuality => (18, "slow"),
         }
+    }
 
-        cmd.arg("-c:v")
-            .arg("libx264")
-            .arg("-profile:v")
-            .arg("baseline")
-            .arg("-level")
-            .arg("3.1")
-            .arg("-pix_fmt")
-            .arg("yuv420p");
+}
+
+/// Codec and container for `export_audio`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AudioFormat {
+    /// LAME VBR at quality 2 (~190 kbit/s)
+    #[default]
+    Mp3,
+    /// Uncompressed 16-bit PCM
+    Wav,
+    /// Lossless FLAC
+    Flac,
+    /// AAC at 192 kbit/s in an .m4a container
+    Aac,
+}
+
+impl AudioFormat {
+    pub fn next(&self) -> Self {
+        match self {
+            AudioFormat::Mp3 => AudioFormat::Wav,
+            AudioFormat::Wav => AudioFormat::Flac,
+            AudioFormat::Flac => AudioFormat::Aac,
+            AudioFormat::Aac => AudioFormat::Mp3,
+        }
     }
 
-    // Add audio codec arguments (detected based on source file)
-    for arg in audio_args {
-        cmd.arg(arg);
+    /// Name shown on the audio format button
+    pub fn name(&self) -> &'static str {
+        match self {
+            AudioFormat::Mp3 => "MP3",
+            AudioFormat::Wav => "WAV",
+            AudioFormat::Flac => "FLAC",
+            AudioFormat::Aac => "AAC",
+        }
     }
 
-    // Quality and optimization flags
-    cmd.arg("-crf")
-        .arg("28")
-        .arg("-preset")
-        .arg("ultrafast")
-        .arg("-movflags")
-        .arg("faststart+frag_keyframe+empty_moov");
+    /// File extension (without the dot); ffmpeg picks the container from it
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AudioFormat::Mp3 => "mp3",
+            AudioFormat::Wav => "wav",
+            AudioFormat::Flac => "flac",
+            AudioFormat::Aac => "m4a",
+        }
+    }
 
-    // Don't use avoid_negative_ts when subtitles are enabled
-    if !has_subtitles {
-        cmd.arg("-avoid_negative_ts").arg("make_zero");
+    /// Format whose `extension` this is (case-insensitive)
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        let extension = extension.to_lowercase();
+        [AudioFormat::Mp3, AudioFormat::Wav, AudioFormat::Flac, AudioFormat::Aac]
+            .into_iter()
+            .find(|format| format.extension() == extension)
     }
 
-    cmd.arg("-y")
-        .arg("-map_chapters")
-        .arg("-1")
-        .arg(output_path);
+    /// Encoder arguments; the lossless formats take no bitrate or quality flag
+    fn codec_args(&self) -> &'static [&'static str] {
+        match self {
+            AudioFormat::Mp3 => &["-c:a", "libmp3lame", "-q:a", "2"],
+            AudioFormat::Wav => &["-c:a", "pcm_s16le"],
+            AudioFormat::Flac => &["-c:a", "flac"],
+            AudioFormat::Aac => &["-c:a", "aac", "-b:a", "192k"],
+        }
+    }
+}
 
-    // Debug: print the command
-    eprintln!("FFmpeg video export command: {:?}", cmd);
+/// Output resolution for `export_clip`, by height
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum OutputScale {
+    /// Keep the source resolution
+    #[default]
+    Original,
+    P1080,
+    P720,
+    P480,
+}
 
-    let output = cmd
-        .output()
-        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+impl OutputScale {
+    pub fn next(&self) -> Self {
+        match self {
+            OutputScale::Original => OutputScale::P1080,
+            OutputScale::P1080 => OutputScale::P720,
+            OutputScale::P720 => OutputScale::P480,
+            OutputScale::P480 => OutputScale::Original,
+        }
+    }
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("ffmpeg failed: {}", stderr));
+    /// Name shown on the resolution button
+    pub fn name(&self) -> &'static str {
+        match self {
+            OutputScale::Original => "Original",
+            OutputScale::P1080 => "1080p",
+            OutputScale::P720 => "720p",
+            OutputScale::P480 => "480p",
+        }
     }
 
-    Ok(())
-}
+    /// Target height, or None to keep the source resolution
+    fn height(&self) -> Option<u32> {
+        match self {
+            OutputScale::Original => None,
+            OutputScale::P1080 => Some(1080),
+            OutputScale::P720 => Some(720),
+            OutputScale::P480 => Some(480),
+        }
+    }
 
-/// Export a video clip as an animated GIF from start_secs to end_secs
-///
-/// Uses optimized settings from atci clipper:
-/// - 10fps for reasonable file size
-/// - Scale to 480px width with Lanczos filtering
-/// - Palette generation for better colors
-/// - Infinite loop
-///
-/// # Arguments
-/// * `input_path` - Path to the input video file
-/// * `output_path` - Path where the output GIF should be saved
-/// * `start_secs` - Start time in seconds
-/// * `end_secs` - End time in seconds
-/// * `subtitle_settings` - Optional subtitle settings (font, size, bold, italic, color)
-/// * `display_subtitles` - Whether to include subtitles in the GIF
-/// * `subtitle_track` - Optional subtitle track index to burn in
-/// * `source_video_width` - Width of the video as displayed in the player (for subtitle scaling)
-///
-/// # Returns
-/// * `Ok(())` on success
-/// * `Err(String)` with error message on failure
-pub fn export_gif(
-    input_path: &str,
-    output_path: &str,
-    start_secs: f32,
-    end_secs: f32,
-    subtitle_settings: Option<&crate::SubtitleSettings>,
-    display_subtitles: bool,
-    subtitle_track: Option<usize>,
-    source_video_width: u32,
-    loop_gif: bool,
-) -> Result<(), String> {
-    // Calculate duration
-    let duration = end_secs - start_secs;
+    /// The `scale` filter (width follows the aspect ratio; smaller sources aren't upscaled)
+    fn filter(&self) -> Option<String> {
+        self.height()
+            .map(|height| format!("scale=-2:'min({},ih)'", height))
+    }
 
-    // Format timestamps for ffmpeg
-    let start_time = format!("{}", start_secs);
-    let duration_time = format!("{}", duration);
+    /// Width of the output for a source of `width` x `height`, matching `filter`
+    fn output_width(&self, width: u32, height: u32) -> u32 {
+        match self.height() {
+            Some(target) if target < height && height > 0 => {
+                let scaled = width as f64 * target as f64 / height as f64;
+                ((scaled / 2.0).round() as u32) * 2
+            }
+            _ => width,
+        }
+    }
+}
 
-    // Build the video filter (-vf) for GIF generation
-    // Key order from atci clipper: fps=10,scale=480:-1:flags=lanczos,split[s0][s1];[s0]palettegen[p];[s1][p]paletteuse
-    let mut filter_parts = Vec::new();
+/// How `export_clip` includes the selected subtitle track
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SubtitleMode {
+    /// No subtitles in the output
+    #[default]
+    None,
+    /// Drawn into the video frames (see `SubtitleBurn`)
+    Burn,
+    /// Muxed as a selectable subtitle stream
+    Soft,
+}
 
-    // GIF output width is 480px (hardcoded in the export)
-    let gif_output_width = 480u32;
+/// How subtitles are drawn into the exported picture
+#[derive(Clone, Debug, PartialEq)]
+pub enum SubtitleBurn {
+    /// A `subtitles` (libass) filter, for text tracks and subtitle files
+    Filter(String),
+    /// An image-based (PGS/VobSub/DVB) stream of the source, by its 0-based
+    /// index among the subtitle streams, drawn with `overlay`
+    Bitmap(usize),
+}
 
-    // Add subtitle filter if requested and settings provided
-    if display_subtitles && subtitle_track.is_some() {
-        if let Some(settings) = subtitle_settings {
-            // Subtract 1 because FFmpeg's si parameter is 0-based, but our track indices are 1-based
-            let track_idx = subtitle_track.unwrap().saturating_sub(1);
+/// Subtitle codec for soft subtitles in the output's container
+///
+/// MP4/MOV only accept `mov_text`, Matroska takes SRT and WebM only WebVTT.
+pub fn soft_subtitle_codec(output_path: &str) -> Result<&'static str, String> {
+    let extension = Path::new(output_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase());
+    match extension.as_deref() {
+        Some("mp4" | "m4v" | "mov") => Ok("mov_text"),
+        Some("mkv") => Ok("srt"),
+        Some("webm") => Ok("webvtt"),
+        _ => Err(format!(
+            "Soft subtitles aren't supported for {}; use .mp4, .mov or .mkv",
+            output_path
+        )),
+    }
+}
 
-            // Scale font size proportionally to output resolution vs source resolution
-            // The subtitle_settings.font_size is calibrated for the player display at source_video_width
-            // We need to scale it down for the 480px GIF output
-            let scale_factor = gif_output_width as f64 / source_video_width as f64;
-            let scaled_font_size = (settings.font_size * scale_factor) as i32;
+/// How much of the source's metadata `export_clip` keeps
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MetadataCopy {
+    /// Leave metadata to ffmpeg's defaults (the clean output)
+    #[default]
+    None,
+    /// Only the title and the video/audio language tags
+    TitleLanguage,
+    /// Every global and per-stream tag (`-map_metadata 0`)
+    ///
+    /// Can carry over tags that no longer match the clip, such as durations.
+    All,
+}
 
-            println!(
-                "[export_gif] Subtitle font scaling: source_width={}, gif_output_width={}, scale_factor={:.3}, original_size={:.1}, scaled_size={}",
-                source_video_width, gif_output_width, scale_factor, settings.font_size, scaled_font_size
-            );
+/// Frame rate of GIF exports unless overridden
+pub const DEFAULT_GIF_FPS: u32 = 10;
+
+/// Width in pixels of GIF exports unless overridden
+pub const DEFAULT_GIF_WIDTH: u32 = 480;
+
+/// Accepted GIF frame rates
+pub const GIF_FPS_RANGE: std::ops::RangeInclusive<u32> = 1..=30;
+
+/// Accepted GIF widths in pixels
+pub const GIF_WIDTH_RANGE: std::ops::RangeInclusive<u32> = 120..=1280;
+
+/// Options for `export_gif`
+#[derive(Clone, Debug)]
+pub struct GifOptions {
+    /// Output frame rate (`GIF_FPS_RANGE`)
+    pub fps: u32,
+    /// Output width in pixels, height follows the aspect ratio (`GIF_WIDTH_RANGE`)
+    pub width: u32,
+    /// Loop the GIF forever instead of playing once
+    pub loop_gif: bool,
+    /// Deinterlace with yadif before anything else is applied
+    pub deinterlace: bool,
+    /// Tone-map HDR sources to SDR (only set for HDR sources)
+    pub tonemap_hdr: bool,
+    /// Scale (only) on the GPU with this hardware filter, falling back to software on failure
+    pub hw_scaler: Option<HwScaler>,
+    /// Subtitle file used instead of the selected track (custom clip subtitles or an opened file)
+    pub subtitle_file: Option<String>,
+    /// Encoding of `subtitle_file`, or None to let ffmpeg decide
+    pub subtitle_charset: Option<String>,
+    /// Shift of burned-in subtitles in ms (positive = later), like the player's delay
+    pub subtitle_offset_ms: i64,
+    /// Lets another thread stop the encode
+    pub cancel: CancelHandle,
+}
 
-            // Convert hex color to FFmpeg format (remove # and convert to BGR format for ASS)
-            let color = settings.color.trim_start_matches('#');
-            // FFmpeg ASS uses BGR format with &H prefix, so we need to reverse RGB to BGR
-            let bgr_color = if color.len() == 6 {
-                format!("{}{}{}", &color[4..6], &color[2..4], &color[0..2])
-            } else {
-                color.to_string()
-            };
-
-            // Build force_style string for subtitle styling
-            // Note: FFmpeg subtitles filter uses ASS/SSA style format
-            // Escape the input path for FFmpeg filter
-            // Need to escape: \ ' : [ ] , ;
-            let escaped_path = input_path
-                .replace("\\", "\\\\")
-                .replace("'", "\\'")
-                .replace(":", "\\:")
-                .replace("[", "\\[")
-                .replace("]", "\\]")
-                .replace(",", "\\,")
-                .replace(";", "\\;");
-
-            filter_parts.push(format!(
-                "subtitles={}:si={}:force_style=FontName={}\\,FontSize={}\\,Bold={}\\,Italic={}\\,PrimaryColour=&H{}",
-                escaped_path,
-                track_idx,
-                settings.font_family,
-                scaled_font_size,
-                if settings.bold { -1 } else { 0 },
-                if settings.italic { -1 } else { 0 },
-                bgr_color
-            ));
+impl Default for GifOptions {
+    fn default() -> Self {
+        Self {
+            fps: DEFAULT_GIF_FPS,
+            width: DEFAULT_GIF_WIDTH,
+            loop_gif: false,
+            deinterlace: false,
+            tonemap_hdr: false,
+            hw_scaler: None,
+            subtitle_file: None,
+            subtitle_charset: None,
+            subtitle_offset_ms: 0,
+            cancel: CancelHandle::default(),
         }
     }
+}
+
+/// Options for `export_contact_sheet`
+#[derive(Clone, Debug)]
+pub struct ContactSheetOptions {
+    /// Number of thumbnail rows
+    pub rows: u32,
+    /// Number of thumbnail columns
+    pub cols: u32,
+    /// Width of each thumbnail in pixels
+    pub tile_width: u32,
+    /// Tone-map HDR sources to SDR (only set for HDR sources)
+    pub tonemap_hdr: bool,
+    /// Lets another thread stop the export
+    pub cancel: CancelHandle,
+}
+
+impl Default for ContactSheetOptions {
+    fn default() -> Self {
+        Self {
+            rows: 4,
+            cols: 4,
+            tile_width: 320,
+            tonemap_hdr: false,
+            cancel: CancelHandle::default(),
+        }
+    }
+}
+
+/// Hardware scaling filters that can offload resizing during export
+///
+/// Only the resize runs on the GPU. Subtitle burn-in (libass, or the overlay
+/// of image-based tracks) and palette generation stay on the CPU, so frames
+/// are uploaded just for the scale and downloaded again afterwards.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HwScaler {
+    VideoToolbox,
+    Vaapi,
+    Cuda,
+}
+
+impl HwScaler {
+    /// Preference order when several are available
+    const ALL: [HwScaler; 3] = [Self::VideoToolbox, Self::Cuda, Self::Vaapi];
+
+    fn filter_name(self) -> &'static str {
+        match self {
+            Self::VideoToolbox => "scale_vt",
+            Self::Vaapi => "scale_vaapi",
+            Self::Cuda => "scale_cuda",
+        }
+    }
+
+    fn device_type(self) -> &'static str {
+        match self {
+            Self::VideoToolbox => "videotoolbox",
+            Self::Vaapi => "vaapi",
+            Self::Cuda => "cuda",
+        }
+    }
+
+    /// Scale segment of a filter chain: upload, scale on the GPU, download
+    fn scale_chain(self, width: u32) -> String {
+        self.sized_chain(&width.to_string(), "-2")
+    }
+
+    /// Like `scale_chain`, to a height that follows `OutputScale::filter`
+    fn scale_chain_to_height(self, height: u32) -> String {
+        self.sized_chain("-2", &format!("'min({},ih)'", height))
+    }
+
+    fn sized_chain(self, width: &str, height: &str) -> String {
+        format!(
+            "format=nv12,hwupload,{}=w={}:h={},hwdownload,format=nv12",
+            self.filter_name(),
+            width,
+            height
+        )
+    }
+}
+
+/// Find a hardware scaling filter this ffmpeg build supports
+///
+/// Only checks that the filter is compiled in; whether the device actually
+/// works is found out on export, which falls back to software scaling.
+pub fn detect_hw_scaler() -> Option<HwScaler> {
+    let output = ffmpeg_command()
+        .arg("-hide_banner")
+        .arg("-filters")
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    parse_hw_scaler(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Pick the preferred hardware scaler from `ffmpeg -filters` output
+fn parse_hw_scaler(filters_output: &str) -> Option<HwScaler> {
+    // Lines look like " ... scale_vt          V->V       Scale Videotoolbox frames"
+    let names: Vec<&str> = filters_output
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .collect();
+
+    HwScaler::ALL
+        .into_iter()
+        .find(|scaler| names.contains(&scaler.filter_name()))
+}
+
+/// Hardware H.264 encoders that can replace libx264 for video exports
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HardwareEncoder {
+    VideoToolbox,
+    Nvenc,
+    Qsv,
+}
+
+impl HardwareEncoder {
+    /// Preference order when several are available
+    const ALL: [HardwareEncoder; 3] = [Self::VideoToolbox, Self::Nvenc, Self::Qsv];
+
+    pub fn encoder_name(self) -> &'static str {
+        match self {
+            Self::VideoToolbox => "h264_videotoolbox",
+            Self::Nvenc => "h264_nvenc",
+            Self::Qsv => "h264_qsv",
+        }
+    }
+
+    /// Encoder arguments; hardware encoders take a bitrate instead of a CRF
+    fn codec_args(self, quality: ExportQuality) -> Vec<String> {
+        let bitrate = match quality {
+            ExportQuality::Fast => "6M",
+            ExportQuality::Balanced => "10M",
+            ExportQuality::HighQuality => "16M",
+        };
+        // QSV wants NV12 frames; the others take yuv420p like libx264
+        let pix_fmt = match self {
+            Self::Qsv => "nv12",
+            Self::VideoToolbox | Self::Nvenc => "yuv420p",
+        };
+        ["-c:v", self.encoder_name(), "-b:v", bitrate, "-pix_fmt", pix_fmt]
+            .map(String::from)
+            .to_vec()
+    }
+}
+
+/// Find a hardware H.264 encoder this ffmpeg build supports
+///
+/// Like `detect_hw_scaler`, only checks that the encoder is compiled in;
+/// `export_clip` falls back to libx264 if it doesn't work on this machine.
+pub fn detect_hw_encoder() -> Option<HardwareEncoder> {
+    let output = ffmpeg_command()
+        .arg("-hide_banner")
+        .arg("-encoders")
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    parse_hw_encoder(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Pick the preferred hardware encoder from `ffmpeg -encoders` output
+fn parse_hw_encoder(encoders_output: &str) -> Option<HardwareEncoder> {
+    // Lines look like " V....D h264_videotoolbox    VideoToolbox H.264 Encoder"
+    let names: Vec<&str> = encoders_output
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .collect();
+
+    HardwareEncoder::ALL
+        .into_iter()
+        .find(|encoder| names.contains(&encoder.encoder_name()))
+}
+
+/// Video encoder arguments for `export_clip`: VP9 for WebM, otherwise the
+/// hardware encoder when one is set, or baseline libx264 at the chosen quality
+fn video_codec_args(
+    is_webm: bool,
+    hardware_encoder: Option<HardwareEncoder>,
+    quality: ExportQuality,
+) -> Vec<String> {
+    if is_webm {
+        return WEBM_VIDEO_ARGS.map(String::from).to_vec();
+    }
+    if let Some(encoder) = hardware_encoder {
+        return encoder.codec_args(quality);
+    }
+    let (crf, preset) = quality.crf_preset();
+    let mut args = ["-c:v", "libx264", "-profile:v", "baseline", "-level", "3.1"]
+        .map(String::from)
+        .to_vec();
+    args.extend(["-pix_fmt", "yuv420p"].map(String::from));
+    args.extend(["-crf".to_string(), crf.to_string(), "-preset".to_string(), preset.to_string()]);
+    args
+}
+
+/// Lowest video bitrate a target-size export accepts, in kbit/s
+///
+/// Anything below this is a blur; it's better to say the target is too small.
+const MIN_TARGET_VIDEO_KBPS: u32 = 150;
+
+/// Share of the target size given to the streams; the rest is left for the container
+const TARGET_SIZE_HEADROOM: f64 = 0.97;
+
+/// Audio bitrate assumed when the audio arguments don't set one (ffmpeg's AAC default)
+const DEFAULT_AUDIO_KBPS: u32 = 128;
+
+/// Video bitrate in kbit/s that makes a clip of `duration_secs` about `target_mb`
+/// megabytes, after `audio_kbps` of audio
+fn target_video_kbps(target_mb: u32, duration_secs: f32, audio_kbps: u32) -> Result<u32, String> {
+    if duration_secs <= 0.0 {
+        return Err("Clip has no duration".to_string());
+    }
+    let budget_kbits = target_mb as f64 * 8.0 * 1024.0 * 1024.0 / 1000.0 * TARGET_SIZE_HEADROOM;
+    let video_kbps = budget_kbits / duration_secs as f64 - audio_kbps as f64;
+    if video_kbps < MIN_TARGET_VIDEO_KBPS as f64 {
+        return Err(format!(
+            "{} MB is too small for a {:.1}s clip: it leaves {:.0} kbit/s for video \
+             (at least {} is needed)",
+            target_mb, duration_secs, video_kbps, MIN_TARGET_VIDEO_KBPS
+        ));
+    }
+    Ok(video_kbps as u32)
+}
+
+/// Audio arguments for a target-size export
+///
+/// A stream copy's bitrate isn't known up front, so it's re-encoded as AAC.
+fn with_budgeted_audio(audio_args: Vec<String>) -> Vec<String> {
+    if audio_args.iter().map(String::as_str).eq(["-c:a", "copy"]) {
+        let bitrate = format!("{}k", DEFAULT_AUDIO_KBPS);
+        return ["-c:a", "aac", "-b:a", &bitrate].map(String::from).to_vec();
+    }
+    audio_args
+}
+
+/// The `-b:a` audio bitrate in kbit/s, if the arguments set one
+fn audio_bitrate_kbps(audio_args: &[String]) -> Option<u32> {
+    let index = audio_args.iter().position(|arg| arg == "-b:a")?;
+    audio_args.get(index + 1)?.strip_suffix('k')?.parse().ok()
+}
+
+/// Video codec arguments for a target-size export at `video_kbps`
+///
+/// Both passes share the stats file at `passlog` (ffmpeg adds `-0.log`).
+fn target_size_codec_args(is_webm: bool, video_kbps: u32, passlog: &Path) -> Vec<String> {
+    let codec_args: &[&str] = if is_webm {
+        &["-c:v", "libvpx-vp9"]
+    } else {
+        &[
+            "-c:v",
+            "libx264",
+            "-profile:v",
+            "baseline",
+            "-level",
+            "3.1",
+            "-preset",
+            "medium",
+        ]
+    };
+    let mut args: Vec<String> = codec_args.iter().map(|arg| arg.to_string()).collect();
+    args.extend(["-pix_fmt", "yuv420p"].map(String::from));
+    args.extend(["-b:v".to_string(), format!("{}k", video_kbps)]);
+    args.push("-passlogfile".to_string());
+    args.push(passlog.to_string_lossy().to_string());
+    args
+}
+
+/// Numbers the pass log files of target-size exports running at the same time
+static PASSLOG_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A fresh pass log prefix in the temp directory
+fn passlog_prefix() -> std::path::PathBuf {
+    let number = PASSLOG_COUNTER.fetch_add(1, Ordering::SeqCst);
+    std::env::temp_dir().join(format!("asve_passlog_{}_{}", std::process::id(), number))
+}
+
+/// Delete the stats files a two-pass encode left behind
+fn remove_passlog_files(passlog: &Path) {
+    let prefix = passlog.to_string_lossy();
+    // x264 also writes macroblock-tree stats; the .temp files are left by failed passes
+    for suffix in ["-0.log", "-0.log.mbtree", "-0.log.temp", "-0.log.mbtree.temp"] {
+        let _ = std::fs::remove_file(format!("{}{}", prefix, suffix));
+    }
+}
+
+/// Arguments for the first pass of a two-pass encode, from the second pass's
+///
+/// The same encode with `-pass 1`, minus audio, subtitles and the MP4 muxer
+/// flags, written to the null muxer instead of the output path (the last
+/// argument).
+fn first_pass_args(second_pass_args: &[OsString]) -> Vec<OsString> {
+    let Some((_output_path, args)) = second_pass_args.split_last() else {
+        return Vec::new();
+    };
+    let mut first_pass = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "-movflags" {
+            iter.next();
+        } else if arg == "-pass" {
+            iter.next();
+            first_pass.extend(["-pass", "1"].map(OsString::from));
+        } else {
+            first_pass.push(arg.clone());
+        }
+    }
+    first_pass.extend(["-an", "-sn", "-f", "null", "-"].map(OsString::from));
+    first_pass
+}
+
+/// Build the subtitle burn-in filter used by `export_clip`, if subtitles are enabled
+///
+/// Returns `Ok(None)` when no subtitles should be burned in. The options'
+/// `subtitle_file` is burned in instead of the selected track, and their
+/// `output_scale` sets the size the subtitles are drawn at. Image-based tracks
+/// can't be styled and come back as `SubtitleBurn::Bitmap`.
+pub fn clip_subtitle_filter(
+    input_path: &str,
+    subtitle_settings: Option<&crate::SubtitleSettings>,
+    display_subtitles: bool,
+    subtitle_track: Option<usize>,
+    source_video_width: u32,
+    options: &ExportOptions,
+) -> Result<Option<SubtitleBurn>, String> {
+    let subtitle_file = options.subtitle_file.as_deref();
+    let (Some(settings), true) = (subtitle_settings, display_subtitles) else {
+        return Ok(None);
+    };
+    let Some(track) = subtitle_track.or(subtitle_file.map(|_| 1)) else {
+        return Ok(None);
+    };
+    if subtitle_file.is_none() && is_bitmap_subtitle_track(input_path, track) {
+        return Ok(Some(SubtitleBurn::Bitmap(track.saturating_sub(1))));
+    }
+
+    // Get the output video resolution (the source's, unless a smaller output scale is picked)
+    let (input_video_width, input_video_height) = get_video_resolution(input_path)?;
+    let output_video_width = options
+        .output_scale
+        .output_width(input_video_width, input_video_height);
+
+    Ok(Some(SubtitleBurn::Filter(build_subtitle_filter(
+        input_path,
+        settings,
+        track,
+        subtitle_file,
+        options.subtitle_charset.as_deref(),
+        output_video_width,
+        source_video_width,
+    ))))
+}
+
+/// Whether a subtitle track (1-based) of the source is image-based
+fn is_bitmap_subtitle_track(input_path: &str, subtitle_track: usize) -> bool {
+    crate::subtitle_detector::detect_subtitle_streams(input_path)
+        .get(subtitle_track.saturating_sub(1))
+        .is_some_and(|stream| !stream.is_text)
+}
+
+/// Label of the picture coming out of a `bitmap_subtitle_graph`
+const OVERLAY_OUTPUT: &str = "[vout]";
+
+/// Filter graph that overlays an image-based subtitle stream of the source
+/// between the `before` and `after` filters
+///
+/// libass only draws text, so the `subtitles` filter can't burn in PGS, VobSub
+/// or DVB tracks. Their pictures are made for the source frame, so `before`
+/// mustn't resize it. The subtitle timing offset delays the subtitle stream.
+fn bitmap_subtitle_graph(
+    stream_index: usize,
+    offset_ms: i64,
+    before: &[String],
+    after: &[String],
+) -> String {
+    let mut graph = String::new();
+    let mut video = "[0:v]".to_string();
+    if !before.is_empty() {
+        graph.push_str(&format!("{}{}[base];", video, before.join(",")));
+        video = "[base]".to_string();
+    }
+    let mut subtitles = format!("[0:s:{}]", stream_index);
+    if offset_ms != 0 {
+        let offset_secs = offset_ms as f64 / 1000.0;
+        graph.push_str(&format!(
+            "{}setpts=PTS{:+}/TB[sub];",
+            subtitles, offset_secs
+        ));
+        subtitles = "[sub]".to_string();
+    }
+    // Keep the picture going after the last subtitle
+    graph.push_str(&format!("{}{}overlay=eof_action=pass", video, subtitles));
+    for filter in after {
+        graph.push(',');
+        graph.push_str(filter);
+    }
+    graph.push_str(OVERLAY_OUTPUT);
+    graph
+}
+
+/// ffmpeg arguments that apply a filter from `clip_video_filter` or `gif_video_filter`
+///
+/// A subtitle overlay reads a second stream, which `-vf` can't do, so it goes
+/// through `-filter_complex` with its output mapped. That turns off ffmpeg's
+/// automatic stream picks, so other streams the output needs must be mapped too.
+pub fn video_filter_args(filter: &str) -> Vec<String> {
+    let args = if filter.ends_with(OVERLAY_OUTPUT) {
+        vec!["-filter_complex", filter, "-map", OVERLAY_OUTPUT]
+    } else {
+        vec!["-vf", filter]
+    };
+    args.into_iter().map(String::from).collect()
+}
+
+/// Delay burned-in subtitles by `offset_ms` (negative shows them earlier)
+///
+/// The subtitles filter has no delay option, so the frame timestamps it sees
+/// are shifted back by the offset and restored after it.
+fn offset_subtitle_filter(subtitle_filter: &str, offset_ms: i64) -> String {
+    if offset_ms == 0 {
+        return subtitle_filter.to_string();
+    }
+    let offset_secs = offset_ms as f64 / 1000.0;
+    format!(
+        "setpts=PTS{:+}/TB,{},setpts=PTS{:+}/TB",
+        -offset_secs, subtitle_filter, offset_secs
+    )
+}
+
+/// Build the full `-vf` value used by `export_clip`
+///
+/// Deinterlacing runs on the untouched source fields, then tone mapping so
+/// subtitles are drawn in SDR, then the frame
+/// rate conversion and output scaling so subtitles are burned into the output
+/// frames at their final size (sharper, and matching the font scaling). Image
+/// subtitles are overlaid before the frame rate conversion instead, making the
+/// result a `-filter_complex` graph (see `video_filter_args`). TS files
+/// always get a `format=yuv420p` conversion; other files only need a filter
+/// when something else is applied.
+pub fn clip_video_filter(
+    subtitle: Option<&SubtitleBurn>,
+    is_ts_file: bool,
+    options: &ExportOptions,
+) -> Option<String> {
+    let mut filter_parts = Vec::new();
+
+    if options.deinterlace {
+        filter_parts.push("yadif".to_string());
+    }
+    if options.tonemap_hdr {
+        filter_parts.push(HDR_TONEMAP_FILTER.to_string());
+    }
+    let overlay_at = filter_parts.len();
+    if let Some(fps) = options.output_fps {
+        filter_parts.push(format!("fps={}", fps));
+    }
+    match (options.output_scale.height(), options.hw_scaler) {
+        (Some(height), Some(scaler)) => filter_parts.push(scaler.scale_chain_to_height(height)),
+        _ => filter_parts.extend(options.output_scale.filter()),
+    }
+    if let Some(SubtitleBurn::Filter(sub_filter)) = subtitle {
+        filter_parts.push(offset_subtitle_filter(sub_filter, options.subtitle_offset_ms));
+    }
+    if is_ts_file {
+        filter_parts.push("format=yuv420p".to_string());
+    }
+    // Last, so the held frame already has its subtitles burned in
+    if let Some(hold_secs) = options.intro_hold_secs {
+        filter_parts.push(format!("tpad=start_mode=clone:start_duration={}", hold_secs));
+    }
+
+    if let Some(SubtitleBurn::Bitmap(stream_index)) = subtitle {
+        let (before, after) = filter_parts.split_at(overlay_at);
+        Some(bitmap_subtitle_graph(
+            *stream_index,
+            options.subtitle_offset_ms,
+            before,
+            after,
+        ))
+    } else if filter_parts.is_empty() {
+        None
+    } else {
+        Some(filter_parts.join(","))
+    }
+}
+
+/// Whether an output path is a WebM file (encoded as VP9/Opus instead of H.264/AAC)
+fn is_webm_output(output_path: &str) -> bool {
+    Path::new(output_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("webm"))
+}
+
+/// VP9 in constant quality mode for WebM clips
+const WEBM_VIDEO_ARGS: [&str; 8] = [
+    "-c:v",
+    "libvpx-vp9",
+    "-crf",
+    "30",
+    "-b:v",
+    "0",
+    "-pix_fmt",
+    "yuv420p",
+];
+
+/// Audio arguments for a WebM clip: the codec becomes Opus (WebM takes neither
+/// AAC nor most copied streams) and any filters are kept
+fn with_opus_audio(audio_args: Vec<String>) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut iter = audio_args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-c:a" | "-b:a" => {
+                iter.next();
+            }
+            _ => args.push(arg),
+        }
+    }
+    let mut opus_args = ["-c:a", "libopus", "-b:a", "160k"].map(String::from).to_vec();
+    opus_args.extend(args);
+    opus_args
+}
+
+/// Audio arguments for a clip whose first frame is held for `hold_secs`
+///
+/// The audio is delayed by the hold (silence until the picture starts moving),
+/// which needs re-encoding, so a stream copy becomes AAC. An existing `-af`
+/// filter is kept in front of the delay.
+fn with_intro_audio_delay(audio_args: Vec<String>, hold_secs: f32) -> Vec<String> {
+    let delay = format!("adelay=delays={}:all=1", (hold_secs * 1000.0).round() as u64);
+    let mut args = Vec::new();
+    let mut has_filter = false;
+    let mut iter = audio_args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-c:a" if iter.as_slice().first().map(String::as_str) == Some("copy") => {
+                iter.next();
+                args.extend(["-c:a", "aac", "-b:a", "256k"].map(String::from));
+            }
+            "-af" => {
+                let filter = iter.next().unwrap_or_default();
+                args.push(arg);
+                args.push(format!("{},{}", filter, delay));
+                has_filter = true;
+            }
+            _ => args.push(arg),
+        }
+    }
+    if !has_filter {
+        args.push("-af".to_string());
+        args.push(delay);
+    }
+    args
+}
+
+/// Build the full `-vf` filter chain used by `export_gif`
+///
+/// Key order from atci clipper: fps=10,scale=480:-1:flags=lanczos,split[s0][s1];[s0]palettegen[p];[s1][p]paletteuse
+/// (with the frame rate and width taken from `options`)
+pub fn gif_video_filter(
+    input_path: &str,
+    subtitle_settings: Option<&crate::SubtitleSettings>,
+    display_subtitles: bool,
+    subtitle_track: Option<usize>,
+    source_video_width: u32,
+    options: &GifOptions,
+) -> String {
+    let mut filter_parts = Vec::new();
+
+    // Subtitles are sized for the GIF's output width
+    let gif_output_width = options.width;
+
+    // Deinterlace the source fields first, then tone-map HDR sources before
+    // anything is drawn on top
+    if options.deinterlace {
+        filter_parts.push("yadif".to_string());
+    }
+    if options.tonemap_hdr {
+        filter_parts.push(HDR_TONEMAP_FILTER.to_string());
+    }
+
+    // Add subtitle filter if requested and settings provided (custom subtitles
+    // replace the track); image-based tracks are overlaid instead
+    let subtitle_file = options.subtitle_file.as_deref();
+    let mut bitmap_stream = None;
+    if let (Some(settings), Some(track), true) = (
+        subtitle_settings,
+        subtitle_track.or(subtitle_file.map(|_| 1)),
+        display_subtitles,
+    ) {
+        if subtitle_file.is_none() && is_bitmap_subtitle_track(input_path, track) {
+            bitmap_stream = Some(track.saturating_sub(1));
+        } else {
+            let subtitle_filter = build_subtitle_filter(
+                input_path,
+                settings,
+                track,
+                subtitle_file,
+                options.subtitle_charset.as_deref(),
+                gif_output_width,
+                source_video_width,
+            );
+            filter_parts.push(offset_subtitle_filter(
+                &subtitle_filter,
+                options.subtitle_offset_ms,
+            ));
+        }
+    }
+    let overlay_at = filter_parts.len();
 
     // Add base filters: fps reduction and scaling
-    filter_parts.push("fps=10".to_string());
-    filter_parts.push("scale=480:-1:flags=lanczos".to_string());
+    filter_parts.push(format!("fps={}", options.fps));
+    match options.hw_scaler {
+        Some(scaler) => filter_parts.push(scaler.scale_chain(gif_output_width)),
+        None => filter_parts.push(format!("scale={}:-1:flags=lanczos", gif_output_width)),
+    }
+
+    // Add palette generation filter
+    // split[s0][s1];[s0]palettegen[p];[s1][p]paletteuse
+    filter_parts.push("split[s0][s1];[s0]palettegen[p];[s1][p]paletteuse".to_string());
+
+    match bitmap_stream {
+        Some(stream_index) => {
+            let (before, after) = filter_parts.split_at(overlay_at);
+            bitmap_subtitle_graph(stream_index, options.subtitle_offset_ms, before, after)
+        }
+        None => filter_parts.join(","),
+    }
+}
+
+/// Run a quick dry-run of a filter chain to catch errors before a long export
+///
+/// Encodes half a second starting at `start_secs` to the null muxer, so bad
+/// font names or badly escaped paths fail in a moment instead of after the
+/// whole export has run.
+pub fn dry_run_filter(input_path: &str, start_secs: f32, vf_filter: &str) -> Result<(), String> {
+    let output = ffmpeg_command()
+        .arg("-ss")
+        .arg(format!("{}", start_secs))
+        .arg("-t")
+        .arg("0.5")
+        .arg("-i")
+        .arg(input_path)
+        .arg("-copyts")
+        .args(video_filter_args(vf_filter))
+        .arg("-an")
+        .arg("-f")
+        .arg("null")
+        .arg("-")
+        .output()
+        .map_err(|e| Tool::Ffmpeg.spawn_error(&e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("ffmpeg filter test failed: {}", stderr));
+    }
+
+    Ok(())
+}
+
+/// Tags kept by `MetadataCopy::TitleLanguage`
+#[derive(Debug, Default, PartialEq)]
+struct SourceTags {
+    title: Option<String>,
+    video_language: Option<String>,
+    audio_language: Option<String>,
+}
+
+/// Read the source's title and language tags (cached, see `probe_file`)
+fn probe_source_tags(input_path: &str) -> SourceTags {
+    probe_file(input_path)
+        .map(|probe| probe.source_tags())
+        .unwrap_or_default()
+}
+
+/// ffmpeg arguments that drop all metadata and set only the given tags again
+fn selective_metadata_args(tags: &SourceTags) -> Vec<String> {
+    let mut args = vec!["-map_metadata".to_string(), "-1".to_string()];
+    let entries = [
+        ("-metadata", "title", &tags.title),
+        ("-metadata:s:v:0", "language", &tags.video_language),
+        ("-metadata:s:a:0", "language", &tags.audio_language),
+    ];
+    for (flag, key, value) in entries {
+        if let Some(value) = value {
+            args.push(flag.to_string());
+            args.push(format!("{}={}", key, value));
+        }
+    }
+    args
+}
+
+/// ffmpeg arguments for the requested metadata copy mode
+fn metadata_args(input_path: &str, copy_metadata: MetadataCopy) -> Vec<String> {
+    match copy_metadata {
+        MetadataCopy::None => Vec::new(),
+        MetadataCopy::TitleLanguage => selective_metadata_args(&probe_source_tags(input_path)),
+        MetadataCopy::All => vec!["-map_metadata".to_string(), "0".to_string()],
+    }
+}
+
+/// Duration, streams and size of a media file, as reported by ffprobe
+#[derive(Debug, Default, PartialEq)]
+pub struct MediaProbe {
+    pub duration_secs: f32,
+    pub has_video: bool,
+    pub has_audio: bool,
+    pub size_bytes: u64,
+}
+
+/// Probe a media file's duration, streams and size (cached, see `probe_file`)
+pub fn probe_media(path: &str) -> Result<MediaProbe, String> {
+    Ok(probe_file(path)?.media_probe())
+}
+
+/// Technical details of a media file, for the file info panel
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MediaInfo {
+    /// Frame rate of the first video stream
+    pub fps: Option<f32>,
+    /// (width, height) of the first video stream
+    pub resolution: Option<(u32, u32)>,
+    pub duration_secs: f32,
+    pub video_codec: Option<String>,
+    pub audio_codec: Option<String>,
+    /// Overall bit rate in bits per second
+    pub bit_rate: Option<u64>,
+    /// The first video stream uses an HDR transfer (PQ/HLG)
+    pub is_hdr: bool,
+    /// The first video stream says it's interlaced (some interlaced captures
+    /// are flagged progressive, or not at all)
+    pub is_interlaced: bool,
+}
+
+/// Streams and container details from one `ffprobe -show_format -show_streams` run
+///
+/// Only the fields asve uses are kept. Cached per file by `probe_file`.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ProbeResult {
+    #[serde(default)]
+    pub streams: Vec<ProbeStream>,
+    pub format: Option<ProbeFormat>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ProbeStream {
+    pub codec_type: String,
+    pub codec_name: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub r_frame_rate: Option<String>,
+    pub color_transfer: Option<String>,
+    pub field_order: Option<String>,
+    pub channel_layout: Option<String>,
+    pub duration: Option<String>,
+    pub bit_rate: Option<String>,
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ProbeFormat {
+    pub duration: Option<String>,
+    pub bit_rate: Option<String>,
+    pub size: Option<String>,
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+}
+
+impl ProbeResult {
+    /// First stream of a type ("video", "audio", "subtitle")
+    fn first_stream(&self, codec_type: &str) -> Option<&ProbeStream> {
+        self.streams.iter().find(|stream| stream.codec_type == codec_type)
+    }
+
+    /// Frame rate of the first video stream
+    pub fn fps(&self) -> Option<f32> {
+        self.first_stream("video")?
+            .r_frame_rate
+            .as_deref()
+            .and_then(parse_frame_rate)
+    }
+
+    /// (width, height) of the first video stream
+    pub fn resolution(&self) -> Option<(u32, u32)> {
+        let video = self.first_stream("video")?;
+        Some((video.width?, video.height?))
+    }
+
+    /// Channel layout of the first audio stream (e.g. "stereo", "5.1(side)")
+    pub fn channel_layout(&self) -> Option<&str> {
+        self.first_stream("audio")?.channel_layout.as_deref()
+    }
+
+    /// Container duration and size, and which kinds of streams there are
+    pub fn media_probe(&self) -> MediaProbe {
+        let format = self.format.as_ref();
+        MediaProbe {
+            duration_secs: format
+                .and_then(|format| format.duration.as_deref())
+                .and_then(|duration| duration.parse().ok())
+                .unwrap_or(0.0),
+            has_video: self.first_stream("video").is_some(),
+            has_audio: self.first_stream("audio").is_some(),
+            size_bytes: format
+                .and_then(|format| format.size.as_deref())
+                .and_then(|size| size.parse().ok())
+                .unwrap_or(0),
+        }
+    }
+
+    /// The title and the first video/audio stream languages
+    fn source_tags(&self) -> SourceTags {
+        let language = |codec_type: &str| {
+            self.first_stream(codec_type)
+                .and_then(|stream| stream.tags.get("language").cloned())
+        };
+
+        SourceTags {
+            title: self.format.as_ref().and_then(|format| format.tags.get("title").cloned()),
+            video_language: language("video"),
+            audio_language: language("audio"),
+        }
+    }
+
+    /// Details for the file info panel
+    ///
+    /// The container's duration and bit rate are used when it has them, falling
+    /// back to the first video stream's.
+    pub fn media_info(&self) -> MediaInfo {
+        let video = self.first_stream("video");
+        let audio = self.first_stream("audio");
+        let format = self.format.as_ref();
+
+        let duration = format
+            .and_then(|format| format.duration.as_deref())
+            .or(video.and_then(|video| video.duration.as_deref()));
+        let bit_rate = format
+            .and_then(|format| format.bit_rate.as_deref())
+            .or(video.and_then(|video| video.bit_rate.as_deref()));
+
+        MediaInfo {
+            fps: self.fps(),
+            resolution: self.resolution(),
+            duration_secs: duration.and_then(|duration| duration.parse().ok()).unwrap_or(0.0),
+            video_codec: video.and_then(|video| video.codec_name.clone()),
+            audio_codec: audio.and_then(|audio| audio.codec_name.clone()),
+            bit_rate: bit_rate.and_then(|bit_rate| bit_rate.parse().ok()),
+            is_hdr: video
+                .and_then(|video| video.color_transfer.as_deref())
+                .is_some_and(is_hdr_transfer),
+            is_interlaced: video
+                .and_then(|video| video.field_order.as_deref())
+                .is_some_and(is_interlaced_field_order),
+        }
+    }
+}
+
+/// Parse an ffprobe frame rate, a fraction like "30000/1001" or a plain number
+fn parse_frame_rate(rate: &str) -> Option<f32> {
+    let fps = match rate.split_once('/') {
+        Some((num, den)) => {
+            let den: f32 = den.parse().ok()?;
+            if den <= 0.0 {
+                return None;
+            }
+            num.parse::<f32>().ok()? / den
+        }
+        None => rate.parse().ok()?,
+    };
+    (fps > 0.0).then_some(fps)
+}
+
+/// Parse ffprobe's `-show_format -show_streams` JSON
+fn parse_probe_result(json: &str) -> Result<ProbeResult, String> {
+    serde_json::from_str(json).map_err(|e| format!("Failed to parse ffprobe JSON: {}", e))
+}
+
+/// Cached probe results by path, with the file's modification time and size
+/// when it was probed (so files rewritten by an export are probed again)
+type ProbeCacheEntry = (Option<SystemTime>, u64, Arc<ProbeResult>);
+static PROBE_CACHE: LazyLock<Mutex<HashMap<String, ProbeCacheEntry>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Probe a file's streams and container with a single ffprobe run
+///
+/// Results are cached, so the frame rate, resolution and audio layout
+/// lookups made while loading and exporting a file share one ffprobe process.
+pub fn probe_file(input_path: &str) -> Result<Arc<ProbeResult>, String> {
+    let stamp = std::fs::metadata(input_path)
+        .ok()
+        .map(|metadata| (metadata.modified().ok(), metadata.len()));
+    if let Some((modified, len)) = stamp {
+        if let Ok(cache) = PROBE_CACHE.lock() {
+            if let Some((cached_modified, cached_len, probe)) = cache.get(input_path) {
+                if *cached_modified == modified && *cached_len == len {
+                    return Ok(probe.clone());
+                }
+            }
+        }
+    }
+
+    let output = ffprobe_command()
+        .args([
+            "-v",
+            "error",
+            "-show_format",
+            "-show_streams",
+            "-of",
+            "json",
+            input_path,
+        ])
+        .output()
+        .map_err(|e| Tool::Ffprobe.spawn_error(&e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("ffprobe failed: {}", stderr.trim()));
+    }
+
+    let probe = Arc::new(parse_probe_result(&String::from_utf8_lossy(&output.stdout))?);
+    // Files that can't be stat'ed (e.g. URLs) aren't cached
+    if let Some((modified, len)) = stamp {
+        if let Ok(mut cache) = PROBE_CACHE.lock() {
+            cache.insert(input_path.to_string(), (modified, len, probe.clone()));
+        }
+    }
+    Ok(probe)
+}
+
+/// Probe a media file's frame rate, resolution, codecs and bit rate (cached, see `probe_file`)
+pub fn get_media_info(input_path: &str) -> Result<MediaInfo, String> {
+    Ok(probe_file(input_path)?.media_info())
+}
+
+/// Sample rate audio is decoded at for the waveform (plenty for peak levels)
+const WAVEFORM_SAMPLE_RATE: u32 = 8000;
+
+/// Decoded samples folded into one peak while reading, so long files don't
+/// have to be held in memory
+const WAVEFORM_BLOCK_SAMPLES: usize = 80;
+
+/// Number of peaks in a waveform, whatever the length of the video
+pub const WAVEFORM_PEAKS: usize = 1000;
+
+/// Highest absolute level (capped at 1.0) in each of `count` equal slices of `samples`
+///
+/// Gives fewer than `count` values when there are fewer samples than that.
+fn downsample_peaks(samples: &[f32], count: usize) -> Vec<f32> {
+    let count = count.min(samples.len());
+    (0..count)
+        .map(|idx| {
+            let start = idx * samples.len() / count;
+            let end = (idx + 1) * samples.len() / count;
+            samples[start..end]
+                .iter()
+                .fold(0.0f32, |peak, sample| peak.max(sample.abs()))
+                .min(1.0)
+        })
+        .collect()
+}
+
+/// Audio waveform of a media file as up to `WAVEFORM_PEAKS` peak levels (0.0-1.0)
+///
+/// Decodes the first audio stream as 8kHz mono floats. Files without audio
+/// give an empty Vec.
+pub fn extract_waveform(input_path: &str) -> Result<Vec<f32>, String> {
+    if !probe_media(input_path)?.has_audio {
+        return Ok(Vec::new());
+    }
+
+    let mut child = ffmpeg_command()
+        .args(["-v", "error", "-i"])
+        .arg(input_path)
+        .args(["-map", "0:a:0", "-vn", "-ac", "1", "-ar"])
+        .arg(WAVEFORM_SAMPLE_RATE.to_string())
+        .args(["-f", "f32le", "-"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| Tool::Ffmpeg.spawn_error(&e))?;
+
+    // Fold samples into block peaks as they arrive
+    let mut block_peaks = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        let mut reader = BufReader::new(stdout);
+        let mut sample = [0u8; 4];
+        let mut peak = 0.0f32;
+        let mut block_len = 0;
+        while reader.read_exact(&mut sample).is_ok() {
+            peak = peak.max(f32::from_le_bytes(sample).abs());
+            block_len += 1;
+            if block_len == WAVEFORM_BLOCK_SAMPLES {
+                block_peaks.push(peak);
+                peak = 0.0;
+                block_len = 0;
+            }
+        }
+        if block_len > 0 {
+            block_peaks.push(peak);
+        }
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| format!("Failed to wait for ffmpeg: {}", e))?;
+    if !status.success() {
+        return Err("ffmpeg failed to decode the audio for the waveform".to_string());
+    }
+
+    Ok(downsample_peaks(&block_peaks, WAVEFORM_PEAKS))
+}
+
+/// Check an exported file against what the export should have produced
+///
+/// ffmpeg can exit successfully and still write an empty or truncated file,
+/// so returns a warning for each thing that looks wrong. The duration may be
+/// off by up to 5% (at least half a second) for keyframe and frame rounding.
+pub fn validate_export(
+    output: &MediaProbe,
+    expected_duration_secs: f32,
+    expect_video: bool,
+    expect_audio: bool,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    if output.size_bytes == 0 {
+        warnings.push("output file is empty".to_string());
+    }
+
+    let tolerance = (expected_duration_secs * 0.05).max(0.5);
+    if output.duration_secs <= 0.0 {
+        warnings.push("output has no duration".to_string());
+    } else if (output.duration_secs - expected_duration_secs).abs() > tolerance {
+        warnings.push(format!(
+            "output is {:.2}s long, expected {:.2}s",
+            output.duration_secs, expected_duration_secs
+        ));
+    }
+
+    if expect_video && !output.has_video {
+        warnings.push("output has no video stream".to_string());
+    }
+    if expect_audio && !output.has_audio {
+        warnings.push("output has no audio stream".to_string());
+    }
+
+    warnings
+}
+
+/// Format a file size for display (e.g. "4.2 MB")
+pub fn format_file_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Escape a value for an ffmetadata file (`=`, `;`, `#`, `\` and newlines)
+fn escape_ffmetadata(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '=' | ';' | '#' | '\\' | '\n') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Build an ffmetadata chapters file from markers inside a clip
+///
+/// Chapter times are rebased so the clip start is 0. Each chapter runs until
+/// the next marker (or the clip end). A held intro frame (`hold_ms`) pushes the
+/// chapters back by its length; a chapter opening the clip also covers the hold.
+/// Returns `None` when no markers fall inside the clip.
+pub fn format_chapters_metadata(
+    markers: &[crate::Marker],
+    clip_start_ms: u64,
+    clip_end_ms: u64,
+    hold_ms: u64,
+) -> Option<String> {
+    let in_clip: Vec<&crate::Marker> = markers
+        .iter()
+        .filter(|m| m.time_ms >= clip_start_ms && m.time_ms < clip_end_ms)
+        .collect();
+
+    if in_clip.is_empty() {
+        return None;
+    }
+
+    let mut metadata = String::from(";FFMETADATA1\n");
+    for (i, marker) in in_clip.iter().enumerate() {
+        let end_ms = in_clip
+            .get(i + 1)
+            .map(|next| next.time_ms)
+            .unwrap_or(clip_end_ms);
+
+        metadata.push_str("\n[CHAPTER]\nTIMEBASE=1/1000\n");
+        let start_ms = match marker.time_ms - clip_start_ms {
+            0 => 0,
+            offset_ms => offset_ms + hold_ms,
+        };
+        metadata.push_str(&format!("START={}\n", start_ms));
+        metadata.push_str(&format!("END={}\n", end_ms - clip_start_ms + hold_ms));
+        metadata.push_str(&format!("title={}\n", escape_ffmetadata(&marker.name)));
+    }
+
+    Some(metadata)
+}
+
+/// Chapter markers for the source's own chapters that overlap a clip
+///
+/// `chapters` are (start secs, title) in order, as listed by the player. The
+/// chapter already running at the clip start is moved up to the clip start so
+/// the opening of the clip keeps its title.
+pub fn source_chapter_markers(
+    chapters: &[(f64, String)],
+    clip_start_ms: u64,
+    clip_end_ms: u64,
+) -> Vec<crate::Marker> {
+    let to_ms = |secs: f64| (secs.max(0.0) * 1000.0) as u64;
+    chapters
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (start_secs, title))| {
+            let start_ms = to_ms(*start_secs);
+            let end_ms = chapters
+                .get(i + 1)
+                .map(|(next_secs, _)| to_ms(*next_secs))
+                .unwrap_or(u64::MAX);
+            (start_ms < clip_end_ms && end_ms > clip_start_ms).then(|| crate::Marker {
+                time_ms: start_ms.max(clip_start_ms),
+                name: title.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Most chapters generated from subtitles; denser dialogue is thinned out
+pub const MAX_SUBTITLE_CHAPTERS: usize = 50;
+
+/// Longest chapter title taken from a subtitle, in characters
+const SUBTITLE_CHAPTER_TITLE_CHARS: usize = 60;
+
+/// Chapter markers at the start of each subtitle inside a clip
+///
+/// The subtitle's text, on one line and without `<i>`-style tags, is the
+/// chapter title. When the clip has more than `MAX_SUBTITLE_CHAPTERS`
+/// subtitles, one only starts a chapter once an even share of the clip has
+/// passed since the previous chapter.
+pub fn subtitle_chapter_markers(
+    entries: &[crate::subtitle_extractor::SubtitleEntry],
+    clip_start_ms: u64,
+    clip_end_ms: u64,
+) -> Vec<crate::Marker> {
+    let in_clip: Vec<_> = entries
+        .iter()
+        .filter(|e| e.start_ms >= clip_start_ms && e.start_ms < clip_end_ms)
+        .collect();
+    let min_gap_ms = if in_clip.len() > MAX_SUBTITLE_CHAPTERS {
+        (clip_end_ms - clip_start_ms) / MAX_SUBTITLE_CHAPTERS as u64
+    } else {
+        0
+    };
+
+    let mut markers: Vec<crate::Marker> = Vec::new();
+    for entry in in_clip {
+        // Chapters need distinct start times too
+        if let Some(last) = markers.last() {
+            if entry.start_ms <= last.time_ms || entry.start_ms < last.time_ms + min_gap_ms {
+                continue;
+            }
+        }
+        markers.push(crate::Marker {
+            time_ms: entry.start_ms,
+            name: subtitle_chapter_title(&entry.text),
+        });
+    }
+    markers
+}
+
+/// One-line, tag-free and shortened subtitle text for a chapter title
+fn subtitle_chapter_title(text: &str) -> String {
+    let mut plain = String::new();
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => plain.push(c),
+            _ => {}
+        }
+    }
+    let title = plain.split_whitespace().collect::<Vec<_>>().join(" ");
+    if title.chars().count() > SUBTITLE_CHAPTER_TITLE_CHARS {
+        let short: String = title.chars().take(SUBTITLE_CHAPTER_TITLE_CHARS - 1).collect();
+        format!("{}…", short.trim_end())
+    } else {
+        title
+    }
+}
+
+/// Makes ffmpeg write machine-readable progress to stdout instead of stats to stderr
+const PROGRESS_ARGS: [&str; 3] = ["-progress", "pipe:1", "-nostats"];
+
+/// Fraction of an export done, from one line of ffmpeg's `-progress` output
+///
+/// `time_offset` is subtracted from the output time, for commands using
+/// `-copyts` whose output timestamps start at the clip start.
+fn progress_fraction(line: &str, time_offset: f32, total_secs: f32) -> Option<f32> {
+    let (key, value) = line.trim().split_once('=')?;
+    match key {
+        "progress" if value == "end" => Some(1.0),
+        // Despite the name, ffmpeg reports out_time_ms in microseconds
+        "out_time_ms" if total_secs > 0.0 => {
+            let out_secs = value.parse::<f64>().ok()? / 1_000_000.0;
+            Some(((out_secs as f32 - time_offset) / total_secs).clamp(0.0, 1.0))
+        }
+        _ => None,
+    }
+}
+
+/// Run an ffmpeg command started with `PROGRESS_ARGS`, reporting progress as it goes
+///
+/// The process is parked in `cancel` while it runs so it can be killed.
+/// Returns the exit status and everything ffmpeg wrote to stderr.
+fn run_with_progress(
+    cmd: &mut Command,
+    time_offset: f32,
+    total_secs: f32,
+    progress: &impl Fn(f32),
+    cancel: &CancelHandle,
+) -> Result<(ExitStatus, String), String> {
+    if cancel.is_cancelled() {
+        return Err("Export cancelled".to_string());
+    }
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| Tool::Ffmpeg.spawn_error(&e))?;
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    if let Ok(mut slot) = cancel.child.lock() {
+        *slot = Some(child);
+    }
+    // A cancel that landed while spawning found no process to kill
+    if cancel.is_cancelled() {
+        cancel.cancel();
+    }
+
+    // Drain stderr on its own thread so a full pipe can't stall ffmpeg
+    let stderr_reader = std::thread::spawn(move || {
+        let mut text = String::new();
+        if let Some(mut stderr) = stderr {
+            let _ = stderr.read_to_string(&mut text);
+        }
+        text
+    });
+
+    if let Some(stdout) = stdout {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if let Some(fraction) = progress_fraction(&line, time_offset, total_secs) {
+                progress(fraction);
+            }
+        }
+    }
+
+    // stdout closes when ffmpeg exits (or is killed); wait so its files are released
+    let child = cancel.child.lock().ok().and_then(|mut slot| slot.take());
+    let status = match child {
+        Some(mut child) => child
+            .wait()
+            .map_err(|e| format!("Failed to wait for ffmpeg: {}", e))?,
+        None => return Err("Lost track of the ffmpeg process".to_string()),
+    };
+    let stderr = stderr_reader.join().unwrap_or_default();
+    if cancel.is_cancelled() {
+        return Err("Export cancelled".to_string());
+    }
+    Ok((status, stderr))
+}
+
+/// Export a video clip from start_secs to end_secs using ffmpeg CLI
+///
+/// # Arguments
+/// * `input_path` - Path to the input video file
+/// * `output_path` - Path where the output clip should be saved (`.webm` is encoded as VP9/Opus)
+/// * `start_secs` - Start time in seconds
+/// * `end_secs` - End time in seconds
+/// * `subtitle_settings` - Optional subtitle settings (font, size, bold, italic, color)
+/// * `subtitle_mode` - Whether subtitles are left out, burned in or muxed as a stream
+/// * `subtitle_track` - Optional subtitle track index (1-based) to burn in or mux
+/// * `source_video_width` - Width of the video as displayed in the player (for subtitle scaling)
+/// * `options` - Optional encoding settings (output fps, quality, chapters, metadata)
+/// * `progress` - Called with the fraction of the clip written so far (0.0 to 1.0)
+///
+/// # Returns
+/// * `Ok(())` on success
+/// * `Err(String)` with error message on failure
+pub fn export_clip(
+    input_path: &str,
+    output_path: &str,
+    start_secs: f32,
+    end_secs: f32,
+    subtitle_settings: Option<&crate::SubtitleSettings>,
+    subtitle_mode: SubtitleMode,
+    subtitle_track: Option<usize>,
+    source_video_width: u32,
+    options: &ExportOptions,
+    progress: impl Fn(f32),
+) -> Result<(), String> {
+    // Calculate duration; a held first frame makes the output that much longer
+    let duration = end_secs - start_secs;
+    let hold_secs = options.intro_hold_secs.unwrap_or(0.0);
+
+    // Format timestamps for ffmpeg
+    let start_time = format!("{}", start_secs);
+    let duration_time = format!("{}", duration + hold_secs);
+
+    // Detect video metadata using ffprobe; the frame count follows the output fps
+    let fps = match options.output_fps {
+        Some(output_fps) => output_fps,
+        None => get_video_fps(input_path).unwrap_or(30.0),
+    };
+    let frame_count = ((duration + hold_secs) * fps).trunc() as u32;
+
+    // Get audio codec arguments based on file analysis
+    let mut audio_args = get_audio_codec_args(input_path)?;
+    if hold_secs > 0.0 {
+        audio_args = with_intro_audio_delay(audio_args, hold_secs);
+    }
+    let is_webm = is_webm_output(output_path);
+    if is_webm {
+        audio_args = with_opus_audio(audio_args);
+    }
+
+    // Target size: the video bitrate is what's left after the audio; the
+    // hardware encoders don't do two passes, so x264/VP9 are used
+    let two_pass = match options.target_size_mb {
+        Some(target_mb) => {
+            audio_args = with_budgeted_audio(audio_args);
+            let audio_kbps = audio_bitrate_kbps(&audio_args).unwrap_or(DEFAULT_AUDIO_KBPS);
+            let video_kbps = target_video_kbps(target_mb, duration + hold_secs, audio_kbps)?;
+            println!(
+                "Targeting {} MB: {} kbit/s video + {} kbit/s audio",
+                target_mb, video_kbps, audio_kbps
+            );
+            Some((video_kbps, passlog_prefix()))
+        }
+        None => None,
+    };
+    let video_args = match &two_pass {
+        Some((video_kbps, passlog)) => target_size_codec_args(is_webm, *video_kbps, passlog),
+        None => video_codec_args(is_webm, options.hardware_encoder, options.quality),
+    };
+
+    // Check if input is a .ts file for special handling
+    let is_ts_file = input_path.ends_with(".ts");
+
+    // Build subtitle filter if needed
+    let subtitle_filter = clip_subtitle_filter(
+        input_path,
+        subtitle_settings,
+        subtitle_mode == SubtitleMode::Burn,
+        subtitle_track,
+        source_video_width,
+        options,
+    )?;
+
+    // Check the container takes soft subtitles before starting the encode
+    let soft_codec = match subtitle_mode {
+        SubtitleMode::Soft => Some(soft_subtitle_codec(output_path)?),
+        SubtitleMode::None | SubtitleMode::Burn => None,
+    };
+
+    // Build ffmpeg command matching atci clipper for maximum speed
+    // Key optimization: -ss BEFORE -i for fast seeking
+    let mut cmd = ffmpeg_command();
+    cmd.args(PROGRESS_ARGS);
+
+    // The hardware scaler needs a device to upload frames to (only used when resizing)
+    let hw_scaler = options.hw_scaler.filter(|_| options.output_scale.height().is_some());
+    if let Some(scaler) = hw_scaler {
+        cmd.arg("-init_hw_device")
+            .arg(format!("{}=hw", scaler.device_type()))
+            .arg("-filter_hw_device")
+            .arg("hw");
+    }
+
+    cmd.arg("-ss").arg(&start_time).arg("-i").arg(input_path);
+
+    // Chapters come from a second (stream-less) ffmetadata input
+    if let Some(ref chapters) = options.chapters_path {
+        cmd.arg("-f").arg("ffmetadata").arg("-i").arg(chapters);
+    }
+
+    // Soft subtitles come from the custom subtitles file (seeked like the source,
+    // after any chapters input) or the selected track of the source. A held intro
+    // frame and the subtitle timing offset move them against the picture, so then
+    // they're read from their own input shifted by both.
+    let subtitle_input_index = if options.chapters_path.is_some() { 2 } else { 1 };
+    let subtitle_shift_secs = hold_secs + options.subtitle_offset_ms as f32 / 1000.0;
+    let soft_subtitle_map = match (soft_codec, &options.subtitle_file) {
+        (None, _) => None,
+        (Some(_), Some(subtitle_file)) => {
+            cmd.arg("-ss").arg(&start_time);
+            if subtitle_shift_secs != 0.0 {
+                cmd.arg("-itsoffset").arg(format!("{}", subtitle_shift_secs));
+            }
+            if let Some(charset) = &options.subtitle_charset {
+                cmd.arg("-sub_charenc").arg(charset);
+            }
+            cmd.arg("-i").arg(subtitle_file);
+            Some(format!("{}:s:0", subtitle_input_index))
+        }
+        (Some(_), None) if subtitle_shift_secs != 0.0 => subtitle_track.map(|track| {
+            cmd.arg("-ss")
+                .arg(&start_time)
+                .arg("-itsoffset")
+                .arg(format!("{}", subtitle_shift_secs))
+                .arg("-i")
+                .arg(input_path);
+            format!("{}:s:{}", subtitle_input_index, track.saturating_sub(1))
+        }),
+        (Some(_), None) => subtitle_track.map(|track| format!("0:s:{}", track.saturating_sub(1))),
+    };
+
+    // Before the other maps, so an overlay's output (which is mapped with it)
+    // is the first stream
+    if let Some(vf) = clip_video_filter(subtitle_filter.as_ref(), is_ts_file, options) {
+        cmd.args(video_filter_args(&vf));
+    }
+    let burns_bitmap = matches!(subtitle_filter, Some(SubtitleBurn::Bitmap(_)));
+
+    // Keep the picked audio track instead of the one ffmpeg would choose; mapping
+    // a subtitle stream or an overlay turns off the automatic picks, so it needs
+    // these too
+    if options.audio_track.is_some() || soft_subtitle_map.is_some() || burns_bitmap {
+        let audio_map = match options.audio_track {
+            // Optional, so a stale pick can't fail the export of a file without audio
+            Some(track) => format!("0:a:{}?", track),
+            None => "0:a:0?".to_string(),
+        };
+        if !burns_bitmap {
+            cmd.arg("-map").arg("0:v:0");
+        }
+        cmd.arg("-map").arg(audio_map);
+    }
+    if let (Some(map), Some(codec)) = (&soft_subtitle_map, soft_codec) {
+        cmd.arg("-map").arg(map).arg("-c:s").arg(codec);
+    }
+
+    // When using subtitles, we need to use copyts and -to instead of -t
+    let has_subtitles = subtitle_filter.is_some();
+
+    if has_subtitles {
+        cmd.arg("-copyts");
+    }
+
+    if is_ts_file {
+        // For TS files: use vsync cfr
+        if has_subtitles {
+            // Use -to with absolute endpoint when subtitles are enabled
+            cmd.arg("-to").arg(format!("{}", end_secs + hold_secs));
+        } else {
+            cmd.arg("-t").arg(&duration_time);
+        }
+
+        cmd.args(&video_args);
+        cmd.arg("-vsync").arg("cfr");
+    } else {
+        // For non-TS files: use double seek and frame count (when no subtitles)
+        // or use -to (when subtitles are enabled)
+        if has_subtitles {
+            // Use -to with absolute endpoint when subtitles are enabled
+            cmd.arg("-to")
+                .arg(format!("{}", end_secs + hold_secs))
+                .arg("-frames:v")
+                .arg(frame_count.to_string());
+        } else {
+            cmd.arg("-ss")
+                .arg("00:00:00.001")
+                .arg("-t")
+                .arg(&duration_time)
+                .arg("-frames:v")
+                .arg(frame_count.to_string());
+        }
+
+        cmd.args(&video_args);
+    }
+
+    // Add audio codec arguments (detected based on source file)
+    for arg in audio_args {
+        cmd.arg(arg);
+    }
+
+    // Optimization flags (WebM doesn't take movflags)
+    if !is_webm {
+        cmd.arg("-movflags").arg("faststart+frag_keyframe+empty_moov");
+    }
+
+    // Don't use avoid_negative_ts when subtitles are enabled
+    if !has_subtitles {
+        cmd.arg("-avoid_negative_ts").arg("make_zero");
+    }
+
+    // Source tags (title, dates, languages) are only kept when requested
+    cmd.args(metadata_args(input_path, options.copy_metadata));
+
+    if two_pass.is_some() {
+        cmd.arg("-pass").arg("2");
+    }
+
+    // Source chapters are never mapped directly; the ones inside the clip (when
+    // metadata is kept) and marker chapters come in through the chapters file
+    cmd.arg("-y")
+        .arg("-map_chapters")
+        .arg(if options.chapters_path.is_some() { "1" } else { "-1" })
+        .arg(output_path);
+
+    // Debug: print the command
+    eprintln!("FFmpeg video export command: {:?}", cmd);
+
+    // With -copyts the output timestamps start at the clip start
+    let time_offset = if has_subtitles { start_secs } else { 0.0 };
+    let total_secs = duration + hold_secs;
+    let (status, stderr) = match &two_pass {
+        // Pass 1 only writes the rate stats; each pass is half of the progress
+        Some((_, passlog)) => {
+            let args: Vec<OsString> = cmd.get_args().map(OsString::from).collect();
+            let mut first_pass = ffmpeg_command();
+            first_pass.args(first_pass_args(&args));
+            eprintln!("FFmpeg first pass command: {:?}", first_pass);
+
+            let first_progress = |fraction: f32| progress(fraction * 0.5);
+            let second_progress = |fraction: f32| progress(0.5 + fraction * 0.5);
+            let result = run_with_progress(
+                &mut first_pass,
+                time_offset,
+                total_secs,
+                &first_progress,
+                &options.cancel,
+            )
+            .and_then(|(status, stderr)| {
+                if !status.success() {
+                    return Err(format!("ffmpeg first pass failed: {}", stderr));
+                }
+                run_with_progress(
+                    &mut cmd,
+                    time_offset,
+                    total_secs,
+                    &second_progress,
+                    &options.cancel,
+                )
+            });
+            remove_passlog_files(passlog);
+            result?
+        }
+        None => run_with_progress(
+            &mut cmd,
+            time_offset,
+            total_secs,
+            &progress,
+            &options.cancel,
+        )?,
+    };
+
+    if !status.success() {
+        // The filter may be compiled in without a usable device; retry in software
+        if hw_scaler.is_some() {
+            eprintln!(
+                "Hardware scaling failed, retrying with software scaling: {}",
+                stderr
+            );
+            return export_clip(
+                input_path,
+                output_path,
+                start_secs,
+                end_secs,
+                subtitle_settings,
+                subtitle_mode,
+                subtitle_track,
+                source_video_width,
+                &ExportOptions {
+                    hw_scaler: None,
+                    ..options.clone()
+                },
+                progress,
+            );
+        }
+
+        // The encoder may be compiled in without a usable GPU; retry with libx264
+        if let (Some(encoder), None) = (options.hardware_encoder, &two_pass) {
+            eprintln!(
+                "{} failed, retrying with libx264: {}",
+                encoder.encoder_name(),
+                stderr
+            );
+            return export_clip(
+                input_path,
+                output_path,
+                start_secs,
+                end_secs,
+                subtitle_settings,
+                subtitle_mode,
+                subtitle_track,
+                source_video_width,
+                &ExportOptions {
+                    hardware_encoder: None,
+                    ..options.clone()
+                },
+                progress,
+            );
+        }
+
+        return Err(format!("ffmpeg failed: {}", stderr));
+    }
+
+    Ok(())
+}
+
+/// Export a video clip as an animated GIF from start_secs to end_secs
+///
+/// Uses optimized settings from atci clipper:
+/// - 10fps for reasonable file size (`options.fps`)
+/// - Scale to 480px width with Lanczos filtering (`options.width`)
+/// - Palette generation for better colors
+/// - Infinite loop
+///
+/// # Arguments
+/// * `input_path` - Path to the input video file
+/// * `output_path` - Path where the output GIF should be saved
+/// * `start_secs` - Start time in seconds
+/// * `end_secs` - End time in seconds
+/// * `subtitle_settings` - Optional subtitle settings (font, size, bold, italic, color)
+/// * `display_subtitles` - Whether to include subtitles in the GIF
+/// * `subtitle_track` - Optional subtitle track index to burn in
+/// * `source_video_width` - Width of the video as displayed in the player (for subtitle scaling)
+/// * `options` - Frame rate, width, looping, tone mapping and hardware scaling
+/// * `progress` - Called with the fraction of the clip written so far (0.0 to 1.0)
+///
+/// # Returns
+/// * `Ok(())` on success
+/// * `Err(String)` with error message on failure
+pub fn export_gif(
+    input_path: &str,
+    output_path: &str,
+    start_secs: f32,
+    end_secs: f32,
+    subtitle_settings: Option<&crate::SubtitleSettings>,
+    display_subtitles: bool,
+    subtitle_track: Option<usize>,
+    source_video_width: u32,
+    options: &GifOptions,
+    progress: impl Fn(f32),
+) -> Result<(), String> {
+    if !GIF_FPS_RANGE.contains(&options.fps) {
+        return Err(format!(
+            "GIF frame rate must be between {} and {}",
+            GIF_FPS_RANGE.start(),
+            GIF_FPS_RANGE.end()
+        ));
+    }
+    if !GIF_WIDTH_RANGE.contains(&options.width) {
+        return Err(format!(
+            "GIF width must be between {} and {}",
+            GIF_WIDTH_RANGE.start(),
+            GIF_WIDTH_RANGE.end()
+        ));
+    }
 
-    // Add palette generation filter
-    // split[s0][s1];[s0]palettegen[p];[s1][p]paletteuse
-    let vf_filter = format!(
-        "{},split[s0][s1];[s0]palettegen[p];[s1][p]paletteuse",
-        filter_parts.join(",")
+    // Calculate duration
+    let duration = end_secs - start_secs;
+
+    // Format timestamps for ffmpeg
+    let start_time = format!("{}", start_secs);
+    let duration_time = format!("{}", duration);
+
+    // Build the video filter (-vf) for GIF generation
+    let vf_filter = gif_video_filter(
+        input_path,
+        subtitle_settings,
+        display_subtitles,
+        subtitle_track,
+        source_video_width,
+        options,
     );
 
     // Build ffmpeg command with correct argument order from atci clipper:
     // -ss {start} -t {duration} -i {input} -vf {filter} -loop 0 -y {output}
     // Note: Unlike video exports, GIFs don't need -to for subtitles
-    let mut cmd = Command::new("ffmpeg");
+    let mut cmd = ffmpeg_command();
+    cmd.args(PROGRESS_ARGS);
+
+    // The hardware scaler needs a device to upload frames to
+    if let Some(scaler) = options.hw_scaler {
+        cmd.arg("-init_hw_device")
+            .arg(format!("{}=hw", scaler.device_type()))
+            .arg("-filter_hw_device")
+            .arg("hw");
+    }
 
     cmd.arg("-ss")
         .arg(&start_time)
@@ -512,10 +2515,9 @@ pub fn export_gif(
         .arg(input_path)
         .arg("-copyts");
 
-    cmd.arg("-vf")
-        .arg(&vf_filter)
+    cmd.args(video_filter_args(&vf_filter))
         .arg("-loop")
-        .arg(if loop_gif { "0" } else { "-1" }) // 0 = infinite loop, -1 = no loop
+        .arg(if options.loop_gif { "0" } else { "-1" }) // 0 = infinite loop, -1 = no loop
         .arg("-y") // Overwrite output file
         .arg(output_path);
 
@@ -523,14 +2525,1262 @@ pub fn export_gif(
     eprintln!("FFmpeg GIF export command: {:?}", cmd);
     eprintln!("GIF filter chain: {}", vf_filter);
 
-    let output = cmd
-        .output()
-        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+    // -copyts keeps the output timestamps starting at the clip start
+    let (status, stderr) =
+        run_with_progress(&mut cmd, start_secs, duration, &progress, &options.cancel)?;
+
+    if !status.success() {
+        // The filter may be compiled in without a usable device; retry in software
+        if options.hw_scaler.is_some() {
+            eprintln!(
+                "Hardware scaling failed, retrying with software scaling: {}",
+                stderr
+            );
+            return export_gif(
+                input_path,
+                output_path,
+                start_secs,
+                end_secs,
+                subtitle_settings,
+                display_subtitles,
+                subtitle_track,
+                source_video_width,
+                &GifOptions {
+                    hw_scaler: None,
+                    ..options.clone()
+                },
+                progress,
+            );
+        }
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(format!("ffmpeg failed: {}", stderr));
     }
 
     Ok(())
 }
+
+/// Build the filter chain for a contact sheet of `frame_count` source frames
+///
+/// Every n-th frame is picked so that rows × cols frames are spread evenly
+/// across the clip, then scaled down and laid out with the `tile` filter.
+pub fn contact_sheet_filter(frame_count: u32, options: &ContactSheetOptions) -> String {
+    let tiles = (options.rows * options.cols).max(1);
+    let step = frame_count.div_ceil(tiles).max(1);
+
+    let mut filter_parts = Vec::new();
+    if options.tonemap_hdr {
+        filter_parts.push(HDR_TONEMAP_FILTER.to_string());
+    }
+    filter_parts.push(format!("select=not(mod(n\\,{}))", step));
+    filter_parts.push(format!("scale={}:-2", options.tile_width));
+    filter_parts.push(format!(
+        "tile={}x{}:padding=4:margin=4",
+        options.cols, options.rows
+    ));
+    filter_parts.join(",")
+}
+
+/// Export a grid of frames sampled across a clip as a single PNG
+///
+/// # Arguments
+/// * `input_path` - Path to the input video file
+/// * `output_path` - Path where the output PNG should be saved
+/// * `start_secs` - Start time in seconds
+/// * `end_secs` - End time in seconds
+/// * `options` - Grid size, thumbnail width and tone mapping
+///
+/// # Returns
+/// * `Ok(())` on success
+/// * `Err(String)` with error message on failure
+pub fn export_contact_sheet(
+    input_path: &str,
+    output_path: &str,
+    start_secs: f32,
+    end_secs: f32,
+    options: &ContactSheetOptions,
+) -> Result<(), String> {
+    let duration = end_secs - start_secs;
+    let fps = get_video_fps(input_path).unwrap_or(30.0);
+    let frame_count = (duration * fps).trunc() as u32;
+    let vf_filter = contact_sheet_filter(frame_count, options);
+
+    // -ss before -i for fast seeking; the tile filter emits one image, padded
+    // with blank cells if the clip runs out of frames
+    let mut cmd = ffmpeg_command();
+    cmd.arg("-ss")
+        .arg(format!("{}", start_secs))
+        .arg("-t")
+        .arg(format!("{}", duration))
+        .arg("-i")
+        .arg(input_path)
+        .arg("-vf")
+        .arg(&vf_filter)
+        .arg("-frames:v")
+        .arg("1")
+        .arg("-y")
+        .arg(output_path);
+
+    // Debug: print the command and filter
+    eprintln!("FFmpeg contact sheet command: {:?}", cmd);
+    eprintln!("Contact sheet filter chain: {}", vf_filter);
+
+    // Run like the encodes so Cancel can stop it
+    let (status, stderr) = run_with_progress(&mut cmd, 0.0, duration, &|_| {}, &options.cancel)?;
+
+    if !status.success() {
+        return Err(format!("ffmpeg contact sheet export failed: {}", stderr));
+    }
+
+    Ok(())
+}
+
+/// Image format for `export_frame`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// Lossless PNG
+    #[default]
+    Png,
+    /// JPEG at high quality (`-q:v 2`)
+    Jpeg,
+}
+
+impl ImageFormat {
+    /// Guess the format from an output path's extension (PNG by default)
+    pub fn from_output_path(output_path: &str) -> Self {
+        let extension = Path::new(output_path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase());
+        match extension.as_deref() {
+            Some("jpg") | Some("jpeg") => ImageFormat::Jpeg,
+            _ => ImageFormat::Png,
+        }
+    }
+
+    /// ffmpeg encoder arguments for this format
+    fn codec_args(&self) -> [&'static str; 2] {
+        match self {
+            ImageFormat::Png => ["-c:v", "png"],
+            ImageFormat::Jpeg => ["-q:v", "2"],
+        }
+    }
+}
+
+/// Export the single frame at `time_secs` as an image
+///
+/// Subtitles are burned in with the same filter as `export_clip`; the frame
+/// rate and intro hold options don't apply to a single frame and are ignored.
+///
+/// # Arguments
+/// * `input_path` - Path to the input video file
+/// * `output_path` - Path where the image should be saved
+/// * `time_secs` - Time of the frame in seconds
+/// * `format` - Image format (should match the output extension)
+/// * `subtitle_settings` - Optional subtitle styling settings
+/// * `display_subtitles` - Whether to burn in subtitles
+/// * `subtitle_track` - Subtitle stream index to burn in, if any
+/// * `source_video_width` - Width of the source video, for font scaling
+/// * `options` - Tone mapping, deinterlacing, output scale and subtitle file/offset
+///
+/// # Returns
+/// * `Ok(())` on success
+/// * `Err(String)` with error message on failure, or if `time_secs` is past the end of the file
+pub fn export_frame(
+    input_path: &str,
+    output_path: &str,
+    time_secs: f32,
+    format: ImageFormat,
+    subtitle_settings: Option<&crate::SubtitleSettings>,
+    display_subtitles: bool,
+    subtitle_track: Option<usize>,
+    source_video_width: u32,
+    options: &ExportOptions,
+) -> Result<(), String> {
+    let duration_secs = probe_media(input_path)?.duration_secs;
+    if duration_secs > 0.0 && time_secs as f64 >= duration_secs {
+        return Err(format!(
+            "Frame time {:.3}s is past the end of the file ({:.3}s)",
+            time_secs, duration_secs
+        ));
+    }
+
+    let subtitle_filter = clip_subtitle_filter(
+        input_path,
+        subtitle_settings,
+        display_subtitles,
+        subtitle_track,
+        source_video_width,
+        options,
+    )?;
+    let frame_options = ExportOptions {
+        output_fps: None,
+        intro_hold_secs: None,
+        hw_scaler: None,
+        ..options.clone()
+    };
+    let vf_filter = clip_video_filter(subtitle_filter.as_ref(), false, &frame_options);
+
+    // -ss before -i for fast seeking; burned-in subtitles need the source
+    // timestamps, like in export_clip
+    let mut cmd = ffmpeg_command();
+    cmd.arg("-ss")
+        .arg(format!("{}", time_secs))
+        .arg("-i")
+        .arg(input_path);
+    if subtitle_filter.is_some() {
+        cmd.arg("-copyts");
+    }
+    if let Some(vf) = &vf_filter {
+        cmd.args(video_filter_args(vf));
+    }
+    cmd.arg("-frames:v")
+        .arg("1")
+        .args(format.codec_args())
+        .arg("-y")
+        .arg(output_path);
+
+    // Debug: print the command
+    eprintln!("FFmpeg frame export command: {:?}", cmd);
+
+    let (status, stderr) = run_with_progress(&mut cmd, 0.0, 0.0, &|_| {}, &options.cancel)?;
+
+    if !status.success() {
+        return Err(format!("ffmpeg frame export failed: {}", stderr));
+    }
+
+    Ok(())
+}
+
+/// Export the audio of a clip from start_secs to end_secs
+///
+/// # Arguments
+/// * `input_path` - Path to the input video file
+/// * `output_path` - Path where the output audio file should be saved
+/// * `start_secs` - Start time in seconds
+/// * `end_secs` - End time in seconds
+/// * `audio_track` - Index among the source's audio streams, or None for ffmpeg's default pick
+/// * `format` - Codec to encode with (should match the output extension)
+/// * `cancel` - Lets another thread stop the export
+///
+/// # Returns
+/// * `Ok(())` on success
+/// * `Err(String)` with error message on failure
+pub fn export_audio(
+    input_path: &str,
+    output_path: &str,
+    start_secs: f32,
+    end_secs: f32,
+    audio_track: Option<usize>,
+    format: AudioFormat,
+    cancel: &CancelHandle,
+) -> Result<(), String> {
+    // Calculate duration
+    let duration = end_secs - start_secs;
+
+    // -ss before -i for fast seeking, -vn to drop the video stream
+    let mut cmd = ffmpeg_command();
+    cmd.arg("-ss")
+        .arg(format!("{}", start_secs))
+        .arg("-t")
+        .arg(format!("{}", duration))
+        .arg("-i")
+        .arg(input_path);
+
+    // Take the audio track the player is using
+    if let Some(track) = audio_track {
+        cmd.arg("-map").arg(format!("0:a:{}?", track));
+    }
+
+    cmd.arg("-vn")
+        .args(format.codec_args())
+        .arg("-y")
+        .arg(output_path);
+
+    // Debug: print the command
+    eprintln!("FFmpeg audio export command: {:?}", cmd);
+
+    let (status, stderr) = run_with_progress(&mut cmd, 0.0, duration, &|_| {}, cancel)?;
+
+    if !status.success() {
+        return Err(format!("ffmpeg audio export failed: {}", stderr));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Marker;
+
+    /// Burned-in text subtitles, for the filter chain tests
+    fn text_subtitles() -> SubtitleBurn {
+        SubtitleBurn::Filter("subtitles=x".to_string())
+    }
+
+    fn marker(time_ms: u64, name: &str) -> Marker {
+        Marker {
+            time_ms,
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_format_chapters_metadata_rebases_to_clip() {
+        let markers = vec![
+            marker(500, "Before"),
+            marker(2000, "Intro"),
+            marker(5000, "Main"),
+            marker(9000, "After"),
+        ];
+
+        let metadata = format_chapters_metadata(&markers, 1000, 8000, 0).unwrap();
+        assert_eq!(
+            metadata,
+            ";FFMETADATA1\n\
+             \n[CHAPTER]\nTIMEBASE=1/1000\nSTART=1000\nEND=4000\ntitle=Intro\n\
+             \n[CHAPTER]\nTIMEBASE=1/1000\nSTART=4000\nEND=7000\ntitle=Main\n"
+        );
+    }
+
+    #[test]
+    fn test_subtitle_chapter_markers() {
+        let entry = |start_ms: u64, text: &str| crate::subtitle_extractor::SubtitleEntry {
+            start_ms,
+            end_ms: start_ms + 1000,
+            text: text.to_string(),
+        };
+        let entries = vec![
+            entry(500, "Before"),
+            entry(2000, "<i>Hello</i>\nthere"),
+            entry(2000, "Same start"),
+            entry(5000, &"long ".repeat(20)),
+        ];
+        let markers = subtitle_chapter_markers(&entries, 1000, 9000);
+        assert_eq!(markers.len(), 2);
+        assert_eq!((markers[0].time_ms, markers[0].name.as_str()), (2000, "Hello there"));
+        assert_eq!(markers[1].name.chars().count(), SUBTITLE_CHAPTER_TITLE_CHARS);
+        assert!(markers[1].name.ends_with('…'));
+
+        // Dense dialogue is capped
+        let dense: Vec<_> = (0..1000).map(|i| entry(i * 100, "line")).collect();
+        let markers = subtitle_chapter_markers(&dense, 0, 100_000);
+        assert!(markers.len() <= MAX_SUBTITLE_CHAPTERS);
+        assert!(markers.len() > MAX_SUBTITLE_CHAPTERS / 2);
+    }
+
+    #[test]
+    fn test_source_chapter_markers() {
+        let chapters = vec![
+            (0.0, "Opening".to_string()),
+            (10.0, "Part 1".to_string()),
+            (20.0, "Part 2".to_string()),
+            (30.0, "Credits".to_string()),
+        ];
+        let markers = source_chapter_markers(&chapters, 15_000, 25_000);
+        let got: Vec<_> = markers.iter().map(|m| (m.time_ms, m.name.as_str())).collect();
+        assert_eq!(got, vec![(15_000, "Part 1"), (20_000, "Part 2")]);
+
+        // The last chapter runs to the end of the file
+        let markers = source_chapter_markers(&chapters, 40_000, 45_000);
+        assert_eq!(markers.len(), 1);
+        assert_eq!((markers[0].time_ms, markers[0].name.as_str()), (40_000, "Credits"));
+
+        assert!(source_chapter_markers(&[], 0, 1000).is_empty());
+    }
+
+    #[test]
+    fn test_format_chapters_metadata_intro_hold() {
+        let markers = vec![marker(1000, "Opening"), marker(3000, "Main")];
+        let metadata = format_chapters_metadata(&markers, 1000, 8000, 1500).unwrap();
+        assert_eq!(
+            metadata,
+            ";FFMETADATA1\n\
+             \n[CHAPTER]\nTIMEBASE=1/1000\nSTART=0\nEND=3500\ntitle=Opening\n\
+             \n[CHAPTER]\nTIMEBASE=1/1000\nSTART=3500\nEND=8500\ntitle=Main\n"
+        );
+    }
+
+    #[test]
+    fn test_format_chapters_metadata_none_outside_clip() {
+        let markers = vec![marker(500, "Before"), marker(9000, "After")];
+        assert_eq!(format_chapters_metadata(&markers, 1000, 8000, 0), None);
+    }
+
+    #[test]
+    fn test_background_box_style_alpha() {
+        // Fully opaque box has 00 alpha, 60% opacity is 0x66 (102 = 255 - 153)
+        assert_eq!(
+            background_box_style(1.0),
+            "\\,BorderStyle=3\\,OutlineColour=&H00000000\\,BackColour=&H00000000"
+        );
+        assert!(background_box_style(0.6).contains("BackColour=&H66000000"));
+        assert!(background_box_style(0.0).contains("BackColour=&HFF000000"));
+    }
+
+    #[test]
+    fn test_parse_version_line() {
+        let output = "ffmpeg version 7.1 Copyright (c) 2000-2024 the FFmpeg developers\n\
+                      built with Apple clang\n";
+        assert_eq!(
+            parse_version_line(Tool::Ffmpeg, output).as_deref(),
+            Some("ffmpeg version 7.1 Copyright (c) 2000-2024 the FFmpeg developers")
+        );
+        assert_eq!(parse_version_line(Tool::Ffprobe, output), None);
+        assert_eq!(parse_version_line(Tool::Ffmpeg, "usage: ls\n"), None);
+    }
+
+    #[test]
+    fn test_spawn_error_messages() {
+        let not_found = std::io::Error::from(std::io::ErrorKind::NotFound);
+        assert!(spawn_error_message(Tool::Ffmpeg, "ffmpeg", &not_found)
+            .starts_with("ffmpeg not found on PATH. Install FFmpeg"));
+        assert!(spawn_error_message(Tool::Ffprobe, "/opt/ffprobe", &not_found)
+            .starts_with("ffprobe not found at /opt/ffprobe."));
+        let denied = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+        assert!(spawn_error_message(Tool::Ffmpeg, "ffmpeg", &denied)
+            .starts_with("Failed to execute ffmpeg: "));
+
+        assert_eq!(missing_tools_message(&[]), None);
+        assert!(missing_tools_message(&Tool::ALL)
+            .unwrap()
+            .starts_with("ffmpeg and ffprobe could not be run"));
+    }
+
+    #[test]
+    fn test_build_subtitle_filter_custom_file() {
+        let settings = crate::SubtitleSettings::default();
+        let track_filter = build_subtitle_filter("in.mkv", &settings, 2, None, None, 1920, 1920);
+        assert!(track_filter.starts_with("subtitles=in.mkv:si=1:force_style="));
+
+        let file_filter = build_subtitle_filter(
+            "in.mkv",
+            &settings,
+            1,
+            Some("/tmp/a:b.srt"),
+            None,
+            1920,
+            1920,
+        );
+        assert!(file_filter.starts_with("subtitles=/tmp/a\\:b.srt:force_style="));
+
+        // An encoding only applies to a standalone file
+        let file_filter = build_subtitle_filter(
+            "in.mkv",
+            &settings,
+            1,
+            Some("a.srt"),
+            Some("windows-1251"),
+            1920,
+            1920,
+        );
+        assert!(file_filter.starts_with("subtitles=a.srt:charenc=windows-1251:force_style="));
+        let track_filter = build_subtitle_filter(
+            "in.mkv",
+            &settings,
+            2,
+            None,
+            Some("windows-1251"),
+            1920,
+            1920,
+        );
+        assert!(track_filter.starts_with("subtitles=in.mkv:si=1:force_style="));
+    }
+
+    #[test]
+    fn test_hex_colors() {
+        assert_eq!(normalize_hex_color("#fff").as_deref(), Some("#FFFFFF"));
+        assert_eq!(normalize_hex_color(" 1a2b3c ").as_deref(), Some("#1A2B3C"));
+        assert_eq!(normalize_hex_color("#80ff0000").as_deref(), Some("#80FF0000"));
+        assert_eq!(normalize_hex_color("#12345"), None);
+        assert_eq!(normalize_hex_color("#GGGGGG"), None);
+        assert_eq!(normalize_hex_color(""), None);
+
+        assert_eq!(ass_bgr_color("#1A2B3C"), "003C2B1A");
+        assert_eq!(ass_bgr_color("#F00"), "000000FF");
+        assert_eq!(ass_bgr_color("#80FF0000"), "7F0000FF");
+        assert_eq!(ass_bgr_color("#FFFFFFFF"), "00FFFFFF");
+        assert_eq!(ass_bgr_color("white"), "00FFFFFF");
+    }
+
+    #[test]
+    fn test_outline_style() {
+        assert_eq!(
+            outline_style("#1A2B3C", 2.0, false),
+            "\\,BorderStyle=1\\,OutlineColour=&H003C2B1A\\,Outline=2.0\\,Shadow=0"
+        );
+        assert!(outline_style("#000000", -1.0, true).ends_with("Outline=0.0\\,Shadow=2"));
+
+        // The background box takes over the outline color
+        let mut settings = crate::SubtitleSettings::default();
+        let filter = build_subtitle_filter("in.mkv", &settings, 1, None, None, 960, 1920);
+        assert!(filter.contains("OutlineColour=&H00000000\\,Outline=1.0"));
+        settings.background_box = true;
+        let filter = build_subtitle_filter("in.mkv", &settings, 1, None, None, 1920, 1920);
+        assert!(filter.contains("BorderStyle=3"));
+        assert!(!filter.contains("BorderStyle=1"));
+    }
+
+    #[test]
+    fn test_subtitle_filter_alignment() {
+        let mut settings = crate::SubtitleSettings::default();
+        let filter = build_subtitle_filter("in.mkv", &settings, 1, None, None, 1920, 1920);
+        assert!(filter.ends_with("\\,Alignment=2\\,MarginV=10"));
+
+        settings.alignment = 8;
+        settings.title_safe = true;
+        let filter = build_subtitle_filter("in.mkv", &settings, 1, None, None, 1920, 1920);
+        assert!(filter.contains("\\,Alignment=8\\,MarginL="));
+        assert_eq!(filter.matches("MarginV=").count(), 1);
+
+        settings.alignment = 0;
+        let filter = build_subtitle_filter("in.mkv", &settings, 1, None, None, 1920, 1920);
+        assert!(filter.contains("\\,Alignment=1\\,"));
+    }
+
+    #[test]
+    fn test_title_safe_style_margins() {
+        assert_eq!(
+            title_safe_style(0.0),
+            "\\,MarginL=10\\,MarginR=10\\,MarginV=10"
+        );
+        assert_eq!(
+            title_safe_style(0.1),
+            "\\,MarginL=48\\,MarginR=48\\,MarginV=39"
+        );
+    }
+
+    #[test]
+    fn test_parse_hw_scaler() {
+        let filters = " ... scale             V->V       Scale the input video size.\n \
+                       ... scale_vaapi       V->V       Scale to/from VAAPI surfaces.\n \
+                       ... scale_cuda        V->V       GPU accelerated video resizer\n";
+        assert_eq!(parse_hw_scaler(filters), Some(HwScaler::Cuda));
+        assert_eq!(parse_hw_scaler(" ... scale  V->V  Scale\n"), None);
+    }
+
+    #[test]
+    fn test_target_video_kbps() {
+        // 8 MB over a minute, minus 128 kbit/s of audio
+        assert_eq!(target_video_kbps(8, 60.0, 128), Ok(956));
+        // A long clip in a small target leaves too little for the video
+        let err = target_video_kbps(8, 600.0, 128).unwrap_err();
+        assert!(err.contains("too small"), "{}", err);
+        assert!(target_video_kbps(8, 0.0, 128).is_err());
+    }
+
+    #[test]
+    fn test_target_size_audio_budget() {
+        let copy = ["-c:a", "copy"].map(String::from).to_vec();
+        let args = with_budgeted_audio(copy);
+        assert_eq!(audio_bitrate_kbps(&args), Some(DEFAULT_AUDIO_KBPS));
+
+        let opus = ["-c:a", "libopus", "-b:a", "160k"].map(String::from).to_vec();
+        assert_eq!(audio_bitrate_kbps(&with_budgeted_audio(opus)), Some(160));
+        let aac = ["-c:a", "aac"].map(String::from).to_vec();
+        assert_eq!(audio_bitrate_kbps(&aac), None);
+    }
+
+    #[test]
+    fn test_first_pass_args() {
+        let passlog = Path::new("/tmp/log");
+        let mut second: Vec<String> = ["-ss", "5", "-i", "in.mkv"].map(String::from).to_vec();
+        second.extend(target_size_codec_args(false, 900, passlog));
+        let tail = ["-movflags", "faststart", "-pass", "2", "-y", "out.mp4"];
+        second.extend(tail.map(String::from));
+        let second: Vec<OsString> = second.into_iter().map(OsString::from).collect();
+
+        let first: Vec<String> = first_pass_args(&second)
+            .into_iter()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+        assert!(first.windows(2).any(|w| w == ["-b:v", "900k"]));
+        assert!(first.windows(2).any(|w| w == ["-passlogfile", "/tmp/log"]));
+        assert!(first.windows(2).any(|w| w == ["-pass", "1"]));
+        assert!(!first.contains(&"-movflags".to_string()));
+        assert!(!first.contains(&"out.mp4".to_string()));
+        assert!(first.ends_with(&["-an", "-sn", "-f", "null", "-"].map(String::from)));
+    }
+
+    #[test]
+    fn test_audio_format_codec_args() {
+        assert_eq!(AudioFormat::Mp3.codec_args()[1], "libmp3lame");
+        assert_eq!(AudioFormat::Aac.codec_args(), ["-c:a", "aac", "-b:a", "192k"]);
+        // ffmpeg rejects a bitrate for the lossless codecs
+        for format in [AudioFormat::Wav, AudioFormat::Flac] {
+            assert!(!format.codec_args().contains(&"-b:a"));
+            assert!(!format.codec_args().contains(&"-q:a"));
+        }
+        assert_eq!(AudioFormat::Wav.codec_args()[1], "pcm_s16le");
+        assert_eq!(AudioFormat::Flac.extension(), "flac");
+        assert_eq!(AudioFormat::Aac.extension(), "m4a");
+        assert_eq!(AudioFormat::from_extension("M4A"), Some(AudioFormat::Aac));
+        assert_eq!(AudioFormat::from_extension("wav"), Some(AudioFormat::Wav));
+        assert_eq!(AudioFormat::from_extension("mp4"), None);
+    }
+
+    #[test]
+    fn test_hw_encoder_detection_and_args() {
+        let encoders = " V....D libx264              libx264 H.264 / AVC (codec h264)\n \
+                        V....D h264_qsv             H.264 / AVC (Intel Quick Sync Video)\n \
+                        V....D h264_nvenc           NVIDIA NVENC H.264 encoder\n";
+        assert_eq!(parse_hw_encoder(encoders), Some(HardwareEncoder::Nvenc));
+        assert_eq!(parse_hw_encoder(" V....D libx264  libx264 H.264\n"), None);
+
+        // A bitrate replaces the CRF and preset
+        let args = video_codec_args(false, Some(HardwareEncoder::Qsv), ExportQuality::Balanced);
+        assert_eq!(args, ["-c:v", "h264_qsv", "-b:v", "10M", "-pix_fmt", "nv12"]);
+        // WebM always uses VP9
+        let args = video_codec_args(true, Some(HardwareEncoder::Nvenc), ExportQuality::Fast);
+        assert_eq!(args[1], "libvpx-vp9");
+    }
+
+    #[test]
+    fn test_gif_video_filter_hw_scaling() {
+        let options = GifOptions {
+            hw_scaler: Some(HwScaler::VideoToolbox),
+            ..Default::default()
+        };
+        let filter = gif_video_filter("in.mp4", None, false, None, 1920, &options);
+        assert!(filter.starts_with("fps=10,format=nv12,hwupload,scale_vt=w=480:h=-2,hwdownload"));
+
+        let filter = gif_video_filter("in.mp4", None, false, None, 1920, &GifOptions::default());
+        assert!(filter.starts_with("fps=10,scale=480:-1:flags=lanczos,split"));
+    }
+
+    #[test]
+    fn test_gif_video_filter_overrides() {
+        let options = GifOptions {
+            fps: 24,
+            width: 800,
+            ..Default::default()
+        };
+        let filter = gif_video_filter("in.mp4", None, false, None, 1920, &options);
+        assert_eq!(
+            filter,
+            "fps=24,scale=800:-1:flags=lanczos,split[s0][s1];[s0]palettegen[p];[s1][p]paletteuse"
+        );
+
+        let options = GifOptions {
+            hw_scaler: Some(HwScaler::VideoToolbox),
+            ..options
+        };
+        let filter = gif_video_filter("in.mp4", None, false, None, 1920, &options);
+        assert!(filter.starts_with("fps=24,format=nv12,hwupload,scale_vt=w=800:h=-2"));
+    }
+
+    #[test]
+    fn test_is_hdr_transfer() {
+        assert!(is_hdr_transfer("smpte2084"));
+        assert!(is_hdr_transfer("arib-std-b67"));
+        assert!(!is_hdr_transfer("bt709"));
+        assert!(!is_hdr_transfer(""));
+    }
+
+    #[test]
+    fn test_clip_video_filter_tonemaps_first() {
+        let options = ExportOptions {
+            tonemap_hdr: true,
+            output_fps: Some(24.0),
+            ..Default::default()
+        };
+        let filter = clip_video_filter(Some(&text_subtitles()), false, &options).unwrap();
+        assert!(filter.starts_with("zscale=t=linear"));
+        assert!(filter.ends_with("format=yuv420p,fps=24,subtitles=x"));
+
+        // SDR exports are unchanged
+        assert_eq!(clip_video_filter(None, false, &ExportOptions::default()), None);
+    }
+
+    #[test]
+    fn test_clip_video_filter_deinterlace() {
+        let options = ExportOptions {
+            deinterlace: true,
+            ..Default::default()
+        };
+        // Before the TS pixel format conversion
+        let filter = clip_video_filter(None, true, &options).unwrap();
+        assert_eq!(filter, "yadif,format=yuv420p");
+        let filter = clip_video_filter(Some(&text_subtitles()), false, &options).unwrap();
+        assert_eq!(filter, "yadif,subtitles=x");
+
+        assert!(is_interlaced_field_order("tt"));
+        assert!(is_interlaced_field_order("bt"));
+        assert!(!is_interlaced_field_order("progressive"));
+        assert!(!is_interlaced_field_order("unknown"));
+    }
+
+    #[test]
+    fn test_output_scale_filter_chain() {
+        let options = ExportOptions {
+            output_scale: OutputScale::P720,
+            ..Default::default()
+        };
+        // Scaled before the subtitles are burned in, so they're drawn at the output size
+        let filter = clip_video_filter(Some(&text_subtitles()), true, &options).unwrap();
+        assert_eq!(filter, "scale=-2:'min(720,ih)',subtitles=x,format=yuv420p");
+        let filter = clip_video_filter(None, false, &options).unwrap();
+        assert_eq!(filter, "scale=-2:'min(720,ih)'");
+
+        // On the GPU the resize keeps the same target size
+        let options = ExportOptions {
+            hw_scaler: Some(HwScaler::Cuda),
+            ..options
+        };
+        let filter = clip_video_filter(Some(&text_subtitles()), false, &options).unwrap();
+        assert_eq!(
+            filter,
+            "format=nv12,hwupload,scale_cuda=w=-2:h='min(720,ih)',hwdownload,format=nv12,\
+             subtitles=x"
+        );
+        // ...and isn't set up at all without a resize
+        let options = ExportOptions {
+            output_scale: OutputScale::Original,
+            ..options
+        };
+        assert_eq!(clip_video_filter(None, false, &options), None);
+
+        // The subtitle font scaling follows the scaled width (never upscaled)
+        assert_eq!(OutputScale::P1080.output_width(3840, 2160), 1920);
+        assert_eq!(OutputScale::P720.output_width(1440, 1080), 960);
+        assert_eq!(OutputScale::P1080.output_width(1280, 720), 1280);
+        assert_eq!(OutputScale::Original.output_width(3840, 2160), 3840);
+    }
+
+    #[test]
+    fn test_intro_hold_filters() {
+        let options = ExportOptions {
+            intro_hold_secs: Some(1.5),
+            ..Default::default()
+        };
+        let filter = clip_video_filter(Some(&text_subtitles()), false, &options).unwrap();
+        assert_eq!(filter, "subtitles=x,tpad=start_mode=clone:start_duration=1.5");
+
+        // Copied audio is re-encoded so it can be delayed
+        let args = with_intro_audio_delay(vec!["-c:a".into(), "copy".into()], 1.5);
+        assert_eq!(args, ["-c:a", "aac", "-b:a", "256k", "-af", "adelay=delays=1500:all=1"]);
+
+        // An existing audio filter runs before the delay
+        let args = with_intro_audio_delay(
+            ["-c:a", "aac", "-af", "channelmap=channel_layout=5.1"].map(String::from).to_vec(),
+            2.0,
+        );
+        assert_eq!(args[3], "channelmap=channel_layout=5.1,adelay=delays=2000:all=1");
+    }
+
+    #[test]
+    fn test_parse_source_tags() {
+        let json = r#"{
+            "streams": [
+                {"codec_type": "video", "tags": {"language": "und"}},
+                {"codec_type": "audio", "tags": {"language": "jpn"}},
+                {"codec_type": "audio", "tags": {"language": "eng"}}
+            ],
+            "format": {"tags": {"title": "Episode 1"}}
+        }"#;
+        assert_eq!(
+            parse_probe_result(json).unwrap().source_tags(),
+            SourceTags {
+                title: Some("Episode 1".to_string()),
+                video_language: Some("und".to_string()),
+                audio_language: Some("jpn".to_string()),
+            }
+        );
+        assert!(parse_probe_result("not json").is_err());
+    }
+
+    #[test]
+    fn test_selective_metadata_args_skips_missing_tags() {
+        let tags = SourceTags {
+            title: Some("Episode 1".to_string()),
+            video_language: None,
+            audio_language: Some("jpn".to_string()),
+        };
+        assert_eq!(
+            selective_metadata_args(&tags),
+            vec![
+                "-map_metadata",
+                "-1",
+                "-metadata",
+                "title=Episode 1",
+                "-metadata:s:a:0",
+                "language=jpn"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_media_probe() {
+        let json = r#"{
+            "streams": [{"codec_type": "video"}, {"codec_type": "audio"}],
+            "format": {"duration": "12.480000", "size": "1048576"}
+        }"#;
+        assert_eq!(
+            parse_probe_result(json).unwrap().media_probe(),
+            MediaProbe {
+                duration_secs: 12.48,
+                has_video: true,
+                has_audio: true,
+                size_bytes: 1048576,
+            }
+        );
+    }
+
+    #[test]
+    fn test_probe_result_media_info() {
+        let json = r#"{
+            "streams": [
+                {"codec_type": "video", "codec_name": "hevc", "width": 3840, "height": 2160,
+                 "r_frame_rate": "24000/1001", "color_transfer": "smpte2084",
+                 "field_order": "progressive"},
+                {"codec_type": "audio", "codec_name": "eac3", "r_frame_rate": "0/0"}
+            ],
+            "format": {"duration": "5400.250000", "bit_rate": "18000000"}
+        }"#;
+        let probe = parse_probe_result(json).unwrap();
+        assert_eq!(probe.channel_layout(), None);
+        let info = probe.media_info();
+        assert!((info.fps.unwrap() - 23.976).abs() < 0.001);
+        assert_eq!(info.resolution, Some((3840, 2160)));
+        assert_eq!(info.duration_secs, 5400.25);
+        assert_eq!(info.video_codec.as_deref(), Some("hevc"));
+        assert_eq!(info.audio_codec.as_deref(), Some("eac3"));
+        assert_eq!(info.bit_rate, Some(18_000_000));
+        assert!(info.is_hdr);
+        assert!(!info.is_interlaced);
+
+        // Audio-only files have no frame rate or resolution
+        let json = r#"{"streams": [
+            {"codec_type": "audio", "codec_name": "mp3", "channel_layout": "5.1(side)"}
+        ]}"#;
+        let probe = parse_probe_result(json).unwrap();
+        assert_eq!(probe.channel_layout(), Some("5.1(side)"));
+        let info = probe.media_info();
+        assert_eq!(info.fps, None);
+        assert_eq!(info.resolution, None);
+        assert_eq!(info.bit_rate, None);
+
+        assert_eq!(parse_frame_rate("30/1"), Some(30.0));
+        assert_eq!(parse_frame_rate("25"), Some(25.0));
+        assert_eq!(parse_frame_rate("0/0"), None);
+    }
+
+    #[test]
+    fn test_downsample_peaks() {
+        let samples = [0.1, -0.5, 0.2, 0.3, -0.1, 0.0, 2.0, 0.4];
+        assert_eq!(downsample_peaks(&samples, 4), vec![0.5, 0.3, 0.1, 1.0]);
+        assert_eq!(downsample_peaks(&samples, 2), vec![0.5, 1.0]);
+        // Never more peaks than samples
+        assert_eq!(downsample_peaks(&samples[..2], 10), vec![0.1, 0.5]);
+        assert!(downsample_peaks(&[], 10).is_empty());
+    }
+
+    #[test]
+    fn test_validate_export() {
+        let good = MediaProbe {
+            duration_secs: 10.2,
+            has_video: true,
+            has_audio: true,
+            size_bytes: 4096,
+        };
+        assert!(validate_export(&good, 10.0, true, true).is_empty());
+
+        let empty = MediaProbe::default();
+        assert_eq!(
+            validate_export(&empty, 10.0, true, false),
+            vec![
+                "output file is empty",
+                "output has no duration",
+                "output has no video stream"
+            ]
+        );
+
+        let short_silent = MediaProbe {
+            duration_secs: 4.0,
+            has_audio: false,
+            ..good
+        };
+        assert_eq!(
+            validate_export(&short_silent, 10.0, true, true),
+            vec!["output is 4.00s long, expected 10.00s", "output has no audio stream"]
+        );
+    }
+
+    #[test]
+    fn test_contact_sheet_filter() {
+        // 150 frames over a 4x4 grid: every 10th frame
+        let filter = contact_sheet_filter(150, &ContactSheetOptions::default());
+        assert_eq!(
+            filter,
+            "select=not(mod(n\\,10)),scale=320:-2,tile=4x4:padding=4:margin=4"
+        );
+
+        // Fewer frames than tiles still picks every frame
+        let options = ContactSheetOptions {
+            rows: 2,
+            cols: 3,
+            ..Default::default()
+        };
+        assert!(contact_sheet_filter(4, &options).starts_with("select=not(mod(n\\,1))"));
+    }
+
+    #[test]
+    fn test_format_file_size() {
+        assert_eq!(format_file_size(512), "512 B");
+        assert_eq!(format_file_size(1536), "1.5 KB");
+        assert_eq!(format_file_size(4_404_019), "4.2 MB");
+    }
+
+    #[test]
+    fn test_format_chapters_metadata_escapes_titles() {
+        let markers = vec![marker(0, "A=B; #1")];
+        let metadata = format_chapters_metadata(&markers, 0, 1000, 0).unwrap();
+        assert!(metadata.contains("title=A\\=B\\; \\#1\n"));
+    }
+
+    // Integration tests below run the real ffmpeg/ffprobe binaries against a
+    // synthetic video. Enable with `cargo test --features ffmpeg-tests`.
+
+    /// Create a scratch directory for a single test
+    fn test_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "asve_export_test_{}_{}",
+            name,
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).expect("Failed to create test directory");
+        dir
+    }
+
+    /// Generate a 5 second 320x240 test pattern with a sine tone
+    fn make_test_video(dir: &Path) -> String {
+        let path = dir.join("source.mp4");
+        let status = ffmpeg_command()
+            .args(["-v", "error", "-y"])
+            .args(["-f", "lavfi", "-i", "testsrc=duration=5:size=320x240:rate=30"])
+            .args(["-f", "lavfi", "-i", "sine=frequency=440:duration=5"])
+            .args(["-c:v", "libx264", "-pix_fmt", "yuv420p", "-c:a", "aac", "-shortest"])
+            .arg(&path)
+            .status()
+            .expect("Failed to run ffmpeg");
+        assert!(status.success(), "Failed to generate test video");
+        path.to_string_lossy().to_string()
+    }
+
+    /// Generate a test video with an embedded SRT subtitle track
+    fn make_test_video_with_subtitles(dir: &Path) -> String {
+        let srt_path = dir.join("source.srt");
+        std::fs::write(
+            &srt_path,
+            "1\n00:00:00,000 --> 00:00:05,000\nBurned in text\n",
+        )
+        .expect("Failed to write test subtitles");
+
+        let path = dir.join("source.mkv");
+        let status = ffmpeg_command()
+            .args(["-v", "error", "-y"])
+            .args(["-f", "lavfi", "-i", "testsrc=duration=5:size=320x240:rate=30"])
+            .arg("-i")
+            .arg(&srt_path)
+            .args(["-c:v", "libx264", "-pix_fmt", "yuv420p", "-c:s", "srt"])
+            .arg(&path)
+            .status()
+            .expect("Failed to run ffmpeg");
+        assert!(status.success(), "Failed to generate test video with subtitles");
+        path.to_string_lossy().to_string()
+    }
+
+    /// Read the container duration of a media file in seconds
+    fn probe_duration(path: &str) -> f32 {
+        let output = ffprobe_command()
+            .args(["-v", "error", "-show_entries", "format=duration"])
+            .args(["-of", "default=noprint_wrappers=1:nokey=1"])
+            .arg(path)
+            .output()
+            .expect("Failed to run ffprobe");
+        String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .expect("Failed to parse duration")
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "ffmpeg-tests"), ignore)]
+    fn test_export_clip_duration() {
+        let dir = test_dir("clip");
+        let input = make_test_video(&dir);
+        let output = dir.join("clip.mp4").to_string_lossy().to_string();
+
+        let options = ExportOptions::default();
+        export_clip(
+            &input,
+            &output,
+            1.0,
+            3.0,
+            None,
+            SubtitleMode::None,
+            None,
+            320,
+            &options,
+            |_| {},
+        )
+        .expect("export_clip failed");
+
+        assert!(Path::new(&output).exists());
+        let duration = probe_duration(&output);
+        assert!((duration - 2.0).abs() < 0.2, "Unexpected duration: {}", duration);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "ffmpeg-tests"), ignore)]
+    fn test_export_clip_output_fps() {
+        let dir = test_dir("clip_fps");
+        let input = make_test_video(&dir);
+        let output = dir.join("clip.mp4").to_string_lossy().to_string();
+
+        let options = ExportOptions {
+            output_fps: Some(15.0),
+            ..Default::default()
+        };
+        export_clip(
+            &input,
+            &output,
+            1.0,
+            3.0,
+            None,
+            SubtitleMode::None,
+            None,
+            320,
+            &options,
+            |_| {},
+        )
+        .expect("export_clip failed");
+
+        let fps = get_video_fps(&output).unwrap();
+        assert!((fps - 15.0).abs() < 0.5, "Unexpected fps: {}", fps);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "ffmpeg-tests"), ignore)]
+    fn test_export_clip_with_burned_subtitles() {
+        let dir = test_dir("clip_subs");
+        let input = make_test_video_with_subtitles(&dir);
+        let output = dir.join("clip.mp4").to_string_lossy().to_string();
+        let settings = crate::SubtitleSettings::default();
+
+        export_clip(
+            &input,
+            &output,
+            1.0,
+            3.0,
+            Some(&settings),
+            SubtitleMode::Burn,
+            Some(1),
+            320,
+            &ExportOptions::default(),
+            |_| {},
+        )
+        .expect("export_clip with subtitles failed");
+
+        // The subtitle path uses -copyts, so only check that a playable file came out
+        assert!(Path::new(&output).exists());
+        assert!(probe_duration(&output) > 0.0);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "ffmpeg-tests"), ignore)]
+    fn test_export_gif() {
+        let dir = test_dir("gif");
+        let input = make_test_video(&dir);
+        let output = dir.join("clip.gif").to_string_lossy().to_string();
+
+        let options = GifOptions {
+            loop_gif: true,
+            ..Default::default()
+        };
+        export_gif(&input, &output, 1.0, 3.0, None, false, None, 320, &options, |_| {})
+            .expect("export_gif failed");
+
+        assert!(Path::new(&output).exists());
+        let (width, _height) = get_video_resolution(&output).unwrap();
+        assert_eq!(width, 480);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "ffmpeg-tests"), ignore)]
+    fn test_export_audio_duration() {
+        let dir = test_dir("audio");
+        let input = make_test_video(&dir);
+        let output = dir.join("clip.mp3").to_string_lossy().to_string();
+
+        export_audio(
+            &input,
+            &output,
+            1.0,
+            3.0,
+            Some(0),
+            AudioFormat::Mp3,
+            &CancelHandle::default(),
+        )
+        .expect("export_audio failed");
+
+        assert!(Path::new(&output).exists());
+        let duration = probe_duration(&output);
+        assert!((duration - 2.0).abs() < 0.2, "Unexpected duration: {}", duration);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "ffmpeg-tests"), ignore)]
+    fn test_export_contact_sheet() {
+        let dir = test_dir("sheet");
+        let input = make_test_video(&dir);
+        let output = dir.join("clip_sheet.png").to_string_lossy().to_string();
+
+        let options = ContactSheetOptions {
+            rows: 2,
+            cols: 3,
+            tile_width: 100,
+            ..Default::default()
+        };
+        export_contact_sheet(&input, &output, 1.0, 3.0, &options)
+            .expect("export_contact_sheet failed");
+
+        // 3 tiles of 100px, 2 gaps of 4px and a 4px margin on each side
+        let (width, _height) = get_video_resolution(&output).unwrap();
+        assert_eq!(width, 3 * 100 + 2 * 4 + 2 * 4);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "ffmpeg-tests"), ignore)]
+    fn test_dry_run_filter_rejects_bad_filter() {
+        let dir = test_dir("dry_run");
+        let input = make_test_video(&dir);
+
+        assert!(dry_run_filter(&input, 1.0, "fps=10").is_ok());
+        assert!(dry_run_filter(&input, 1.0, "not_a_real_filter=1").is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_webm_output_args() {
+        assert!(is_webm_output("clip.WebM"));
+        assert!(!is_webm_output("clip.mp4"));
+
+        let args = with_opus_audio(["-c:a", "copy"].map(String::from).to_vec());
+        assert_eq!(args, ["-c:a", "libopus", "-b:a", "160k"]);
+        let args = with_opus_audio(
+            ["-c:a", "aac", "-b:a", "256k", "-af", "adelay=delays=500:all=1"]
+                .map(String::from)
+                .to_vec(),
+        );
+        assert_eq!(args, ["-c:a", "libopus", "-b:a", "160k", "-af", "adelay=delays=500:all=1"]);
+    }
+
+    #[test]
+    fn test_offset_subtitle_filter() {
+        assert_eq!(offset_subtitle_filter("subtitles=a.srt", 0), "subtitles=a.srt");
+        assert_eq!(
+            offset_subtitle_filter("subtitles=a.srt", 1500),
+            "setpts=PTS-1.5/TB,subtitles=a.srt,setpts=PTS+1.5/TB"
+        );
+        assert_eq!(
+            offset_subtitle_filter("subtitles=a.srt", -250),
+            "setpts=PTS+0.25/TB,subtitles=a.srt,setpts=PTS-0.25/TB"
+        );
+    }
+
+    #[test]
+    fn test_bitmap_subtitle_overlay() {
+        // Overlaid at the source size, before the resize, and shifted by the offset
+        let options = ExportOptions {
+            deinterlace: true,
+            output_scale: OutputScale::P720,
+            subtitle_offset_ms: 500,
+            ..Default::default()
+        };
+        let filter = clip_video_filter(Some(&SubtitleBurn::Bitmap(1)), false, &options).unwrap();
+        assert_eq!(
+            filter,
+            "[0:v]yadif[base];[0:s:1]setpts=PTS+0.5/TB[sub];\
+             [base][sub]overlay=eof_action=pass,scale=-2:'min(720,ih)'[vout]"
+        );
+        assert_eq!(
+            video_filter_args(&filter),
+            ["-filter_complex", filter.as_str(), "-map", "[vout]"]
+        );
+
+        let options = ExportOptions::default();
+        let filter = clip_video_filter(Some(&SubtitleBurn::Bitmap(0)), false, &options).unwrap();
+        assert_eq!(filter, "[0:v][0:s:0]overlay=eof_action=pass[vout]");
+
+        // The GIF palette steps come after the overlay
+        let after = [
+            "fps=10".to_string(),
+            "split[s0][s1];[s1][p]paletteuse".to_string(),
+        ];
+        assert_eq!(
+            bitmap_subtitle_graph(0, 0, &[], &after),
+            "[0:v][0:s:0]overlay=eof_action=pass,fps=10,split[s0][s1];[s1][p]paletteuse[vout]"
+        );
+
+        // Text subtitles stay a plain -vf chain
+        assert_eq!(
+            video_filter_args("yadif,subtitles=x"),
+            ["-vf", "yadif,subtitles=x"]
+        );
+    }
+
+    #[test]
+    fn test_soft_subtitle_codec() {
+        assert_eq!(soft_subtitle_codec("clip.mp4"), Ok("mov_text"));
+        assert_eq!(soft_subtitle_codec("clip.MOV"), Ok("mov_text"));
+        assert_eq!(soft_subtitle_codec("clip.mkv"), Ok("srt"));
+        assert_eq!(soft_subtitle_codec("clip.webm"), Ok("webvtt"));
+        assert!(soft_subtitle_codec("clip.gif").is_err());
+        assert!(soft_subtitle_codec("clip").is_err());
+    }
+
+    #[test]
+    fn test_progress_fraction() {
+        assert_eq!(progress_fraction("out_time_ms=1000000", 0.0, 4.0), Some(0.25));
+        // -copyts output starts at the clip start
+        assert_eq!(progress_fraction("out_time_ms=11000000", 10.0, 4.0), Some(0.25));
+        assert_eq!(progress_fraction("out_time_ms=9000000", 0.0, 4.0), Some(1.0));
+        assert_eq!(progress_fraction("out_time_ms=N/A", 0.0, 4.0), None);
+        assert_eq!(progress_fraction("progress=continue", 0.0, 4.0), None);
+        assert_eq!(progress_fraction("progress=end", 0.0, 4.0), Some(1.0));
+        assert_eq!(progress_fraction("frame=12", 0.0, 4.0), None);
+    }
+
+    #[test]
+    fn test_export_quality_args() {
+        let args = |quality: ExportQuality| video_codec_args(false, None, quality)[8..].to_vec();
+        assert_eq!(args(ExportQuality::Fast), ["-crf", "28", "-preset", "ultrafast"]);
+        assert_eq!(args(ExportQuality::Balanced), ["-crf", "23", "-preset", "medium"]);
+        assert_eq!(args(ExportQuality::HighQuality), ["-crf", "18", "-preset", "slow"]);
+        assert_eq!(ExportQuality::default(), ExportQuality::Fast);
+        assert_eq!(ExportQuality::HighQuality.next(), ExportQuality::Fast);
+    }
+
+    #[test]
+    fn test_image_format_from_output_path() {
+        assert_eq!(ImageFormat::from_output_path("frame.png"), ImageFormat::Png);
+        assert_eq!(ImageFormat::from_output_path("frame.JPG"), ImageFormat::Jpeg);
+        assert_eq!(ImageFormat::from_output_path("frame.jpeg"), ImageFormat::Jpeg);
+        assert_eq!(ImageFormat::from_output_path("frame"), ImageFormat::Png);
+        assert_eq!(ImageFormat::Jpeg.codec_args(), ["-q:v", "2"]);
+    }
+}
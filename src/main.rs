@@ -9,6 +9,8 @@ extern crate objc;
 use clap::Parser;
 
 mod assets;
+mod batch_export;
+mod bookmarks_tab;
 mod config;
 mod controls_window;
 mod custom_titlebar;
@@ -21,13 +23,15 @@ mod subtitle_detector;
 mod subtitle_extractor;
 mod subtitle_window;
 mod theme;
+mod thumbnail_strip;
 mod unified_window;
 mod video_player;
 mod video_player_window;
 
 use gpui::{
-    Action, AnyWindowHandle, App, AppContext, Application, BorrowAppContext, Entity, Global, Menu,
-    MenuItem, PathPromptOptions, SharedString, SystemMenuType, WindowOptions, actions, px,
+    Action, AnyWindowHandle, App, AppContext, Application, BorrowAppContext, Entity, Global,
+    KeyBinding, Menu, MenuItem, PathPromptOptions, SharedString, SystemMenuType, WindowOptions,
+    actions, px,
 };
 use gpui_component::Theme;
 use unified_window::UnifiedWindow;
@@ -38,7 +42,76 @@ use video_player::ClockTime;
 #[action(no_json)]
 pub struct SwitchTheme(pub SharedString);
 
+/// Action to change the padding added around a subtitle by "Clip with padding"
+#[derive(Action, Clone, PartialEq)]
+#[action(no_json)]
+pub struct SetClipPadding(pub u64);
+
+/// Padding choices (in milliseconds) offered in the Preferences menu
+const CLIP_PADDING_OPTIONS: [u64; 5] = [0, 150, 300, 500, 1000];
+
+/// Action to change the UI refresh rate cap (0 = uncapped)
+#[derive(Action, Clone, PartialEq)]
+#[action(no_json)]
+pub struct SetRenderFpsCap(pub u32);
+
+/// Refresh rate choices offered in the Preferences menu (0 = uncapped)
+const RENDER_FPS_OPTIONS: [u32; 4] = [30, 60, 120, 0];
+
+/// Action to change the row spacing of the subtitle list
+#[derive(Action, Clone, PartialEq)]
+#[action(no_json)]
+pub struct SetListDensity(pub ListDensity);
+
+/// Action to change the text size of the subtitle list
+#[derive(Action, Clone, PartialEq)]
+#[action(no_json)]
+pub struct SetListFontSize(pub ListFontSize);
+
+/// Action to change how long the on-screen display stays up (0 = off)
+#[derive(Action, Clone, PartialEq)]
+#[action(no_json)]
+pub struct SetOsdDuration(pub u64);
+
+/// Duration choices (in milliseconds) offered in the Preferences menu (0 = off)
+const OSD_DURATION_OPTIONS: [u64; 4] = [0, 1000, 2000, 4000];
+
+/// Action to show or hide one line of the on-screen display
+#[derive(Action, Clone, PartialEq)]
+#[action(no_json)]
+pub struct ToggleOsdField(pub OsdField);
+
+/// Action to pick the executable used for ffmpeg or ffprobe
+#[derive(Action, Clone, PartialEq)]
+#[action(no_json)]
+pub struct ChooseToolPath(pub ffmpeg_export::Tool);
+
+/// Action to pick the default save folder for an export format
+#[derive(Action, Clone, PartialEq)]
+#[action(no_json)]
+pub struct ChooseExportDir(pub controls_window::ExportFormat);
+
+/// Action to pick the subtitle text encoding (None = auto-detect)
+#[derive(Action, Clone, PartialEq)]
+#[action(no_json)]
+pub struct SetSubtitleCharset(pub Option<&'static str>);
+
+/// Action to seek forwards (or backwards, when negative) by a number of seconds
+#[derive(Action, Clone, PartialEq)]
+#[action(no_json)]
+pub struct SeekBy(pub f64);
+
+/// Action to save the subtitle text as a plain .txt transcript
+#[derive(Action, Clone, PartialEq)]
+#[action(no_json)]
+pub struct ExportTranscript {
+    pub include_timestamps: bool,
+    pub clip_only: bool, // Only subtitles overlapping the current clip
+}
+
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 #[derive(Parser, Debug)]
 #[command(name = "asve")]
@@ -47,23 +120,48 @@ struct Cli {
     /// Path to video file to open
     video_path: Option<String>,
 
-    /// Clip start time (supports: 90.5, 01:30.500, 00:01:30.500, or 90500)
+    /// Clip start time (supports: 90.5, 01:30.500, 00:01:30.500, 00:01:30:12, or 90500)
     #[arg(long)]
     clip_start: Option<String>,
 
-    /// Clip end time (supports: 120.75, 02:00.750, 00:02:00.750, or 120750)
-    #[arg(long)]
+    /// Clip end time (supports: 120.75, 02:00.750, 00:02:00.750, 00:02:00:18, or 120750;
+    /// a leading - counts back from the end of the video, e.g. -5.0)
+    #[arg(long, allow_hyphen_values = true)]
     clip_end: Option<String>,
+
+    /// Write the rendered frame to a PNG at this path, then quit
+    #[arg(long)]
+    dump_frame: Option<String>,
+
+    /// Time of the frame to dump (same formats as --clip-start; defaults to the clip start or 0)
+    #[arg(long)]
+    dump_frame_at: Option<String>,
+
+    /// Export the clips listed in a JSON jobs file without opening a window, then quit
+    #[arg(long)]
+    jobs: Option<String>,
+
+    /// Number of --jobs exports to run at once (capped at the CPU count)
+    #[arg(long, default_value_t = 1)]
+    concurrency: usize,
 }
 
+/// Frame rate assumed for HH:MM:SS:FF timestamps when the video's isn't known
+const DEFAULT_TIMESTAMP_FPS: f32 = 30.0;
+
 /// Parse a timestamp string into milliseconds
 ///
 /// Supports multiple formats:
 /// - Seconds (decimal): "90.5" → 90,500 ms
 /// - MM:SS.mmm: "01:30.500" → 90,500 ms
 /// - HH:MM:SS.mmm: "00:01:30.500" → 90,500 ms
+/// - HH:MM:SS:FF: "00:01:30:15" → 90,500 ms at 30fps
 /// - Milliseconds (integer): "90500" → 90,500 ms
-fn parse_timestamp(input: &str) -> Result<f32, String> {
+///
+/// The FF frame index depends on the frame rate, so `fps` should be the
+/// video's; without it (e.g. no file to probe) 30fps is assumed, which puts
+/// the time slightly off for 24/25/60fps footage.
+fn parse_timestamp(input: &str, fps: Option<f32>) -> Result<f32, String> {
     let input = input.trim();
 
     // Count colons to determine format
@@ -178,6 +276,46 @@ fn parse_timestamp(input: &str) -> Result<f32, String> {
                 (hours * 60 * 60 * 1000) + (minutes * 60 * 1000) + (seconds * 1000) + milliseconds;
             Ok(total_ms as f32)
         }
+        3 => {
+            // HH:MM:SS:FF format (SMPTE-style frame count)
+            let parts: Vec<&str> = input.split(':').collect();
+            let [hours, minutes, seconds, frames] = parts[..] else {
+                return Err(format!("Invalid HH:MM:SS:FF format: {}", input));
+            };
+
+            let hours = hours
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid hours: {}", hours))?;
+            let minutes = minutes
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid minutes: {}", minutes))?;
+            let seconds = seconds
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid seconds: {}", seconds))?;
+            let frames = frames
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid frames: {}", frames))?;
+
+            if minutes >= 60 {
+                return Err(format!("Minutes must be less than 60: {}", minutes));
+            }
+            if seconds >= 60 {
+                return Err(format!("Seconds must be less than 60: {}", seconds));
+            }
+
+            let fps = fps
+                .filter(|fps| *fps > 0.0)
+                .unwrap_or(DEFAULT_TIMESTAMP_FPS);
+            if frames as f32 >= fps {
+                return Err(format!(
+                    "Frames must be less than the frame rate ({}): {}",
+                    fps, frames
+                ));
+            }
+
+            let whole_ms = (hours * 60 * 60 * 1000) + (minutes * 60 * 1000) + (seconds * 1000);
+            Ok(whole_ms as f32 + frames as f32 * 1000.0 / fps)
+        }
         _ => Err(format!(
             "Invalid timestamp format (too many colons): {}",
             input
@@ -185,8 +323,73 @@ fn parse_timestamp(input: &str) -> Result<f32, String> {
     }
 }
 
+/// Parse a `--clip-end` value into milliseconds
+///
+/// Accepts everything `parse_timestamp` does, plus a leading `-` for a time
+/// relative to the end of the video ("-5.0" → 5 seconds before the end).
+/// Relative times come back negative and are resolved with `resolve_clip_end`
+/// once the duration is known.
+fn parse_clip_end(input: &str, fps: Option<f32>) -> Result<f32, String> {
+    let Some(relative) = input.trim().strip_prefix('-') else {
+        return parse_timestamp(input, fps);
+    };
+    let ms = parse_timestamp(relative, fps)?;
+    if ms <= 0.0 {
+        return Err(format!("Relative end time must be before the end: {}", input));
+    }
+    Ok(-ms)
+}
+
+/// Resolve a clip end from `parse_clip_end` against the video duration
+///
+/// Fails when a relative end reaches back past the start of the video.
+fn resolve_clip_end(end_ms: f32, duration_ms: f32) -> Result<f32, String> {
+    if end_ms >= 0.0 {
+        return Ok(end_ms);
+    }
+    let resolved = duration_ms + end_ms;
+    if resolved <= 0.0 {
+        return Err(format!(
+            "relative end of {} ms is longer than the video ({} ms)",
+            -end_ms, duration_ms
+        ));
+    }
+    Ok(resolved)
+}
+
+/// Frame rate for HH:MM:SS:FF command line timestamps
+///
+/// Only probes the video when one of the timestamps actually has frames.
+fn timestamp_fps(video_path: Option<&str>, timestamps: &[Option<&str>]) -> Option<f32> {
+    let uses_frames = timestamps
+        .iter()
+        .flatten()
+        .any(|timestamp| timestamp.matches(':').count() == 3);
+    if !uses_frames {
+        return None;
+    }
+    ffmpeg_export::get_video_fps(video_path?).ok()
+}
+
 fn main() {
     let cli = Cli::parse();
+
+    // Batch exports only need ffmpeg, so they run before mpv or the GUI start
+    if let Some(ref jobs_path) = cli.jobs {
+        let app_config = config::Config::load();
+        ffmpeg_export::Tool::Ffmpeg.set_path(app_config.ffmpeg_path.clone());
+        ffmpeg_export::Tool::Ffprobe.set_path(app_config.ffprobe_path.clone());
+
+        match batch_export::run_jobs_file(jobs_path, cli.concurrency) {
+            Ok(true) => std::process::exit(0),
+            Ok(false) => std::process::exit(1),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(2);
+            }
+        }
+    }
+
     // Initialize mpv before creating the GPUI application
     if let Err(e) = video_player::init() {
         eprintln!("Failed to initialize mpv: {}", e);
@@ -207,8 +410,16 @@ fn main() {
     }
 
     // Parse clip times
+    let fps = timestamp_fps(
+        cli.video_path.as_deref(),
+        &[
+            cli.clip_start.as_deref(),
+            cli.clip_end.as_deref(),
+            cli.dump_frame_at.as_deref(),
+        ],
+    );
     if let Some(ref start_str) = cli.clip_start {
-        match parse_timestamp(start_str) {
+        match parse_timestamp(start_str, fps) {
             Ok(ms) => parsed_clip_start = Some(ms),
             Err(e) => {
                 eprintln!("Error parsing --clip-start: {}", e);
@@ -220,7 +431,7 @@ fn main() {
     }
 
     if let Some(ref end_str) = cli.clip_end {
-        match parse_timestamp(end_str) {
+        match parse_clip_end(end_str, fps) {
             Ok(ms) => parsed_clip_end = Some(ms),
             Err(e) => {
                 eprintln!("Error parsing --clip-end: {}", e);
@@ -237,6 +448,41 @@ fn main() {
         std::process::exit(1);
     }
 
+    // Resolve an end relative to the end of the video against its duration
+    let parsed_clip_end = match (parsed_clip_end, cli.video_path.as_deref()) {
+        (Some(end), Some(video_path)) if end < 0.0 => {
+            let app_config = config::Config::load();
+            ffmpeg_export::Tool::Ffprobe.set_path(app_config.ffprobe_path.clone());
+            match ffmpeg_export::probe_media(video_path) {
+                Ok(probe) if probe.duration_secs > 0.0 => {
+                    match resolve_clip_end(end, probe.duration_secs * 1000.0) {
+                        Ok(resolved) => {
+                            println!("Resolved --clip-end {} ms to {} ms", end, resolved);
+                            Some(resolved)
+                        }
+                        Err(e) => {
+                            eprintln!("Error: --clip-end: {}", e);
+                            std::process::exit(1);
+                        }
+                    }
+                }
+                Ok(_) => {
+                    eprintln!("Error: --clip-end is relative but the video has no duration");
+                    std::process::exit(1);
+                }
+                Err(e) => {
+                    eprintln!("Error reading the video duration for --clip-end: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        (Some(end), None) if end < 0.0 => {
+            eprintln!("Error: a relative --clip-end needs a video file");
+            std::process::exit(1);
+        }
+        (end, _) => end,
+    };
+
     // Validate that clip_start < clip_end
     if let (Some(start), Some(end)) = (parsed_clip_start, parsed_clip_end) {
         if start >= end {
@@ -248,16 +494,62 @@ fn main() {
         }
     }
 
+    // Validate frame dump options
+    if (cli.dump_frame.is_some() || cli.dump_frame_at.is_some()) && cli.video_path.is_none() {
+        eprintln!("Error: --dump-frame can only be used when providing a video file");
+        std::process::exit(1);
+    }
+    if cli.dump_frame_at.is_some() && cli.dump_frame.is_none() {
+        eprintln!("Error: --dump-frame-at requires --dump-frame");
+        std::process::exit(1);
+    }
+    let dump_frame_ms = match cli.dump_frame_at {
+        Some(ref at_str) => match parse_timestamp(at_str, fps) {
+            Ok(ms) => ms,
+            Err(e) => {
+                eprintln!("Error parsing --dump-frame-at: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => parsed_clip_start.unwrap_or(0.0),
+    };
+
     Application::new()
         .with_assets(assets::Assets)
         .run(move |cx: &mut App| {
-            cx.set_global(AppState::new());
+            // Load config so saved preferences are available to AppState and the theme
+            let app_config = config::Config::load();
+            cx.set_global(AppState::new(&app_config));
+
+            // Point exports and probes at the configured ffmpeg/ffprobe (PATH by default)
+            ffmpeg_export::Tool::Ffmpeg.set_path(app_config.ffmpeg_path.clone());
+            ffmpeg_export::Tool::Ffprobe.set_path(app_config.ffprobe_path.clone());
 
             // Initialize gpui-component (required before using any gpui-component features)
             gpui_component::init(cx);
 
-            // Load config and initialize theme with saved preference
-            let app_config = config::Config::load();
+            // Single-key shortcuts only apply when a text input doesn't have focus
+            cx.bind_keys([
+                KeyBinding::new("space", TogglePlay, Some("UnifiedWindow && !Input")),
+                KeyBinding::new("j", CycleSubtitleTrack, Some("UnifiedWindow && !Input")),
+                KeyBinding::new("a", SetLoopA, Some("UnifiedWindow && !Input")),
+                KeyBinding::new("b", SetLoopB, Some("UnifiedWindow && !Input")),
+                KeyBinding::new("escape", ClearLoop, Some("UnifiedWindow && !Input")),
+                KeyBinding::new("i", SetClipStart, Some("UnifiedWindow && !Input")),
+                KeyBinding::new("o", SetClipEnd, Some("UnifiedWindow && !Input")),
+                KeyBinding::new("r", RepeatExport, Some("UnifiedWindow && !Input")),
+                KeyBinding::new(".", FrameStepForward, Some("UnifiedWindow && !Input")),
+                KeyBinding::new(",", FrameStepBackward, Some("UnifiedWindow && !Input")),
+                KeyBinding::new("right", SeekBy(5.0), Some("UnifiedWindow && !Input")),
+                KeyBinding::new("left", SeekBy(-5.0), Some("UnifiedWindow && !Input")),
+                KeyBinding::new("shift-right", SeekBy(1.0), Some("UnifiedWindow && !Input")),
+                KeyBinding::new("shift-left", SeekBy(-1.0), Some("UnifiedWindow && !Input")),
+                KeyBinding::new("shift-b", ToggleBorderless, Some("UnifiedWindow && !Input")),
+                KeyBinding::new("0", ZoomToFit, Some("UnifiedWindow && !Input")),
+                KeyBinding::new("1", ZoomActualSize, Some("UnifiedWindow && !Input")),
+            ]);
+
+            // Initialize theme with saved preference
             theme::init_with_theme_name(app_config.theme_name.as_deref(), cx);
 
             // Bring the menu bar to the foreground (so you can see the menu bar)
@@ -265,6 +557,9 @@ fn main() {
             // Register the `quit` function so it can be referenced by the `MenuItem::action` in the menu bar
             cx.on_action(quit);
             cx.on_action(open_file);
+            cx.on_action(open_subtitles);
+            cx.on_action(duplicate_window);
+            cx.on_action(export_transcript);
 
             // Register the theme change action handler
             cx.on_action(|action: &SwitchTheme, cx| {
@@ -284,6 +579,207 @@ fn main() {
                 }
             });
 
+            // Register the clip padding preference handler
+            cx.on_action(|action: &SetClipPadding, cx| {
+                cx.update_global::<AppState, _>(|state, _| {
+                    state.clip_padding_ms = action.0;
+                });
+
+                let mut app_config = config::Config::load();
+                app_config.clip_padding_ms = Some(action.0);
+                let _ = app_config.save();
+
+                set_app_menus(cx);
+            });
+
+            // Register the UI refresh rate preference handler
+            cx.on_action(|action: &SetRenderFpsCap, cx| {
+                cx.update_global::<AppState, _>(|state, _| {
+                    state.render_fps_cap = action.0;
+                });
+
+                let mut app_config = config::Config::load();
+                app_config.render_fps_cap = Some(action.0);
+                let _ = app_config.save();
+
+                set_app_menus(cx);
+            });
+
+            // Register the subtitle list density preference handler
+            cx.on_action(|action: &SetListDensity, cx| {
+                cx.update_global::<AppState, _>(|state, _| {
+                    state.list_density = action.0;
+                });
+
+                let mut app_config = config::Config::load();
+                app_config.subtitle_list_density = Some(action.0.name().to_string());
+                let _ = app_config.save();
+
+                set_app_menus(cx);
+                cx.refresh_windows();
+            });
+
+            // Register the subtitle list font size preference handler
+            cx.on_action(|action: &SetListFontSize, cx| {
+                cx.update_global::<AppState, _>(|state, _| {
+                    state.list_font_size = action.0;
+                });
+
+                let mut app_config = config::Config::load();
+                app_config.subtitle_list_font_size = Some(action.0.name().to_string());
+                let _ = app_config.save();
+
+                set_app_menus(cx);
+                cx.refresh_windows();
+            });
+
+            // Register the stop-at-clip-end toggle
+            cx.on_action(|_: &ToggleStopAtClipEnd, cx| {
+                let enabled = !cx.global::<AppState>().stop_at_clip_end;
+                cx.update_global::<AppState, _>(|state, _| {
+                    state.stop_at_clip_end = enabled;
+                });
+
+                let mut app_config = config::Config::load();
+                app_config.stop_at_clip_end = Some(enabled);
+                let _ = app_config.save();
+
+                set_app_menus(cx);
+            });
+
+            // Register the export filter pre-flight toggle
+            cx.on_action(|_: &ToggleFilterPreflight, cx| {
+                let enabled = !cx.global::<AppState>().filter_preflight;
+                cx.update_global::<AppState, _>(|state, _| {
+                    state.filter_preflight = enabled;
+                });
+
+                let mut app_config = config::Config::load();
+                app_config.filter_preflight = Some(enabled);
+                let _ = app_config.save();
+
+                set_app_menus(cx);
+            });
+
+            // Register the hardware scaling toggle
+            cx.on_action(|_: &ToggleHardwareScaling, cx| {
+                let enabled = !cx.global::<AppState>().hardware_scaling;
+                cx.update_global::<AppState, _>(|state, _| {
+                    state.hardware_scaling = enabled;
+                });
+
+                let mut app_config = config::Config::load();
+                app_config.hardware_scaling = Some(enabled);
+                let _ = app_config.save();
+
+                set_app_menus(cx);
+            });
+
+            // Register the borderless window toggle (also shift-b, since the buttons are hidden)
+            cx.on_action(|_: &ToggleBorderless, cx| {
+                let enabled = !cx.global::<AppState>().borderless;
+                cx.update_global::<AppState, _>(|state, _| {
+                    state.borderless = enabled;
+                });
+
+                let mut app_config = config::Config::load();
+                app_config.borderless_window = Some(enabled);
+                let _ = app_config.save();
+
+                set_app_menus(cx);
+                cx.refresh_windows();
+            });
+
+            // Register the on-screen display duration preference handler
+            cx.on_action(|action: &SetOsdDuration, cx| {
+                cx.update_global::<AppState, _>(|state, _| {
+                    state.osd.duration_ms = action.0;
+                });
+
+                let mut app_config = config::Config::load();
+                app_config.osd_duration_ms = Some(action.0);
+                let _ = app_config.save();
+
+                set_app_menus(cx);
+            });
+
+            // Register the on-screen display line toggles
+            cx.on_action(|action: &ToggleOsdField, cx| {
+                let enabled = !cx.global::<AppState>().osd.shows(action.0);
+                cx.update_global::<AppState, _>(|state, _| match action.0 {
+                    OsdField::Time => state.osd.show_time = enabled,
+                    OsdField::Title => state.osd.show_title = enabled,
+                    OsdField::Speed => state.osd.show_speed = enabled,
+                });
+
+                let mut app_config = config::Config::load();
+                match action.0 {
+                    OsdField::Time => app_config.osd_show_time = Some(enabled),
+                    OsdField::Title => app_config.osd_show_title = Some(enabled),
+                    OsdField::Speed => app_config.osd_show_speed = Some(enabled),
+                }
+                let _ = app_config.save();
+
+                set_app_menus(cx);
+            });
+
+            // Register the ffmpeg/ffprobe location handlers
+            cx.on_action(choose_tool_path);
+            cx.on_action(choose_export_dir);
+            cx.on_action(|_: &ResetToolPaths, cx| {
+                for tool in ffmpeg_export::Tool::ALL {
+                    tool.set_path(None);
+                }
+
+                let mut app_config = config::Config::load();
+                app_config.ffmpeg_path = None;
+                app_config.ffprobe_path = None;
+                let _ = app_config.save();
+
+                set_app_menus(cx);
+            });
+
+            cx.on_action(|_: &ResetExportDirs, cx| {
+                cx.update_global::<AppState, _>(|state, _| {
+                    state.export_dirs.clear();
+                });
+
+                let mut app_config = config::Config::load();
+                app_config.export_dirs = None;
+                let _ = app_config.save();
+
+                set_app_menus(cx);
+            });
+
+            // Register the subtitle encoding preference; reload the list in the new encoding
+            cx.on_action(|action: &SetSubtitleCharset, cx| {
+                let charset = action.0.map(str::to_string);
+                cx.update_global::<AppState, _>(|state, _| {
+                    state.subtitle_charset = charset.clone();
+                });
+
+                if let Ok(player) = cx.global::<AppState>().video_player.lock() {
+                    if let Err(e) = player.set_subtitle_codepage(charset.as_deref()) {
+                        eprintln!("Failed to set subtitle encoding: {}", e);
+                    }
+                }
+
+                let mut app_config = config::Config::load();
+                app_config.subtitle_charset = charset;
+                let _ = app_config.save();
+
+                set_app_menus(cx);
+
+                if let Some(unified_window_entity) =
+                    cx.global::<AppState>().unified_window_entity.clone()
+                {
+                    let subtitles = unified_window_entity.read(cx).subtitles.clone();
+                    subtitles.update(cx, |subtitles, cx| {
+                        subtitles.reload_subtitles(cx);
+                    });
+                }
+            });
+
             // Add menu items
             set_app_menus(cx);
 
@@ -312,6 +808,28 @@ fn main() {
                             parsed_clip_start,
                             parsed_clip_end,
                         );
+
+                        if let Some(output_path) = cli.dump_frame.clone() {
+                            let video_player = cx.global::<AppState>().video_player.clone();
+                            cx.spawn(async move |cx| {
+                                let result = cx
+                                    .background_executor()
+                                    .spawn(async move {
+                                        dump_frame(&video_player, dump_frame_ms, &output_path)
+                                            .map(|()| output_path)
+                                    })
+                                    .await;
+                                match result {
+                                    Ok(output_path) => println!("Dumped frame to {}", output_path),
+                                    Err(e) => {
+                                        eprintln!("Failed to dump frame: {}", e);
+                                        std::process::exit(1);
+                                    }
+                                }
+                                let _ = cx.update(|cx| cx.quit());
+                            })
+                            .detach();
+                        }
                     } else {
                         eprintln!(
                             "Error: Invalid file type. Supported formats: {}",
@@ -328,12 +846,11 @@ fn main() {
                 let total_width = 1260.0;
                 let total_height = 720.0;
 
+                let default_bounds =
+                    gpui::Bounds::centered(None, gpui::size(px(total_width), px(total_height)), cx);
+
                 let unified_window_options = WindowOptions {
-                    window_bounds: Some(gpui::WindowBounds::Windowed(gpui::Bounds::centered(
-                        None,
-                        gpui::size(px(total_width), px(total_height)),
-                        cx,
-                    ))),
+                    window_bounds: Some(restored_window_bounds(cx, default_bounds)),
                     window_background: gpui::WindowBackgroundAppearance::Opaque,
                     focus: true,
                     is_movable: true,
@@ -354,6 +871,10 @@ fn main() {
                 let window = cx
                     .open_window(unified_window_options, move |window, cx| {
                         let unified_entity = cx.new(|cx| UnifiedWindow::new(window, cx));
+                        window.on_window_should_close(cx, |window, _| {
+                            save_window_bounds(window.window_bounds());
+                            true
+                        });
 
                         // Store the entity for later access
                         if let Ok(mut holder) = holder_clone.lock() {
@@ -376,15 +897,35 @@ fn main() {
 
                 println!("Unified window created (no video loaded)");
             }
+
+            // Like the mpv check above, but a warning: playback still works without ffmpeg
+            check_ffmpeg_tools(cx);
         });
 }
 
+/// Warn with a toast when ffmpeg or ffprobe can't be run
+///
+/// The check runs `-version` in the background so startup isn't held up.
+fn check_ffmpeg_tools(cx: &mut App) {
+    cx.spawn(async move |cx| {
+        let missing = cx
+            .background_executor()
+            .spawn(async { ffmpeg_export::missing_tools() })
+            .await;
+        if let Some(message) = ffmpeg_export::missing_tools_message(&missing) {
+            let _ = cx.update(|cx| notify_error(cx, message));
+        }
+    })
+    .detach();
+}
+
 /// Extract the native window handle from GPUI and create a child window/view for video rendering
 ///
 /// This function uses the stored AnyWindowHandle to access the unified window's window_handle()
 /// method, which provides raw window handle access via the raw-window-handle crate.
 /// - On macOS, this creates a child NSView for mpv rendering
 /// - On Windows, this creates a child HWND for mpv rendering
+/// - On Linux, this creates a hidden X11 window for mpv rendering
 ///
 /// Returns the child window/view handle as a usize if successful.
 fn extract_and_set_display_handle(cx: &mut App) -> Option<usize> {
@@ -408,6 +949,126 @@ fn extract_and_set_display_handle(cx: &mut App) -> Option<usize> {
     }
 }
 
+/// Row spacing of the subtitle list
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ListDensity {
+    Compact,
+    Normal,
+    Comfortable,
+}
+
+impl ListDensity {
+    pub const ALL: [ListDensity; 3] = [Self::Compact, Self::Normal, Self::Comfortable];
+
+    /// Name used in the menu and the config file
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Compact => "Compact",
+            Self::Normal => "Normal",
+            Self::Comfortable => "Comfortable",
+        }
+    }
+
+    /// Parse a name stored in the config file
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|density| density.name() == name)
+    }
+
+    /// Vertical padding (in pixels) above and below each list row
+    pub fn row_padding(self) -> f32 {
+        match self {
+            Self::Compact => 4.0,
+            Self::Normal => 8.0,
+            Self::Comfortable => 12.0,
+        }
+    }
+}
+
+/// Lines that can be shown in the on-screen display over the video
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OsdField {
+    Time,
+    Title,
+    Speed,
+}
+
+impl OsdField {
+    pub const ALL: [OsdField; 3] = [Self::Time, Self::Title, Self::Speed];
+
+    /// Name used in the menu
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Time => "Show Time",
+            Self::Title => "Show File Name",
+            Self::Speed => "Show Speed",
+        }
+    }
+}
+
+/// On-screen display settings (shown over the video on seek or mouse movement)
+#[derive(Clone, Copy, Debug)]
+pub struct OsdSettings {
+    pub duration_ms: u64, // How long it stays up before fading out (0 = never shown)
+    pub show_time: bool,
+    pub show_title: bool,
+    pub show_speed: bool,
+}
+
+impl OsdSettings {
+    /// Whether a line is shown
+    pub fn shows(&self, field: OsdField) -> bool {
+        match field {
+            OsdField::Time => self.show_time,
+            OsdField::Title => self.show_title,
+            OsdField::Speed => self.show_speed,
+        }
+    }
+}
+
+/// Text size of the subtitle list
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ListFontSize {
+    Small,
+    Normal,
+    Large,
+}
+
+impl ListFontSize {
+    pub const ALL: [ListFontSize; 3] = [Self::Small, Self::Normal, Self::Large];
+
+    /// Name used in the menu and the config file
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Small => "Small",
+            Self::Normal => "Normal",
+            Self::Large => "Large",
+        }
+    }
+
+    /// Parse a name stored in the config file
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|size| size.name() == name)
+    }
+
+    /// Subtitle text size in rems (Normal matches `text_sm`)
+    pub fn text_rems(self) -> f32 {
+        match self {
+            Self::Small => 0.75,
+            Self::Normal => 0.875,
+            Self::Large => 1.0,
+        }
+    }
+
+    /// Timecode text size in rems (Normal matches `text_xs`)
+    pub fn timecode_rems(self) -> f32 {
+        match self {
+            Self::Small => 0.625,
+            Self::Normal => 0.75,
+            Self::Large => 0.875,
+        }
+    }
+}
+
 /// Subtitle styling settings
 #[derive(Clone, Debug)]
 pub struct SubtitleSettings {
@@ -416,6 +1077,14 @@ pub struct SubtitleSettings {
     pub bold: bool,
     pub italic: bool,
     pub color: String,
+    pub background_box: bool,    // Draw a box behind subtitles for readability
+    pub background_opacity: f64, // Opacity of the background box (0.0 - 1.0)
+    pub title_safe: bool,        // Keep subtitles inside the title-safe region
+    pub title_safe_inset: f64,   // Title-safe inset as a fraction of the frame (0.0 - 0.2)
+    pub outline_color: String,   // Outline around the text (hidden by the background box)
+    pub outline_width: f64,      // Outline thickness (0 = no outline)
+    pub shadow: bool,            // Drop shadow behind the text
+    pub alignment: u8,           // Numpad-style ASS position (1-9, 2 = bottom center)
 }
 
 impl SubtitleSettings {
@@ -426,46 +1095,153 @@ impl SubtitleSettings {
             bold: false,
             italic: false,
             color: "#FFFFFF".to_string(),
+            background_box: false,
+            background_opacity: 0.6,
+            title_safe: false,
+            title_safe_inset: 0.05,
+            outline_color: "#000000".to_string(),
+            outline_width: 2.0,
+            shadow: false,
+            alignment: 2,
         }
     }
 }
 
+/// Picture adjustments applied during playback, each -100..100 (0 = unchanged)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct VideoAdjustments {
+    pub brightness: i64,
+    pub contrast: i64,
+    pub saturation: i64,
+    pub gamma: i64,
+}
+
+/// A named marker dropped at a position in the video, exported as a chapter
+///
+/// Markers double as bookmarks: they're listed in the Bookmarks tab and
+/// remembered per file.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Marker {
+    pub time_ms: u64,
+    pub name: String,
+}
+
 pub struct AppState {
     pub file_path: Option<String>,
     pub initial_window: Option<AnyWindowHandle>,
     pub unified_window: Option<AnyWindowHandle>,
+    pub duplicate_windows: Vec<(AnyWindowHandle, usize)>, // Extra windows and their video surfaces
     pub unified_window_entity: Option<Entity<UnifiedWindow>>, // Entity for accessing UnifiedWindow fields
     pub video_nsview: Option<usize>, // Pointer to the child NSView for video rendering
     pub video_player: Arc<Mutex<video_player::VideoPlayer>>,
     pub synced_to_video: bool,
     pub selected_subtitle_track: Option<usize>, // Currently selected subtitle track index
+    pub selected_subtitle_sid: Option<i64>, // mpv track id of that track, when mpv has listed it
+    pub selected_audio_track: Option<usize>, // Audio stream index for playback and exports
     pub display_subtitles: bool,
+    pub subtitle_offset_ms: i64, // Shift applied to subtitle timing (positive = later)
     pub subtitle_settings: SubtitleSettings,
     pub source_video_width: u32, // Horizontal resolution of the source video for subtitle scaling
+    pub source_video_fps: f32,   // Frame rate of the source video for frame counting
+    pub source_is_hdr: bool,     // Whether the source video uses an HDR transfer (PQ/HLG)
+    pub deinterlace: bool,       // Deinterlace playback and exports (on for interlaced sources)
+    pub media_info: Option<ffmpeg_export::MediaInfo>, // Loaded file details for the info panel
     pub has_video_loaded: bool,  // Whether a video has been loaded
     pub custom_subtitle_mode: bool, // Whether custom subtitle mode is enabled in clip tab
+    pub clip_padding_ms: u64,       // Padding added before/after a subtitle by "Clip with padding"
+    pub filter_preflight: bool,     // Dry-run the export filter chain before long exports
+    pub render_fps_cap: u32,        // Max UI refresh rate for polling/repaints (0 = uncapped)
+    pub markers: Vec<Marker>,       // Named markers for the loaded video, sorted by time
+    pub stop_at_clip_end: bool,     // Pause Play Clip at the clip end instead of playing on
+    pub list_density: ListDensity,  // Row spacing of the subtitle list
+    pub list_font_size: ListFontSize, // Text size of the subtitle list
+    pub hardware_scaling: bool,     // Scale on the GPU during exports when ffmpeg supports it
+    pub osd: OsdSettings,           // On-screen display over the video
+    pub volume: f64,                // Playback volume (0-100), kept when loading another file
+    pub muted: bool,
+    pub adjustments: VideoAdjustments, // Playback-only picture adjustments, kept across files
+    pub export_dirs: HashMap<String, String>, // Default save folder per export format
+    pub subtitle_charset: Option<String>,     // Subtitle text encoding (None = auto-detect)
+    pub borderless: bool, // Hide the titlebar and window buttons; drag the video to move
+    pub notifications: Vec<(String, Instant, bool)>, // Toasts, when last raised, and if they're errors
+    pub waveform: Vec<f32>, // Audio peak levels (0.0-1.0) of the loaded video, empty until ready
 }
 
 impl AppState {
-    fn new() -> Self {
+    fn new(config: &config::Config) -> Self {
         Self {
             file_path: None,
             initial_window: None,
             unified_window: None,
+            duplicate_windows: Vec::new(),
             unified_window_entity: None,
             video_nsview: None,
             video_player: Arc::new(Mutex::new(video_player::VideoPlayer::new())),
             synced_to_video: true,            // Default to checked/synced
             selected_subtitle_track: Some(1), // the first track is selected initially so that when the user clicks the subtitles toggle they turn on
-            display_subtitles: false,
+            selected_subtitle_sid: None,
+            selected_audio_track: None,
+            display_subtitles: config.display_subtitles.unwrap_or(false),
+            subtitle_offset_ms: 0,
             subtitle_settings: SubtitleSettings::default(),
             source_video_width: 1920, // Default to 1920 (will be updated when video loads)
+            source_video_fps: 30.0,   // Default to 30fps (will be updated when video loads)
+            source_is_hdr: false,
+            deinterlace: false,
+            media_info: None,
             has_video_loaded: false,  // No video loaded initially
             custom_subtitle_mode: false, // Default to off
+            clip_padding_ms: config.clip_padding_ms.unwrap_or(300),
+            filter_preflight: config.filter_preflight.unwrap_or(true),
+            render_fps_cap: config.render_fps_cap.unwrap_or(30),
+            markers: Vec::new(),
+            stop_at_clip_end: config.stop_at_clip_end.unwrap_or(true),
+            list_density: config
+                .subtitle_list_density
+                .as_deref()
+                .and_then(ListDensity::from_name)
+                .unwrap_or(ListDensity::Normal),
+            list_font_size: config
+                .subtitle_list_font_size
+                .as_deref()
+                .and_then(ListFontSize::from_name)
+                .unwrap_or(ListFontSize::Normal),
+            hardware_scaling: config.hardware_scaling.unwrap_or(false),
+            osd: OsdSettings {
+                duration_ms: config.osd_duration_ms.unwrap_or(2000),
+                show_time: config.osd_show_time.unwrap_or(true),
+                show_title: config.osd_show_title.unwrap_or(true),
+                show_speed: config.osd_show_speed.unwrap_or(false),
+            },
+            export_dirs: config.export_dirs.clone().unwrap_or_default(),
+            subtitle_charset: config.subtitle_charset.clone(),
+            borderless: config.borderless_window.unwrap_or(false),
+            volume: 100.0,
+            muted: false,
+            adjustments: VideoAdjustments::default(),
+            notifications: Vec::new(),
+            waveform: Vec::new(),
         }
     }
 
-    /// Get the unified window handle
+    /// mpv track id to show for the selected subtitle track; the list position
+    /// stands in until mpv has listed the track
+    pub fn selected_subtitle_track_id(&self) -> Option<i32> {
+        self.selected_subtitle_sid
+            .map(|id| id as i32)
+            .or(self.selected_subtitle_track.map(|track| track as i32))
+    }
+
+    /// Minimum time between UI refreshes, or None when uncapped
+    pub fn render_frame_interval(&self) -> Option<Duration> {
+        if self.render_fps_cap == 0 {
+            None
+        } else {
+            Some(Duration::from_secs_f64(1.0 / self.render_fps_cap as f64))
+        }
+    }
+
+    /// Get the unified window handle
     pub fn unified_window(&self) -> Option<AnyWindowHandle> {
         self.unified_window
     }
@@ -473,6 +1249,82 @@ impl AppState {
 
 impl Global for AppState {}
 
+/// Persist the current markers as the loaded file's bookmarks
+pub fn save_markers(cx: &App) {
+    let app_state = cx.global::<AppState>();
+    let Some(file_path) = app_state.file_path.as_deref() else {
+        return;
+    };
+
+    let mut file_settings = config::FileSettings::load(file_path);
+    file_settings.bookmarks = app_state.markers.clone();
+    if let Err(e) = file_settings.save(file_path) {
+        eprintln!("Failed to save bookmarks: {}", e);
+    }
+}
+
+/// How long a toast stays over the video
+pub const NOTIFICATION_DURATION: Duration = Duration::from_secs(4);
+
+/// Log an error and show it as a toast over the video
+///
+/// The console isn't visible in a GUI build, so failures the user should
+/// know about go through here rather than a bare `eprintln!`. Raising a
+/// message that's already showing restarts its timer instead of stacking it.
+pub fn notify_error(cx: &mut App, message: impl Into<String>) {
+    let message = message.into();
+    eprintln!("{}", message);
+    push_notification(cx, message, true);
+}
+
+/// Log a status message and show it as a (non-error) toast over the video
+pub fn notify_info(cx: &mut App, message: impl Into<String>) {
+    let message = message.into();
+    println!("{}", message);
+    push_notification(cx, message, false);
+}
+
+fn push_notification(cx: &mut App, message: String, is_error: bool) {
+    cx.update_global::<AppState, _>(|state, _| {
+        let notifications = &mut state.notifications;
+        notifications.retain(|(shown, _, _)| *shown != message);
+        notifications.push((message, Instant::now(), is_error));
+    });
+    // Notify by id so this is safe while the unified window is being updated
+    if let Some(unified_window) = &cx.global::<AppState>().unified_window_entity {
+        let entity_id = unified_window.entity_id();
+        cx.notify(entity_id);
+    }
+}
+
+/// Request a re-render, respecting the user's refresh rate cap
+///
+/// Called from `on_next_frame` polling loops. When the last render was too
+/// recent, a timer wakes the view once the interval has elapsed so the loop
+/// keeps running without rendering faster than the cap.
+pub fn notify_rate_limited<T: 'static>(
+    last_render_time: &mut Instant,
+    cx: &mut gpui::Context<T>,
+) {
+    let now = Instant::now();
+    let elapsed = now.duration_since(*last_render_time);
+
+    match cx.global::<AppState>().render_frame_interval() {
+        Some(interval) if elapsed < interval => {
+            let remaining = interval - elapsed;
+            cx.spawn(async move |this, cx| {
+                cx.background_executor().timer(remaining).await;
+                this.update(cx, |_, cx| cx.notify()).ok();
+            })
+            .detach();
+        }
+        _ => {
+            *last_render_time = now;
+            cx.notify();
+        }
+    }
+}
+
 fn set_app_menus(cx: &mut App) {
     let registry = theme::ThemeRegistry::new();
     let current_theme_name = Theme::global(cx).theme_name().to_string();
@@ -491,6 +1343,122 @@ fn set_app_menus(cx: &mut App) {
         })
         .collect();
 
+    // Build clip padding submenu with checkmark on the current value
+    let current_padding = cx.global::<AppState>().clip_padding_ms;
+    let padding_items: Vec<MenuItem> = CLIP_PADDING_OPTIONS
+        .iter()
+        .map(|&padding_ms| {
+            MenuItem::action(format!("{} ms", padding_ms), SetClipPadding(padding_ms))
+                .checked(padding_ms == current_padding)
+        })
+        .collect();
+
+    // Build refresh rate submenu with checkmark on the current cap
+    let current_fps_cap = cx.global::<AppState>().render_fps_cap;
+    let fps_items: Vec<MenuItem> = RENDER_FPS_OPTIONS
+        .iter()
+        .map(|&fps| {
+            let label = if fps == 0 {
+                "Uncapped".to_string()
+            } else {
+                format!("{} fps", fps)
+            };
+            MenuItem::action(label, SetRenderFpsCap(fps)).checked(fps == current_fps_cap)
+        })
+        .collect();
+
+    // Build subtitle list density and font size submenus
+    let current_density = cx.global::<AppState>().list_density;
+    let density_items: Vec<MenuItem> = ListDensity::ALL
+        .iter()
+        .map(|&density| {
+            MenuItem::action(density.name(), SetListDensity(density))
+                .checked(density == current_density)
+        })
+        .collect();
+    let current_font_size = cx.global::<AppState>().list_font_size;
+    let font_size_items: Vec<MenuItem> = ListFontSize::ALL
+        .iter()
+        .map(|&font_size| {
+            MenuItem::action(font_size.name(), SetListFontSize(font_size))
+                .checked(font_size == current_font_size)
+        })
+        .collect();
+
+    // Build on-screen display submenu: duration choices, then the lines to show
+    let osd = cx.global::<AppState>().osd;
+    let mut osd_items: Vec<MenuItem> = OSD_DURATION_OPTIONS
+        .iter()
+        .map(|&duration_ms| {
+            let label = if duration_ms == 0 {
+                "Off".to_string()
+            } else {
+                format!("{} s", duration_ms / 1000)
+            };
+            MenuItem::action(label, SetOsdDuration(duration_ms))
+                .checked(duration_ms == osd.duration_ms)
+        })
+        .collect();
+    osd_items.push(MenuItem::separator());
+    osd_items.extend(OsdField::ALL.iter().map(|&field| {
+        MenuItem::action(field.name(), ToggleOsdField(field)).checked(osd.shows(field))
+    }));
+
+    // Build ffmpeg location submenu: each tool shows the executable in use
+    let mut tool_items: Vec<MenuItem> = ffmpeg_export::Tool::ALL
+        .iter()
+        .map(|&tool| {
+            MenuItem::action(
+                format!("Choose {}... ({})", tool.name(), tool.path()),
+                ChooseToolPath(tool),
+            )
+        })
+        .collect();
+    let using_path_lookup = ffmpeg_export::Tool::ALL
+        .iter()
+        .all(|tool| tool.path() == tool.name());
+    tool_items.push(MenuItem::separator());
+    tool_items.push(MenuItem::action("Use PATH", ResetToolPaths).checked(using_path_lookup));
+
+    // Build export folders submenu: each format shows where its exports are saved
+    let export_dirs = &cx.global::<AppState>().export_dirs;
+    let mut export_dir_items: Vec<MenuItem> = controls_window::ExportFormat::ALL
+        .iter()
+        .map(|&format| {
+            let folder = export_dirs
+                .get(format.as_str())
+                .map(String::as_str)
+                .unwrap_or("source folder");
+            MenuItem::action(
+                format!("Choose {} Folder... ({})", format.name(), folder),
+                ChooseExportDir(format),
+            )
+        })
+        .collect();
+    export_dir_items.push(MenuItem::separator());
+    export_dir_items.push(
+        MenuItem::action("Save Next to Source", ResetExportDirs).checked(export_dirs.is_empty()),
+    );
+
+    // Build subtitle encoding submenu: auto-detect, then the supported encodings
+    let current_charset = cx.global::<AppState>().subtitle_charset.as_deref();
+    let mut charset_items = vec![
+        MenuItem::action("Auto-detect", SetSubtitleCharset(None))
+            .checked(current_charset.is_none()),
+        MenuItem::separator(),
+    ];
+    charset_items.extend(subtitle_extractor::SUBTITLE_CHARSETS.iter().map(
+        |&(label, name)| {
+            let is_current = current_charset.is_some_and(|c| c.eq_ignore_ascii_case(label));
+            MenuItem::action(name, SetSubtitleCharset(Some(label))).checked(is_current)
+        },
+    ));
+
+    let borderless = cx.global::<AppState>().borderless;
+    let filter_preflight = cx.global::<AppState>().filter_preflight;
+    let hardware_scaling = cx.global::<AppState>().hardware_scaling;
+    let stop_at_clip_end = cx.global::<AppState>().stop_at_clip_end;
+
     cx.set_menus(vec![
         // Application menu
         Menu {
@@ -499,6 +1467,41 @@ fn set_app_menus(cx: &mut App) {
                 MenuItem::os_submenu("Services", SystemMenuType::Services),
                 MenuItem::separator(),
                 MenuItem::action("Open...", OpenFile),
+                MenuItem::action("Open Subtitles...", OpenSubtitles),
+                MenuItem::action("Duplicate Window", DuplicateWindow),
+                MenuItem::submenu(Menu {
+                    name: "Export Transcript".into(),
+                    items: vec![
+                        MenuItem::action(
+                            "Whole Video...",
+                            ExportTranscript {
+                                include_timestamps: false,
+                                clip_only: false,
+                            },
+                        ),
+                        MenuItem::action(
+                            "Whole Video with Timestamps...",
+                            ExportTranscript {
+                                include_timestamps: true,
+                                clip_only: false,
+                            },
+                        ),
+                        MenuItem::action(
+                            "Clip...",
+                            ExportTranscript {
+                                include_timestamps: false,
+                                clip_only: true,
+                            },
+                        ),
+                        MenuItem::action(
+                            "Clip with Timestamps...",
+                            ExportTranscript {
+                                include_timestamps: true,
+                                clip_only: true,
+                            },
+                        ),
+                    ],
+                }),
                 MenuItem::separator(),
                 MenuItem::action("Quit", Quit),
             ],
@@ -508,18 +1511,210 @@ fn set_app_menus(cx: &mut App) {
             name: "Theme".into(),
             items: theme_items,
         },
+        // Preferences menu for persisted user settings
+        Menu {
+            name: "Preferences".into(),
+            items: vec![
+                MenuItem::submenu(Menu {
+                    name: "Clip Padding".into(),
+                    items: padding_items,
+                }),
+                MenuItem::submenu(Menu {
+                    name: "UI Refresh Rate".into(),
+                    items: fps_items,
+                }),
+                MenuItem::submenu(Menu {
+                    name: "Subtitle List Density".into(),
+                    items: density_items,
+                }),
+                MenuItem::submenu(Menu {
+                    name: "Subtitle List Font Size".into(),
+                    items: font_size_items,
+                }),
+                MenuItem::submenu(Menu {
+                    name: "Subtitle Encoding".into(),
+                    items: charset_items,
+                }),
+                MenuItem::submenu(Menu {
+                    name: "On-Screen Display".into(),
+                    items: osd_items,
+                }),
+                MenuItem::submenu(Menu {
+                    name: "FFmpeg Location".into(),
+                    items: tool_items,
+                }),
+                MenuItem::submenu(Menu {
+                    name: "Export Folders".into(),
+                    items: export_dir_items,
+                }),
+                MenuItem::separator(),
+                MenuItem::action("Borderless Window", ToggleBorderless).checked(borderless),
+                MenuItem::action("Stop at Clip End", ToggleStopAtClipEnd)
+                    .checked(stop_at_clip_end),
+                MenuItem::action("Test Filters Before Export", ToggleFilterPreflight)
+                    .checked(filter_preflight),
+                MenuItem::action("Use GPU Scaling for Exports", ToggleHardwareScaling)
+                    .checked(hardware_scaling),
+            ],
+        },
     ]);
 }
 
 // Associate actions using the `actions!` macro (or `Action` derive macro)
-actions!(set_menus, [Quit, OpenFile]);
+actions!(
+    set_menus,
+    [
+        Quit,
+        OpenFile,
+        OpenSubtitles,
+        DuplicateWindow,
+        ToggleFilterPreflight,
+        ToggleStopAtClipEnd,
+        ToggleHardwareScaling,
+        CycleSubtitleTrack,
+        SetLoopA,
+        SetLoopB,
+        ClearLoop,
+        SetClipStart,
+        SetClipEnd,
+        RepeatExport,
+        FrameStepForward,
+        FrameStepBackward,
+        ZoomToFit,
+        ZoomActualSize,
+        TogglePlay,
+        ToggleBorderless,
+        ResetToolPaths,
+        ResetExportDirs
+    ]
+);
 
 // Define the quit function that is registered with the App
 fn quit(_: &Quit, cx: &mut App) {
     println!("Gracefully quitting the application . . .");
+    if let Some(handle) = cx.global::<AppState>().unified_window {
+        if let Ok(bounds) = handle.update(cx, |_, window, _| window.window_bounds()) {
+            save_window_bounds(bounds);
+        }
+    }
     cx.quit();
 }
 
+/// Bounds for a new main window: where it was when the app was last closed, or
+/// `default` if nothing usable was saved or the saved spot is on no connected display
+fn restored_window_bounds(cx: &App, default: gpui::Bounds<gpui::Pixels>) -> gpui::WindowBounds {
+    let Some(saved) = config::Config::load()
+        .window_bounds
+        .filter(|saved| saved.is_usable())
+    else {
+        return gpui::WindowBounds::Windowed(default);
+    };
+    let bounds = gpui::Bounds {
+        origin: gpui::point(px(saved.x), px(saved.y)),
+        size: gpui::size(px(saved.width), px(saved.height)),
+    };
+    // The monitor it was on may have been unplugged since
+    if !cx
+        .displays()
+        .iter()
+        .any(|display| display.bounds().intersects(&bounds))
+    {
+        return gpui::WindowBounds::Windowed(default);
+    }
+    if saved.maximized {
+        gpui::WindowBounds::Maximized(bounds)
+    } else {
+        gpui::WindowBounds::Windowed(bounds)
+    }
+}
+
+/// Save the main window's bounds so the next launch reopens it in the same place
+/// (fullscreen is saved as the size it restores to)
+fn save_window_bounds(bounds: gpui::WindowBounds) {
+    let restore = bounds.get_bounds();
+    let mut app_config = config::Config::load();
+    app_config.window_bounds = Some(config::SavedWindowBounds {
+        x: f32::from(restore.origin.x),
+        y: f32::from(restore.origin.y),
+        width: f32::from(restore.size.width),
+        height: f32::from(restore.size.height),
+        maximized: matches!(bounds, gpui::WindowBounds::Maximized(_)),
+    });
+    if let Err(e) = app_config.save() {
+        eprintln!("Failed to save window bounds: {}", e);
+    }
+}
+
+/// Poll the video player until `done` returns true, giving up after `timeout`
+fn wait_for_player(
+    video_player: &Arc<Mutex<video_player::VideoPlayer>>,
+    timeout: Duration,
+    done: impl Fn(&video_player::VideoPlayer) -> bool,
+) -> bool {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if let Ok(player) = video_player.lock() {
+            if done(&player) {
+                return true;
+            }
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    false
+}
+
+/// Seek to `time_ms` and write the frame rendered there to a PNG (blocking)
+///
+/// Waits for mpv to finish the seek and then for a render requested after it,
+/// so the image never shows the frame from before the seek.
+fn dump_frame(
+    video_player: &Arc<Mutex<video_player::VideoPlayer>>,
+    time_ms: f32,
+    output_path: &str,
+) -> Result<(), String> {
+    let timeout = Duration::from_secs(10);
+
+    if !wait_for_player(video_player, timeout, |player| {
+        player
+            .get_position_duration()
+            .is_some_and(|(_, duration)| duration.nseconds() > 0)
+    }) {
+        return Err("Timed out waiting for the video to load".to_string());
+    }
+
+    let restarts_before = {
+        let player = video_player.lock().map_err(|e| e.to_string())?;
+        player.pause().map_err(|e| e.to_string())?;
+        let restarts_before = player.playback_restart_count();
+        let nanos = (time_ms * 1_000_000.0) as u64;
+        player
+            .seek(ClockTime::from_nseconds(nanos))
+            .map_err(|e| e.to_string())?;
+        restarts_before
+    };
+
+    if !wait_for_player(video_player, timeout, |player| {
+        player.playback_restart_count() > restarts_before
+    }) {
+        return Err("Timed out waiting for the seek to finish".to_string());
+    }
+
+    let frame_before = {
+        let player = video_player.lock().map_err(|e| e.to_string())?;
+        player.request_render();
+        player.get_frame_buffer()
+    };
+
+    if !wait_for_player(video_player, timeout, |player| {
+        !Arc::ptr_eq(&player.get_frame_buffer(), &frame_before)
+    }) {
+        return Err("Timed out waiting for a frame to be rendered".to_string());
+    }
+
+    let player = video_player.lock().map_err(|e| e.to_string())?;
+    player.save_current_frame(output_path)
+}
+
 /// Create the unified video player window and load the video file
 pub fn create_video_windows(
     cx: &mut App,
@@ -549,12 +1744,27 @@ pub fn create_video_windows(
     let total_width = 1260.0;
     let total_height = 720.0;
 
+    // Open where the current window is when switching files, otherwise where the
+    // window was when the app was last closed
+    let window_bounds = old_unified_window
+        .and_then(|handle| {
+            handle
+                .update(cx, |_, window, _| window.window_bounds())
+                .ok()
+        })
+        .unwrap_or_else(|| {
+            restored_window_bounds(
+                cx,
+                gpui::Bounds {
+                    origin: gpui::point(px(20.0), px(20.0)),
+                    size: gpui::size(px(total_width), px(total_height)),
+                },
+            )
+        });
+
     // Create the unified window FIRST before closing the initial window
     let unified_window_options = WindowOptions {
-        window_bounds: Some(gpui::WindowBounds::Windowed(gpui::Bounds {
-            origin: gpui::point(px(20.0), px(20.0)),
-            size: gpui::size(px(total_width), px(total_height)),
-        })),
+        window_bounds: Some(window_bounds),
         window_background: gpui::WindowBackgroundAppearance::Opaque,
         focus: true,
         is_movable: true,
@@ -575,6 +1785,10 @@ pub fn create_video_windows(
     let unified_window = cx
         .open_window(unified_window_options, move |window, cx| {
             let unified_entity = cx.new(|cx| UnifiedWindow::new(window, cx));
+            window.on_window_should_close(cx, |window, _| {
+                save_window_bounds(window.window_bounds());
+                true
+            });
 
             // Store the entity for later access
             if let Ok(mut holder) = holder_clone.lock() {
@@ -610,10 +1824,32 @@ pub fn create_video_windows(
             })
             .ok();
     }
+    // Duplicates show the previous video, so close them too
+    let duplicate_windows = cx.global::<AppState>().duplicate_windows.clone();
+    for (window, surface) in duplicate_windows {
+        window
+            .update(cx, |_, window, _| {
+                window.remove_window();
+            })
+            .ok();
+        release_duplicate_window(cx, surface);
+    }
 
-    // Get video resolution before updating AppState
-    let (video_width, _video_height) =
-        crate::ffmpeg_export::get_video_resolution(&path_string).unwrap_or((1920, 1080));
+    // Probe the source once before updating AppState (resolution, frame rate, HDR, ...)
+    let media_info = crate::ffmpeg_export::get_media_info(&path_string)
+        .map_err(|e| eprintln!("Failed to probe {}: {}", path_string, e))
+        .ok();
+    let info = media_info.clone().unwrap_or_default();
+    let (video_width, _video_height) = info.resolution.unwrap_or((1920, 1080));
+    let video_fps = info.fps.unwrap_or(30.0);
+    let video_is_hdr = info.is_hdr;
+    if video_is_hdr {
+        println!("Source video is HDR; tone-mapping is available for exports");
+    }
+    let video_is_interlaced = info.is_interlaced;
+    if video_is_interlaced {
+        println!("Source video is interlaced; deinterlacing it");
+    }
 
     // Update AppState with new window, file path, and source video resolution
     cx.update_global::<AppState, _>(|state, _| {
@@ -623,8 +1859,39 @@ pub fn create_video_windows(
         state.video_nsview = None;
         state.file_path = Some(path_string.clone());
         state.source_video_width = video_width;
+        state.source_video_fps = video_fps;
+        state.source_is_hdr = video_is_hdr;
+        state.deinterlace = video_is_interlaced;
+        // Picked again once mpv has listed the new file's audio tracks
+        state.selected_audio_track = None;
+        state.selected_subtitle_sid = None;
+        state.media_info = media_info;
         state.has_video_loaded = true; // Mark that a video has been loaded
+        // Restore the bookmarks saved for this file
+        state.markers = config::FileSettings::load(&path_string).bookmarks;
+        // Subtitle timing fixes are per file
+        state.subtitle_offset_ms = 0;
+        state.waveform = Vec::new();
     });
+    if let Ok(player) = cx.global::<AppState>().video_player.lock() {
+        if let Err(e) = player.set_subtitle_delay(0.0) {
+            eprintln!("Failed to reset subtitle delay: {}", e);
+        }
+        // Keep the volume the user picked for the previous file
+        let app_state = cx.global::<AppState>();
+        if let Err(e) = player.set_volume(app_state.volume) {
+            eprintln!("Failed to restore volume: {}", e);
+        }
+        if let Err(e) = player.set_muted(app_state.muted) {
+            eprintln!("Failed to restore mute: {}", e);
+        }
+        if let Err(e) = player.set_deinterlace(app_state.deinterlace) {
+            eprintln!("Failed to set deinterlacing: {}", e);
+        }
+        if let Err(e) = player.set_subtitle_codepage(app_state.subtitle_charset.as_deref()) {
+            eprintln!("Failed to set subtitle encoding: {}", e);
+        }
+    };
 
     // Update the titlebar with the filename
     unified_window_entity.update(cx, |unified_window, cx| {
@@ -686,9 +1953,18 @@ pub fn create_video_windows(
                         println!("Video paused and ready");
                     }
                 }
+
+                // Restore the remembered subtitle display state
+                let app_state = cx.global::<AppState>();
+                if app_state.display_subtitles {
+                    let track = app_state.selected_subtitle_track_id();
+                    if let Err(e) = player.set_subtitle_display(true, track) {
+                        eprintln!("Failed to enable subtitle display: {}", e);
+                    }
+                }
             }
             Err(e) => {
-                eprintln!("Failed to load video file: {}", e);
+                notify_error(cx, format!("Failed to load video file: {}", e));
             }
         }
     };
@@ -697,29 +1973,63 @@ pub fn create_video_windows(
     // there must be some better way to do this, but this does consistently work
     if let Some(start_ms) = clip_start {
         let video_player = cx.global::<AppState>().video_player.clone();
-        cx.spawn(async move |_cx| {
+        cx.spawn(async move |cx| {
             // Wait a bit for mpv to be fully ready
-            use std::time::Duration;
             std::thread::sleep(Duration::from_millis(100));
 
-            if let Ok(player) = video_player.lock() {
-                // Convert milliseconds to nanoseconds
-                let nanos = (start_ms * 1_000_000.0) as u64;
-                let clock_time = ClockTime::from_nseconds(nanos);
-                if let Err(e) = player.seek(clock_time) {
-                    eprintln!("Failed to seek to clip start: {}", e);
-                } else {
-                    println!("Seeked to clip start: {} ms", start_ms);
+            let seek_result = match video_player.lock() {
+                Ok(player) => {
+                    // Convert milliseconds to nanoseconds
+                    let nanos = (start_ms * 1_000_000.0) as u64;
+                    player.seek(ClockTime::from_nseconds(nanos))
+                }
+                Err(_) => return,
+            };
+            match seek_result {
+                Ok(()) => println!("Seeked to clip start: {} ms", start_ms),
+                Err(e) => {
+                    let _ = cx.update(|cx| {
+                        notify_error(cx, format!("Failed to seek to clip start: {}", e));
+                    });
                 }
             }
         })
         .detach();
     }
 
+    // Build the scrubber waveform on a background thread
+    let path_for_waveform = path_clone.clone();
+    cx.spawn(async move |cx| {
+        let video_path = path_for_waveform.clone();
+        let waveform = cx
+            .background_executor()
+            .spawn(async move { ffmpeg_export::extract_waveform(&video_path) })
+            .await;
+
+        match waveform {
+            Ok(waveform) => {
+                let _ = cx.update(|cx| {
+                    // Ignore a waveform for a file that's been replaced since
+                    if cx.global::<AppState>().file_path.as_deref() != Some(&path_for_waveform) {
+                        return;
+                    }
+                    cx.update_global::<AppState, _>(|state, _| state.waveform = waveform);
+                    if let Some(unified_window) = &cx.global::<AppState>().unified_window_entity {
+                        let controls = unified_window.read(cx).controls.clone();
+                        controls.update(cx, |_, cx| cx.notify());
+                    }
+                });
+            }
+            Err(e) => eprintln!("Failed to build waveform: {}", e),
+        }
+    })
+    .detach();
+
     // Load subtitle streams in the unified window on a background thread
     let unified_window_handle = cx.global::<AppState>().unified_window;
     if let Some(window_handle) = unified_window_handle {
         let path_for_subtitles = path_clone.clone();
+        let charset = cx.global::<AppState>().subtitle_charset.clone();
 
         cx.spawn(async move |cx| {
             // Run blocking subtitle loading on background executor
@@ -729,6 +2039,7 @@ pub fn create_video_windows(
                     println!("Loading subtitles on background thread...");
                     crate::subtitle_window::SubtitleWindow::load_subtitle_data_blocking(
                         &path_for_subtitles,
+                        charset.as_deref(),
                     )
                 })
                 .await;
@@ -762,6 +2073,300 @@ pub fn create_video_windows(
     }
 }
 
+/// Let the user pick the ffmpeg or ffprobe executable
+///
+/// The choice is checked with `-version` before it's used or saved, so a
+/// wrong binary doesn't break every export.
+fn choose_tool_path(action: &ChooseToolPath, cx: &mut App) {
+    let tool = action.0;
+    let paths = cx.prompt_for_paths(PathPromptOptions {
+        files: true,
+        directories: false,
+        multiple: false,
+        prompt: Some(format!("Select the {} executable", tool.name()).into()),
+    });
+
+    cx.spawn(async move |cx| {
+        let Ok(Ok(Some(paths))) = paths.await else {
+            return;
+        };
+        let Some(path) = paths.first().map(|path| path.to_string_lossy().to_string()) else {
+            return;
+        };
+
+        let check_path = path.clone();
+        let result = cx
+            .background_executor()
+            .spawn(async move { ffmpeg_export::check_tool(tool, &check_path) })
+            .await;
+
+        match result {
+            Ok(version) => {
+                println!("Using {}: {}", path, version);
+                tool.set_path(Some(path.clone()));
+
+                let mut app_config = config::Config::load();
+                match tool {
+                    ffmpeg_export::Tool::Ffmpeg => app_config.ffmpeg_path = Some(path),
+                    ffmpeg_export::Tool::Ffprobe => app_config.ffprobe_path = Some(path),
+                }
+                let _ = app_config.save();
+
+                let _ = cx.update(|cx| set_app_menus(cx));
+            }
+            Err(e) => {
+                eprintln!("Not using {} for {}: {}", path, tool.name(), e);
+            }
+        }
+    })
+    .detach();
+}
+
+/// Pick the folder the save dialog starts in for one export format
+fn choose_export_dir(action: &ChooseExportDir, cx: &mut App) {
+    let format = action.0;
+    let paths = cx.prompt_for_paths(PathPromptOptions {
+        files: false,
+        directories: true,
+        multiple: false,
+        prompt: Some(format!("Save {} exports to", format.name()).into()),
+    });
+
+    cx.spawn(async move |cx| {
+        let Ok(Ok(Some(paths))) = paths.await else {
+            return;
+        };
+        let Some(dir) = paths.first().map(|path| path.to_string_lossy().to_string()) else {
+            return;
+        };
+
+        let _ = cx.update(|cx| {
+            cx.update_global::<AppState, _>(|state, _| {
+                state.export_dirs.insert(format.as_str().to_string(), dir.clone());
+            });
+
+            let mut app_config = config::Config::load();
+            app_config
+                .export_dirs
+                .get_or_insert_with(HashMap::new)
+                .insert(format.as_str().to_string(), dir);
+            let _ = app_config.save();
+
+            set_app_menus(cx);
+        });
+    })
+    .detach();
+}
+
+/// Add a subtitle file from disk as a track of the loaded video
+fn open_subtitles(_: &OpenSubtitles, cx: &mut App) {
+    let app_state = cx.global::<AppState>();
+    let (Some(window_handle), Some(unified_window_entity)) = (
+        app_state.unified_window,
+        app_state.unified_window_entity.clone(),
+    ) else {
+        notify_error(cx, "Open a video before adding subtitles");
+        return;
+    };
+    let paths = cx.prompt_for_paths(PathPromptOptions {
+        files: true,
+        directories: false,
+        multiple: false,
+        prompt: Some("Select a subtitle file".into()),
+    });
+
+    cx.spawn(async move |cx| {
+        let Ok(Ok(Some(paths))) = paths.await else {
+            return;
+        };
+        let Some(path) = paths.first().map(|path| path.to_string_lossy().to_string()) else {
+            return;
+        };
+
+        let _ = cx.update(|cx| {
+            let subtitle_entity = unified_window_entity.read(cx).subtitles.clone();
+            window_handle
+                .update(cx, |_any_view, window, cx| {
+                    subtitle_entity.update(cx, |subtitle_window, cx| {
+                        if let Err(e) = subtitle_window.load_external_srt(&path, window, cx) {
+                            notify_error(cx, format!("Failed to open subtitles: {}", e));
+                        }
+                    });
+                })
+                .ok();
+        });
+    })
+    .detach();
+}
+
+/// Save the loaded subtitle track as a plain-text transcript
+fn export_transcript(action: &ExportTranscript, cx: &mut App) {
+    let Some(unified_window_entity) = cx.global::<AppState>().unified_window_entity.clone() else {
+        notify_error(cx, "No video loaded to export a transcript from");
+        return;
+    };
+    let subtitles = unified_window_entity.read(cx).subtitles.clone();
+    subtitles.update(cx, |subtitles, cx| {
+        subtitles.save_transcript(action.include_timestamps, action.clip_only, cx);
+    });
+}
+
+/// Open another unified window showing the same video player
+///
+/// The window gets its own hidden platform surface, which the player draws a
+/// view for at the window's size. Its subtitle list and controls are
+/// independent, so it can show a different subtitle track or a different
+/// part of the list; mpv's zoom and pan stay with the main window.
+fn duplicate_window(_: &DuplicateWindow, cx: &mut App) {
+    let app_state = cx.global::<AppState>();
+    let Some(path_string) = app_state.file_path.clone() else {
+        notify_error(cx, "No video loaded to duplicate");
+        return;
+    };
+    let offset = 40.0 * (app_state.duplicate_windows.len() + 1) as f32;
+    let video_player = app_state.video_player.clone();
+
+    let file_name = std::path::Path::new(&path_string)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Video Player")
+        .to_string();
+
+    let window_options = WindowOptions {
+        window_bounds: Some(gpui::WindowBounds::Windowed(gpui::Bounds {
+            origin: gpui::point(px(20.0 + offset), px(20.0 + offset)),
+            size: gpui::size(px(1260.0), px(720.0)),
+        })),
+        window_background: gpui::WindowBackgroundAppearance::Opaque,
+        focus: true,
+        is_movable: true,
+        titlebar: Some(gpui::TitlebarOptions {
+            title: Some(file_name.into()),
+            appears_transparent: true,
+            traffic_light_position: Some(gpui::point(px(8.0), px(12.0))),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let entity_holder: Arc<Mutex<Option<Entity<UnifiedWindow>>>> = Arc::new(Mutex::new(None));
+    let holder_clone = entity_holder.clone();
+
+    let window_handle = match cx.open_window(window_options, move |window, cx| {
+        // The new window's own child surface, added to the player as a view
+        let surface = platform::create_view_surface(window, video_player);
+        let unified_entity = cx.new(|cx| {
+            let mut unified_window = UnifiedWindow::new(window, cx);
+            unified_window.view_surface = surface;
+            unified_window
+        });
+        if let Some(surface) = surface {
+            window.on_window_should_close(cx, move |_, cx| {
+                release_duplicate_window(cx, surface);
+                true
+            });
+        }
+        if let Ok(mut holder) = holder_clone.lock() {
+            *holder = Some(unified_entity.clone());
+        }
+        cx.new(|cx| {
+            use gpui::AnyView;
+            gpui_component::Root::new(AnyView::from(unified_entity), window, cx)
+        })
+    }) {
+        Ok(handle) => handle,
+        Err(e) => {
+            notify_error(cx, format!("Failed to open duplicate window: {}", e));
+            return;
+        }
+    };
+    let window_handle: AnyWindowHandle = window_handle.into();
+
+    let Some(unified_entity) = entity_holder.lock().ok().and_then(|holder| holder.clone()) else {
+        return;
+    };
+    let Some(surface) = unified_entity.read(cx).view_surface else {
+        window_handle
+            .update(cx, |_, window, _| {
+                window.remove_window();
+            })
+            .ok();
+        notify_error(cx, "Failed to create a video surface for the new window");
+        return;
+    };
+    cx.update_global::<AppState, _>(|state, _| {
+        state.duplicate_windows.push((window_handle, surface));
+    });
+
+    // Load the subtitle list for the duplicate's own subtitle window
+    let charset = cx.global::<AppState>().subtitle_charset.clone();
+    cx.spawn(async move |cx| {
+        let subtitle_data = cx
+            .background_executor()
+            .spawn(async move {
+                crate::subtitle_window::SubtitleWindow::load_subtitle_data_blocking(
+                    &path_string,
+                    charset.as_deref(),
+                )
+            })
+            .await;
+
+        if let Some(data) = subtitle_data {
+            cx.update(|cx| {
+                let subtitle_entity = unified_entity.read(cx).subtitles.clone();
+                window_handle
+                    .update(cx, |_any_view, window, app_cx| {
+                        subtitle_entity.update(app_cx, |subtitle_window, cx| {
+                            subtitle_window.update_with_loaded_data(window, data, cx);
+                        });
+                    })
+                    .ok();
+            })
+        }
+    })
+    .detach();
+}
+
+/// Stop drawing a duplicate window's view and destroy its surface
+fn release_duplicate_window(cx: &mut App, surface: usize) {
+    cx.update_global::<AppState, _>(|state, _| {
+        state.duplicate_windows.retain(|(_, view)| *view != surface);
+    });
+    if let Ok(player) = cx.global::<AppState>().video_player.lock() {
+        player.remove_view(surface);
+    }
+    platform::destroy_video_surface(surface);
+}
+
+/// Whether a path has one of the video extensions the app can open
+fn is_supported_video(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| {
+            ffmpeg_export::get_video_extensions().contains(&ext.to_lowercase().as_str())
+        })
+}
+
+/// Open a video picked or dropped by the user, replacing the current one
+///
+/// Unsupported files are reported instead of opened.
+pub fn open_video_path(cx: &mut App, path: &std::path::Path) {
+    if !is_supported_video(path) {
+        notify_error(
+            cx,
+            format!(
+                "Invalid file type. Supported formats: {}",
+                ffmpeg_export::get_video_extensions().join(", ")
+            ),
+        );
+        return;
+    }
+
+    let path_string = path.to_string_lossy().to_string();
+    let path_clone = path_string.clone();
+    create_video_windows(cx, path_string, path_clone, None, None);
+}
+
 // Define the open file function that prompts for a file path
 fn open_file(_: &OpenFile, cx: &mut App) {
     let paths = cx.prompt_for_paths(PathPromptOptions {
@@ -774,29 +2379,65 @@ fn open_file(_: &OpenFile, cx: &mut App) {
     cx.spawn(async move |cx| {
         if let Ok(Ok(Some(paths))) = paths.await {
             if let Some(path) = paths.first() {
-                // Check if the file has a valid extension
-                let extension = path.extension().and_then(|e| e.to_str());
-                let supported_extensions = ffmpeg_export::get_video_extensions();
-
-                if let Some(ext) = extension {
-                    let ext_lower = ext.to_lowercase();
-                    if supported_extensions.contains(&ext_lower.as_str()) {
-                        let path_string = path.to_string_lossy().to_string();
-                        let path_clone = path_string.clone();
-
-                        cx.update(|cx| {
-                            create_video_windows(cx, path_string, path_clone, None, None);
-                        });
-                    } else {
-                        // Invalid file type
-                        eprintln!(
-                            "Invalid file type. Supported formats: {}",
-                            supported_extensions.join(", ")
-                        );
-                    }
-                }
+                let _ = cx.update(|cx| open_video_path(cx, path));
             }
         }
     })
     .detach();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timestamp_formats() {
+        assert_eq!(parse_timestamp("90.5", None), Ok(90_500.0));
+        assert_eq!(parse_timestamp("01:30.500", None), Ok(90_500.0));
+        assert_eq!(parse_timestamp("00:01:30.500", None), Ok(90_500.0));
+        assert_eq!(parse_timestamp("90500", None), Ok(90_500.0));
+    }
+
+    #[test]
+    fn test_is_supported_video() {
+        use std::path::Path;
+        assert!(is_supported_video(Path::new("/videos/clip.mkv")));
+        assert!(is_supported_video(Path::new("CLIP.MP4")));
+        assert!(!is_supported_video(Path::new("notes.txt")));
+        assert!(!is_supported_video(Path::new("no_extension")));
+    }
+
+    #[test]
+    fn test_parse_timestamp_frames() {
+        assert_eq!(parse_timestamp("00:00:01:15", Some(30.0)), Ok(1500.0));
+        assert_eq!(parse_timestamp("00:01:00:12", Some(24.0)), Ok(60_500.0));
+        // 30fps is assumed when the frame rate isn't known
+        assert_eq!(parse_timestamp("00:00:01:15", None), Ok(1500.0));
+
+        // Frame indices run from 0 to fps - 1
+        assert!(parse_timestamp("00:00:01:30", Some(30.0)).is_err());
+        assert!(parse_timestamp("00:00:01:24", Some(24.0)).is_err());
+        assert!(parse_timestamp("00:00:01:29", Some(29.97)).is_ok());
+    }
+
+    #[test]
+    fn test_parse_clip_end_relative() {
+        assert_eq!(parse_clip_end("120.75", None), Ok(120_750.0));
+        assert_eq!(parse_clip_end("-5.0", None), Ok(-5000.0));
+        assert_eq!(parse_clip_end(" -00:01:30.500", None), Ok(-90_500.0));
+        assert!(parse_clip_end("-0", None).is_err());
+        assert!(parse_clip_end("--5", None).is_err());
+        // Plain timestamps still reject negatives
+        assert!(parse_timestamp("-5.0", None).is_err());
+
+        assert_eq!(resolve_clip_end(-5000.0, 60_000.0), Ok(55_000.0));
+        assert_eq!(resolve_clip_end(30_000.0, 60_000.0), Ok(30_000.0));
+        // Longer than the video
+        assert!(resolve_clip_end(-60_000.0, 60_000.0).is_err());
+        assert!(resolve_clip_end(-90_000.0, 60_000.0).is_err());
+
+        // clap accepts the leading hyphen as the value
+        let cli = Cli::try_parse_from(["asve", "video.mp4", "--clip-end", "-5.0"]).unwrap();
+        assert_eq!(cli.clip_end.as_deref(), Some("-5.0"));
+    }
+}
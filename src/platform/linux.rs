@@ -0,0 +1,215 @@
+//! Linux-specific platform implementation using a hidden X11 window for OpenGL rendering
+//!
+//! Only X11 (GLX) is supported. Under Wayland the hidden window is created through
+//! XWayland, which is fine because it is never shown - frames are read back from an FBO.
+
+use gpui::Window;
+use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+use std::os::raw::c_int;
+use std::ptr;
+use std::sync::{Arc, Mutex, OnceLock};
+use x11::{glx, xlib};
+
+use crate::video_player::VideoPlayer;
+
+/// X display connection that owns the hidden windows
+///
+/// X destroys a window when the connection that created it closes, so the
+/// connection is opened once and kept for the life of the process.
+static DISPLAY: OnceLock<usize> = OnceLock::new();
+
+/// Open (or reuse) the X display connection for the hidden windows
+fn display() -> Option<*mut xlib::Display> {
+    let display = *DISPLAY.get_or_init(|| unsafe {
+        // The render thread makes the GL context current from another thread
+        xlib::XInitThreads();
+        xlib::XOpenDisplay(ptr::null()) as usize
+    });
+    if display == 0 {
+        None
+    } else {
+        Some(display as *mut xlib::Display)
+    }
+}
+
+/// Create a hidden X11 window for OpenGL context and off-screen video rendering
+///
+/// This function:
+/// 1. Opens an X display connection (XWayland under Wayland)
+/// 2. Creates an unmapped window with a GLX-capable visual
+/// 3. Configures the video player with the window ID
+/// 4. Window is never mapped - used only for OpenGL context and FBO rendering
+///
+/// # Returns
+/// The X window ID as a usize, or None if creation fails
+pub fn create_child_video_surface(
+    window: &mut Window,
+    video_player: Arc<Mutex<VideoPlayer>>,
+) -> Option<usize> {
+    let hidden_window_id = create_hidden_window(window)?;
+
+    // Pass the hidden window to the video player
+    if let Ok(mut player) = video_player.lock() {
+        player.set_window_handle(hidden_window_id);
+        println!("Hidden X11 window set on video player");
+    } else {
+        eprintln!("Failed to lock video player mutex");
+    }
+
+    Some(hidden_window_id)
+}
+
+/// Create a hidden X11 window for another view of an already set up player
+///
+/// The player keeps its one GL context; the window gets its own render
+/// target in it, sized with `VideoPlayer::set_view_render_size`.
+///
+/// # Returns
+/// The X window ID as a usize, or None if creation fails
+pub fn create_view_surface(
+    window: &mut Window,
+    video_player: Arc<Mutex<VideoPlayer>>,
+) -> Option<usize> {
+    let hidden_window_id = create_hidden_window(window)?;
+
+    if let Ok(player) = video_player.lock() {
+        player.add_view(hidden_window_id);
+        println!("Hidden X11 window added as a video player view");
+    } else {
+        eprintln!("Failed to lock video player mutex");
+    }
+
+    Some(hidden_window_id)
+}
+
+/// Create the unmapped X11 window behind a video surface
+fn create_hidden_window(window: &mut Window) -> Option<usize> {
+    match window.window_handle().map(|handle| handle.as_raw()) {
+        Ok(RawWindowHandle::Xlib(_)) | Ok(RawWindowHandle::Xcb(_)) => {}
+        Ok(RawWindowHandle::Wayland(_)) => {
+            println!("Wayland window: creating the OpenGL surface through XWayland");
+        }
+        Ok(other) => eprintln!("Unexpected window handle type: {:?}", other),
+        Err(e) => eprintln!("Failed to get window handle: {}", e),
+    }
+
+    // Get the unified window bounds to calculate video area size
+    let window_bounds = window.bounds();
+
+    // Calculate video area dimensions (76% width, 75% height)
+    let video_width = (f32::from(window_bounds.size.width) * 0.76).max(1.0) as u32;
+    let video_height = (f32::from(window_bounds.size.height) * 0.75).max(1.0) as u32;
+
+    println!(
+        "Creating hidden window with size: {}x{} for OpenGL context",
+        video_width, video_height
+    );
+
+    let Some(display) = display() else {
+        eprintln!("Failed to open X display - is DISPLAY set?");
+        return None;
+    };
+
+    unsafe {
+        let screen = xlib::XDefaultScreen(display);
+        let root = xlib::XRootWindow(display, screen);
+
+        // Pick a visual that GLX can render to
+        let mut visual_attrs: [c_int; 5] = [
+            glx::GLX_RGBA,
+            glx::GLX_DOUBLEBUFFER,
+            glx::GLX_DEPTH_SIZE,
+            24,
+            0, // Null terminator
+        ];
+        let visual_info = glx::glXChooseVisual(display, screen, visual_attrs.as_mut_ptr());
+        if visual_info.is_null() {
+            eprintln!("No GLX visual available");
+            return None;
+        }
+
+        let mut attrs: xlib::XSetWindowAttributes = std::mem::zeroed();
+        attrs.colormap =
+            xlib::XCreateColormap(display, root, (*visual_info).visual, xlib::AllocNone);
+        attrs.border_pixel = 0;
+
+        // Do NOT map the window - keep it hidden
+        let hidden_window = xlib::XCreateWindow(
+            display,
+            root,
+            0,
+            0,
+            video_width,
+            video_height,
+            0,
+            (*visual_info).depth,
+            xlib::InputOutput as u32,
+            (*visual_info).visual,
+            xlib::CWColormap | xlib::CWBorderPixel,
+            &mut attrs,
+        );
+        xlib::XFree(visual_info as *mut _);
+        xlib::XFlush(display);
+
+        if hidden_window == 0 {
+            eprintln!("Failed to create hidden X11 window");
+            return None;
+        }
+
+        println!("Hidden X11 window created: 0x{:x}", hidden_window);
+
+        Some(hidden_window as usize)
+    }
+}
+
+/// Resize the hidden X11 window to match new video dimensions
+///
+/// # Arguments
+/// * `hidden_handle` - The X window ID as a usize
+/// * `width` - New width in pixels
+/// * `height` - New height in pixels
+pub fn resize_child_video_surface(hidden_handle: usize, width: f64, height: f64) {
+    let Some(display) = display() else {
+        return;
+    };
+    if hidden_handle == 0 || width < 1.0 || height < 1.0 {
+        return;
+    }
+
+    unsafe {
+        xlib::XResizeWindow(
+            display,
+            hidden_handle as xlib::Window,
+            width as u32,
+            height as u32,
+        );
+        xlib::XFlush(display);
+    }
+
+    println!(
+        "Resized hidden X11 window to {}x{}",
+        width as i32, height as i32
+    );
+}
+
+/// Destroy a hidden X11 window made by `create_view_surface`
+pub fn destroy_video_surface(hidden_handle: usize) {
+    let Some(display) = display() else {
+        return;
+    };
+    if hidden_handle == 0 {
+        return;
+    }
+
+    unsafe {
+        xlib::XDestroyWindow(display, hidden_handle as xlib::Window);
+        xlib::XFlush(display);
+    }
+
+    println!("Destroyed hidden X11 window 0x{:x}", hidden_handle);
+}
+
+/// Start moving the window, handing the drag to the compositor/window manager
+pub fn start_window_drag(window: &mut Window) {
+    window.start_window_move();
+}
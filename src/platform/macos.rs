@@ -3,6 +3,7 @@
 use cocoa::foundation::{NSPoint, NSRect, NSSize};
 use gpui::Window;
 use objc::runtime::Object;
+use raw_window_handle::{HasWindowHandle, RawWindowHandle};
 use std::sync::{Arc, Mutex};
 
 use crate::video_player::VideoPlayer;
@@ -21,6 +22,45 @@ pub fn create_child_video_surface(
     window: &mut Window,
     video_player: Arc<Mutex<VideoPlayer>>,
 ) -> Option<usize> {
+    let content_view_ptr = create_hidden_view(window)?;
+
+    // Pass the content view pointer to the video player
+    // (OpenGL context will attach to this view)
+    if let Ok(mut player) = video_player.lock() {
+        player.set_window_handle(content_view_ptr);
+        println!("Hidden window content view set on video player");
+    } else {
+        eprintln!("Failed to lock video player mutex");
+    }
+
+    Some(content_view_ptr)
+}
+
+/// Create a hidden NSWindow for another view of an already set up player
+///
+/// The player keeps its one GL context; the window gets its own render
+/// target in it, sized with `VideoPlayer::set_view_render_size`.
+///
+/// # Returns
+/// The NSView pointer (content view of hidden window) as a usize, or None if creation fails
+pub fn create_view_surface(
+    window: &mut Window,
+    video_player: Arc<Mutex<VideoPlayer>>,
+) -> Option<usize> {
+    let content_view_ptr = create_hidden_view(window)?;
+
+    if let Ok(player) = video_player.lock() {
+        player.add_view(content_view_ptr);
+        println!("Hidden window content view added as a video player view");
+    } else {
+        eprintln!("Failed to lock video player mutex");
+    }
+
+    Some(content_view_ptr)
+}
+
+/// Create the hidden NSWindow behind a video surface, returning its content view
+fn create_hidden_view(window: &mut Window) -> Option<usize> {
     // Get the unified window bounds to calculate video area size
     let window_bounds = window.bounds();
 
@@ -80,15 +120,6 @@ pub fn create_child_video_surface(
                     content_view_ptr
                 );
 
-                // Pass the content view pointer to the video player
-                // (OpenGL context will attach to this view)
-                if let Ok(mut player) = video_player.lock() {
-                    player.set_window_handle(content_view_ptr);
-                    println!("Hidden window content view set on video player");
-                } else {
-                    eprintln!("Failed to lock video player mutex");
-                }
-
                 Some(content_view_ptr)
             } else {
                 eprintln!("Failed to get content view from hidden window");
@@ -131,3 +162,60 @@ pub fn resize_child_video_surface(
         }
     }
 }
+
+/// Close the hidden NSWindow behind a content view made by `create_view_surface`
+pub fn destroy_video_surface(content_view_handle: usize) {
+    unsafe {
+        let content_view = content_view_handle as *mut Object;
+        if content_view.is_null() {
+            return;
+        }
+
+        let hidden_window: *mut Object = msg_send![content_view, window];
+        if !hidden_window.is_null() {
+            let _: () = msg_send![hidden_window, close];
+        }
+    }
+}
+
+/// The NSWindow behind a GPUI window
+unsafe fn ns_window(window: &Window) -> Option<*mut Object> {
+    let handle = window.window_handle().ok()?;
+    let RawWindowHandle::AppKit(appkit_handle) = handle.as_raw() else {
+        return None;
+    };
+    let ns_view = appkit_handle.ns_view.as_ptr() as *mut Object;
+    let ns_window: *mut Object = msg_send![ns_view, window];
+    (!ns_window.is_null()).then_some(ns_window)
+}
+
+/// Show or hide the traffic light buttons
+pub fn set_window_buttons_hidden(window: &mut Window, hidden: bool) {
+    unsafe {
+        let Some(ns_window) = ns_window(window) else {
+            return;
+        };
+        // NSWindowCloseButton, NSWindowMiniaturizeButton, NSWindowZoomButton
+        for button_kind in 0usize..3 {
+            let button: *mut Object = msg_send![ns_window, standardWindowButton: button_kind];
+            if !button.is_null() {
+                let _: () = msg_send![button, setHidden: hidden];
+            }
+        }
+    }
+}
+
+/// Start moving the window with the mouse-down event being handled
+pub fn start_window_drag(window: &mut Window) {
+    unsafe {
+        let Some(ns_window) = ns_window(window) else {
+            return;
+        };
+        let app_class = objc::runtime::Class::get("NSApplication").unwrap();
+        let app: *mut Object = msg_send![app_class, sharedApplication];
+        let event: *mut Object = msg_send![app, currentEvent];
+        if !event.is_null() {
+            let _: () = msg_send![ns_window, performWindowDragWithEvent: event];
+        }
+    }
+}
@@ -25,7 +25,8 @@ pub struct WindowInfo {
 /// * `video_player` - The video player instance to configure
 ///
 /// # Returns
-/// The platform-specific handle (NSView pointer on macOS, HWND on Windows) as a usize
+/// The platform-specific handle (NSView pointer on macOS, HWND on Windows, X window ID on
+/// Linux) as a usize
 #[cfg(target_os = "macos")]
 pub fn create_child_video_surface(
     window: &mut Window,
@@ -42,10 +43,66 @@ pub fn create_child_video_surface(
     windows::create_child_video_surface(window, video_player)
 }
 
+#[cfg(target_os = "linux")]
+pub fn create_child_video_surface(
+    window: &mut Window,
+    video_player: Arc<Mutex<VideoPlayer>>,
+) -> Option<usize> {
+    linux::create_child_video_surface(window, video_player)
+}
+
+/// Create another child surface for a second view of the same video player
+///
+/// mpv allows one render context per player, so the new surface doesn't get
+/// a GL context of its own: the player adds a render target for it in the
+/// existing one, keyed by the returned handle (see `VideoPlayer::add_view`).
+///
+/// # Returns
+/// The platform-specific handle, as for `create_child_video_surface`
+#[cfg(target_os = "macos")]
+pub fn create_view_surface(
+    window: &mut Window,
+    video_player: Arc<Mutex<VideoPlayer>>,
+) -> Option<usize> {
+    macos::create_view_surface(window, video_player)
+}
+
+#[cfg(target_os = "windows")]
+pub fn create_view_surface(
+    window: &mut Window,
+    video_player: Arc<Mutex<VideoPlayer>>,
+) -> Option<usize> {
+    windows::create_view_surface(window, video_player)
+}
+
+#[cfg(target_os = "linux")]
+pub fn create_view_surface(
+    window: &mut Window,
+    video_player: Arc<Mutex<VideoPlayer>>,
+) -> Option<usize> {
+    linux::create_view_surface(window, video_player)
+}
+
+/// Destroy a surface made by `create_view_surface`
+#[cfg(target_os = "macos")]
+pub fn destroy_video_surface(child_handle: usize) {
+    macos::destroy_video_surface(child_handle);
+}
+
+#[cfg(target_os = "windows")]
+pub fn destroy_video_surface(child_handle: usize) {
+    windows::destroy_video_surface(child_handle);
+}
+
+#[cfg(target_os = "linux")]
+pub fn destroy_video_surface(child_handle: usize) {
+    linux::destroy_video_surface(child_handle);
+}
+
 /// Resize the child video surface
 ///
 /// # Arguments
-/// * `child_handle` - Platform-specific handle (NSView* or HWND)
+/// * `child_handle` - Platform-specific handle (NSView*, HWND or X window ID)
 /// * `width` - New width in pixels
 /// * `height` - New height in pixels
 /// * `window_height` - Total window height (needed for macOS coordinate system)
@@ -69,6 +126,16 @@ pub fn resize_child_video_surface(
     windows::resize_child_video_surface(child_handle, width, height);
 }
 
+#[cfg(target_os = "linux")]
+pub fn resize_child_video_surface(
+    child_handle: usize,
+    width: f64,
+    height: f64,
+    _window_height: f64,
+) {
+    linux::resize_child_video_surface(child_handle, width, height);
+}
+
 /// Enable child window support by adding WS_CLIPCHILDREN to the parent window
 ///
 /// This prevents GPUI's GPU rendering from painting over child windows.
@@ -90,8 +157,49 @@ pub fn enable_child_window_support(_window: &mut Window) -> Option<()> {
     Some(())
 }
 
+#[cfg(target_os = "linux")]
+pub fn enable_child_window_support(_window: &mut Window) -> Option<()> {
+    // The X11 video window is never mapped, so there is nothing to clip
+    Some(())
+}
+
+/// Show or hide the native window buttons (for the borderless window mode)
+#[cfg(target_os = "macos")]
+pub fn set_window_buttons_hidden(window: &mut Window, hidden: bool) {
+    macos::set_window_buttons_hidden(window, hidden);
+}
+
+#[cfg(target_os = "windows")]
+pub fn set_window_buttons_hidden(_window: &mut Window, _hidden: bool) {
+    // Windows has no native buttons here; CustomTitlebar draws them
+}
+
+#[cfg(target_os = "linux")]
+pub fn set_window_buttons_hidden(_window: &mut Window, _hidden: bool) {
+    // Decorations belong to the window manager; there are no buttons to hide
+}
+
+/// Start moving the window with the mouse, for drag regions in the window content
+#[cfg(target_os = "macos")]
+pub fn start_window_drag(window: &mut Window) {
+    macos::start_window_drag(window);
+}
+
+#[cfg(target_os = "windows")]
+pub fn start_window_drag(window: &mut Window) {
+    windows::start_window_drag(window);
+}
+
+#[cfg(target_os = "linux")]
+pub fn start_window_drag(window: &mut Window) {
+    linux::start_window_drag(window);
+}
+
 #[cfg(target_os = "macos")]
 mod macos;
 
 #[cfg(target_os = "windows")]
 mod windows;
+
+#[cfg(target_os = "linux")]
+mod linux;
@@ -7,6 +7,7 @@ use windows::core::*;
 use windows::Win32::Foundation::*;
 use windows::Win32::Graphics::Gdi::*;
 use windows::Win32::System::LibraryLoader::*;
+use windows::Win32::UI::Input::KeyboardAndMouse::ReleaseCapture;
 use windows::Win32::UI::WindowsAndMessaging::*;
 
 use crate::video_player::VideoPlayer;
@@ -28,6 +29,44 @@ pub fn create_child_video_surface(
     window: &mut Window,
     video_player: Arc<Mutex<VideoPlayer>>,
 ) -> Option<usize> {
+    let hidden_hwnd_ptr = create_hidden_window(window)?;
+
+    // Pass the hidden HWND to the video player
+    if let Ok(mut player) = video_player.lock() {
+        player.set_window_handle(hidden_hwnd_ptr);
+        println!("Hidden HWND set on video player");
+    } else {
+        eprintln!("Failed to lock video player mutex");
+    }
+
+    Some(hidden_hwnd_ptr)
+}
+
+/// Create a hidden HWND for another view of an already set up player
+///
+/// The player keeps its one GL context; the window gets its own render
+/// target in it, sized with `VideoPlayer::set_view_render_size`.
+///
+/// # Returns
+/// The HWND as a usize, or None if creation fails
+pub fn create_view_surface(
+    window: &mut Window,
+    video_player: Arc<Mutex<VideoPlayer>>,
+) -> Option<usize> {
+    let hidden_hwnd_ptr = create_hidden_window(window)?;
+
+    if let Ok(player) = video_player.lock() {
+        player.add_view(hidden_hwnd_ptr);
+        println!("Hidden HWND added as a video player view");
+    } else {
+        eprintln!("Failed to lock video player mutex");
+    }
+
+    Some(hidden_hwnd_ptr)
+}
+
+/// Create the hidden popup window behind a video surface
+fn create_hidden_window(window: &mut Window) -> Option<usize> {
     // Get the unified window bounds to calculate video area size
     let window_bounds = window.bounds();
 
@@ -110,17 +149,7 @@ pub fn create_child_video_surface(
         // Do NOT call ShowWindow - keep window hidden
         // Window is only used for OpenGL context, rendering happens to FBO
 
-        let hidden_hwnd_ptr = hidden_hwnd.0 as isize as usize;
-
-        // Pass the hidden HWND to the video player
-        if let Ok(mut player) = video_player.lock() {
-            player.set_window_handle(hidden_hwnd_ptr);
-            println!("Hidden HWND set on video player");
-        } else {
-            eprintln!("Failed to lock video player mutex");
-        }
-
-        Some(hidden_hwnd_ptr)
+        Some(hidden_hwnd.0 as isize as usize)
     }
 }
 
@@ -155,6 +184,20 @@ pub fn resize_child_video_surface(hidden_handle: usize, width: f64, height: f64)
     }
 }
 
+/// Destroy a hidden HWND made by `create_view_surface`
+pub fn destroy_video_surface(hidden_handle: usize) {
+    unsafe {
+        let hidden_hwnd = HWND(hidden_handle as isize as *mut _);
+        if hidden_hwnd.0.is_null() {
+            return;
+        }
+
+        if let Err(e) = DestroyWindow(hidden_hwnd) {
+            eprintln!("Failed to destroy hidden window: {}", e);
+        }
+    }
+}
+
 /// Window procedure for the hidden video window
 ///
 /// This handles basic window messages for the hidden window.
@@ -193,3 +236,24 @@ pub fn enable_child_window_support(_window: &mut Window) -> Option<()> {
     println!("enable_child_window_support called but not needed for hidden windows");
     Some(())
 }
+
+/// Start moving the window as if its caption had been grabbed
+pub fn start_window_drag(window: &mut Window) {
+    // Get the raw window handle (HWND)
+    if let Ok(handle) = window.window_handle() {
+        if let RawWindowHandle::Win32(win32_handle) = handle.as_raw() {
+            unsafe {
+                let hwnd = HWND(win32_handle.hwnd.get() as _);
+                // Release mouse capture to allow Windows to handle dragging
+                let _ = ReleaseCapture();
+                // Send WM_NCLBUTTONDOWN with HTCAPTION to start window dragging
+                SendMessageW(
+                    hwnd,
+                    WM_NCLBUTTONDOWN,
+                    Some(WPARAM(HTCAPTION as usize)),
+                    Some(LPARAM(0)),
+                );
+            }
+        }
+    }
+}
@@ -1,16 +1,32 @@
 use crate::subtitle_extractor::SubtitleEntry;
-use gpui::{div, prelude::*, Context, Entity, IntoElement, Render, ScrollHandle, Window};
+use crate::theme::OneDarkExt;
+use gpui::{
+    div, prelude::*, px, App, Context, Entity, IntoElement, MouseButton, Render, ScrollHandle,
+    Window,
+};
 use gpui_component::{
     checkbox::Checkbox,
     input::{Input, InputState},
+    ActiveTheme,
 };
 
+/// Which end of a subtitle a timing nudge moves
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SubtitleEdge {
+    Start,
+    End,
+}
+
+/// How far one click of a timing nudge button moves a subtitle edge
+const NUDGE_STEP_MS: i64 = 100;
+
 /// Clip tab for custom subtitle editing
 pub struct SubtitleClipTab {
     custom_subtitle_input: Entity<InputState>,
     controls: Option<Entity<crate::controls_window::ControlsWindow>>,
     subtitle_entries: Vec<SubtitleEntry>, // Reference to subtitle entries
     scroll_handle: ScrollHandle,          // For scrolling the text box
+    timing_scroll_handle: ScrollHandle,   // For scrolling the timing list
     custom_mode_enabled: bool,            // Track custom subtitle mode state
     last_loaded_content: String,          // Track last loaded content to avoid redundant reloads
 }
@@ -62,6 +78,7 @@ impl SubtitleClipTab {
             controls: None,
             subtitle_entries: Vec::new(),
             scroll_handle: ScrollHandle::new(),
+            timing_scroll_handle: ScrollHandle::new(),
             custom_mode_enabled: false, // Start with custom mode disabled
             last_loaded_content: String::new(),
         }
@@ -92,7 +109,10 @@ impl SubtitleClipTab {
                             self.last_loaded_content = srt_content.clone();
                         }
                         Err(e) => {
-                            eprintln!("Failed to load custom subtitle: {}", e);
+                            crate::notify_error(
+                                cx,
+                                format!("Failed to load custom subtitle: {}", e),
+                            );
                         }
                     },
                     Err(e) => {
@@ -105,7 +125,7 @@ impl SubtitleClipTab {
         } else {
             // Custom mode disabled - remove custom subtitles
             let video_player = cx.global::<crate::AppState>().video_player.clone();
-            let selected_track = cx.global::<crate::AppState>().selected_subtitle_track;
+            let selected_track = cx.global::<crate::AppState>().selected_subtitle_track_id();
             let lock_result = video_player.lock();
 
             match lock_result {
@@ -116,7 +136,7 @@ impl SubtitleClipTab {
 
                     // Re-enable the original subtitle track if one was selected
                     if let Some(track_index) = selected_track {
-                        if let Err(e) = player.set_subtitle_track(track_index as i32) {
+                        if let Err(e) = player.set_subtitle_track(track_index) {
                             eprintln!("Failed to restore original subtitle track: {}", e);
                         }
                     }
@@ -144,7 +164,7 @@ impl SubtitleClipTab {
     }
 
     /// Convert the custom subtitle text to proper SRT format with sequence numbers
-    pub fn get_custom_subtitle_srt(&self, cx: &Context<Self>) -> String {
+    pub fn get_custom_subtitle_srt(&self, cx: &App) -> String {
         let content = self.custom_subtitle_input.read(cx).text().to_string();
 
         // Parse the content and add sequence numbers
@@ -205,16 +225,9 @@ impl SubtitleClipTab {
         }
 
         // Format without sequence numbers
-        let mut srt_text = String::new();
-        for (_index, entry) in clip_subtitles {
-            srt_text.push_str(&format!(
-                "{} --> {}\n",
-                entry.format_start_time(),
-                entry.format_end_time()
-            ));
-            srt_text.push_str(&format!("{}\n", entry.text));
-            srt_text.push('\n');
-        }
+        let entries: Vec<SubtitleEntry> =
+            clip_subtitles.into_iter().map(|(_index, entry)| entry).collect();
+        let srt_text = format_clip_text(&entries);
 
         // Update the text box
         self.custom_subtitle_input.update(cx, |input, cx| {
@@ -222,6 +235,32 @@ impl SubtitleClipTab {
         });
     }
 
+    /// Move one edge of a custom subtitle and write the result back to the text box
+    ///
+    /// Only the clip's working copy changes; the source track is left alone.
+    fn nudge(
+        &mut self,
+        index: usize,
+        edge: SubtitleEdge,
+        delta_ms: i64,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let mut entries = crate::subtitle_extractor::parse_srt(&self.get_custom_subtitle_srt(cx));
+
+        match nudge_subtitle(&mut entries, index, edge, delta_ms) {
+            Ok(()) => {
+                let srt_text = format_clip_text(&entries);
+                self.custom_subtitle_input.update(cx, |input, cx| {
+                    input.set_value(srt_text, window, cx);
+                });
+            }
+            Err(e) => {
+                eprintln!("Can't retime subtitle: {}", e);
+            }
+        }
+    }
+
     /// Check if there's a valid clip range and update if needed
     fn check_and_update_clip(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(controls_entity) = &self.controls {
@@ -249,6 +288,70 @@ impl SubtitleClipTab {
     }
 }
 
+/// Format entries as the clip text box shows them (SRT without sequence numbers)
+fn format_clip_text(entries: &[SubtitleEntry]) -> String {
+    let mut srt_text = String::new();
+    for entry in entries {
+        srt_text.push_str(&format!(
+            "{} --> {}\n",
+            entry.format_start_time(),
+            entry.format_end_time()
+        ));
+        srt_text.push_str(&format!("{}\n", entry.text));
+        srt_text.push('\n');
+    }
+    srt_text
+}
+
+/// Move one edge of a subtitle by delta_ms
+///
+/// Rejects moves that would put the start at or after the end, before zero,
+/// or further into the previous/next subtitle.
+fn nudge_subtitle(
+    entries: &mut [SubtitleEntry],
+    index: usize,
+    edge: SubtitleEdge,
+    delta_ms: i64,
+) -> Result<(), String> {
+    let entry = entries.get(index).ok_or("No such subtitle")?;
+    let (start_ms, end_ms) = (entry.start_ms as i64, entry.end_ms as i64);
+    let (new_start_ms, new_end_ms) = match edge {
+        SubtitleEdge::Start => (start_ms + delta_ms, end_ms),
+        SubtitleEdge::End => (start_ms, end_ms + delta_ms),
+    };
+
+    if new_start_ms < 0 {
+        return Err("subtitle can't start before the video".to_string());
+    }
+    if new_start_ms >= new_end_ms {
+        return Err("subtitle must end after it starts".to_string());
+    }
+
+    let previous_end_ms = index
+        .checked_sub(1)
+        .and_then(|i| entries.get(i))
+        .map(|previous| previous.end_ms as i64);
+    if edge == SubtitleEdge::Start
+        && delta_ms < 0
+        && previous_end_ms.is_some_and(|previous_end_ms| new_start_ms < previous_end_ms)
+    {
+        return Err("subtitle would overlap the previous one".to_string());
+    }
+
+    let next_start_ms = entries.get(index + 1).map(|next| next.start_ms as i64);
+    if edge == SubtitleEdge::End
+        && delta_ms > 0
+        && next_start_ms.is_some_and(|next_start_ms| new_end_ms > next_start_ms)
+    {
+        return Err("subtitle would overlap the next one".to_string());
+    }
+
+    let entry = &mut entries[index];
+    entry.start_ms = new_start_ms as u64;
+    entry.end_ms = new_end_ms as u64;
+    Ok(())
+}
+
 impl Render for SubtitleClipTab {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         // keep the clip subtitles aligned with the real ones unless the custom checkbox is checked
@@ -258,6 +361,32 @@ impl Render for SubtitleClipTab {
 
         let custom_mode_enabled = self.custom_mode_enabled;
 
+        // Timing rows for the working copy (only editable in custom mode)
+        let timing_entries = if custom_mode_enabled {
+            crate::subtitle_extractor::parse_srt(&self.get_custom_subtitle_srt(cx))
+        } else {
+            Vec::new()
+        };
+
+        let theme = cx.theme();
+        let border_variant_color = theme.border_variant();
+        let hover_bg = theme.element_hover();
+        let text_color = theme.text();
+        let text_muted_color = theme.text_muted();
+
+        // Small -/+ button that nudges one edge of a subtitle
+        let nudge_button = move |id: (&'static str, usize), label: &'static str| {
+            div()
+                .id(id)
+                .px_1()
+                .rounded_md()
+                .cursor_pointer()
+                .text_xs()
+                .text_color(text_muted_color)
+                .hover(move |style| style.bg(hover_bg))
+                .child(label)
+        };
+
         div()
             .w_full()
             .flex_1()
@@ -292,5 +421,110 @@ impl Render for SubtitleClipTab {
                             .child(Input::new(&self.custom_subtitle_input).h_full()),
                     ),
             )
+            // Timing list: nudge each subtitle's start/end in custom mode
+            .when(!timing_entries.is_empty(), |this| {
+                this.child(
+                    div()
+                        .id("clip-timing-scroll")
+                        .w_full()
+                        .max_h(px(160.0))
+                        .flex()
+                        .flex_col()
+                        .overflow_y_scroll()
+                        .track_scroll(&self.timing_scroll_handle)
+                        .children(timing_entries.iter().enumerate().map(|(idx, entry)| {
+                            let edge_controls = |edge: SubtitleEdge, time: String| {
+                                let (minus_id, plus_id) = match edge {
+                                    SubtitleEdge::Start => ("start-minus", "start-plus"),
+                                    SubtitleEdge::End => ("end-minus", "end-plus"),
+                                };
+                                div()
+                                    .flex()
+                                    .flex_row()
+                                    .items_center()
+                                    .gap_1()
+                                    .child(nudge_button((minus_id, idx), "-").on_click(
+                                        cx.listener(move |this, _, window, cx| {
+                                            this.nudge(idx, edge, -NUDGE_STEP_MS, window, cx);
+                                        }),
+                                    ))
+                                    .child(div().text_xs().text_color(text_color).child(time))
+                                    .child(nudge_button((plus_id, idx), "+").on_click(
+                                        cx.listener(move |this, _, window, cx| {
+                                            this.nudge(idx, edge, NUDGE_STEP_MS, window, cx);
+                                        }),
+                                    ))
+                            };
+
+                            div()
+                                .w_full()
+                                .flex()
+                                .flex_row()
+                                .items_center()
+                                .justify_between()
+                                .px_2()
+                                .py_1()
+                                .border_b_1()
+                                .border_color(border_variant_color)
+                                .child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(text_muted_color)
+                                        .child(format!("#{}", idx + 1)),
+                                )
+                                .child(edge_controls(
+                                    SubtitleEdge::Start,
+                                    entry.format_start_time(),
+                                ))
+                                .child(edge_controls(SubtitleEdge::End, entry.format_end_time()))
+                        })),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(start_ms: u64, end_ms: u64) -> SubtitleEntry {
+        SubtitleEntry {
+            start_ms,
+            end_ms,
+            text: "text".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_nudge_subtitle_moves_edges() {
+        let mut entries = vec![entry(1000, 2000), entry(3000, 4000)];
+        nudge_subtitle(&mut entries, 0, SubtitleEdge::End, 500).unwrap();
+        nudge_subtitle(&mut entries, 1, SubtitleEdge::Start, -300).unwrap();
+        assert_eq!((entries[0].start_ms, entries[0].end_ms), (1000, 2500));
+        assert_eq!((entries[1].start_ms, entries[1].end_ms), (2700, 4000));
+    }
+
+    #[test]
+    fn test_nudge_subtitle_keeps_order_and_gaps() {
+        let mut entries = vec![entry(0, 2000), entry(2000, 2100)];
+        assert!(nudge_subtitle(&mut entries, 0, SubtitleEdge::Start, -100).is_err());
+        assert!(nudge_subtitle(&mut entries, 0, SubtitleEdge::End, 100).is_err());
+        assert!(nudge_subtitle(&mut entries, 1, SubtitleEdge::Start, -100).is_err());
+        assert!(nudge_subtitle(&mut entries, 1, SubtitleEdge::Start, 100).is_err());
+        assert!(nudge_subtitle(&mut entries, 2, SubtitleEdge::Start, 100).is_err());
+
+        // Shrinking is always fine
+        nudge_subtitle(&mut entries, 0, SubtitleEdge::End, -100).unwrap();
+        assert_eq!(entries[0].end_ms, 1900);
+    }
+
+    #[test]
+    fn test_format_clip_text_round_trips() {
+        let entries = vec![entry(1000, 2000), entry(3000, 4500)];
+        let text = format_clip_text(&entries);
+        assert_eq!(
+            text,
+            "00:00:01,000 --> 00:00:02,000\ntext\n\n00:00:03,000 --> 00:00:04,500\ntext\n\n"
+        );
     }
 }
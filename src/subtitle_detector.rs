@@ -1,23 +1,26 @@
 //! Subtitle stream detection using ffprobe
 //!
 //! This module provides functionality to detect and enumerate subtitle streams
-//! in video files that can be exported as SRT (SubRip) format.
+//! in video files. Text streams can be exported as SRT (SubRip) format; image-based
+//! (PGS/VobSub/DVB) streams are listed too so they can be shown and burned in.
 
-use serde::Deserialize;
-use std::collections::HashMap;
-use std::process::Command;
+use crate::ffmpeg_export::ProbeResult;
 
 /// Information about a subtitle stream found in a video file
 #[derive(Debug, Clone)]
 pub struct SubtitleStream {
     /// Human-readable display title for UI
     pub display_title: String,
+    /// Subtitle file on disk this entry was loaded from (None for streams of the video)
+    pub external_path: Option<String>,
+    /// Whether the stream is text that can be parsed into the list (false for bitmaps)
+    pub is_text: bool,
 }
 
-/// Detect all text-based subtitle streams in a video file
+/// Detect the text-based and image-based subtitle streams in a video file
 ///
-/// Uses ffprobe to enumerate subtitle streams and filters to only include
-/// streams that can be exported as SRT format.
+/// Uses ffprobe to enumerate subtitle streams. Text streams can be exported as
+/// SRT format; image-based ones are returned with `is_text: false`.
 ///
 /// # Arguments
 ///
@@ -25,81 +28,35 @@ pub struct SubtitleStream {
 ///
 /// # Returns
 ///
-/// A vector of `SubtitleStream` structs, one for each text-based subtitle stream found.
+/// A vector of `SubtitleStream` structs, one for each supported subtitle stream found.
 /// Returns an empty vector if no suitable streams are found or if ffprobe fails.
 pub fn detect_subtitle_streams(file_path: &str) -> Vec<SubtitleStream> {
-    // Use ffprobe to get all subtitle streams in JSON format
-    let output = Command::new("ffprobe")
-        .args([
-            "-v",
-            "error",
-            "-select_streams",
-            "s", // Select subtitle streams
-            "-show_entries",
-            "stream=index,codec_name:stream_tags=language", // Get index, codec, and language
-            "-of",
-            "json", // Output as JSON for easier parsing
-            file_path,
-        ])
-        .output();
-
-    let output = match output {
-        Ok(output) => output,
+    // Shares the cached ffprobe run with the rest of the loading (see `probe_file`)
+    match crate::ffmpeg_export::probe_file(file_path) {
+        Ok(probe) => subtitle_streams(&probe),
         Err(e) => {
-            eprintln!("Failed to execute ffprobe: {}", e);
-            return Vec::new();
+            eprintln!("{}", e);
+            Vec::new()
         }
-    };
-
-    if !output.status.success() {
-        eprintln!(
-            "ffprobe failed: {}",
-            String::from_utf8_lossy(&output.stderr)
-        );
-        return Vec::new();
     }
-
-    let json_output = String::from_utf8_lossy(&output.stdout);
-
-    // Parse JSON output
-    parse_ffprobe_json(&json_output)
-}
-
-/// ffprobe JSON output structures
-#[derive(Debug, Deserialize)]
-struct FfprobeOutput {
-    streams: Vec<FfprobeStream>,
 }
 
-#[derive(Debug, Deserialize)]
-struct FfprobeStream {
-    codec_name: String,
-    #[serde(default)]
-    tags: HashMap<String, String>,
-}
-
-/// Parse ffprobe JSON output to extract subtitle stream information
-fn parse_ffprobe_json(json: &str) -> Vec<SubtitleStream> {
+/// Extract the subtitle stream information from a file's probe result
+fn subtitle_streams(probe: &ProbeResult) -> Vec<SubtitleStream> {
     let mut streams = Vec::new();
 
-    // Parse JSON using serde_json
-    let ffprobe_output: FfprobeOutput = match serde_json::from_str(json) {
-        Ok(output) => output,
-        Err(e) => {
-            eprintln!("Failed to parse ffprobe JSON: {}", e);
-            return streams;
-        }
-    };
-
     let mut subtitle_index = 0;
-    for stream in ffprobe_output.streams {
+    for stream in probe.streams.iter().filter(|stream| stream.codec_type == "subtitle") {
+        let codec_name = stream.codec_name.as_deref().unwrap_or_default();
+
         // Check if this codec is text-based
         let is_text = matches!(
-            stream.codec_name.as_str(),
+            codec_name,
             "subrip" | "ass" | "ssa" | "webvtt" | "mov_text" | "srt" | "text"
         );
+        let is_image = matches!(codec_name, "hdmv_pgs_subtitle" | "dvd_subtitle" | "dvb_subtitle");
 
-        if is_text {
+        if is_text || is_image {
             // Extract language from tags
             let language = stream
                 .tags
@@ -108,9 +65,13 @@ fn parse_ffprobe_json(json: &str) -> Vec<SubtitleStream> {
                 .cloned();
 
             // Create display title
-            let display_title = format_display_title(subtitle_index, &stream.codec_name, &language);
+            let display_title = format_display_title(subtitle_index, codec_name, &language);
 
-            streams.push(SubtitleStream { display_title });
+            streams.push(SubtitleStream {
+                display_title,
+                external_path: None,
+                is_text,
+            });
 
             subtitle_index += 1;
         }
@@ -126,6 +87,9 @@ fn format_display_title(index: usize, codec_name: &str, language: &Option<String
         "ass" | "ssa" => "ASS",
         "webvtt" => "WebVTT",
         "mov_text" => "MOV Text",
+        "hdmv_pgs_subtitle" => "PGS, image",
+        "dvd_subtitle" => "VobSub, image",
+        "dvb_subtitle" => "DVB, image",
         _ => codec_name,
     };
 
@@ -144,6 +108,10 @@ fn format_display_title(index: usize, codec_name: &str, language: &Option<String
 mod tests {
     use super::*;
 
+    fn parse_ffprobe_json(json: &str) -> Vec<SubtitleStream> {
+        subtitle_streams(&serde_json::from_str(json).unwrap())
+    }
+
     #[test]
     fn test_parse_empty_json() {
         let json = r#"{"streams": []}"#;
@@ -153,22 +121,42 @@ mod tests {
 
     #[test]
     fn test_parse_single_subtitle() {
-        let json = r#"{"streams": [{"codec_name": "subrip", "tags": {"language": "eng"}}]}"#;
+        let json = r#"{"streams": [{"codec_type": "subtitle", "codec_name": "subrip", "tags": {"language": "eng"}}]}"#;
         let streams = parse_ffprobe_json(json);
         assert_eq!(streams.len(), 1);
         assert!(streams[0].display_title.contains("ENG"));
     }
 
     #[test]
-    fn test_filter_non_text_subtitles() {
-        let json = r#"{"streams": [{"codec_name": "subrip", "tags": {}}, {"codec_name": "dvd_subtitle", "tags": {}}]}"#;
+    fn test_image_subtitles_flagged() {
+        let json = r#"{"streams": [{"codec_type": "subtitle", "codec_name": "subrip", "tags": {}}, {"codec_type": "subtitle", "codec_name": "dvd_subtitle", "tags": {}}, {"codec_type": "subtitle", "codec_name": "hdmv_pgs_subtitle", "tags": {"language": "fre"}}]}"#;
+        let streams = parse_ffprobe_json(json);
+        assert_eq!(streams.len(), 3);
+        assert!(streams[0].is_text);
+        assert!(!streams[1].is_text);
+        assert_eq!(streams[1].display_title, "Subtitle 2 (VobSub, image)");
+        assert!(!streams[2].is_text);
+        assert_eq!(streams[2].display_title, "Subtitle 3 - FRE (PGS, image)");
+    }
+
+    #[test]
+    fn test_skip_unknown_subtitle_codecs() {
+        let json = r#"{"streams": [{"codec_type": "subtitle", "codec_name": "subrip", "tags": {}}, {"codec_type": "subtitle", "codec_name": "eia_608", "tags": {}}]}"#;
+        let streams = parse_ffprobe_json(json);
+        assert_eq!(streams.len(), 1);
+    }
+
+    #[test]
+    fn test_skip_other_stream_types() {
+        let json = r#"{"streams": [{"codec_type": "video", "codec_name": "h264"}, {"codec_type": "audio", "codec_name": "aac"}, {"codec_type": "subtitle", "codec_name": "ass"}]}"#;
         let streams = parse_ffprobe_json(json);
         assert_eq!(streams.len(), 1);
+        assert_eq!(streams[0].display_title, "Subtitle 1 (ASS)");
     }
 
     #[test]
     fn test_multiple_subtitle_streams() {
-        let json = r#"{"streams": [{"codec_name": "subrip", "tags": {"language": "eng"}}, {"codec_name": "subrip", "tags": {"language": "spa"}}]}"#;
+        let json = r#"{"streams": [{"codec_type": "subtitle", "codec_name": "subrip", "tags": {"language": "eng"}}, {"codec_type": "subtitle", "codec_name": "subrip", "tags": {"language": "spa"}}]}"#;
         let streams = parse_ffprobe_json(json);
         assert_eq!(streams.len(), 2);
     }
@@ -3,7 +3,27 @@
 //! This module provides functionality to extract subtitle streams from video files
 //! and parse them into a structured format for display.
 
-use std::process::Command;
+use encoding_rs::Encoding;
+use std::path::{Path, PathBuf};
+
+/// Character encodings offered for subtitle files, as (label, menu name)
+///
+/// The labels are understood by both encoding_rs and ffmpeg's `-sub_charenc`.
+pub const SUBTITLE_CHARSETS: &[(&str, &str)] = &[
+    ("UTF-8", "Unicode (UTF-8)"),
+    ("windows-1252", "Western (Windows-1252)"),
+    ("windows-1250", "Central European (Windows-1250)"),
+    ("windows-1251", "Cyrillic (Windows-1251)"),
+    ("KOI8-R", "Cyrillic (KOI8-R)"),
+    ("windows-1253", "Greek (Windows-1253)"),
+    ("windows-1254", "Turkish (Windows-1254)"),
+    ("windows-1255", "Hebrew (Windows-1255)"),
+    ("windows-1256", "Arabic (Windows-1256)"),
+    ("Shift_JIS", "Japanese (Shift-JIS)"),
+    ("EUC-KR", "Korean (EUC-KR)"),
+    ("GBK", "Chinese Simplified (GBK)"),
+    ("Big5", "Chinese Traditional (Big5)"),
+];
 
 /// A single subtitle entry with timing and text
 #[derive(Debug, Clone)]
@@ -26,6 +46,15 @@ impl SubtitleEntry {
     pub fn format_end_time(&self) -> String {
         format_timecode(self.end_ms)
     }
+
+    /// Format the time range as shown in the list ("HH:MM:SS,mmm --> HH:MM:SS,mmm")
+    pub fn format_range(&self) -> String {
+        format!(
+            "{} --> {}",
+            self.format_start_time(),
+            self.format_end_time()
+        )
+    }
 }
 
 /// Extract a subtitle stream from a video file and convert to SRT format
@@ -37,13 +66,24 @@ impl SubtitleEntry {
 ///
 /// * `file_path` - Path to the video file
 /// * `stream_index` - Index of the subtitle stream to extract (e.g., 0, 1, 2...)
+/// * `charset` - Encoding of the stream's text (`-sub_charenc`), or None to let ffmpeg decide
 ///
 /// # Returns
 ///
 /// The SRT content as a string, or an error message if extraction fails.
-pub fn extract_subtitle_stream(file_path: &str, stream_index: usize) -> Result<String, String> {
+pub fn extract_subtitle_stream(
+    file_path: &str,
+    stream_index: usize,
+    charset: Option<&str>,
+) -> Result<String, String> {
+    let mut command = crate::ffmpeg_export::ffmpeg_command();
+    if let Some(charset) = charset {
+        // Input option, so it has to come before -i
+        command.args(["-sub_charenc", charset]);
+    }
+
     // Use ffmpeg to extract the subtitle stream and convert to SRT
-    let output = Command::new("ffmpeg")
+    let output = command
         .args([
             "-i",
             file_path,
@@ -54,7 +94,7 @@ pub fn extract_subtitle_stream(file_path: &str, stream_index: usize) -> Result<S
             "-",   // Output to stdout
         ])
         .output()
-        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+        .map_err(|e| crate::ffmpeg_export::Tool::Ffmpeg.spawn_error(&e))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -70,8 +110,41 @@ pub fn extract_subtitle_stream(file_path: &str, stream_index: usize) -> Result<S
     Ok(srt_content)
 }
 
+/// Decode the bytes of a subtitle file to text
+///
+/// `charset` is an encoding label such as "windows-1251". With None the
+/// encoding is detected: a byte order mark wins, then valid UTF-8, then a
+/// statistical guess.
+pub fn decode_subtitle_bytes(bytes: &[u8], charset: Option<&str>) -> Result<String, String> {
+    if let Some(label) = charset {
+        let encoding = Encoding::for_label(label.as_bytes())
+            .ok_or_else(|| format!("Unknown character encoding: {}", label))?;
+        let (text, _, _) = encoding.decode(bytes);
+        return Ok(text.into_owned());
+    }
+
+    if let Some((encoding, bom_length)) = Encoding::for_bom(bytes) {
+        let (text, _) = encoding.decode_without_bom_handling(&bytes[bom_length..]);
+        return Ok(text.into_owned());
+    }
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return Ok(text.to_string());
+    }
+
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(bytes, true);
+    let encoding = detector.guess(None, true);
+    println!("Detected subtitle encoding: {}", encoding.name());
+    let (text, _) = encoding.decode_without_bom_handling(bytes);
+    Ok(text.into_owned())
+}
+
 /// Parse SRT content into a vector of SubtitleEntry structs
 ///
+/// Entries are found by their timecode lines, so missing or malformed index
+/// numbers and blank lines inside a block don't break parsing. Entries without
+/// any text are skipped.
+///
 /// # Arguments
 ///
 /// * `srt_content` - Raw SRT content as a string
@@ -81,63 +154,83 @@ pub fn extract_subtitle_stream(file_path: &str, stream_index: usize) -> Result<S
 /// A vector of `SubtitleEntry` structs representing each subtitle.
 pub fn parse_srt(srt_content: &str) -> Vec<SubtitleEntry> {
     let mut entries = Vec::new();
-    let mut lines = srt_content.lines().peekable();
+    let lines: Vec<&str> = srt_content.trim_start_matches('\u{feff}').lines().collect();
+    let mut i = 0;
 
-    while let Some(line) = lines.next() {
-        let line = line.trim();
+    while i < lines.len() {
+        // Each entry starts at its timecode line; anything before it is skipped
+        let Some((start_ms, end_ms)) = parse_timecode_line(lines[i]) else {
+            i += 1;
+            continue;
+        };
+        i += 1;
 
-        // Skip empty lines
-        if line.is_empty() {
-            continue;
-        }
-
-        // Try to parse as entry index
-        if line.parse::<usize>().is_ok() {
-            // Next line should be timecode
-            if let Some(timecode_line) = lines.next() {
-                if let Some((start_ms, end_ms)) = parse_timecode_line(timecode_line) {
-                    // Collect text lines until we hit an empty line or EOF
-                    let mut text_lines = Vec::new();
-                    while let Some(&next_line) = lines.peek() {
-                        let next_line = next_line.trim();
-                        if next_line.is_empty() {
-                            lines.next(); // Consume the empty line
-                            break;
-                        }
-                        text_lines.push(lines.next().unwrap().to_string());
-                    }
-
-                    let text = text_lines.join("\n");
-
-                    entries.push(SubtitleEntry {
-                        start_ms,
-                        end_ms,
-                        text,
-                    });
+        // Collect text lines until the next timecode line or EOF
+        let mut text_lines = Vec::new();
+        while i < lines.len() && parse_timecode_line(lines[i]).is_none() {
+            text_lines.push(lines[i].trim());
+            i += 1;
+        }
+
+        // If another entry follows, its index line (a number, or a short
+        // malformed one, after a blank line) was collected at the end of this
+        // entry's text
+        if i < lines.len() {
+            if let Some(pos) = text_lines.iter().rposition(|line| !line.is_empty()) {
+                if is_index_like(text_lines[pos]) && pos > 0 && text_lines[pos - 1].is_empty() {
+                    text_lines.truncate(pos);
                 }
             }
         }
+
+        // Blank lines inside a block are dropped so the text stays valid SRT
+        let text = text_lines
+            .into_iter()
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if text.is_empty() {
+            continue;
+        }
+
+        entries.push(SubtitleEntry {
+            start_ms,
+            end_ms,
+            text,
+        });
     }
 
     entries
 }
 
+/// Whether a line looks like a SRT index rather than subtitle text
+///
+/// Besides plain numbers, short single words (e.g., "abc" or "12a") are
+/// treated as malformed indices; anything with spaces is dialogue.
+fn is_index_like(line: &str) -> bool {
+    line.parse::<usize>().is_ok()
+        || (line.chars().count() <= 8 && !line.contains(char::is_whitespace))
+}
+
 /// Parse a SRT timecode line (e.g., "00:00:10,500 --> 00:00:13,000")
 ///
+/// Spacing around the arrow may vary, and position hints after the end time
+/// (e.g., "X1:100 X2:200 Y1:50 Y2:80") are ignored.
+///
 /// Returns (start_ms, end_ms) if parsing succeeds, None otherwise.
 fn parse_timecode_line(line: &str) -> Option<(u64, u64)> {
-    let parts: Vec<&str> = line.split("-->").collect();
-    if parts.len() != 2 {
-        return None;
-    }
+    let (start, end) = line.split_once("-->")?;
 
-    let start_ms = parse_timecode(parts[0].trim())?;
-    let end_ms = parse_timecode(parts[1].trim())?;
+    let start_ms = parse_timecode(start.trim())?;
+    let end_ms = parse_timecode(end.split_whitespace().next()?)?;
 
     Some((start_ms, end_ms))
 }
 
 /// Parse a single SRT timecode (e.g., "00:00:10,500") into milliseconds
+///
+/// A period is accepted in place of the comma, as some files use "00:00:10.500".
 fn parse_timecode(timecode: &str) -> Option<u64> {
     // Format: HH:MM:SS,mmm
     let parts: Vec<&str> = timecode.split(':').collect();
@@ -148,8 +241,8 @@ fn parse_timecode(timecode: &str) -> Option<u64> {
     let hours: u64 = parts[0].parse().ok()?;
     let minutes: u64 = parts[1].parse().ok()?;
 
-    // Seconds and milliseconds are separated by comma
-    let sec_parts: Vec<&str> = parts[2].split(',').collect();
+    // Seconds and milliseconds are separated by comma (or period)
+    let sec_parts: Vec<&str> = parts[2].split([',', '.']).collect();
     if sec_parts.len() != 2 {
         return None;
     }
@@ -160,6 +253,457 @@ fn parse_timecode(timecode: &str) -> Option<u64> {
     Some(hours * 3600000 + minutes * 60000 + seconds * 1000 + milliseconds)
 }
 
+/// Parse WebVTT content into a vector of SubtitleEntry structs
+///
+/// The `WEBVTT` header and `NOTE`, `STYLE` and `REGION` blocks are skipped.
+/// Cue identifiers and cue settings (e.g., "align:start position:10%") are
+/// dropped, as are inline tags like `<v Speaker>` and `<c.yellow>`.
+///
+/// # Arguments
+///
+/// * `vtt_content` - Raw WebVTT content as a string
+///
+/// # Returns
+///
+/// A vector of `SubtitleEntry` structs representing each cue.
+pub fn parse_vtt(vtt_content: &str) -> Vec<SubtitleEntry> {
+    let mut entries = Vec::new();
+    let mut lines = vtt_content
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .peekable();
+
+    while lines.peek().is_some() {
+        // Blocks are separated by blank lines
+        let block: Vec<&str> = lines
+            .by_ref()
+            .skip_while(|line| line.trim().is_empty())
+            .take_while(|line| !line.trim().is_empty())
+            .collect();
+        let Some(first) = block.first() else {
+            break;
+        };
+        if ["WEBVTT", "NOTE", "STYLE", "REGION"]
+            .iter()
+            .any(|keyword| first.starts_with(keyword))
+        {
+            continue;
+        }
+
+        // The timing line comes first, or second after a cue identifier
+        let Some((timing_index, (start_ms, end_ms))) = block
+            .iter()
+            .take(2)
+            .enumerate()
+            .find_map(|(i, line)| parse_vtt_timing_line(line).map(|times| (i, times)))
+        else {
+            continue;
+        };
+
+        let text = block[timing_index + 1..]
+            .iter()
+            .map(|line| clean_vtt_text(line.trim()))
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        if text.is_empty() {
+            continue;
+        }
+
+        entries.push(SubtitleEntry {
+            start_ms,
+            end_ms,
+            text,
+        });
+    }
+
+    entries
+}
+
+/// Parse a WebVTT timing line (e.g., "00:10.500 --> 00:13.000 align:start")
+///
+/// Hours are optional in WebVTT timestamps; cue settings after the end time are ignored.
+fn parse_vtt_timing_line(line: &str) -> Option<(u64, u64)> {
+    let (start, end) = line.split_once("-->")?;
+
+    let start_ms = parse_vtt_timestamp(start.trim())?;
+    let end_ms = parse_vtt_timestamp(end.split_whitespace().next()?)?;
+
+    Some((start_ms, end_ms))
+}
+
+/// Parse a WebVTT timestamp ("HH:MM:SS.mmm" or "MM:SS.mmm") into milliseconds
+fn parse_vtt_timestamp(timestamp: &str) -> Option<u64> {
+    match timestamp.matches(':').count() {
+        1 => parse_timecode(&format!("00:{}", timestamp)),
+        2 => parse_timecode(timestamp),
+        _ => None,
+    }
+}
+
+/// Strip WebVTT inline tags and decode the character references cue text may use
+fn clean_vtt_text(text: &str) -> String {
+    let mut result = String::new();
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => result.push(c),
+            _ => {}
+        }
+    }
+    result
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+/// Check whether subtitle text is WebVTT (starts with the `WEBVTT` header)
+fn is_webvtt(content: &str) -> bool {
+    content
+        .trim_start_matches('\u{feff}')
+        .trim_start()
+        .starts_with("WEBVTT")
+}
+
+/// Subtitle file extensions checked for sidecars next to a video, in priority order
+const SIDECAR_EXTENSIONS: [&str; 5] = ["srt", "vtt", "ass", "ssa", "sub"];
+
+/// Find a subtitle file next to a video with the same file stem
+///
+/// VobSub `.sub` files (which come with a matching `.idx`) are skipped since
+/// they can't be displayed as text.
+pub fn find_sidecar_subtitle(video_path: &str) -> Option<PathBuf> {
+    let video_path = Path::new(video_path);
+
+    SIDECAR_EXTENSIONS
+        .iter()
+        .map(|ext| video_path.with_extension(ext))
+        .filter(|path| path.is_file())
+        .find(|path| !is_vobsub(path))
+}
+
+/// Check whether a `.sub`/`.idx` path is a VobSub bitmap subtitle
+fn is_vobsub(path: &Path) -> bool {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    match extension.as_str() {
+        "idx" => true,
+        // VobSub .sub files are MPEG program streams and have an .idx next to them
+        "sub" => {
+            path.with_extension("idx").is_file()
+                || std::fs::read(path)
+                    .map(|bytes| bytes.starts_with(&[0x00, 0x00, 0x01, 0xBA]))
+                    .unwrap_or(false)
+        }
+        _ => false,
+    }
+}
+
+/// Load an external subtitle file, choosing the parser by file extension
+///
+/// Supports `.srt`, `.vtt`, `.ass`/`.ssa`, and text-based MicroDVD `.sub` files.
+/// WebVTT content is recognized by its header too, since some are saved as `.srt`.
+/// MicroDVD timing is frame-based, so `fps` is used to convert it to time.
+/// VobSub (`.idx`/`.sub`) files are rejected since they are bitmap images.
+///
+/// # Arguments
+///
+/// * `path` - Path to the subtitle file
+/// * `fps` - Frame rate of the video the subtitles belong to
+/// * `charset` - Encoding of the file, or None to auto-detect it
+///
+/// # Returns
+///
+/// The parsed subtitle entries, or an error message if the file can't be used.
+pub fn load_external_subtitle(
+    path: &str,
+    fps: f32,
+    charset: Option<&str>,
+) -> Result<Vec<SubtitleEntry>, String> {
+    let file_path = Path::new(path);
+    let extension = file_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    if is_vobsub(file_path) {
+        return Err(format!(
+            "{} is a VobSub (bitmap) subtitle and can't be displayed as text",
+            path
+        ));
+    }
+
+    let bytes = std::fs::read(file_path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let content = decode_subtitle_bytes(&bytes, charset)?;
+
+    let entries = match extension.as_str() {
+        "vtt" => parse_vtt(&content),
+        "srt" if is_webvtt(&content) => parse_vtt(&content),
+        "srt" => parse_srt(&content),
+        "ass" | "ssa" => parse_ass(&content),
+        "sub" => {
+            let is_microdvd = content
+                .trim_start_matches('\u{feff}')
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .is_some_and(|line| line.starts_with('{'));
+            if !is_microdvd {
+                return Err(format!("Unsupported .sub subtitle format: {}", path));
+            }
+            parse_microdvd(&content, fps)
+        }
+        _ => return Err(format!("Unsupported subtitle file type: {}", path)),
+    };
+
+    if entries.is_empty() {
+        return Err(format!("No subtitles found in {}", path));
+    }
+
+    Ok(entries)
+}
+
+/// Parse ASS/SSA content into a vector of SubtitleEntry structs
+///
+/// Reads `Dialogue:` lines from the `[Events]` section using the field order
+/// from its `Format:` line. Override tags like `{\i1}` are stripped and `\N`
+/// line breaks become newlines. Entries are sorted by start time since ASS
+/// events don't have to be in order.
+pub fn parse_ass(ass_content: &str) -> Vec<SubtitleEntry> {
+    let mut entries = Vec::new();
+    let mut in_events = false;
+    // Default v4+ field order, replaced by the section's Format line
+    let mut fields: Vec<String> = [
+        "layer", "start", "end", "style", "name", "marginl", "marginr", "marginv", "effect",
+        "text",
+    ]
+    .iter()
+    .map(|f| f.to_string())
+    .collect();
+
+    for line in ass_content.trim_start_matches('\u{feff}').lines() {
+        let line = line.trim();
+
+        if line.starts_with('[') {
+            in_events = line.eq_ignore_ascii_case("[events]");
+            continue;
+        }
+        if !in_events {
+            continue;
+        }
+
+        if let Some(format) = line.strip_prefix("Format:") {
+            fields = format.split(',').map(|f| f.trim().to_lowercase()).collect();
+            continue;
+        }
+
+        let Some(dialogue) = line.strip_prefix("Dialogue:") else {
+            continue;
+        };
+
+        // Text is always the last field and may itself contain commas
+        let values: Vec<&str> = dialogue.splitn(fields.len(), ',').collect();
+        if values.len() != fields.len() {
+            continue;
+        }
+        let field = |name: &str| {
+            fields
+                .iter()
+                .position(|f| f == name)
+                .map(|i| values[i].trim())
+        };
+
+        let (Some(start_ms), Some(end_ms)) = (
+            field("start").and_then(parse_ass_timecode),
+            field("end").and_then(parse_ass_timecode),
+        ) else {
+            continue;
+        };
+
+        let text = clean_ass_text(field("text").unwrap_or(""));
+        if text.is_empty() {
+            continue;
+        }
+
+        entries.push(SubtitleEntry {
+            start_ms,
+            end_ms,
+            text,
+        });
+    }
+
+    entries.sort_by_key(|entry| entry.start_ms);
+    entries
+}
+
+/// Parse an ASS timecode (e.g., "0:01:23.45", centisecond precision) into milliseconds
+fn parse_ass_timecode(timecode: &str) -> Option<u64> {
+    let parts: Vec<&str> = timecode.split(':').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+
+    let hours: u64 = parts[0].parse().ok()?;
+    let minutes: u64 = parts[1].parse().ok()?;
+    let (secs_str, frac_str) = parts[2].split_once('.')?;
+    let seconds: u64 = secs_str.parse().ok()?;
+    // Pad/truncate the fraction to 3 digits so "45" means 450ms
+    let frac_ms: u64 = format!("{:0<3}", frac_str).get(..3)?.parse().ok()?;
+
+    Some(hours * 3600000 + minutes * 60000 + seconds * 1000 + frac_ms)
+}
+
+/// Remove `{...}` formatting tags (ASS overrides, MicroDVD styles) from text
+fn strip_brace_tags(text: &str) -> String {
+    let mut cleaned = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '{' => in_tag = true,
+            '}' if in_tag => in_tag = false,
+            _ if !in_tag => cleaned.push(c),
+            _ => {}
+        }
+    }
+    cleaned
+}
+
+/// Strip ASS override tags and convert ASS escapes to plain text
+fn clean_ass_text(text: &str) -> String {
+    strip_brace_tags(text)
+        .replace("\\N", "\n")
+        .replace("\\n", "\n")
+        .replace("\\h", " ")
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parse MicroDVD content (e.g., "{100}{200}Text|Second line") into entries
+///
+/// Timing is in frames and converted using `fps`. A leading `{1}{1}23.976`
+/// line overrides `fps` with the frame rate stored in the file. Formatting
+/// tags like `{y:i}` are stripped and `|` becomes a line break.
+pub fn parse_microdvd(microdvd_content: &str, fps: f32) -> Vec<SubtitleEntry> {
+    let mut entries = Vec::new();
+    let mut fps = fps;
+
+    for line in microdvd_content.trim_start_matches('\u{feff}').lines() {
+        let Some((start_frame, end_frame, text)) = parse_microdvd_line(line.trim()) else {
+            continue;
+        };
+
+        // Frame rate header line
+        if start_frame == 1 && end_frame == Some(1) {
+            if let Ok(file_fps) = text.trim().parse::<f32>() {
+                if file_fps > 0.0 {
+                    fps = file_fps;
+                    continue;
+                }
+            }
+        }
+
+        if fps <= 0.0 {
+            continue;
+        }
+
+        let text = strip_brace_tags(text)
+            .split('|')
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        if text.is_empty() {
+            continue;
+        }
+
+        let frame_to_ms = |frame: u64| (frame as f64 * 1000.0 / fps as f64).round() as u64;
+        let start_ms = frame_to_ms(start_frame);
+        // An empty end frame means "until the next subtitle"; show it for 2 seconds
+        let end_ms = end_frame.map(frame_to_ms).unwrap_or(start_ms + 2000);
+
+        entries.push(SubtitleEntry {
+            start_ms,
+            end_ms,
+            text,
+        });
+    }
+
+    entries
+}
+
+/// Split a MicroDVD line into (start frame, optional end frame, text)
+fn parse_microdvd_line(line: &str) -> Option<(u64, Option<u64>, &str)> {
+    let rest = line.strip_prefix('{')?;
+    let (start, rest) = rest.split_once('}')?;
+    let rest = rest.strip_prefix('{')?;
+    let (end, text) = rest.split_once('}')?;
+
+    let start_frame = start.trim().parse().ok()?;
+    let end_frame = if end.trim().is_empty() {
+        None
+    } else {
+        Some(end.trim().parse().ok()?)
+    };
+
+    Some((start_frame, end_frame, text))
+}
+
+/// Build a plain-text transcript from subtitle entries
+///
+/// Each entry becomes one line. Lines repeated from the previous entry are
+/// dropped, since roll-up live captions carry the last line or two over into
+/// the next entry. With timestamps, lines start with `[HH:MM:SS]`.
+pub fn to_transcript(entries: &[SubtitleEntry], include_timestamps: bool) -> String {
+    let mut transcript = String::new();
+    let mut previous_lines: Vec<&str> = Vec::new();
+
+    for entry in entries {
+        let lines: Vec<&str> = entry
+            .text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        let new_lines: Vec<&str> = lines
+            .iter()
+            .copied()
+            .filter(|line| !previous_lines.contains(line))
+            .collect();
+        previous_lines = lines;
+
+        if new_lines.is_empty() {
+            continue;
+        }
+
+        if include_timestamps {
+            let secs = entry.start_ms / 1000;
+            transcript.push_str(&format!(
+                "[{:02}:{:02}:{:02}] ",
+                secs / 3600,
+                (secs % 3600) / 60,
+                secs % 60
+            ));
+        }
+        transcript.push_str(&new_lines.join(" "));
+        transcript.push('\n');
+    }
+
+    transcript
+}
+
 /// Format milliseconds as SRT timecode (HH:MM:SS,mmm)
 fn format_timecode(ms: u64) -> String {
     let hours = ms / 3600000;
@@ -177,6 +721,40 @@ fn format_timecode(ms: u64) -> String {
 mod tests {
     use super::*;
 
+    fn entry(start_ms: u64, text: &str) -> SubtitleEntry {
+        SubtitleEntry {
+            start_ms,
+            end_ms: start_ms + 1000,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_to_transcript_joins_lines() {
+        let entries = vec![entry(0, "Hello\nthere."), entry(2000, "General Kenobi!")];
+        assert_eq!(to_transcript(&entries, false), "Hello there.\nGeneral Kenobi!\n");
+    }
+
+    #[test]
+    fn test_to_transcript_dedupes_rolling_captions() {
+        let entries = vec![
+            entry(0, "we're going"),
+            entry(1000, "we're going\nto the store"),
+            entry(2000, "to the store\nto buy milk"),
+            entry(3000, "to buy milk"),
+        ];
+        assert_eq!(
+            to_transcript(&entries, false),
+            "we're going\nto the store\nto buy milk\n"
+        );
+    }
+
+    #[test]
+    fn test_to_transcript_with_timestamps() {
+        let entries = vec![entry(3_723_500, "Line one")];
+        assert_eq!(to_transcript(&entries, true), "[01:02:03] Line one\n");
+    }
+
     #[test]
     fn test_parse_timecode() {
         assert_eq!(parse_timecode("00:00:10,500"), Some(10500));
@@ -220,4 +798,247 @@ with multiple lines
         assert_eq!(entries[1].end_ms, 18500);
         assert_eq!(entries[1].text, "Second subtitle line\nwith multiple lines");
     }
+
+    #[test]
+    fn test_parse_srt_empty() {
+        assert!(parse_srt("").is_empty());
+        assert!(parse_srt("\n\n   \n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_srt_blank_lines_within_block() {
+        let srt = "1\n00:00:01,000 --> 00:00:02,000\nFirst line\n\nSecond line\n\n2\n00:00:03,000 --> 00:00:04,000\nNext\n";
+
+        let entries = parse_srt(srt);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].text, "First line\nSecond line");
+        assert_eq!(entries[1].text, "Next");
+    }
+
+    #[test]
+    fn test_parse_srt_blank_line_before_unindexed_block() {
+        // Without an index on the next block, the line after the blank is still text
+        let srt = "00:00:01,000 --> 00:00:02,000\nLine A\n\nLine B\n00:00:03,000 --> 00:00:04,000\nNext\n";
+
+        let entries = parse_srt(srt);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].text, "Line A\nLine B");
+        assert_eq!(entries[1].text, "Next");
+    }
+
+    #[test]
+    fn test_parse_srt_malformed_indices() {
+        // Missing index, non-numeric index, and no blank line between blocks
+        let srt = "00:00:01,000 --> 00:00:02,000\nNo index\n\nabc\n00:00:03,000 --> 00:00:04,000\nBad index\n00:00:05,000 --> 00:00:06,000\nNo gap\n";
+
+        let entries = parse_srt(srt);
+        assert_eq!(entries.len(), 3);
+        // "abc" sits where the next block's index goes, so it isn't text
+        assert_eq!(entries[0].text, "No index");
+        assert_eq!(entries[1].start_ms, 3000);
+        assert_eq!(entries[1].text, "Bad index");
+        assert_eq!(entries[2].start_ms, 5000);
+        assert_eq!(entries[2].text, "No gap");
+    }
+
+    #[test]
+    fn test_parse_srt_overlapping_timestamps() {
+        let srt = "1\n00:00:01,000 --> 00:00:05,000\nSpeaker one\n\n2\n00:00:02,000 --> 00:00:03,000\nSpeaker two\n";
+
+        let entries = parse_srt(srt);
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[0].start_ms, entries[0].end_ms), (1000, 5000));
+        assert_eq!((entries[1].start_ms, entries[1].end_ms), (2000, 3000));
+    }
+
+    #[test]
+    fn test_parse_srt_skips_entries_without_text() {
+        let srt = "1\n00:00:01,000 --> 00:00:02,000\n\n2\n00:00:03,000 --> 00:00:04,000\nHas text\n";
+
+        let entries = parse_srt(srt);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].start_ms, 3000);
+        assert_eq!(entries[0].text, "Has text");
+    }
+
+    #[test]
+    fn test_parse_srt_crlf_and_bom() {
+        let srt = "\u{feff}1\r\n00:00:01,000 --> 00:00:02,000\r\nWindows line\r\n\r\n";
+
+        let entries = parse_srt(srt);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].text, "Windows line");
+    }
+
+    #[test]
+    fn test_parse_timecode_line_spacing_and_positions() {
+        assert_eq!(
+            parse_timecode_line("00:00:10,500-->00:00:13,000"),
+            Some((10500, 13000))
+        );
+        assert_eq!(
+            parse_timecode_line("  00:00:10,500   -->  00:00:13,000  "),
+            Some((10500, 13000))
+        );
+        assert_eq!(
+            parse_timecode_line("00:00:10,500 --> 00:00:13,000 X1:100 X2:200 Y1:50 Y2:80"),
+            Some((10500, 13000))
+        );
+        assert_eq!(
+            parse_timecode_line("00:00:10.500 --> 00:00:13.000"),
+            Some((10500, 13000))
+        );
+        assert_eq!(parse_timecode_line("00:00:10,500 00:00:13,000"), None);
+    }
+
+    #[test]
+    fn test_format_times_round_trip() {
+        for ms in [0, 999, 10500, 90250, 5025678, 359999999] {
+            let entry = SubtitleEntry {
+                start_ms: ms,
+                end_ms: ms + 1500,
+                text: String::new(),
+            };
+            assert_eq!(parse_timecode(&entry.format_start_time()), Some(ms));
+            assert_eq!(parse_timecode(&entry.format_end_time()), Some(ms + 1500));
+        }
+    }
+
+    #[test]
+    fn test_format_range() {
+        let entry = SubtitleEntry {
+            start_ms: 10500,
+            end_ms: 3723004,
+            text: "First line\nSecond line".to_string(),
+        };
+        assert_eq!(entry.format_range(), "00:00:10,500 --> 01:02:03,004");
+        assert_eq!(
+            parse_timecode_line(&entry.format_range()),
+            Some((10500, 3723004))
+        );
+    }
+
+    #[test]
+    fn test_parse_vtt() {
+        let vtt = "WEBVTT - Episode 1\n\n\
+                   1\n00:00:01.000 --> 00:00:02.500\nFirst cue\n\n\
+                   00:03.000 --> 00:04.000 align:start position:10% line:0\n\
+                   <v Narrator>Top left</v>\n<c.yellow>second line</c>\n\n\
+                   intro\n01:00:00.250 --> 01:00:01.000 size:50%\nFish &amp; chips\n";
+
+        let entries = parse_vtt(vtt);
+        assert_eq!(entries.len(), 3);
+        assert_eq!((entries[0].start_ms, entries[0].end_ms), (1000, 2500));
+        assert_eq!(entries[0].text, "First cue");
+        assert_eq!((entries[1].start_ms, entries[1].end_ms), (3000, 4000));
+        assert_eq!(entries[1].text, "Top left\nsecond line");
+        assert_eq!(entries[2].start_ms, 3600250);
+        assert_eq!(entries[2].text, "Fish & chips");
+    }
+
+    #[test]
+    fn test_parse_vtt_skips_note_and_style_blocks() {
+        let vtt = "\u{feff}WEBVTT\r\n\r\n\
+                   NOTE This file was converted\r\n\
+                   00:00:00.000 --> 00:00:09.000 is not a cue\r\n\r\n\
+                   STYLE\r\n::cue { color: white }\r\n\r\n\
+                   00:00:05.000 --> 00:00:06.000\r\nOnly cue\r\n\r\n\
+                   NOTE\r\ntrailing comment\r\n";
+
+        let entries = parse_vtt(vtt);
+        assert_eq!(entries.len(), 1);
+        assert_eq!((entries[0].start_ms, entries[0].end_ms), (5000, 6000));
+        assert_eq!(entries[0].text, "Only cue");
+        assert!(is_webvtt(vtt));
+        assert!(!is_webvtt("1\n00:00:05,000 --> 00:00:06,000\nSRT\n"));
+    }
+
+    #[test]
+    fn test_parse_ass() {
+        let ass = r#"[Script Info]
+Title: Test
+
+[V4+ Styles]
+Format: Name, Fontname, Fontsize
+Style: Default,Arial,20
+
+[Events]
+Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text
+Dialogue: 0,0:00:05.00,0:00:07.50,Default,,0,0,0,,Second, with a comma
+Dialogue: 0,0:00:01.25,0:00:03.00,Default,,0,0,0,,{\i1}First{\i0} line\NSecond line
+Comment: 0,0:00:02.00,0:00:03.00,Default,,0,0,0,,Not shown
+Dialogue: 0,0:00:08.00,0:00:09.00,Default,,0,0,0,,{\pos(10,10)}
+"#;
+
+        let entries = parse_ass(ass);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].start_ms, 1250);
+        assert_eq!(entries[0].end_ms, 3000);
+        assert_eq!(entries[0].text, "First line\nSecond line");
+        assert_eq!(entries[1].start_ms, 5000);
+        assert_eq!(entries[1].end_ms, 7500);
+        assert_eq!(entries[1].text, "Second, with a comma");
+    }
+
+    #[test]
+    fn test_parse_ass_custom_format_order() {
+        let ass = "[Events]\nFormat: Start, End, Text\nDialogue: 0:00:01.00,0:00:02.00,Hello\n";
+
+        let entries = parse_ass(ass);
+        assert_eq!(entries.len(), 1);
+        assert_eq!((entries[0].start_ms, entries[0].end_ms), (1000, 2000));
+        assert_eq!(entries[0].text, "Hello");
+    }
+
+    #[test]
+    fn test_parse_microdvd() {
+        let sub = "{0}{50}First line|Second line\n{100}{150}{y:i}Italic\n{200}{}Open ended\n";
+
+        let entries = parse_microdvd(sub, 25.0);
+        assert_eq!(entries.len(), 3);
+        assert_eq!((entries[0].start_ms, entries[0].end_ms), (0, 2000));
+        assert_eq!(entries[0].text, "First line\nSecond line");
+        assert_eq!((entries[1].start_ms, entries[1].end_ms), (4000, 6000));
+        assert_eq!(entries[1].text, "Italic");
+        assert_eq!((entries[2].start_ms, entries[2].end_ms), (8000, 10000));
+    }
+
+    #[test]
+    fn test_parse_microdvd_fps_header() {
+        let sub = "{1}{1}10\n{10}{20}Uses file fps\n";
+
+        let entries = parse_microdvd(sub, 25.0);
+        assert_eq!(entries.len(), 1);
+        assert_eq!((entries[0].start_ms, entries[0].end_ms), (1000, 2000));
+    }
+
+    #[test]
+    fn test_load_external_subtitle_rejects_vobsub() {
+        let dir = std::env::temp_dir().join(format!("asve_vobsub_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let sub_path = dir.join("movie.sub");
+        std::fs::write(&sub_path, [0x00, 0x00, 0x01, 0xBA, 0x44]).unwrap();
+        std::fs::write(dir.join("movie.idx"), "# VobSub index file, v7\n").unwrap();
+
+        let result = load_external_subtitle(sub_path.to_str().unwrap(), 25.0, None);
+        assert!(result.unwrap_err().contains("bitmap"));
+        assert_eq!(find_sidecar_subtitle(dir.join("movie.mkv").to_str().unwrap()), None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_decode_subtitle_bytes() {
+        // "Привет" in Windows-1251 and "字幕" in Shift-JIS
+        let cyrillic = [0xCF, 0xF0, 0xE8, 0xE2, 0xE5, 0xF2];
+        assert_eq!(decode_subtitle_bytes(&cyrillic, Some("windows-1251")).unwrap(), "Привет");
+        let japanese = [0x8E, 0x9A, 0x96, 0x8B];
+        assert_eq!(decode_subtitle_bytes(&japanese, Some("Shift_JIS")).unwrap(), "字幕");
+
+        // Auto-detect keeps UTF-8 as is and strips a BOM
+        assert_eq!(decode_subtitle_bytes("Привет".as_bytes(), None).unwrap(), "Привет");
+        assert_eq!(decode_subtitle_bytes(b"\xEF\xBB\xBFHello", None).unwrap(), "Hello");
+
+        assert!(decode_subtitle_bytes(b"Hello", Some("not-an-encoding")).is_err());
+    }
 }
@@ -1,20 +1,24 @@
 use crate::theme::OneDarkExt;
 use gpui::{
-    div, prelude::*, px, size, Context, Entity, IntoElement, MouseButton, Pixels, Render,
-    ScrollStrategy, SharedString, Size, TextRun, Window,
+    div, prelude::*, px, rems, size, App, ClipboardItem, Context, Entity, IntoElement, MouseButton,
+    Pixels, Render, Rems, ScrollStrategy, ScrollWheelEvent, SharedString, Size, TextRun, Window,
 };
 use gpui_component::ActiveTheme;
 use gpui_component::{v_virtual_list, VirtualListScrollHandle};
+use std::path::Path;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 use gpui_component::{
     checkbox::Checkbox,
     input::{Input, InputState},
     menu::{ContextMenuExt, PopupMenuItem},
     select::{Select, SelectEvent, SelectItem, SelectState},
-    IndexPath,
+    tooltip::Tooltip,
+    IndexPath, Sizable,
 };
 
+use crate::bookmarks_tab::BookmarksTab;
 use crate::subtitle_clip_tab::SubtitleClipTab;
 use crate::subtitle_detector::SubtitleStream;
 use crate::subtitle_extractor::SubtitleEntry;
@@ -24,8 +28,9 @@ use crate::AppState;
 /// Active tab in the subtitle window
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum SubtitleTab {
-    Video, // Default view with sync, dropdown, search
-    Clip,  // Custom subtitle editor for clips
+    Video,     // Default view with sync, dropdown, search
+    Clip,      // Custom subtitle editor for clips
+    Bookmarks, // Saved bookmarks for the current file
 }
 
 // Implement SelectItem for SubtitleStream
@@ -36,6 +41,18 @@ impl SelectItem for SubtitleStream {
         self.display_title.clone().into()
     }
 
+    // Image-based tracks are greyed out: they play and burn in, but have no text to list
+    fn render(&self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let is_text = self.is_text;
+        div()
+            .id(SharedString::from(self.display_title.clone()))
+            .child(self.display_title.clone())
+            .when(!is_text, |this| {
+                this.text_color(cx.theme().text_muted())
+                    .tooltip(|window, cx| Tooltip::new(IMAGE_SUBTITLE_NOTE).build(window, cx))
+            })
+    }
+
     fn value(&self) -> &Self::Value {
         self
     }
@@ -44,6 +61,7 @@ impl SelectItem for SubtitleStream {
 /// Subtitle window with stream selection and SRT display
 pub struct SubtitleWindow {
     select_state: Entity<SelectState<Vec<SubtitleStream>>>,
+    subtitle_streams: Vec<SubtitleStream>, // Streams listed in the select, for cycling tracks
     sync_enabled: bool, // Whether subtitles are synced to video
     search_input: Entity<InputState>,
     pub subtitle_entries: Vec<SubtitleEntry>,
@@ -56,11 +74,32 @@ pub struct SubtitleWindow {
     last_scrolled_to_video: Option<usize>,  // Last video position we scrolled to
     last_submitted_search_term: Option<String>, // Last search term submitted via Enter (to distinguish NEW vs SAME searches)
     active_tab: SubtitleTab,                    // Currently active tab
+    clip_tab_pinned: bool, // Keep the Clip tab available while custom subtitles are being edited
     clip_tab: Entity<SubtitleClipTab>,          // Clip tab component
+    bookmarks_tab: Entity<BookmarksTab>,        // Bookmarks tab component
     controls: Option<Entity<crate::controls_window::ControlsWindow>>, // Reference to controls window to check clip state
     right_clicked_item: Option<usize>, // Index of the right-clicked subtitle item
+    timing_mode: bool, // Clicking a subtitle picks it for "Sync here" instead of seeking
+    timing_target: Option<usize>, // Subtitle whose start is aligned to the playhead by "Sync here"
+    search_within_clip: bool, // Only match subtitles inside the current clip range
+    search_case_sensitive: bool, // "Aa": match the search term's case exactly
+    search_regex: bool,       // ".*": treat the search term as a regular expression
+    scroll_locked: bool, // Don't follow the playhead in the list (reading ahead)
+    manual_scroll_at: Option<Instant>, // Last time the user scrolled the list themselves
+    offset_input: Entity<InputState>,  // Subtitle delay field (ms)
+    shown_offset_ms: i64,              // Offset last written to the delay field
+    last_render_time: Instant,         // For rate limiting renders to the refresh rate cap
 }
 
+/// Step of the subtitle delay +/- buttons
+const OFFSET_STEP_MS: i64 = 100;
+
+/// Title prefix of the stream entry for a sidecar subtitle file
+const SIDECAR_TITLE_PREFIX: &str = "External: ";
+
+/// Shown for image-based (PGS/VobSub) tracks, which have no text to list
+const IMAGE_SUBTITLE_NOTE: &str = "Image-based subtitles can be burned in but not searched.";
+
 // Data structure to hold loaded subtitle information
 #[derive(Clone)]
 pub struct SubtitleData {
@@ -71,20 +110,42 @@ pub struct SubtitleData {
 impl SubtitleWindow {
     /// Load subtitle data on a background thread (safe to call from non-UI thread)
     /// Returns the streams and parsed entries for the first stream
-    pub fn load_subtitle_data_blocking(file_path: &str) -> Option<SubtitleData> {
+    ///
+    /// `charset` is the subtitle encoding preference (None = auto-detect).
+    pub fn load_subtitle_data_blocking(
+        file_path: &str,
+        charset: Option<&str>,
+    ) -> Option<SubtitleData> {
         // Detect subtitle streams (blocking ffprobe call)
-        let streams = crate::subtitle_detector::detect_subtitle_streams(file_path);
-
-        if streams.is_empty() {
-            println!("No text-based subtitle streams found");
-            return None;
+        let mut streams = crate::subtitle_detector::detect_subtitle_streams(file_path);
+
+        if !streams.iter().any(|stream| stream.is_text) {
+            // Fall back to a subtitle file sitting next to the video, listed after
+            // any image-based streams so those can still be picked and burned in
+            if let Some((sidecar, entries)) = Self::load_sidecar_subtitle(file_path, charset) {
+                if streams.is_empty() {
+                    return Some(SubtitleData {
+                        streams: vec![sidecar],
+                        first_stream_entries: entries,
+                    });
+                }
+                streams.push(sidecar);
+            } else {
+                println!("No text-based subtitle streams found");
+                if streams.is_empty() {
+                    return None;
+                }
+            }
         }
 
         println!("Found {} subtitle stream(s)", streams.len());
 
-        // Extract and parse the first stream (blocking ffmpeg call)
-        let first_stream_entries =
-            match crate::subtitle_extractor::extract_subtitle_stream(file_path, 0) {
+        // Extract and parse the first stream (blocking ffmpeg call); image-based
+        // streams have no text to list
+        let first_stream_entries = if !streams[0].is_text {
+            Vec::new()
+        } else {
+            match crate::subtitle_extractor::extract_subtitle_stream(file_path, 0, charset) {
                 Ok(srt_content) => {
                     let entries = crate::subtitle_extractor::parse_srt(&srt_content);
                     println!("Loaded {} subtitle entries", entries.len());
@@ -94,7 +155,8 @@ impl SubtitleWindow {
                     eprintln!("Failed to extract subtitle stream: {}", e);
                     Vec::new()
                 }
-            };
+            }
+        };
 
         Some(SubtitleData {
             streams,
@@ -102,6 +164,37 @@ impl SubtitleWindow {
         })
     }
 
+    /// Load a sidecar subtitle file (.srt/.vtt/.ass/.ssa/.sub) next to the video, if any
+    fn load_sidecar_subtitle(
+        file_path: &str,
+        charset: Option<&str>,
+    ) -> Option<(SubtitleStream, Vec<SubtitleEntry>)> {
+        let sidecar = crate::subtitle_extractor::find_sidecar_subtitle(file_path)?;
+        let sidecar_path = sidecar.to_string_lossy().to_string();
+        // MicroDVD timing is frame-based, so it needs the video's frame rate
+        let fps = crate::ffmpeg_export::get_video_fps(file_path).unwrap_or(30.0);
+
+        match crate::subtitle_extractor::load_external_subtitle(&sidecar_path, fps, charset) {
+            Ok(entries) => {
+                println!("Loaded {} entries from sidecar {}", entries.len(), sidecar_path);
+                let file_name = sidecar
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or(sidecar_path.clone());
+                let stream = SubtitleStream {
+                    display_title: format!("{}{}", SIDECAR_TITLE_PREFIX, file_name),
+                    external_path: Some(sidecar_path),
+                    is_text: true,
+                };
+                Some((stream, entries))
+            }
+            Err(e) => {
+                eprintln!("Failed to load sidecar subtitles: {}", e);
+                None
+            }
+        }
+    }
+
     /// Update the subtitle window with pre-loaded data (must be called on UI thread)
     pub fn update_with_loaded_data(
         &mut self,
@@ -127,27 +220,7 @@ impl SubtitleWindow {
                 if let SelectEvent::Confirm(Some(_selected_stream)) = event {
                     // Get the selected index from the SelectState
                     if let Some(index_path) = this.select_state.read(cx).selected_index(cx) {
-                        let index = index_path.row;
-                        this.load_subtitle_stream(index, cx);
-
-                        // Update AppState with the selected subtitle track
-                        cx.update_global::<AppState, _>(|state, _| {
-                            state.selected_subtitle_track = Some(index + 1);
-                        });
-
-                        // If subtitle display is enabled in controls, update the video player
-                        let (display_subtitles, video_player) = {
-                            let app_state = cx.global::<AppState>();
-                            (app_state.display_subtitles, app_state.video_player.clone())
-                        };
-
-                        if display_subtitles {
-                            if let Ok(player) = video_player.lock() {
-                                if let Err(e) = player.set_subtitle_track((index + 1) as i32) {
-                                    eprintln!("Failed to set subtitle track: {}", e);
-                                }
-                            }
-                        }
+                        this.select_subtitle_stream(index_path.row, cx);
                     }
                 }
             },
@@ -155,6 +228,7 @@ impl SubtitleWindow {
         .detach();
 
         self.select_state = new_select_state;
+        self.subtitle_streams = data.streams.clone();
 
         // Set the subtitle entries
         self.subtitle_entries = data.first_stream_entries.clone();
@@ -164,6 +238,10 @@ impl SubtitleWindow {
             clip_tab.set_subtitle_entries(data.first_stream_entries);
         });
 
+        if !self.subtitle_streams.is_empty() {
+            self.set_selected_track(0, cx);
+        }
+
         cx.notify();
     }
 
@@ -179,27 +257,7 @@ impl SubtitleWindow {
                 if let SelectEvent::Confirm(Some(_selected_stream)) = event {
                     // Get the selected index from the SelectState
                     if let Some(index_path) = this.select_state.read(cx).selected_index(cx) {
-                        let index = index_path.row;
-                        this.load_subtitle_stream(index, cx);
-
-                        // Update AppState with the selected subtitle track
-                        cx.update_global::<AppState, _>(|state, _| {
-                            state.selected_subtitle_track = Some(index + 1);
-                        });
-
-                        // If subtitle display is enabled in controls, update the video player
-                        let app_state = cx.global::<AppState>();
-                        if app_state.display_subtitles {
-                            let video_player = app_state.video_player.clone();
-
-                            if let Ok(player) = video_player.lock() {
-                                if let Err(e) = player.set_subtitle_track((index + 1) as i32) {
-                                    eprintln!("Failed to set subtitle track: {}", e);
-                                }
-                            } else {
-                                eprintln!("Failed to lock video player for subtitle track change");
-                            };
-                        }
+                        this.select_subtitle_stream(index_path.row, cx);
                     }
                 }
             },
@@ -209,11 +267,18 @@ impl SubtitleWindow {
         // Create search input
         let search_input = cx.new(|cx| InputState::new(window, cx));
 
+        // Create subtitle delay input
+        let offset_input = cx.new(|cx| InputState::new(window, cx).placeholder("0 ms"));
+
         // Create clip tab component
         let clip_tab = cx.new(|cx| SubtitleClipTab::new(window, cx));
 
+        // Create bookmarks tab component
+        let bookmarks_tab = cx.new(|cx| BookmarksTab::new(window, cx));
+
         Self {
             select_state,
+            subtitle_streams: Vec::new(),
             sync_enabled: true, // Default to synced to video
             search_input,
             subtitle_entries: Vec::new(),
@@ -226,9 +291,21 @@ impl SubtitleWindow {
             last_scrolled_to_video: None,
             last_submitted_search_term: None,
             active_tab: SubtitleTab::Video, // Default to Video tab
+            clip_tab_pinned: false,
             clip_tab,
+            bookmarks_tab,
             controls: None, // Will be set by UnifiedWindow after creation
             right_clicked_item: None,
+            timing_mode: false,
+            search_within_clip: false,
+            search_case_sensitive: false,
+            search_regex: false,
+            scroll_locked: false,
+            manual_scroll_at: None,
+            offset_input,
+            shown_offset_ms: 0,
+            timing_target: None,
+            last_render_time: Instant::now(),
         }
     }
 
@@ -266,6 +343,105 @@ impl SubtitleWindow {
         });
     }
 
+    /// Switch to a subtitle stream: load its entries, remember it as the
+    /// selected track, and show it in the player if subtitles are displayed
+    fn select_subtitle_stream(&mut self, index: usize, cx: &mut Context<Self>) {
+        self.load_subtitle_stream(index, cx);
+        self.set_selected_track(index, cx);
+    }
+
+    /// Remember a stream as the selected track (its list position for exports,
+    /// its mpv track id for playback) and show it if subtitles are displayed
+    fn set_selected_track(&mut self, index: usize, cx: &mut Context<Self>) {
+        let video_player = cx.global::<AppState>().video_player.clone();
+        let track_id = match video_player.lock() {
+            Ok(player) => self.mpv_subtitle_track_id(index, &player),
+            Err(_) => None,
+        };
+
+        // Update AppState with the selected subtitle track
+        cx.update_global::<AppState, _>(|state, _| {
+            state.selected_subtitle_track = Some(index + 1);
+            state.selected_subtitle_sid = track_id;
+        });
+
+        // If subtitle display is enabled in controls, update the video player
+        let app_state = cx.global::<AppState>();
+        if app_state.display_subtitles {
+            if let Some(track) = app_state.selected_subtitle_track_id() {
+                if let Ok(player) = video_player.lock() {
+                    if let Err(e) = player.set_subtitle_track(track) {
+                        eprintln!("Failed to set subtitle track: {}", e);
+                    }
+                } else {
+                    eprintln!("Failed to lock video player for subtitle track change");
+                };
+            }
+        }
+    }
+
+    /// mpv's track id for a stream in the list. mpv numbers subtitle files it
+    /// auto-loaded or was given after the video's own tracks, so list positions
+    /// only line up for embedded streams; a file mpv doesn't have yet is added.
+    fn mpv_subtitle_track_id(
+        &self,
+        index: usize,
+        player: &crate::video_player::VideoPlayer,
+    ) -> Option<i64> {
+        let stream = self.subtitle_streams.get(index)?;
+        let embedded_index = self.subtitle_streams[..index]
+            .iter()
+            .filter(|stream| stream.external_path.is_none())
+            .count();
+        let path = stream.external_path.as_deref();
+        player.subtitle_track_id(embedded_index, path).or_else(|| {
+            let path = path?;
+            if let Err(e) = player.add_subtitle_file(path) {
+                eprintln!("Failed to add subtitle file to the player: {}", e);
+                return None;
+            }
+            player.subtitle_track_id(embedded_index, Some(path))
+        })
+    }
+
+    /// Advance to the next subtitle stream, wrapping around at the end
+    pub fn cycle_subtitle_track(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let stream_count = self.subtitle_streams.len();
+        if stream_count == 0 {
+            return;
+        }
+
+        let current = self
+            .select_state
+            .read(cx)
+            .selected_index(cx)
+            .map(|index_path| index_path.row)
+            .unwrap_or(0);
+        let next = (current + 1) % stream_count;
+
+        self.select_state.update(cx, |state, cx| {
+            state.set_selected_index(Some(IndexPath::new(next)), window, cx);
+        });
+        self.select_subtitle_stream(next, cx);
+
+        // Image-based tracks are greyed out in the list; say why nothing shows there
+        let stream = &self.subtitle_streams[next];
+        let note = if stream.is_text {
+            ""
+        } else {
+            " (image-based, not searchable)"
+        };
+        let message = format!(
+            "Subtitle track {}/{}: {}{}",
+            next + 1,
+            stream_count,
+            stream.display_title,
+            note
+        );
+        crate::notify_info(cx, message);
+        cx.notify();
+    }
+
     /// Load subtitle streams for the current video file
     pub fn load_subtitle_streams(
         &mut self,
@@ -283,6 +459,8 @@ impl SubtitleWindow {
 
         println!("Found {} subtitle stream(s)", streams.len());
 
+        self.subtitle_streams = streams.clone();
+
         // Update select state with streams
         self.select_state.update(cx, |state, cx| {
             state.set_items(streams.clone(), window, cx);
@@ -310,7 +488,8 @@ impl SubtitleWindow {
 
                 // Find the current subtitle index if synced to video
                 if app_state.synced_to_video {
-                    self.current_subtitle_index = self.find_subtitle_at_time(self.current_position);
+                    self.current_subtitle_index = self
+                        .find_subtitle_at_time(self.current_position, app_state.subtitle_offset_ms);
                 } else {
                     self.current_subtitle_index = None;
                 }
@@ -319,12 +498,70 @@ impl SubtitleWindow {
     }
 
     /// Find the subtitle entry that corresponds to the given time (in seconds)
-    fn find_subtitle_at_time(&self, time_secs: f32) -> Option<usize> {
+    ///
+    /// Entry times are shifted by the subtitle offset so the highlight follows
+    /// what's shown on screen.
+    fn find_subtitle_at_time(&self, time_secs: f32, offset_ms: i64) -> Option<usize> {
         let time_ms = (time_secs * 1000.0) as u64;
 
-        self.subtitle_entries
-            .iter()
-            .position(|entry| entry.start_ms <= time_ms && time_ms <= entry.end_ms)
+        self.subtitle_entries.iter().position(|entry| {
+            offset_time_ms(entry.start_ms, offset_ms) <= time_ms
+                && time_ms <= offset_time_ms(entry.end_ms, offset_ms)
+        })
+    }
+
+    /// Shift all subtitle timing by offset_ms (replaces any previous offset)
+    pub fn apply_time_offset(&mut self, offset_ms: i64, cx: &mut Context<Self>) {
+        cx.update_global::<AppState, _>(|state, _| {
+            state.subtitle_offset_ms = offset_ms;
+        });
+
+        let video_player = cx.global::<AppState>().video_player.clone();
+        if let Ok(player) = video_player.lock() {
+            if let Err(e) = player.set_subtitle_delay(offset_ms as f64 / 1000.0) {
+                eprintln!("Failed to set subtitle delay: {}", e);
+            }
+        };
+
+        println!("Subtitle offset set to {}", format_offset(offset_ms));
+        cx.notify();
+    }
+
+    /// Nudge the subtitle offset by delta_ms
+    fn nudge_time_offset(&mut self, delta_ms: i64, cx: &mut Context<Self>) {
+        let offset_ms = cx.global::<AppState>().subtitle_offset_ms;
+        self.apply_time_offset(offset_ms + delta_ms, cx);
+    }
+
+    /// Apply the offset typed into the delay field
+    fn submit_offset_input(&mut self, cx: &mut Context<Self>) {
+        let text = self.offset_input.read(cx).value().to_string();
+        match text.trim().trim_end_matches("ms").trim().parse::<i64>() {
+            Ok(offset_ms) => self.apply_time_offset(offset_ms, cx),
+            Err(_) => eprintln!("Invalid subtitle delay: {}", text),
+        }
+    }
+
+    /// Offset that would move the timing target's start onto the playhead
+    fn timing_target_offset(&self) -> Option<i64> {
+        let entry = self.subtitle_entries.get(self.timing_target?)?;
+        let playhead_ms = (self.current_position * 1000.0) as i64;
+        Some(playhead_ms - entry.start_ms as i64)
+    }
+
+    /// Apply the offset that aligns the timing target with the playhead
+    fn sync_here(&mut self, cx: &mut Context<Self>) {
+        if let Some(offset_ms) = self.timing_target_offset() {
+            self.apply_time_offset(offset_ms, cx);
+            self.timing_target = None;
+        }
+    }
+
+    /// Turn timing mode on or off
+    fn toggle_timing_mode(&mut self, checked: bool, cx: &mut Context<Self>) {
+        self.timing_mode = checked;
+        self.timing_target = None;
+        cx.notify();
     }
 
     /// Search for all subtitles matching the search text and find all matches
@@ -340,11 +577,29 @@ impl SubtitleWindow {
             return;
         }
 
-        let search_text_lower = search_text.to_lowercase();
+        // An invalid regex just matches nothing until it's fixed
+        let Some(pattern) =
+            SearchPattern::new(&search_text, self.search_case_sensitive, self.search_regex)
+        else {
+            println!("Invalid search pattern: {}", search_text);
+            cx.notify();
+            return;
+        };
+
+        // With "In clip", matches outside the clip are skipped (no clip = no filter)
+        let clip_range = if self.search_within_clip {
+            self.clip_range_ms(cx)
+        } else {
+            None
+        };
+        let offset_ms = cx.global::<AppState>().subtitle_offset_ms;
 
         // Find all matching indices
         for (i, entry) in self.subtitle_entries.iter().enumerate() {
-            if entry.text.to_lowercase().contains(&search_text_lower) {
+            if clip_range.is_some_and(|range| !is_within_range(entry, offset_ms, range)) {
+                continue;
+            }
+            if pattern.matches(&entry.text) {
                 self.search_result_indices.push(i);
             }
         }
@@ -367,6 +622,72 @@ impl SubtitleWindow {
         cx.notify();
     }
 
+    /// Whether following the playhead in the list is on hold
+    ///
+    /// Either locked by the user, or paused for a few seconds after they
+    /// scrolled the list themselves.
+    fn video_auto_scroll_paused(&self) -> bool {
+        self.scroll_locked
+            || self
+                .manual_scroll_at
+                .is_some_and(|at| at.elapsed() < MANUAL_SCROLL_PAUSE)
+    }
+
+    /// Note a manual scroll so auto-scroll doesn't pull the list back right away
+    fn on_manual_scroll(&mut self) {
+        self.manual_scroll_at = Some(Instant::now());
+        // Scroll back to the current subtitle once the pause is over
+        self.last_scrolled_to_video = None;
+    }
+
+    /// Lock or unlock the list position during playback
+    fn toggle_scroll_lock(&mut self, checked: bool, cx: &mut Context<Self>) {
+        self.scroll_locked = checked;
+        self.last_scrolled_to_video = None;
+        cx.notify();
+    }
+
+    /// Scroll back to the subtitle under the playhead and resume following it
+    fn jump_to_current(&mut self, cx: &mut Context<Self>) {
+        self.manual_scroll_at = None;
+        if let Some(current_idx) = self.current_subtitle_index {
+            self.scroll_handle
+                .scroll_to_item(current_idx, ScrollStrategy::Center);
+            self.last_scrolled_to_video = Some(current_idx);
+        }
+        cx.notify();
+    }
+
+    /// Turn the "In clip" search filter on or off, re-running an active search
+    fn toggle_search_within_clip(&mut self, checked: bool, cx: &mut Context<Self>) {
+        self.search_within_clip = checked;
+        if self.last_submitted_search_term.is_some() {
+            self.last_scrolled_to_search = None;
+            self.update_search_results(cx);
+        }
+        cx.notify();
+    }
+
+    /// Turn case-sensitive search on or off
+    fn toggle_search_case_sensitive(&mut self, checked: bool, cx: &mut Context<Self>) {
+        self.search_case_sensitive = checked;
+        if self.last_submitted_search_term.is_some() {
+            self.last_scrolled_to_search = None;
+            self.update_search_results(cx);
+        }
+        cx.notify();
+    }
+
+    /// Turn regex search on or off
+    fn toggle_search_regex(&mut self, checked: bool, cx: &mut Context<Self>) {
+        self.search_regex = checked;
+        if self.last_submitted_search_term.is_some() {
+            self.last_scrolled_to_search = None;
+            self.update_search_results(cx);
+        }
+        cx.notify();
+    }
+
     /// Move to the next search result (cycling/wrapping)
     fn search_next(&mut self, cx: &mut Context<Self>) {
         // If we don't have any results, do nothing
@@ -382,14 +703,66 @@ impl SubtitleWindow {
         }
     }
 
+    /// Move to the previous search result (wrapping to the last one)
+    fn search_prev(&mut self, cx: &mut Context<Self>) {
+        // If we don't have any results, do nothing
+        if self.search_result_indices.is_empty() {
+            return;
+        }
+
+        // Move to previous result (wrap to the end if at 0)
+        if let Some(current_idx) = self.current_search_result_index {
+            let prev_idx = current_idx
+                .checked_sub(1)
+                .unwrap_or(self.search_result_indices.len() - 1);
+            self.current_search_result_index = Some(prev_idx);
+            cx.notify();
+        }
+    }
+
     /// Get the subtitle index of the current search result
     fn current_search_subtitle_index(&self) -> Option<usize> {
         self.current_search_result_index
             .and_then(|idx| self.search_result_indices.get(idx).copied())
     }
 
-    /// Handle Enter key in search input
-    fn on_search_enter(&mut self, cx: &mut Context<Self>) {
+    /// Status line text showing the position within the list
+    ///
+    /// Shows "Result 3/12" while a search is active, otherwise "Subtitle 42/310"
+    /// for the subtitle under the playhead.
+    fn status_text(&self) -> Option<String> {
+        if let Some(result_idx) = self.current_search_result_index {
+            return Some(format!(
+                "Result {}/{}",
+                result_idx + 1,
+                self.search_result_indices.len()
+            ));
+        }
+
+        if self.subtitle_entries.is_empty() {
+            return None;
+        }
+
+        Some(match self.current_subtitle_index {
+            Some(idx) => format!("Subtitle {}/{}", idx + 1, self.subtitle_entries.len()),
+            None => format!("Subtitle -/{}", self.subtitle_entries.len()),
+        })
+    }
+
+    /// Match count for the submitted search (e.g. "3 / 17"), None when there's no search
+    fn search_count_label(&self, cx: &Context<Self>) -> Option<String> {
+        self.last_submitted_search_term.as_ref()?;
+        if self.search_input.read(cx).text().to_string().is_empty() {
+            return None;
+        }
+        Some(format_search_count(
+            self.current_search_result_index,
+            self.search_result_indices.len(),
+        ))
+    }
+
+    /// Handle Enter key in search input (Shift+Enter searches backwards)
+    fn on_search_enter(&mut self, backwards: bool, cx: &mut Context<Self>) {
         let current_search_text = self.search_input.read(cx).text().to_string();
 
         // If search text is empty, do nothing
@@ -407,6 +780,14 @@ impl SubtitleWindow {
 
             // Perform search and jump to first result (index 0)
             self.update_search_results(cx);
+
+            // Searching backwards starts from the last result instead
+            if backwards && !self.search_result_indices.is_empty() {
+                self.current_search_result_index = Some(self.search_result_indices.len() - 1);
+            }
+        } else if backwards {
+            // SAME search term - decrement to previous result
+            self.search_prev(cx);
         } else {
             // SAME search term - increment to next result
             self.search_next(cx);
@@ -426,24 +807,101 @@ impl SubtitleWindow {
         cx.notify();
     }
 
+    /// The current clip range in milliseconds, if a valid clip is set
+    fn clip_range_ms(&self, cx: &Context<Self>) -> Option<(f32, f32)> {
+        let controls = self.controls.as_ref()?.read(cx);
+
+        // Inline the clip validation logic
+        let start_ms = crate::controls_window::ControlsWindow::parse_masked_time_ms(
+            &controls.clip_start_input.read(cx).value(),
+        )
+        .or(controls.clip_start)?;
+        let end_ms = crate::controls_window::ControlsWindow::parse_masked_time_ms(
+            &controls.clip_end_input.read(cx).value(),
+        )
+        .or(controls.clip_end)?;
+
+        (start_ms < end_ms).then_some((start_ms, end_ms))
+    }
+
+    /// The custom clip subtitles from the Clip tab, as SRT
+    pub fn custom_subtitle_srt(&self, cx: &App) -> String {
+        self.clip_tab.read(cx).get_custom_subtitle_srt(cx)
+    }
+
     /// Check if the Clip tab should be enabled (when there's a valid clip)
     fn is_clip_tab_enabled(&self, cx: &Context<Self>) -> bool {
-        if let Some(controls_entity) = &self.controls {
-            let controls = controls_entity.read(cx);
+        self.clip_range_ms(cx).is_some()
+    }
 
-            // Inline the clip validation logic
-            let start_ms = crate::controls_window::ControlsWindow::parse_masked_time_ms(
-                &controls.clip_start_input.read(cx).value(),
-            )
-            .or(controls.clip_start);
-            let end_ms = crate::controls_window::ControlsWindow::parse_masked_time_ms(
-                &controls.clip_end_input.read(cx).value(),
-            )
-            .or(controls.clip_end);
+    /// Save the subtitle text as a plain .txt transcript
+    ///
+    /// With `clip_only`, only subtitles overlapping the current clip are included.
+    pub fn save_transcript(
+        &self,
+        include_timestamps: bool,
+        clip_only: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let app_state = cx.global::<AppState>();
+        let Some(file_path) = app_state.file_path.clone() else {
+            eprintln!("No video file loaded");
+            return;
+        };
+        let offset_ms = app_state.subtitle_offset_ms;
 
-            return start_ms.is_some() && end_ms.is_some() && start_ms.unwrap() < end_ms.unwrap();
+        // Shifted by the timing offset, so timestamps match what's on screen
+        let shifted = self.subtitle_entries.iter().map(|entry| SubtitleEntry {
+            start_ms: offset_time_ms(entry.start_ms, offset_ms),
+            end_ms: offset_time_ms(entry.end_ms, offset_ms),
+            text: entry.text.clone(),
+        });
+        let entries: Vec<SubtitleEntry> = if clip_only {
+            let Some((clip_start_ms, clip_end_ms)) = self.clip_range_ms(cx) else {
+                crate::notify_error(cx, "Transcript error: no clip is set");
+                return;
+            };
+            shifted
+                .filter(|entry| {
+                    (entry.end_ms as f32) > clip_start_ms && (entry.start_ms as f32) < clip_end_ms
+                })
+                .collect()
+        } else {
+            shifted.collect()
+        };
+
+        if entries.is_empty() {
+            crate::notify_error(cx, "Transcript error: no subtitles to save");
+            return;
         }
-        false
+        let transcript = crate::subtitle_extractor::to_transcript(&entries, include_timestamps);
+
+        // Default to <video name>.txt next to the video
+        let video_path = std::path::PathBuf::from(&file_path);
+        let directory = video_path
+            .parent()
+            .unwrap_or_else(|| std::path::Path::new("."));
+        let default_filename = video_path
+            .file_stem()
+            .and_then(|n| n.to_str())
+            .unwrap_or("transcript")
+            .to_string()
+            + ".txt";
+        let path_receiver = cx.prompt_for_new_path(directory, Some(&default_filename));
+
+        cx.spawn(async move |_this, cx| {
+            if let Ok(Ok(Some(output_path))) = path_receiver.await {
+                match std::fs::write(&output_path, transcript) {
+                    Ok(()) => println!("Transcript saved: {}", output_path.display()),
+                    Err(e) => {
+                        let _ = cx.update(|cx| {
+                            crate::notify_error(cx, format!("Failed to save transcript: {}", e));
+                        });
+                    }
+                }
+            }
+        })
+        .detach();
     }
 
     /// Load a specific subtitle stream by index
@@ -456,36 +914,222 @@ impl SubtitleWindow {
                 return;
             }
         };
+        let charset = app_state.subtitle_charset.clone();
+        let fps = app_state.source_video_fps;
+
+        // Subtitle files from disk are read again instead of extracted from the video
+        if let Some(path) = self
+            .subtitle_streams
+            .get(stream_index)
+            .and_then(|stream| stream.external_path.clone())
+        {
+            println!("Loading subtitle file {}", path);
+            match crate::subtitle_extractor::load_external_subtitle(&path, fps, charset.as_deref())
+            {
+                Ok(entries) => self.set_loaded_entries(entries, cx),
+                Err(e) => crate::notify_error(cx, format!("Failed to load subtitle file: {}", e)),
+            }
+            return;
+        }
+
+        // Image-based streams still play and burn in, but there's no text to list
+        if self
+            .subtitle_streams
+            .get(stream_index)
+            .is_some_and(|stream| !stream.is_text)
+        {
+            println!("Subtitle stream {} is image-based", stream_index);
+            self.set_loaded_entries(Vec::new(), cx);
+            return;
+        }
 
         println!("Loading subtitle stream {}", stream_index);
 
         // Extract subtitle stream to SRT
-        match crate::subtitle_extractor::extract_subtitle_stream(&file_path, stream_index) {
+        match crate::subtitle_extractor::extract_subtitle_stream(
+            &file_path,
+            stream_index,
+            charset.as_deref(),
+        ) {
             Ok(srt_content) => {
                 // Parse SRT content
-                self.subtitle_entries = crate::subtitle_extractor::parse_srt(&srt_content);
-                println!("Loaded {} subtitle entries", self.subtitle_entries.len());
-
-                // Update clip tab with new subtitle entries
-                let entries_clone = self.subtitle_entries.clone();
-                self.clip_tab.update(cx, |clip_tab, _cx| {
-                    clip_tab.set_subtitle_entries(entries_clone);
-                });
-
-                cx.notify();
+                let entries = crate::subtitle_extractor::parse_srt(&srt_content);
+                self.set_loaded_entries(entries, cx);
             }
             Err(e) => {
                 eprintln!("Failed to extract subtitle stream: {}", e);
             }
         }
     }
+
+    /// Replace the subtitle list with freshly loaded entries
+    fn set_loaded_entries(&mut self, entries: Vec<SubtitleEntry>, cx: &mut Context<Self>) {
+        self.subtitle_entries = entries;
+        self.timing_target = None;
+        println!("Loaded {} subtitle entries", self.subtitle_entries.len());
+
+        // Update clip tab with new subtitle entries
+        let entries_clone = self.subtitle_entries.clone();
+        self.clip_tab.update(cx, |clip_tab, _cx| {
+            clip_tab.set_subtitle_entries(entries_clone);
+        });
+
+        cx.notify();
+    }
+
+    /// Load the selected subtitle track again, e.g. after the encoding changed
+    pub fn reload_subtitles(&mut self, cx: &mut Context<Self>) {
+        let selected = self.selected_stream_index(cx);
+        if selected < self.subtitle_streams.len() {
+            self.load_subtitle_stream(selected, cx);
+        }
+    }
+
+    /// Index of the stream picked in the select (the first one if none is)
+    fn selected_stream_index(&self, cx: &App) -> usize {
+        self.select_state
+            .read(cx)
+            .selected_index(cx)
+            .map(|index_path| index_path.row)
+            .unwrap_or(0)
+    }
+
+    /// Whether the selected track is an image-based stream (nothing to list or search)
+    fn selected_stream_is_image(&self, cx: &App) -> bool {
+        self.subtitle_streams
+            .get(self.selected_stream_index(cx))
+            .is_some_and(|stream| !stream.is_text)
+    }
+
+    /// Path of the selected track when it's a subtitle file rather than a stream
+    /// of the video; exports burn or mux the file itself
+    pub fn selected_external_subtitle(&self, cx: &App) -> Option<String> {
+        self.subtitle_streams
+            .get(self.selected_stream_index(cx))
+            .and_then(|stream| stream.external_path.clone())
+    }
+
+    /// Add a subtitle file (.srt/.vtt/.ass/.ssa/.sub) as an "External: <name>" track,
+    /// select it, and add it to the player so displayed subtitles match
+    pub fn load_external_srt(
+        &mut self,
+        path: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Result<(), String> {
+        let app_state = cx.global::<AppState>();
+        let fps = app_state.source_video_fps;
+        let charset = app_state.subtitle_charset.clone();
+
+        // Text that can't be decoded is reported here instead of showing garbage
+        let entries =
+            crate::subtitle_extractor::load_external_subtitle(path, fps, charset.as_deref())?;
+
+        let file_name = Path::new(path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string());
+        self.subtitle_streams.push(SubtitleStream {
+            display_title: format!("{}{}", SIDECAR_TITLE_PREFIX, file_name),
+            external_path: Some(path.to_string()),
+            is_text: true,
+        });
+        let index = self.subtitle_streams.len() - 1;
+        let streams = self.subtitle_streams.clone();
+        self.select_state.update(cx, |state, cx| {
+            state.set_items(streams, window, cx);
+            state.set_selected_index(Some(IndexPath::new(index)), window, cx);
+        });
+        self.set_loaded_entries(entries, cx);
+        // Adds the file to the player unless mpv already has it
+        self.set_selected_track(index, cx);
+
+        println!("Loaded {} entries from {}", self.subtitle_entries.len(), path);
+        Ok(())
+    }
+}
+
+/// How long auto-scroll waits after the user scrolls the subtitle list
+const MANUAL_SCROLL_PAUSE: Duration = Duration::from_secs(4);
+
+/// Shift a subtitle time by an offset, clamping at zero
+fn offset_time_ms(time_ms: u64, offset_ms: i64) -> u64 {
+    time_ms.saturating_add_signed(offset_ms)
+}
+
+/// Whether a subtitle (shifted by the current offset) falls entirely inside a time range
+fn is_within_range(entry: &SubtitleEntry, offset_ms: i64, (start_ms, end_ms): (f32, f32)) -> bool {
+    offset_time_ms(entry.start_ms, offset_ms) as f32 >= start_ms
+        && offset_time_ms(entry.end_ms, offset_ms) as f32 <= end_ms
+}
+
+/// A search term, compiled for the selected case and regex options
+enum SearchPattern {
+    Plain { term: String, case_sensitive: bool },
+    Regex(regex::Regex),
+}
+
+impl SearchPattern {
+    /// None when `use_regex` is set and the term isn't a valid regex
+    fn new(term: &str, case_sensitive: bool, use_regex: bool) -> Option<Self> {
+        if use_regex {
+            regex::RegexBuilder::new(term)
+                .case_insensitive(!case_sensitive)
+                .build()
+                .ok()
+                .map(SearchPattern::Regex)
+        } else if case_sensitive {
+            Some(SearchPattern::Plain {
+                term: term.to_string(),
+                case_sensitive,
+            })
+        } else {
+            Some(SearchPattern::Plain {
+                term: term.to_lowercase(),
+                case_sensitive,
+            })
+        }
+    }
+
+    fn matches(&self, text: &str) -> bool {
+        match self {
+            SearchPattern::Plain {
+                term,
+                case_sensitive: true,
+            } => text.contains(term.as_str()),
+            SearchPattern::Plain { term, .. } => text.to_lowercase().contains(term.as_str()),
+            SearchPattern::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+/// Format the search position, e.g. "3 / 17" ("No matches" when there are none)
+fn format_search_count(current: Option<usize>, total: usize) -> String {
+    match current {
+        _ if total == 0 => "No matches".to_string(),
+        Some(index) => format!("{} / {}", index + 1, total),
+        None => format!("- / {}", total),
+    }
 }
 
-/// Calculate the height of text accounting for line wrapping
-fn calculate_text_height(text: &str, wrap_width: Pixels, window: &mut Window) -> f32 {
-    let style = window.text_style();
+/// Format a subtitle offset for display (e.g. "+1.250s", "-0.400s")
+fn format_offset(offset_ms: i64) -> String {
+    let sign = if offset_ms < 0 { '-' } else { '+' };
+    let abs_ms = offset_ms.unsigned_abs();
+    format!("{}{}.{:03}s", sign, abs_ms / 1000, abs_ms % 1000)
+}
+
+/// Calculate the height of text at the given size accounting for line wrapping
+fn calculate_text_height(
+    text: &str,
+    text_size: Rems,
+    wrap_width: Pixels,
+    window: &mut Window,
+) -> f32 {
+    let mut style = window.text_style();
+    style.font_size = text_size.into();
     let font_size = style.font_size.to_pixels(window.rem_size());
-    let line_height = window.line_height();
+    let line_height = style.line_height_in_pixels(window.rem_size());
 
     // Convert to owned String for SharedString (requires 'static lifetime)
     let text_owned: SharedString = text.to_string().into();
@@ -519,8 +1163,8 @@ impl Render for SubtitleWindow {
         // Update position from video player every frame
         cx.on_next_frame(window, |this, _window, cx| {
             this.update_position_from_player(cx);
-            // Request another render on next frame for continuous updates
-            cx.notify();
+            // Request another render for continuous updates, at most at the refresh rate cap
+            crate::notify_rate_limited(&mut this.last_render_time, cx);
         });
 
         // Auto-scroll: prioritize search result over video position
@@ -533,8 +1177,10 @@ impl Render for SubtitleWindow {
                 self.last_scrolled_to_search = Some(search_subtitle_idx);
             }
         } else if let Some(current_idx) = self.current_subtitle_index {
-            // Otherwise, scroll to current video position subtitle (only if it changed)
-            if self.last_scrolled_to_video != Some(current_idx) {
+            // Otherwise, scroll to current video position subtitle (only if it changed),
+            // unless the user is reading elsewhere in the list
+            if self.last_scrolled_to_video != Some(current_idx) && !self.video_auto_scroll_paused()
+            {
                 self.scroll_handle
                     .scroll_to_item(current_idx, ScrollStrategy::Bottom);
                 self.last_scrolled_to_video = Some(current_idx);
@@ -546,18 +1192,51 @@ impl Render for SubtitleWindow {
         let current_subtitle_index = self.current_subtitle_index;
         let search_result_indices = self.search_result_indices.clone();
         let current_search_subtitle_idx = self.current_search_subtitle_index();
+        let subtitle_offset_ms = cx.global::<AppState>().subtitle_offset_ms;
+        let timing_mode = self.timing_mode;
+        let timing_target = self.timing_target;
+        let search_within_clip = self.search_within_clip;
+        let search_case_sensitive = self.search_case_sensitive;
+        let search_count = self
+            .search_count_label(cx)
+            .map(|label| (label, self.search_result_indices.is_empty()));
+        let search_regex = self.search_regex;
+        let scroll_locked = self.scroll_locked;
+        let has_clip = self.clip_range_ms(cx).is_some();
+
+        // Keep the delay field in sync with offsets applied elsewhere (Sync here, file load)
+        if subtitle_offset_ms != self.shown_offset_ms {
+            self.shown_offset_ms = subtitle_offset_ms;
+            self.offset_input.update(cx, |input, cx| {
+                input.set_value(subtitle_offset_ms.to_string(), window, cx);
+            });
+        }
 
-        // Calculate wrap width from window bounds (subtitle pane is ~24% of window)
-        let window_width = window.bounds().size.width;
-        let subtitle_pane_width = window_width * 0.24;
+        // Calculate wrap width from window bounds (same pane width as the unified layout)
+        let window_width = f32::from(window.bounds().size.width);
+        let subtitle_pane_width = px(crate::unified_window::subtitle_pane_width(window_width));
         let text_wrap_width = (subtitle_pane_width - px(40.0)).max(px(100.0));
 
+        // Row spacing and text sizes from the list preferences
+        let list_density = cx.global::<AppState>().list_density;
+        let list_font_size = cx.global::<AppState>().list_font_size;
+        let row_padding = list_density.row_padding();
+        let text_size = rems(list_font_size.text_rems());
+        let timecode_size = rems(list_font_size.timecode_rems());
+
+        // Fixed part of each row: padding, the timecode line, the gap and the border
+        let mut timecode_style = window.text_style();
+        timecode_style.font_size = timecode_size.into();
+        let timecode_height = f32::from(timecode_style.line_height_in_pixels(window.rem_size()));
+        let row_chrome_height = row_padding * 2.0 + timecode_height + 4.0 + 1.0;
+
         // Pre-calculate heights for each entry (needed for both item_sizes and closure)
         let item_heights: Vec<f32> = entries
             .iter()
             .map(|entry| {
-                let text_height = calculate_text_height(&entry.text, text_wrap_width, window);
-                40.0 + text_height
+                let text_height =
+                    calculate_text_height(&entry.text, text_size, text_wrap_width, window);
+                row_chrome_height + text_height
             })
             .collect();
         let item_heights = Rc::new(item_heights);
@@ -573,8 +1252,19 @@ impl Render for SubtitleWindow {
         // Get the view entity before entering the div builder
         let view = cx.entity().clone();
 
+        // Once the user is editing custom subtitles on the Clip tab, keep the tab
+        // available even if a clip bound is cleared, until they leave it
+        if self.active_tab == SubtitleTab::Clip && cx.global::<AppState>().custom_subtitle_mode {
+            self.clip_tab_pinned = true;
+        }
+
         // Check if Clip tab should be enabled
-        let clip_tab_enabled = self.is_clip_tab_enabled(cx);
+        let clip_tab_enabled = self.is_clip_tab_enabled(cx) || self.clip_tab_pinned;
+
+        // Without a valid clip (and no edit in progress) there's nothing to show on the Clip tab
+        if !clip_tab_enabled && self.active_tab == SubtitleTab::Clip {
+            self.active_tab = SubtitleTab::Video;
+        }
         let active_tab = self.active_tab;
 
         let theme = cx.theme();
@@ -630,7 +1320,9 @@ impl Render for SubtitleWindow {
                             .on_mouse_down(
                                 MouseButton::Left,
                                 cx.listener(|this, _, _, cx| {
+                                    // Explicitly leaving the Clip tab releases the pin
                                     this.active_tab = SubtitleTab::Video;
+                                    this.clip_tab_pinned = false;
                                     cx.notify();
                                 }),
                             )
@@ -670,6 +1362,35 @@ impl Render for SubtitleWindow {
                                 }),
                             )
                             .child("Clip"),
+                    )
+                    .child(
+                        // Bookmarks tab
+                        div()
+                            .px_4()
+                            .py_2()
+                            .rounded_t_md()
+                            .cursor_pointer()
+                            .text_sm()
+                            .when(active_tab == SubtitleTab::Bookmarks, |div| {
+                                div.bg(element_active_bg)
+                                    .text_color(text_color)
+                                    .border_b_2()
+                                    .border_color(ring_color)
+                            })
+                            .when(active_tab != SubtitleTab::Bookmarks, |div| {
+                                div.bg(element_bg)
+                                    .text_color(text_muted_color)
+                                    .hover(move |style| style.bg(element_hover_bg))
+                            })
+                            .on_mouse_down(
+                                MouseButton::Left,
+                                cx.listener(|this, _, _, cx| {
+                                    this.active_tab = SubtitleTab::Bookmarks;
+                                    this.clip_tab_pinned = false;
+                                    cx.notify();
+                                }),
+                            )
+                            .child("Bookmarks"),
                     ),
             )
             // Video tab content
@@ -700,6 +1421,15 @@ impl Render for SubtitleWindow {
                                         this.toggle_sync(*checked, cx);
                                     })),
                             )
+                            .child(
+                                // Checkbox for picking a subtitle to line up with the playhead
+                                Checkbox::new("timing-mode-checkbox")
+                                    .label("Timing")
+                                    .checked(timing_mode)
+                                    .on_click(cx.listener(|this, checked, _, cx| {
+                                        this.toggle_timing_mode(*checked, cx);
+                                    })),
+                            )
                             .child(
                                 // Select dropdown at half width
                                 div().flex_1().child(
@@ -708,14 +1438,19 @@ impl Render for SubtitleWindow {
                                 ),
                             ),
                     )
-                    // Second row: Search input
+                    // Second row: Search input and the clip range filter
                     .child(
                         div()
                             .w_full()
+                            .flex()
+                            .flex_row()
+                            .gap_2()
+                            .items_center()
                             .on_key_down(cx.listener(|this, event: &gpui::KeyDownEvent, window, cx| {
                                 match event.keystroke.key.as_str() {
                                     "enter" => {
-                                        this.on_search_enter(cx);
+                                        let backwards = event.keystroke.modifiers.shift;
+                                        this.on_search_enter(backwards, cx);
                                     }
                                     "escape" => {
                                         this.on_search_escape(window, cx);
@@ -723,25 +1458,216 @@ impl Render for SubtitleWindow {
                                     _ => {}
                                 }
                             }))
-                            .child(Input::new(&self.search_input)),
-                    ),
+                            .child(div().flex_1().child(Input::new(&self.search_input)))
+                            .when_some(search_count, |this, (label, no_matches)| {
+                                this.child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(if no_matches {
+                                            text_muted_color
+                                        } else {
+                                            text_color
+                                        })
+                                        .child(label),
+                                )
+                            })
+                            .child(
+                                Checkbox::new("search-case-checkbox")
+                                    .label("Aa")
+                                    .checked(search_case_sensitive)
+                                    .on_click(cx.listener(|this, checked, _, cx| {
+                                        this.toggle_search_case_sensitive(*checked, cx);
+                                    })),
+                            )
+                            .child(
+                                Checkbox::new("search-regex-checkbox")
+                                    .label(".*")
+                                    .checked(search_regex)
+                                    .on_click(cx.listener(|this, checked, _, cx| {
+                                        this.toggle_search_regex(*checked, cx);
+                                    })),
+                            )
+                            .child(
+                                Checkbox::new("search-within-clip-checkbox")
+                                    .label("In clip")
+                                    .checked(search_within_clip)
+                                    .disabled(!has_clip)
+                                    .on_click(cx.listener(|this, checked, _, cx| {
+                                        this.toggle_search_within_clip(*checked, cx);
+                                    })),
+                            ),
+                    )
+                    // Subtitle delay: +/- buttons and a field in milliseconds
+                    .child(
+                        div()
+                            .w_full()
+                            .flex()
+                            .flex_row()
+                            .gap_2()
+                            .items_center()
+                            .text_xs()
+                            .text_color(text_muted_color)
+                            .on_key_down(cx.listener(|this, event: &gpui::KeyDownEvent, _, cx| {
+                                if event.keystroke.key == "enter" {
+                                    this.submit_offset_input(cx);
+                                }
+                            }))
+                            .child("Delay (ms)")
+                            .child(
+                                div()
+                                    .px_2()
+                                    .py_1()
+                                    .rounded_md()
+                                    .cursor_pointer()
+                                    .bg(element_bg)
+                                    .text_color(text_color)
+                                    .hover(move |style| style.bg(element_hover_bg))
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(|this, _, _, cx| {
+                                            this.nudge_time_offset(-OFFSET_STEP_MS, cx);
+                                        }),
+                                    )
+                                    .child("-"),
+                            )
+                            .child(div().flex_1().child(Input::new(&self.offset_input).xsmall()))
+                            .child(
+                                div()
+                                    .px_2()
+                                    .py_1()
+                                    .rounded_md()
+                                    .cursor_pointer()
+                                    .bg(element_bg)
+                                    .text_color(text_color)
+                                    .hover(move |style| style.bg(element_hover_bg))
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(|this, _, _, cx| {
+                                            this.nudge_time_offset(OFFSET_STEP_MS, cx);
+                                        }),
+                                    )
+                                    .child("+"),
+                            ),
+                    )
+                    .when(self.selected_stream_is_image(cx), |this| {
+                        this.child(
+                            div()
+                                .w_full()
+                                .text_xs()
+                                .text_color(text_muted_color)
+                                .child(IMAGE_SUBTITLE_NOTE),
+                        )
+                    })
+                    // Third row: position within the list (e.g. "Subtitle 42/310")
+                    // and the scroll lock / jump back controls
+                    .when_some(self.status_text(), |this, status| {
+                        this.child(
+                            div()
+                                .w_full()
+                                .flex()
+                                .flex_row()
+                                .items_center()
+                                .gap_2()
+                                .text_xs()
+                                .text_color(text_muted_color)
+                                .child(div().flex_1().child(status))
+                                .child(
+                                    Checkbox::new("scroll-lock-checkbox")
+                                        .label("Lock scroll")
+                                        .checked(scroll_locked)
+                                        .on_click(cx.listener(|this, checked, _, cx| {
+                                            this.toggle_scroll_lock(*checked, cx);
+                                        })),
+                                )
+                                .child(
+                                    div()
+                                        .px_2()
+                                        .py_1()
+                                        .rounded_md()
+                                        .cursor_pointer()
+                                        .bg(element_bg)
+                                        .text_color(text_color)
+                                        .hover(move |style| style.bg(element_hover_bg))
+                                        .on_mouse_down(
+                                            MouseButton::Left,
+                                            cx.listener(|this, _, _, cx| {
+                                                this.jump_to_current(cx);
+                                            }),
+                                        )
+                                        .child("Jump to current"),
+                                ),
+                        )
+                    })
+                    // Timing mode: preview and apply the offset for the picked subtitle
+                    .when(timing_mode, |this| {
+                        let proposed_offset = self.timing_target_offset();
+                        this.child(
+                            div()
+                                .w_full()
+                                .flex()
+                                .flex_row()
+                                .items_center()
+                                .gap_2()
+                                .text_xs()
+                                .text_color(text_muted_color)
+                                .child(div().flex_1().child(match proposed_offset {
+                                    Some(offset_ms) => format!(
+                                        "Offset {} (current {})",
+                                        format_offset(offset_ms),
+                                        format_offset(subtitle_offset_ms)
+                                    ),
+                                    None => "Click a subtitle, then scrub to where its dialogue \
+                                             starts"
+                                        .to_string(),
+                                }))
+                                .when(proposed_offset.is_some(), |this| {
+                                    this.child(
+                                        div()
+                                            .px_2()
+                                            .py_1()
+                                            .rounded_md()
+                                            .cursor_pointer()
+                                            .bg(element_bg)
+                                            .text_color(text_color)
+                                            .hover(move |style| style.bg(element_hover_bg))
+                                            .on_mouse_down(
+                                                MouseButton::Left,
+                                                cx.listener(|this, _, _, cx| {
+                                                    this.sync_here(cx);
+                                                }),
+                                            )
+                                            .child("Sync here"),
+                                    )
+                                }),
+                        )
+                    }),
             )
             .child(
                 // Virtual list for displaying subtitles
                 // Use dynamic ID so VirtualList gets recreated when data changes
                 div().id("subtitle-list-container").flex_1().w_full()
+                .on_scroll_wheel(cx.listener(|this, _: &ScrollWheelEvent, _, _| {
+                    this.on_manual_scroll();
+                }))
                 .context_menu({
                     let view_for_menu = view.clone();
                     move |menu, _window, cx| {
                         // Context menu for the right-clicked subtitle item
                         // Get the right-clicked item info
+                        // Times are shifted by the subtitle offset to match what's on screen
+                        let offset_ms = cx.global::<AppState>().subtitle_offset_ms;
                         let menu_data = view_for_menu.read(cx).right_clicked_item.and_then(|idx| {
                             view_for_menu.read(cx).subtitle_entries.get(idx).map(|entry| {
-                                (entry.start_ms, entry.end_ms)
+                                (
+                                    offset_time_ms(entry.start_ms, offset_ms),
+                                    offset_time_ms(entry.end_ms, offset_ms),
+                                    entry.text.clone(),
+                                    entry.format_range(),
+                                )
                             })
                         });
 
-                        if let Some((start_ms, end_ms)) = menu_data {
+                        if let Some((start_ms, end_ms, text, timecode)) = menu_data {
                             menu.item(
                                 PopupMenuItem::new("Set clip start").on_click(move |_, _, cx| {
                                     eprintln!("=== SET CLIP START CLICKED! time_ms={} ===", start_ms);
@@ -787,6 +1713,47 @@ impl Render for SubtitleWindow {
                                         });
                                     }
                                 })
+                            ).item(
+                                PopupMenuItem::new("Clip with padding").on_click(move |_, _, cx| {
+                                    let app_state = cx.global::<AppState>();
+                                    let padding_ms = app_state.clip_padding_ms;
+                                    let unified_window_entity = app_state.unified_window_entity.clone();
+
+                                    // Clamp the padded range to [0, duration] (duration is unknown until the video loads)
+                                    let duration_ms = app_state
+                                        .video_player
+                                        .lock()
+                                        .ok()
+                                        .and_then(|player| player.get_position_duration())
+                                        .map(|(_, duration)| duration.nseconds() / 1_000_000)
+                                        .unwrap_or(0);
+                                    let padded_start = start_ms.saturating_sub(padding_ms);
+                                    let padded_end = if duration_ms > 0 {
+                                        (end_ms + padding_ms).min(duration_ms)
+                                    } else {
+                                        end_ms + padding_ms
+                                    };
+
+                                    if let Some(unified_window_entity) = unified_window_entity {
+                                        unified_window_entity.update(cx, |unified_window, app_cx| {
+                                            let controls_entity = unified_window.controls.clone();
+                                            controls_entity.update(app_cx, |controls, cx| {
+                                                controls.set_clip_times(padded_start, padded_end, cx);
+                                            });
+                                        });
+                                    }
+                                })
+                            )
+                            .separator()
+                            .item(
+                                // Multi-line entries are copied with their line breaks
+                                PopupMenuItem::new("Copy text").on_click(move |_, _, cx| {
+                                    cx.write_to_clipboard(ClipboardItem::new_string(text.clone()));
+                                })
+                            ).item(
+                                PopupMenuItem::new("Copy timecode").on_click(move |_, _, cx| {
+                                    cx.write_to_clipboard(ClipboardItem::new_string(timecode.clone()));
+                                })
                             )
                         } else {
                             menu
@@ -809,6 +1776,7 @@ impl Render for SubtitleWindow {
                                     let is_search_result = search_result_indices.contains(&idx);
                                     let is_active_search_result =
                                         current_search_subtitle_idx == Some(idx);
+                                    let is_timing_target = timing_target == Some(idx);
                                     let start_ms = entry.start_ms;
                                     let end_ms = entry.end_ms;
                                     let item_height = item_heights_for_closure.get(idx).copied().unwrap_or(60.0);
@@ -817,7 +1785,7 @@ impl Render for SubtitleWindow {
                                         .w_full()
                                         .h(px(item_height))
                                         .px_3()
-                                        .py_2()
+                                        .py(px(row_padding))
                                         .border_b_1()
                                         .border_color(border_variant_color)
                                         .cursor_pointer()
@@ -836,26 +1804,46 @@ impl Render for SubtitleWindow {
                                                 div.bg(list_active_bg) // Theme list active for current video subtitle
                                             },
                                         )
-                                        .on_mouse_down(MouseButton::Left, move |_, _, cx| {
-                                            // Seek the video player to the start time of this subtitle
-                                            let app_state = cx.global::<AppState>();
-                                            let video_player = app_state.video_player.clone();
-
-                                            if let Ok(player) = video_player.lock() {
-                                                // Convert milliseconds to nanoseconds
-                                                let nanos = start_ms * 1_000_000;
-                                                let clock_time = ClockTime::from_nseconds(nanos);
-
-                                                println!(
-                                                    "Seeking to subtitle at: {}ms ({}ns)",
-                                                    start_ms, nanos
-                                                );
-
-                                                if let Err(e) = player.seek(clock_time) {
-                                                    eprintln!("Failed to seek: {}", e);
+                                        .when(is_timing_target, |div| {
+                                            div.bg(info_bg) // Subtitle picked for "Sync here"
+                                        })
+                                        .on_mouse_down(MouseButton::Left, {
+                                            let view_clone = view_for_list.clone();
+                                            move |_, _, cx| {
+                                                // In timing mode, pick this subtitle instead of seeking
+                                                if timing_mode {
+                                                    view_clone.update(cx, |this, cx| {
+                                                        this.timing_target = Some(idx);
+                                                        cx.notify();
+                                                    });
+                                                    return;
                                                 }
-                                            };
-                                        } )
+
+                                                // Seek the video player to the start time of this subtitle
+                                                let app_state = cx.global::<AppState>();
+                                                let video_player = app_state.video_player.clone();
+                                                let start_ms =
+                                                    offset_time_ms(start_ms, subtitle_offset_ms);
+
+                                                if let Ok(player) = video_player.lock() {
+                                                    // Convert milliseconds to nanoseconds
+                                                    let nanos = start_ms * 1_000_000;
+                                                    let clock_time = ClockTime::from_nseconds(nanos);
+
+                                                    println!(
+                                                        "Seeking to subtitle at: {}ms ({}ns)",
+                                                        start_ms, nanos
+                                                    );
+
+                                                    if let Err(e) = player.seek(clock_time) {
+                                                        crate::notify_error(
+                                                            cx,
+                                                            format!("Failed to seek: {}", e),
+                                                        );
+                                                    }
+                                                };
+                                            }
+                                        })
                                         .on_mouse_down(MouseButton::Right, {
                                             let view_clone = view_for_list.clone();
                                             move |_, _, cx| {
@@ -876,7 +1864,7 @@ impl Render for SubtitleWindow {
                                                         .flex()
                                                         .flex_row()
                                                         .gap_1()
-                                                        .text_xs()
+                                                        .text_size(timecode_size)
                                                         .text_color(text_muted_color)
                                                         .child(
                                                             div()
@@ -896,7 +1884,7 @@ impl Render for SubtitleWindow {
                                                 )
                                                 .child(
                                                     div()
-                                                        .text_sm()
+                                                        .text_size(text_size)
                                                         .text_color(text_color)
                                                         .child(entry.text.clone()),
                                                 ),
@@ -916,5 +1904,67 @@ impl Render for SubtitleWindow {
             .when(active_tab == SubtitleTab::Clip, |parent| {
                 parent.child(self.clip_tab.clone())
             })
+            // Bookmarks tab content
+            .when(active_tab == SubtitleTab::Bookmarks, |parent| {
+                parent.child(self.bookmarks_tab.clone())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offset_time_ms_clamps_at_zero() {
+        assert_eq!(offset_time_ms(5000, 1250), 6250);
+        assert_eq!(offset_time_ms(5000, -400), 4600);
+        assert_eq!(offset_time_ms(300, -400), 0);
+    }
+
+    #[test]
+    fn test_format_search_count() {
+        assert_eq!(format_search_count(Some(2), 17), "3 / 17");
+        assert_eq!(format_search_count(None, 17), "- / 17");
+        assert_eq!(format_search_count(None, 0), "No matches");
+    }
+
+    #[test]
+    fn test_search_pattern_modes() {
+        let plain = SearchPattern::new("hello", false, false).unwrap();
+        assert!(plain.matches("Well, HELLO there"));
+
+        let exact = SearchPattern::new("Hello", true, false).unwrap();
+        assert!(exact.matches("Hello there"));
+        assert!(!exact.matches("hello there"));
+
+        let regex = SearchPattern::new(r"^\w+ (there|here)$", false, true).unwrap();
+        assert!(regex.matches("HELLO THERE"));
+        assert!(!regex.matches("hello everywhere"));
+        assert!(!SearchPattern::new("Hello", true, true).unwrap().matches("hello"));
+
+        // Regex syntax is literal text in plain mode, and invalid regexes match nothing
+        assert!(SearchPattern::new("(", false, false).unwrap().matches("(laughs)"));
+        assert!(SearchPattern::new("(", false, true).is_none());
+    }
+
+    #[test]
+    fn test_is_within_range() {
+        let entry = SubtitleEntry {
+            start_ms: 2000,
+            end_ms: 3000,
+            text: String::new(),
+        };
+        assert!(is_within_range(&entry, 0, (2000.0, 3000.0)));
+        assert!(!is_within_range(&entry, 0, (2500.0, 5000.0)));
+        // The offset moves the subtitle into the range
+        assert!(is_within_range(&entry, 1000, (2500.0, 5000.0)));
+    }
+
+    #[test]
+    fn test_format_offset() {
+        assert_eq!(format_offset(1250), "+1.250s");
+        assert_eq!(format_offset(-400), "-0.400s");
+        assert_eq!(format_offset(0), "+0.000s");
     }
 }
@@ -0,0 +1,208 @@
+//! Thumbnail strip for previewing the selected clip range
+//!
+//! Frames are extracted with the system ffmpeg CLI and cached as JPEGs in the
+//! temp directory, keyed by video path and timestamp, so moving a clip bound
+//! back and forth only extracts frames that haven't been seen before. The
+//! least recently used thumbnails are removed once the cache outgrows
+//! `MAX_CACHE_BYTES`.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::SystemTime;
+
+/// Number of thumbnails shown across the clip range
+pub const STRIP_CELLS: usize = 8;
+
+/// Width of each extracted thumbnail in pixels (height keeps the aspect ratio)
+const THUMBNAIL_WIDTH: u32 = 160;
+
+/// Size the thumbnail cache is trimmed back to (oldest first)
+const MAX_CACHE_BYTES: u64 = 100 * 1024 * 1024;
+
+/// The cache is checked on the first extraction and then after every this many
+const PRUNE_EVERY_EXTRACTIONS: usize = 500;
+
+/// Extractions so far, for spacing out the cache checks
+static EXTRACTIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Timestamps (in milliseconds) for each strip cell, taken from the middle of
+/// evenly-sized slices of the clip range
+pub fn strip_times_ms(start_ms: u64, end_ms: u64, count: usize) -> Vec<u64> {
+    if count == 0 || end_ms <= start_ms {
+        return Vec::new();
+    }
+
+    let span = (end_ms - start_ms) as f64;
+    (0..count)
+        .map(|idx| start_ms + (span * (idx as f64 + 0.5) / count as f64) as u64)
+        .collect()
+}
+
+/// Path of the cached thumbnail for a video frame
+pub fn thumbnail_cache_path(video_path: &str, time_ms: u64) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    video_path.hash(&mut hasher);
+
+    cache_dir().join(format!("{:016x}_{}.jpg", hasher.finish(), time_ms))
+}
+
+fn cache_dir() -> PathBuf {
+    std::env::temp_dir().join("asve_thumbnails")
+}
+
+/// Get the thumbnail for a video frame, extracting it with ffmpeg if it isn't cached
+pub fn cached_thumbnail(video_path: &str, time_ms: u64) -> Result<PathBuf, String> {
+    let output_path = thumbnail_cache_path(video_path, time_ms);
+    if output_path.exists() {
+        // Mark it as recently used so pruning removes other thumbnails first
+        let touched = std::fs::File::options()
+            .write(true)
+            .open(&output_path)
+            .and_then(|file| file.set_modified(SystemTime::now()));
+        if let Err(e) = touched {
+            eprintln!("Failed to touch thumbnail {}: {}", output_path.display(), e);
+        }
+        return Ok(output_path);
+    }
+
+    if let Some(dir) = output_path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create thumbnail directory: {}", e))?;
+    }
+
+    if EXTRACTIONS.fetch_add(1, Ordering::SeqCst) % PRUNE_EVERY_EXTRACTIONS == 0 {
+        prune_cache();
+    }
+
+    extract_thumbnail(video_path, time_ms, &output_path)?;
+    Ok(output_path)
+}
+
+/// Remove the least recently used thumbnails while the cache is over `MAX_CACHE_BYTES`
+fn prune_cache() {
+    let Ok(entries) = std::fs::read_dir(cache_dir()) else {
+        return;
+    };
+    let files: Vec<(PathBuf, SystemTime, u64)> = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let metadata = entry.metadata().ok()?;
+            let modified = metadata.modified().ok()?;
+            metadata
+                .is_file()
+                .then(|| (entry.path(), modified, metadata.len()))
+        })
+        .collect();
+
+    let evicted = files_to_evict(files, MAX_CACHE_BYTES);
+    for path in &evicted {
+        if let Err(e) = std::fs::remove_file(path) {
+            eprintln!(
+                "Failed to remove cached thumbnail {}: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+    if !evicted.is_empty() {
+        println!("Removed {} old thumbnail(s) from the cache", evicted.len());
+    }
+}
+
+/// Oldest files (by modification time) to delete so the rest fit in `max_bytes`
+fn files_to_evict(mut files: Vec<(PathBuf, SystemTime, u64)>, max_bytes: u64) -> Vec<PathBuf> {
+    let mut total: u64 = files.iter().map(|(_, _, size)| size).sum();
+    files.sort_by_key(|(_, modified, _)| *modified);
+
+    let mut evicted = Vec::new();
+    for (path, _, size) in files {
+        if total <= max_bytes {
+            break;
+        }
+        total -= size;
+        evicted.push(path);
+    }
+    evicted
+}
+
+/// Extract a single scaled-down frame at the given time
+fn extract_thumbnail(video_path: &str, time_ms: u64, output_path: &Path) -> Result<(), String> {
+    // Write to a temporary name first so a cancelled or failed extraction
+    // never leaves a truncated image in the cache
+    let partial_path = output_path.with_extension("partial.jpg");
+
+    let output = crate::ffmpeg_export::ffmpeg_command()
+        .arg("-ss")
+        .arg(format!("{:.3}", time_ms as f64 / 1000.0))
+        .arg("-i")
+        .arg(video_path)
+        .arg("-frames:v")
+        .arg("1")
+        .arg("-vf")
+        .arg(format!("scale={}:-2", THUMBNAIL_WIDTH))
+        .arg("-q:v")
+        .arg("5")
+        .arg("-an")
+        .arg("-y")
+        .arg(&partial_path)
+        .output()
+        .map_err(|e| crate::ffmpeg_export::Tool::Ffmpeg.spawn_error(&e))?;
+
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&partial_path);
+        return Err(format!(
+            "Thumbnail extraction failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    std::fs::rename(&partial_path, output_path)
+        .map_err(|e| format!("Failed to store thumbnail: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_times_are_centered_in_each_cell() {
+        assert_eq!(strip_times_ms(0, 8000, 4), vec![1000, 3000, 5000, 7000]);
+        assert_eq!(strip_times_ms(10_000, 12_000, 2), vec![10_500, 11_500]);
+    }
+
+    #[test]
+    fn strip_times_empty_for_invalid_range() {
+        assert!(strip_times_ms(5000, 5000, 8).is_empty());
+        assert!(strip_times_ms(6000, 5000, 8).is_empty());
+        assert!(strip_times_ms(0, 5000, 0).is_empty());
+    }
+
+    #[test]
+    fn cache_path_depends_on_video_and_time() {
+        let a = thumbnail_cache_path("/videos/a.mp4", 1000);
+        assert_eq!(a, thumbnail_cache_path("/videos/a.mp4", 1000));
+        assert_ne!(a, thumbnail_cache_path("/videos/b.mp4", 1000));
+        assert_ne!(a, thumbnail_cache_path("/videos/a.mp4", 2000));
+    }
+
+    #[test]
+    fn eviction_removes_oldest_until_under_limit() {
+        let at = |secs| SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+        let files = vec![
+            (PathBuf::from("new.jpg"), at(30), 40),
+            (PathBuf::from("old.jpg"), at(10), 40),
+            (PathBuf::from("mid.jpg"), at(20), 40),
+        ];
+        assert_eq!(
+            files_to_evict(files.clone(), 80),
+            vec![PathBuf::from("old.jpg")]
+        );
+        assert_eq!(
+            files_to_evict(files.clone(), 50),
+            vec![PathBuf::from("old.jpg"), PathBuf::from("mid.jpg")]
+        );
+        assert!(files_to_evict(files, 120).is_empty());
+    }
+}
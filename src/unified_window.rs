@@ -1,14 +1,14 @@
 use crate::theme::OneDarkExt;
 use gpui::{
-    canvas, div, prelude::*, px, Bounds, Context, Corners, Entity, IntoElement, Render,
-    RenderImage, Size, Window,
+    canvas, div, point, prelude::*, px, size, Bounds, Context, Corners, Entity, ExternalPaths,
+    FocusHandle, IntoElement, MouseButton, Render, RenderImage, ScrollWheelEvent, Size, Window,
 };
 use gpui_component::ActiveTheme;
 use serde::Deserialize;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
-use crate::controls_window::ControlsWindow;
+use crate::controls_window::{ControlsWindow, LoopPoint};
 use crate::custom_titlebar::CustomTitlebar;
 use crate::platform;
 use crate::subtitle_window::SubtitleWindow;
@@ -29,7 +29,233 @@ pub struct UnifiedWindow {
     last_bounds: Option<Bounds<gpui::Pixels>>,
     last_video_render_image: Arc<Mutex<Option<Arc<RenderImage>>>>,
     animation_start_time: Instant,
+    last_render_time: Instant, // For rate limiting the idle animation to the refresh rate cap
     triangle_frames: Vec<String>,
+    focus_handle: FocusHandle, // Focused by default so window-level shortcuts are dispatched
+    osd_shown_at: Option<Instant>, // When the on-screen display was last triggered
+    osd_restart_count: u64,        // Player restart count seen last, to show the OSD on seeks
+    display_mode: VideoDisplayMode,
+    video_pan: (f64, f64), // Pan of the 100% view, as fractions of the source size
+    source_size: Option<(f64, f64)>, // Source size seen by the last zoom sync
+    applied_zoom_pan: Option<(f64, f64, f64)>, // Zoom and pan last sent to mpv
+    window_buttons_hidden: bool, // Whether the native window buttons are hidden (borderless)
+    show_media_info: bool, // Whether the file info panel over the video is expanded
+    pub view_surface: Option<usize>, // Own video surface when this is a duplicate window
+}
+
+/// How the video frame is sized inside the video area
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VideoDisplayMode {
+    /// Scaled to fit the area, letterboxed
+    Fit,
+    /// One source pixel per screen pixel, centered; scroll to pan when larger than the area
+    ActualSize,
+}
+
+/// How long the on-screen display takes to fade out at the end
+const OSD_FADE_MS: u64 = 400;
+
+/// Opacity of the on-screen display `elapsed_ms` after it was triggered
+///
+/// Fully visible for most of `duration_ms`, then fades out linearly over the
+/// last `OSD_FADE_MS`. Returns 0.0 once it's gone (or when it's turned off).
+fn osd_opacity(elapsed_ms: u64, duration_ms: u64) -> f32 {
+    if elapsed_ms >= duration_ms {
+        return 0.0;
+    }
+    let fade_ms = OSD_FADE_MS.min(duration_ms);
+    let remaining_ms = duration_ms - elapsed_ms;
+    if remaining_ms >= fade_ms {
+        1.0
+    } else {
+        remaining_ms as f32 / fade_ms as f32
+    }
+}
+
+/// Most toasts shown at once (the newest ones)
+const MAX_VISIBLE_NOTIFICATIONS: usize = 3;
+
+/// Toasts still on screen at `now`, oldest first, with their opacity and
+/// whether they're errors
+fn visible_notifications(
+    notifications: &[(String, Instant, bool)],
+    now: Instant,
+) -> Vec<(String, f32, bool)> {
+    let duration_ms = crate::NOTIFICATION_DURATION.as_millis() as u64;
+    let visible: Vec<(String, f32, bool)> = notifications
+        .iter()
+        .map(|(message, shown_at, is_error)| {
+            let elapsed_ms = now.saturating_duration_since(*shown_at).as_millis() as u64;
+            let opacity = osd_opacity(elapsed_ms, duration_ms);
+            (message.clone(), opacity, *is_error)
+        })
+        .filter(|(_, opacity, _)| *opacity > 0.0)
+        .collect();
+    let skip = visible.len().saturating_sub(MAX_VISIBLE_NOTIFICATIONS);
+    visible.into_iter().skip(skip).collect()
+}
+
+/// Largest rectangle with the frame's aspect ratio that fits in `area`, centered in it
+///
+/// Returns (x offset, y offset, width, height).
+fn letterbox(area: (f32, f32), frame: (f32, f32)) -> (f32, f32, f32, f32) {
+    if frame.0 <= 0.0 || frame.1 <= 0.0 {
+        return (0.0, 0.0, area.0, area.1);
+    }
+    let scale = (area.0 / frame.0).min(area.1 / frame.1);
+    let (width, height) = (frame.0 * scale, frame.1 * scale);
+    ((area.0 - width) / 2.0, (area.1 - height) / 2.0, width, height)
+}
+
+/// Longest edge of the 100% view's render surface, in device pixels
+const MAX_ACTUAL_SIZE_SURFACE: f32 = 4096.0;
+
+/// Render surface for the 100% view: the video area in device pixels
+///
+/// Painted into an area of its own aspect ratio it fills it exactly, so every
+/// rendered pixel lands on one screen pixel.
+fn actual_size_surface(area: (f32, f32), scale_factor: f32) -> (u32, u32) {
+    let (width, height) = (area.0 * scale_factor, area.1 * scale_factor);
+    let scale = (MAX_ACTUAL_SIZE_SURFACE / width.max(height)).min(1.0);
+    (
+        ((width * scale).round() as u32).max(1),
+        ((height * scale).round() as u32).max(1),
+    )
+}
+
+/// mpv `video-zoom` that shows the source at one pixel per surface pixel
+///
+/// mpv fits the source into the render surface before zooming.
+fn actual_size_zoom(source: (f64, f64), surface: (f64, f64)) -> f64 {
+    let fit_scale = (surface.0 / source.0).min(surface.1 / source.1);
+    -fit_scale.log2()
+}
+
+/// Furthest the 100% view can pan from center along one axis, as a fraction of the source
+///
+/// Zero when the source fits in the shown length.
+fn max_pan(source_len: f64, shown_len: f64) -> f64 {
+    ((source_len - shown_len) / 2.0).max(0.0) / source_len
+}
+
+/// Windows narrower than this stack the subtitle pane under the video so the
+/// video keeps its width
+const NARROW_WINDOW_WIDTH: f32 = 720.0;
+
+/// Share of the height above the controls taken by the stacked subtitle pane
+const STACKED_SUBTITLE_SHARE: f32 = 0.3;
+
+/// The subtitle pane stops growing past this width on large windows
+const MAX_SUBTITLE_PANE_WIDTH: f32 = 520.0;
+
+/// Bounds for the controls height: cramped below, mostly empty space above
+const MIN_CONTROLS_HEIGHT: f32 = 200.0;
+const MAX_CONTROLS_HEIGHT: f32 = 340.0;
+
+/// Sizes of the video, subtitle and controls panes, in pixels
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PaneLayout {
+    pub video_width: f32,
+    pub video_height: f32,
+    pub subtitle_width: f32,
+    pub subtitle_height: f32,
+    pub controls_height: f32,
+    /// The subtitle pane sits under the video instead of beside it
+    pub stacked: bool,
+}
+
+/// Width of the subtitle pane: 24% of the window, capped when wide, and the full
+/// width when narrow (stacked under the video)
+pub fn subtitle_pane_width(total_width: f32) -> f32 {
+    if total_width < NARROW_WINDOW_WIDTH {
+        total_width
+    } else {
+        (total_width * 0.24).min(MAX_SUBTITLE_PANE_WIDTH)
+    }
+}
+
+/// Split the window below the titlebar into video, subtitle and controls panes
+///
+/// Normally 76/24 across and 75/25 down. Narrow windows stack the subtitles
+/// under the video instead. The controls keep a usable height on short windows
+/// (up to half the height) and don't grow without bound on tall ones.
+pub fn pane_layout(total_width: f32, available_height: f32) -> PaneLayout {
+    let subtitle_width = subtitle_pane_width(total_width);
+    let controls_height = (available_height * 0.25)
+        .clamp(MIN_CONTROLS_HEIGHT, MAX_CONTROLS_HEIGHT)
+        .min(available_height * 0.5);
+    let top_height = available_height - controls_height;
+    if total_width < NARROW_WINDOW_WIDTH {
+        let subtitle_height = top_height * STACKED_SUBTITLE_SHARE;
+        PaneLayout {
+            video_width: total_width,
+            video_height: top_height - subtitle_height,
+            subtitle_width,
+            subtitle_height,
+            controls_height,
+            stacked: true,
+        }
+    } else {
+        PaneLayout {
+            video_width: total_width - subtitle_width,
+            video_height: top_height,
+            subtitle_width,
+            subtitle_height: top_height,
+            controls_height,
+            stacked: false,
+        }
+    }
+}
+
+/// Format a time for the on-screen display (H:MM:SS for long videos, MM:SS otherwise)
+fn format_osd_time(seconds: f32, with_hours: bool) -> String {
+    let total_secs = seconds.max(0.0) as u64;
+    if with_hours {
+        format!(
+            "{}:{:02}:{:02}",
+            total_secs / 3600,
+            (total_secs % 3600) / 60,
+            total_secs % 60
+        )
+    } else {
+        format!("{:02}:{:02}", total_secs / 60, total_secs % 60)
+    }
+}
+
+/// Label/value rows of the file info panel; unknown values are left out
+fn media_info_rows(info: &crate::ffmpeg_export::MediaInfo) -> Vec<(&'static str, String)> {
+    let mut rows = Vec::new();
+    if let Some((width, height)) = info.resolution {
+        rows.push(("Resolution", format!("{}×{}", width, height)));
+    }
+    if let Some(fps) = info.fps {
+        rows.push(("Frame rate", format!("{:.3} fps", fps)));
+    }
+    if info.duration_secs > 0.0 {
+        rows.push(("Duration", format_osd_time(info.duration_secs, true)));
+    }
+    if let Some(codec) = &info.video_codec {
+        let mut value = codec.clone();
+        if info.is_hdr {
+            value.push_str(" (HDR)");
+        }
+        if info.is_interlaced {
+            value.push_str(" (interlaced)");
+        }
+        rows.push(("Video", value));
+    }
+    if let Some(codec) = &info.audio_codec {
+        rows.push(("Audio", codec.clone()));
+    }
+    if let Some(bit_rate) = info.bit_rate {
+        let value = if bit_rate >= 1_000_000 {
+            format!("{:.1} Mbit/s", bit_rate as f64 / 1_000_000.0)
+        } else {
+            format!("{} kbit/s", bit_rate / 1000)
+        };
+        rows.push(("Bit rate", value));
+    }
+    rows
 }
 
 impl UnifiedWindow {
@@ -60,6 +286,9 @@ impl UnifiedWindow {
         // Load triangle frames from JSON file
         let triangle_frames = Self::load_triangle_frames();
 
+        let focus_handle = cx.focus_handle();
+        focus_handle.focus(window);
+
         Self {
             titlebar,
             controls,
@@ -71,7 +300,18 @@ impl UnifiedWindow {
             last_bounds: None,
             last_video_render_image: Arc::new(Mutex::new(None)),
             animation_start_time: Instant::now(),
+            last_render_time: Instant::now(),
             triangle_frames,
+            focus_handle,
+            osd_shown_at: None,
+            osd_restart_count: 0,
+            display_mode: VideoDisplayMode::Fit,
+            video_pan: (0.0, 0.0),
+            source_size: None,
+            applied_zoom_pan: None,
+            window_buttons_hidden: false,
+            show_media_info: false,
+            view_surface: None,
         }
     }
 
@@ -150,18 +390,18 @@ impl UnifiedWindow {
     fn resize_video_nsview(&self, window_bounds: Bounds<gpui::Pixels>, cx: &mut Context<Self>) {
         let app_state = cx.global::<crate::AppState>();
 
-        // Get the child window/view handle if it exists
-        if let Some(child_handle) = app_state.video_nsview {
-            // Calculate new video area dimensions
-            let video_width_px = window_bounds.size.width * 0.76;
-            let video_height_px = window_bounds.size.height * 0.75;
-
-            let width_str = format!("{}", video_width_px);
-            let height_str = format!("{}", video_height_px);
+        // Get the child window/view handle if it exists (a duplicate has its own)
+        if let Some(child_handle) = self.view_surface.or(app_state.video_nsview) {
+            // Calculate new video area dimensions (same layout as render)
+            let titlebar_height = if app_state.borderless { 0.0 } else { 37.0 };
+            let layout = pane_layout(
+                f32::from(window_bounds.size.width),
+                f32::from(window_bounds.size.height) - titlebar_height,
+            );
             let window_height_str = format!("{}", window_bounds.size.height);
 
-            let video_width: f64 = width_str.trim_end_matches("px").parse().unwrap_or(960.0);
-            let video_height: f64 = height_str.trim_end_matches("px").parse().unwrap_or(540.0);
+            let video_width = layout.video_width as f64;
+            let video_height = layout.video_height as f64;
             let window_height: f64 = window_height_str
                 .trim_end_matches("px")
                 .parse()
@@ -178,17 +418,179 @@ impl UnifiedWindow {
     }
 }
 
+impl UnifiedWindow {
+    /// Switch between fit and 100% display, recentering the picture
+    pub fn set_display_mode(&mut self, mode: VideoDisplayMode, cx: &mut Context<Self>) {
+        self.display_mode = mode;
+        self.video_pan = (0.0, 0.0);
+        cx.notify();
+    }
+
+    /// Pan the 100% view with the scroll wheel (clamped on the next render)
+    fn scroll_video(&mut self, event: &ScrollWheelEvent, window: &Window, cx: &mut Context<Self>) {
+        if self.display_mode != VideoDisplayMode::ActualSize {
+            return;
+        }
+        let Some((source_width, source_height)) = self.source_size else {
+            return;
+        };
+        let delta = event.delta.pixel_delta(window.line_height());
+        let scale = window.scale_factor() as f64;
+        self.video_pan.0 += f32::from(delta.x) as f64 * scale / source_width;
+        self.video_pan.1 += f32::from(delta.y) as f64 * scale / source_height;
+        cx.notify();
+    }
+
+    /// Apply the display mode to mpv for the current video area size
+    ///
+    /// Runs on every render so resizes, DPI changes and newly loaded files keep
+    /// the mode; mpv is only told when the zoom or pan actually changes.
+    fn sync_video_zoom(&mut self, area: (f32, f32), scale_factor: f32, cx: &mut Context<Self>) {
+        let app_state = cx.global::<crate::AppState>();
+
+        let Ok(player) = app_state.video_player.lock() else {
+            return;
+        };
+
+        // A duplicate draws its own view at its screen size; mpv's zoom and pan
+        // are shared, so they follow the main window
+        if let Some(surface) = self.view_surface {
+            let (width, height) = actual_size_surface(area, scale_factor);
+            player.set_view_render_size(surface, width, height);
+            return;
+        }
+
+        let target = match self.display_mode {
+            VideoDisplayMode::Fit => {
+                let (width, height) = crate::video_player::DEFAULT_RENDER_SIZE;
+                player.set_render_size(width, height);
+                (0.0, 0.0, 0.0)
+            }
+            VideoDisplayMode::ActualSize => {
+                let Some((width, height)) = player.source_dimensions() else {
+                    return;
+                };
+                let source = (width as f64, height as f64);
+                let (surface_width, surface_height) = actual_size_surface(area, scale_factor);
+                player.set_render_size(surface_width, surface_height);
+                let surface = (surface_width as f64, surface_height as f64);
+
+                let max_x = max_pan(source.0, surface.0);
+                let max_y = max_pan(source.1, surface.1);
+                self.video_pan = (
+                    self.video_pan.0.clamp(-max_x, max_x),
+                    self.video_pan.1.clamp(-max_y, max_y),
+                );
+                self.source_size = Some(source);
+
+                let zoom = actual_size_zoom(source, surface);
+                (zoom, self.video_pan.0, self.video_pan.1)
+            }
+        };
+
+        if self.applied_zoom_pan != Some(target) {
+            match player.set_video_zoom_pan(target.0, target.1, target.2) {
+                Ok(()) => self.applied_zoom_pan = Some(target),
+                Err(e) => eprintln!("Failed to set video zoom: {}", e),
+            }
+        }
+    }
+
+    /// Small toggle over the video for picking a display mode
+    fn display_mode_button(
+        &self,
+        label: &'static str,
+        mode: VideoDisplayMode,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let opacity = if self.display_mode == mode { 0.8 } else { 0.4 };
+        div()
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .cursor_pointer()
+            .bg(gpui::black().opacity(opacity))
+            .text_color(gpui::white())
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _, _, cx| {
+                    // Don't start a borderless window drag from the video area
+                    cx.stop_propagation();
+                    this.set_display_mode(mode, cx);
+                }),
+            )
+            .child(label)
+    }
+
+    /// Lines of the on-screen display and its opacity, or None while it's hidden
+    ///
+    /// Seeks (player restarts) bring it up as well as mouse movement.
+    fn osd_lines(&mut self, cx: &mut Context<Self>) -> Option<(Vec<String>, f32)> {
+        let app_state = cx.global::<crate::AppState>();
+        let osd = app_state.osd;
+        let file_name = app_state
+            .file_path
+            .as_ref()
+            .and_then(|path| std::path::Path::new(path).file_name())
+            .map(|name| name.to_string_lossy().to_string());
+
+        let player = app_state.video_player.lock().ok()?;
+        let restart_count = player.playback_restart_count();
+        if restart_count != self.osd_restart_count {
+            self.osd_restart_count = restart_count;
+            self.osd_shown_at = Some(Instant::now());
+        }
+
+        let elapsed_ms = self.osd_shown_at?.elapsed().as_millis() as u64;
+        let opacity = osd_opacity(elapsed_ms, osd.duration_ms);
+        if opacity <= 0.0 {
+            return None;
+        }
+
+        let mut lines = Vec::new();
+        if osd.show_title {
+            if let Some(file_name) = file_name {
+                lines.push(file_name);
+            }
+        }
+        if osd.show_time {
+            if let Some((position, duration)) = player.get_position_duration() {
+                let position_secs = position.nseconds() as f32 / 1_000_000_000.0;
+                let duration_secs = duration.nseconds() as f32 / 1_000_000_000.0;
+                let with_hours = duration_secs >= 3600.0;
+                lines.push(format!(
+                    "{} / {}",
+                    format_osd_time(position_secs, with_hours),
+                    format_osd_time(duration_secs, with_hours)
+                ));
+            }
+        }
+        if osd.show_speed {
+            lines.push(format!("Speed {:.2}x", player.speed()));
+        }
+
+        (!lines.is_empty()).then_some((lines, opacity))
+    }
+}
+
 impl Render for UnifiedWindow {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         // Check if a video is loaded
         let app_state = cx.global::<crate::AppState>();
         let has_video_loaded = app_state.has_video_loaded;
+        let borderless = app_state.borderless;
+
+        // Borderless mode hides the window buttons along with the titlebar
+        if borderless != self.window_buttons_hidden {
+            platform::set_window_buttons_hidden(window, borderless);
+            self.window_buttons_hidden = borderless;
+        }
 
         // Request continuous animation when no video is loaded
         if !has_video_loaded {
             cx.on_next_frame(window, |this, _window, cx| {
                 // Request another frame for continuous portal animation
-                cx.notify();
+                crate::notify_rate_limited(&mut this.last_render_time, cx);
             });
         }
 
@@ -216,15 +618,23 @@ impl Render for UnifiedWindow {
         self.last_bounds = Some(window_bounds);
 
         // Calculate layout dimensions based on proportions
-        // Titlebar takes 37px, remaining height is split: 75% video, 25% controls
-        let titlebar_height = px(37.0);
+        // Titlebar takes 37px (none when borderless), remaining height is split
+        // between video and controls (see pane_layout for the breakpoints)
+        let titlebar_height = if borderless { px(0.0) } else { px(37.0) };
         let available_height = total_height - titlebar_height;
-        let video_section_height = available_height * 0.75;
-        let controls_height = available_height * 0.25;
+        let layout = pane_layout(f32::from(total_width), f32::from(available_height));
+        let video_section_height = px(layout.video_height);
+        let controls_height = px(layout.controls_height);
 
-        // Video takes 76% of width, subtitles take 24%
-        let video_width = total_width * 0.76;
-        let subtitle_width = total_width * 0.24;
+        // Video takes 76% of width, subtitles take 24% (stacked below on narrow windows)
+        let video_width = px(layout.video_width);
+        let subtitle_width = px(layout.subtitle_width);
+        let subtitle_height = px(layout.subtitle_height);
+        let top_height = if layout.stacked {
+            video_section_height + subtitle_height
+        } else {
+            video_section_height
+        };
 
         // Update stored video area size for NSView positioning
         self.video_area_size = Size {
@@ -232,28 +642,152 @@ impl Render for UnifiedWindow {
             height: video_section_height,
         };
 
+        if has_video_loaded {
+            let area = (f32::from(video_width), f32::from(video_section_height));
+            self.sync_video_zoom(area, window.scale_factor(), cx);
+        }
+
+        // On-screen display over the video; keep repainting until it has faded out
+        let osd = if has_video_loaded {
+            self.osd_lines(cx)
+        } else {
+            None
+        };
+        if osd.is_some() {
+            cx.on_next_frame(window, |this, _window, cx| {
+                crate::notify_rate_limited(&mut this.last_render_time, cx);
+            });
+        }
+
+        // Toasts; drop the expired ones and keep repainting while any are fading
+        let now = Instant::now();
+        let toasts = visible_notifications(&cx.global::<crate::AppState>().notifications, now);
+        if !cx.global::<crate::AppState>().notifications.is_empty() {
+            cx.update_global::<crate::AppState, _>(|state, _| {
+                state.notifications.retain(|(_, shown_at, _)| {
+                    now.saturating_duration_since(*shown_at) < crate::NOTIFICATION_DURATION
+                });
+            });
+        }
+        if !toasts.is_empty() {
+            cx.on_next_frame(window, |this, _window, cx| {
+                crate::notify_rate_limited(&mut this.last_render_time, cx);
+            });
+        }
+
         let theme = cx.theme();
         div()
+            .key_context("UnifiedWindow")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(|this, _: &crate::CycleSubtitleTrack, window, cx| {
+                this.subtitles.update(cx, |subtitles, cx| {
+                    subtitles.cycle_subtitle_track(window, cx);
+                });
+            }))
+            .on_action(cx.listener(|this, _: &crate::SetLoopA, _, cx| {
+                this.controls.update(cx, |controls, cx| {
+                    controls.press_loop_point(LoopPoint::A, cx);
+                });
+            }))
+            .on_action(cx.listener(|this, _: &crate::SetLoopB, _, cx| {
+                this.controls.update(cx, |controls, cx| {
+                    controls.press_loop_point(LoopPoint::B, cx);
+                });
+            }))
+            .on_action(cx.listener(|this, _: &crate::ClearLoop, _, cx| {
+                this.controls.update(cx, |controls, cx| {
+                    controls.clear_ab_loop(cx);
+                });
+            }))
+            .on_action(cx.listener(|this, _: &crate::SetClipStart, window, cx| {
+                this.controls.update(cx, |controls, cx| {
+                    controls.mark_clip_start(window, cx);
+                });
+            }))
+            .on_action(cx.listener(|this, _: &crate::SetClipEnd, window, cx| {
+                this.controls.update(cx, |controls, cx| {
+                    controls.mark_clip_end(window, cx);
+                });
+            }))
+            .on_action(cx.listener(|this, _: &crate::RepeatExport, _, cx| {
+                this.controls.update(cx, |controls, cx| {
+                    controls.repeat_last_export(cx);
+                });
+            }))
+            .on_action(cx.listener(|this, _: &crate::TogglePlay, _, cx| {
+                this.controls.update(cx, |controls, cx| {
+                    controls.toggle_play(cx);
+                });
+            }))
+            .on_action(cx.listener(|this, _: &crate::FrameStepForward, _, cx| {
+                this.controls.update(cx, |controls, cx| {
+                    controls.step_frame(true, cx);
+                });
+            }))
+            .on_action(cx.listener(|this, _: &crate::FrameStepBackward, _, cx| {
+                this.controls.update(cx, |controls, cx| {
+                    controls.step_frame(false, cx);
+                });
+            }))
+            .on_action(cx.listener(|this, action: &crate::SeekBy, _, cx| {
+                this.controls.update(cx, |controls, cx| {
+                    controls.seek_by(action.0, cx);
+                });
+            }))
+            .on_action(cx.listener(|this, _: &crate::ZoomToFit, _, cx| {
+                this.set_display_mode(VideoDisplayMode::Fit, cx);
+            }))
+            .on_action(cx.listener(|this, _: &crate::ZoomActualSize, _, cx| {
+                this.set_display_mode(VideoDisplayMode::ActualSize, cx);
+            }))
+            // Dropping a video file opens it in place of the current one
+            .drag_over::<ExternalPaths>(|style, _, _, cx| style.bg(cx.theme().element_hover()))
+            .on_drop(cx.listener(|_, paths: &ExternalPaths, _, cx| {
+                if let Some(path) = paths.paths().first().cloned() {
+                    // Opening replaces this window, so wait until its event handling is done
+                    cx.defer(move |cx| crate::open_video_path(cx, &path));
+                }
+            }))
             .flex()
             .flex_col()
             .bg(theme.editor_background())
             .size_full()
             // Custom titlebar
-            .child(self.titlebar.clone())
-            // Top section: video (left) and subtitles (right)
+            .when(!borderless, |el| el.child(self.titlebar.clone()))
+            // Top section: video (left) and subtitles (right), or stacked when narrow
             .child(
                 div()
                     .flex()
-                    .flex_row()
+                    .when(layout.stacked, |el| el.flex_col())
+                    .when(!layout.stacked, |el| el.flex_row())
                     .w(total_width)
-                    .h(video_section_height)
+                    .h(top_height)
                     // Video area - either show portal or video canvas
                     .child(
                         div()
                             .id("video-area")
+                            .relative()
                             .w(video_width)
                             .h(video_section_height)
                             .bg(theme.editor_background())
+                            .on_mouse_move(cx.listener(|this, _, _, cx| {
+                                this.osd_shown_at = Some(Instant::now());
+                                cx.notify();
+                            }))
+                            // Without a titlebar, the video is the handle for moving the window
+                            .when(borderless, |el| {
+                                el.on_mouse_down(
+                                    MouseButton::Left,
+                                    cx.listener(|_, _, window, _| {
+                                        platform::start_window_drag(window);
+                                    }),
+                                )
+                            })
+                            .on_scroll_wheel(cx.listener(
+                                |this, event: &ScrollWheelEvent, window, cx| {
+                                    this.scroll_video(event, window, cx);
+                                },
+                            ))
                             .when(!has_video_loaded, |el| {
                                 // Show rotating triangle when no video is loaded
                                 let triangle = self.generate_rotating_triangle();
@@ -271,6 +805,7 @@ impl Render for UnifiedWindow {
                             .when(has_video_loaded, |el| {
                                 // Show video canvas when video is loaded
                                 let last_image = self.last_video_render_image.clone();
+                                let view_surface = self.view_surface;
 
                                 el.child(
                                     canvas(
@@ -282,8 +817,13 @@ impl Render for UnifiedWindow {
                                             // Prepare frame data
                                             if let Ok(player) = video_player.lock() {
                                                 // Get Arc<Vec<u8>> - cheap Arc clone, no Vec clone!
-                                                let frame_buffer_arc = player.get_frame_buffer();
-                                                let (width, height) = player.get_video_dimensions();
+                                                // Buffer and size together, so a resize between
+                                                // the two reads can't mismatch them
+                                                let frame = match view_surface {
+                                                    Some(surface) => player.get_view_frame(surface),
+                                                    None => Some(player.get_frame()),
+                                                };
+                                                let (frame_buffer_arc, (width, height)) = frame?;
 
                                                 // Release the player lock
                                                 drop(player);
@@ -308,16 +848,33 @@ impl Render for UnifiedWindow {
                                                         0,
                                                         Delay::from_numer_denom_ms(0, 1),
                                                     );
-                                                    return Some(smallvec::smallvec![frame]);
+                                                    let frames = smallvec::smallvec![frame];
+                                                    return Some((frames, (width, height)));
                                                 }
                                             }
                                             None
                                         },
                                         move |bounds, frame_data, window, _cx| {
                                             // Paint the frame (paint phase)
-                                            if let Some(frames) = frame_data {
+                                            if let Some((frames, (width, height))) = frame_data {
                                                 let new_image = Arc::new(RenderImage::new(frames));
 
+                                                // Keep the frame's aspect ratio, letterboxed
+                                                let (x, y, width, height) = letterbox(
+                                                    (
+                                                        f32::from(bounds.size.width),
+                                                        f32::from(bounds.size.height),
+                                                    ),
+                                                    (width as f32, height as f32),
+                                                );
+                                                let frame_bounds = Bounds::new(
+                                                    point(
+                                                        bounds.origin.x + px(x),
+                                                        bounds.origin.y + px(y),
+                                                    ),
+                                                    size(px(width), px(height)),
+                                                );
+
                                                 // Drop the previous frame from sprite atlas before painting new one
                                                 if let Ok(mut last) = last_image.lock() {
                                                     if let Some(old_image) = last.take() {
@@ -326,7 +883,7 @@ impl Render for UnifiedWindow {
 
                                                     // Paint the new frame
                                                     let _ = window.paint_image(
-                                                        bounds,
+                                                        frame_bounds,
                                                         Corners::default(),
                                                         new_image.clone(),
                                                         0,     // frame_index
@@ -342,6 +899,150 @@ impl Render for UnifiedWindow {
                                     .w_full()
                                     .h_full(),
                                 )
+                            })
+                            // Duplicates follow the main window's display mode
+                            .when(has_video_loaded && self.view_surface.is_none(), |el| {
+                                el.child(
+                                    div()
+                                        .absolute()
+                                        .bottom_3()
+                                        .right_3()
+                                        .flex()
+                                        .gap_1()
+                                        .text_xs()
+                                        .child(self.display_mode_button(
+                                            "Fit",
+                                            VideoDisplayMode::Fit,
+                                            cx,
+                                        ))
+                                        .child(self.display_mode_button(
+                                            "100%",
+                                            VideoDisplayMode::ActualSize,
+                                            cx,
+                                        )),
+                                )
+                            })
+                            // File info: a toggle in the corner, expanding into a panel above it
+                            .when(has_video_loaded, |el| {
+                                let rows = cx
+                                    .global::<crate::AppState>()
+                                    .media_info
+                                    .as_ref()
+                                    .map(media_info_rows)
+                                    .unwrap_or_default();
+                                let show_media_info = self.show_media_info;
+                                el.child(
+                                    div()
+                                        .absolute()
+                                        .bottom_3()
+                                        .left_3()
+                                        .flex()
+                                        .flex_col()
+                                        .items_start()
+                                        .gap_1()
+                                        .text_xs()
+                                        .text_color(gpui::white())
+                                        .when(show_media_info, |el| {
+                                            el.child(
+                                                div()
+                                                    .px_3()
+                                                    .py_2()
+                                                    .rounded_md()
+                                                    .bg(gpui::black().opacity(0.7))
+                                                    .flex()
+                                                    .flex_col()
+                                                    .when(rows.is_empty(), |el| {
+                                                        el.child("No file information available")
+                                                    })
+                                                    .children(rows.into_iter().map(
+                                                        |(label, value)| {
+                                                            div()
+                                                                .flex()
+                                                                .gap_2()
+                                                                .child(
+                                                                    div()
+                                                                        .w(px(72.0))
+                                                                        .opacity(0.7)
+                                                                        .child(label),
+                                                                )
+                                                                .child(value)
+                                                        },
+                                                    )),
+                                            )
+                                        })
+                                        .child(
+                                            div()
+                                                .px_2()
+                                                .py_1()
+                                                .rounded_md()
+                                                .cursor_pointer()
+                                                .bg(gpui::black().opacity(if show_media_info {
+                                                    0.8
+                                                } else {
+                                                    0.4
+                                                }))
+                                                .on_mouse_down(
+                                                    MouseButton::Left,
+                                                    cx.listener(|this, _, _, cx| {
+                                                        // Don't start a borderless window drag
+                                                        cx.stop_propagation();
+                                                        this.show_media_info =
+                                                            !this.show_media_info;
+                                                        cx.notify();
+                                                    }),
+                                                )
+                                                .child("Info"),
+                                        ),
+                                )
+                            })
+                            .when_some(osd, |el, (lines, opacity)| {
+                                el.child(
+                                    div()
+                                        .absolute()
+                                        .top_3()
+                                        .left_3()
+                                        .px_3()
+                                        .py_2()
+                                        .rounded_md()
+                                        .bg(gpui::black().opacity(0.6))
+                                        .text_color(gpui::white())
+                                        .text_sm()
+                                        .opacity(opacity)
+                                        .flex()
+                                        .flex_col()
+                                        .children(lines),
+                                )
+                            })
+                            .when(!toasts.is_empty(), |el| {
+                                el.child(
+                                    div()
+                                        .absolute()
+                                        .top_3()
+                                        .right_3()
+                                        .max_w(px(360.0))
+                                        .flex()
+                                        .flex_col()
+                                        .gap_2()
+                                        .children(toasts.into_iter().map(
+                                            |(message, opacity, is_error)| {
+                                                // Status messages look like the OSD
+                                                let background = if is_error {
+                                                    theme.error().opacity(0.9)
+                                                } else {
+                                                    gpui::black().opacity(0.6)
+                                                };
+                                                div()
+                                                    .px_3()
+                                                    .py_2()
+                                                    .rounded_md()
+                                                    .bg(background)
+                                                    .text_color(gpui::white())
+                                                    .text_sm()
+                                                    .opacity(opacity)
+                                                    .child(message)
+                                            },
+                                        )),
+                                )
                             }),
                     )
                     // Subtitle window area
@@ -350,18 +1051,151 @@ impl Render for UnifiedWindow {
                             .id("subtitle-area")
                             .flex()
                             .w(subtitle_width)
-                            .h(video_section_height)
+                            .h(subtitle_height)
                             .child(self.subtitles.clone()),
                     ),
             )
-            // Bottom section: controls
+            // Bottom section: controls (scrolls when they stack on narrow windows)
             .child(
                 div()
                     .id("controls-area")
                     .flex()
                     .w(total_width)
                     .h(controls_height)
+                    .overflow_y_scroll()
                     .child(self.controls.clone()),
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visible_notifications() {
+        let now = Instant::now();
+        let ago = |ms: u64| now - std::time::Duration::from_millis(ms);
+        let notifications = vec![
+            ("expired".to_string(), ago(5000), true),
+            ("fading".to_string(), ago(3800), true),
+            ("a".to_string(), ago(300), true),
+            ("b".to_string(), ago(200), false),
+            ("c".to_string(), ago(100), true),
+        ];
+
+        // Only the newest few are shown, oldest first
+        let visible = visible_notifications(&notifications, now);
+        let messages: Vec<&str> = visible.iter().map(|(m, _, _)| m.as_str()).collect();
+        assert_eq!(messages, ["a", "b", "c"]);
+        assert!(visible.iter().all(|(_, opacity, _)| *opacity == 1.0));
+        assert!(!visible[1].2);
+
+        let visible = visible_notifications(&notifications[..2], now);
+        assert_eq!(visible.len(), 1);
+        assert!(visible[0].1 > 0.0 && visible[0].1 < 1.0);
+    }
+
+    #[test]
+    fn test_osd_opacity_fades_at_the_end() {
+        assert_eq!(osd_opacity(0, 2000), 1.0);
+        assert_eq!(osd_opacity(1600, 2000), 1.0);
+        assert_eq!(osd_opacity(1800, 2000), 0.5);
+        assert_eq!(osd_opacity(2000, 2000), 0.0);
+        // Turned off
+        assert_eq!(osd_opacity(0, 0), 0.0);
+    }
+
+    #[test]
+    fn test_media_info_rows() {
+        let info = crate::ffmpeg_export::MediaInfo {
+            fps: Some(23.976),
+            resolution: Some((1920, 1080)),
+            duration_secs: 3723.0,
+            video_codec: Some("h264".to_string()),
+            audio_codec: Some("aac".to_string()),
+            bit_rate: Some(4_200_000),
+            is_hdr: false,
+            is_interlaced: true,
+        };
+        assert_eq!(
+            media_info_rows(&info),
+            [
+                ("Resolution", "1920×1080".to_string()),
+                ("Frame rate", "23.976 fps".to_string()),
+                ("Duration", "1:02:03".to_string()),
+                ("Video", "h264 (interlaced)".to_string()),
+                ("Audio", "aac".to_string()),
+                ("Bit rate", "4.2 Mbit/s".to_string()),
+            ]
+        );
+
+        // Audio-only file
+        let info = crate::ffmpeg_export::MediaInfo {
+            audio_codec: Some("mp3".to_string()),
+            bit_rate: Some(320_000),
+            ..Default::default()
+        };
+        assert_eq!(
+            media_info_rows(&info),
+            [("Audio", "mp3".to_string()), ("Bit rate", "320 kbit/s".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_format_osd_time() {
+        assert_eq!(format_osd_time(83.9, false), "01:23");
+        assert_eq!(format_osd_time(3723.0, true), "1:02:03");
+    }
+
+    #[test]
+    fn test_letterbox_and_actual_size_geometry() {
+        // 16:9 frame in a wider area: pillarboxed and centered
+        assert_eq!(letterbox((1000.0, 450.0), (960.0, 540.0)), (100.0, 0.0, 800.0, 450.0));
+        assert_eq!(letterbox((800.0, 600.0), (960.0, 540.0)), (0.0, 75.0, 800.0, 450.0));
+
+        // 1920x1080 fitted into a 960x540 surface needs 2x
+        assert_eq!(actual_size_zoom((1920.0, 1080.0), (960.0, 540.0)), 1.0);
+        // ...is already 1:1 in a 1920x1080 one
+        assert_eq!(actual_size_zoom((1920.0, 1080.0), (1920.0, 1080.0)), 0.0);
+        // ...and 1280x720 fitted into 2560x1600 is shown at 2x, so halve it
+        assert_eq!(actual_size_zoom((1280.0, 720.0), (2560.0, 1600.0)), -1.0);
+
+        // The surface is the area in device pixels, capped at 4096 on its long edge
+        assert_eq!(actual_size_surface((912.0, 750.0), 2.0), (1824, 1500));
+        assert_eq!(actual_size_surface((4000.0, 1000.0), 2.0), (4096, 1024));
+
+        assert_eq!(max_pan(1920.0, 960.0), 0.25);
+        assert_eq!(max_pan(640.0, 960.0), 0.0);
+    }
+
+    #[test]
+    fn test_pane_layout_breakpoints() {
+        // Regular window: 76/24 across, 75/25 down
+        let layout = pane_layout(1200.0, 1000.0);
+        assert_eq!(layout.subtitle_width, 288.0);
+        assert_eq!(layout.video_width, 912.0);
+        assert_eq!(layout.controls_height, 250.0);
+        assert_eq!(layout.video_height, 750.0);
+        assert!(!layout.stacked);
+
+        // Narrow: the subtitles stack under the video, both full width
+        let layout = pane_layout(600.0, 500.0);
+        assert!(layout.stacked);
+        assert_eq!(layout.subtitle_width, 600.0);
+        assert_eq!(layout.video_width, 600.0);
+        // Short: the controls keep their minimum height
+        assert_eq!(layout.controls_height, 200.0);
+        assert_eq!(layout.subtitle_height, 90.0);
+        assert_eq!(layout.video_height, 210.0);
+
+        // Tiny: the controls never take more than half the height
+        assert_eq!(pane_layout(600.0, 300.0).controls_height, 150.0);
+
+        // Large: the subtitle pane and controls stop growing
+        let layout = pane_layout(3000.0, 2000.0);
+        assert_eq!(layout.subtitle_width, 520.0);
+        assert_eq!(layout.video_width, 2480.0);
+        assert_eq!(layout.controls_height, 340.0);
+    }
+}
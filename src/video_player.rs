@@ -28,6 +28,9 @@ use windows::Win32::Graphics::OpenGL::*;
 #[cfg(target_os = "windows")]
 use windows::Win32::UI::WindowsAndMessaging::GetClientRect;
 
+#[cfg(target_os = "linux")]
+use x11::{glx, xlib};
+
 // Include generated mpv bindings
 #[allow(non_upper_case_globals)]
 #[allow(non_camel_case_types)]
@@ -80,6 +83,50 @@ unsafe impl Send for SendGLContext {}
 #[cfg(target_os = "windows")]
 unsafe impl Sync for SendGLContext {}
 
+/// Wrapper for the X display, window and GLXContext that implements Send (Linux)
+/// Safe because we only use it from the render thread (XInitThreads has been called)
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy)]
+struct SendGLContext {
+    display: usize,
+    window: u64,
+    context: usize,
+}
+#[cfg(target_os = "linux")]
+unsafe impl Send for SendGLContext {}
+#[cfg(target_os = "linux")]
+unsafe impl Sync for SendGLContext {}
+
+#[cfg(target_os = "linux")]
+impl SendGLContext {
+    /// Make the GLX context current on the calling thread
+    unsafe fn make_current(&self) -> bool {
+        unsafe {
+            glx::glXMakeCurrent(
+                self.display as *mut xlib::Display,
+                self.window,
+                self.context as glx::GLXContext,
+            ) != 0
+        }
+    }
+
+    /// Release the GLX context from the calling thread
+    unsafe fn release(&self) -> bool {
+        unsafe { glx::glXMakeCurrent(self.display as *mut xlib::Display, 0, ptr::null_mut()) != 0 }
+    }
+}
+
+/// Look up an OpenGL function through GLX (Linux)
+#[cfg(target_os = "linux")]
+fn glx_proc_address(name: &CStr) -> *mut c_void {
+    unsafe {
+        match glx::glXGetProcAddressARB(name.as_ptr() as *const u8) {
+            Some(proc) => proc as *mut c_void,
+            None => ptr::null_mut(),
+        }
+    }
+}
+
 /// Errors that can occur during video playback
 #[derive(Debug)]
 pub enum VideoPlayerError {
@@ -107,6 +154,9 @@ struct PlaybackState {
     position_ns: AtomicU64,
     duration_ns: AtomicU64,
     paused: AtomicBool,
+    restarts: AtomicU64, // Playback restarts (finished seeks/loads) seen by the event loop
+    volume_bits: AtomicU64, // Volume (0-100) as f64 bits
+    muted: AtomicBool,
 }
 
 impl PlaybackState {
@@ -115,6 +165,9 @@ impl PlaybackState {
             position_ns: AtomicU64::new(0),
             duration_ns: AtomicU64::new(0),
             paused: AtomicBool::new(true),
+            restarts: AtomicU64::new(0),
+            volume_bits: AtomicU64::new(100.0f64.to_bits()),
+            muted: AtomicBool::new(false),
         }
     }
 
@@ -141,6 +194,52 @@ impl PlaybackState {
     fn is_paused(&self) -> bool {
         self.paused.load(Ordering::SeqCst)
     }
+
+    fn record_restart(&self) {
+        self.restarts.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn restart_count(&self) -> u64 {
+        self.restarts.load(Ordering::SeqCst)
+    }
+
+    fn set_volume(&self, volume: f64) {
+        self.volume_bits.store(volume.to_bits(), Ordering::SeqCst);
+    }
+
+    fn get_volume(&self) -> f64 {
+        f64::from_bits(self.volume_bits.load(Ordering::SeqCst))
+    }
+
+    fn set_muted(&self, muted: bool) {
+        self.muted.store(muted, Ordering::SeqCst);
+    }
+
+    fn is_muted(&self) -> bool {
+        self.muted.load(Ordering::SeqCst)
+    }
+}
+
+/// Size frames are drawn at until `VideoPlayer::set_render_size` changes it
+pub const DEFAULT_RENDER_SIZE: (u32, u32) = (960, 540);
+
+/// Extra view of the video (a duplicate window), keyed by its platform surface
+///
+/// mpv allows one render context per player, so views aren't separate
+/// contexts: the render thread draws each one into its own FBO after the
+/// main frame, at the view's own size.
+struct RenderView {
+    surface: usize,
+    render_size: (u32, u32),
+    frame: (Arc<Vec<u8>>, (u32, u32)), // Last frame drawn for this view and its size
+}
+
+/// GL objects the render thread keeps for one `RenderView`
+struct ViewTarget {
+    surface: usize,
+    fbo_id: u32,
+    texture_id: u32,
+    allocated_size: (u32, u32),
 }
 
 /// Video player using libmpv
@@ -158,7 +257,9 @@ pub struct VideoPlayer {
     texture_id: Option<u32>,
     video_width: u32,
     video_height: u32,
-    frame_buffer: Arc<Mutex<Arc<Vec<u8>>>>,
+    render_size: Arc<Mutex<(u32, u32)>>, // Size the render thread draws the next frame at
+    frame_buffer: Arc<Mutex<(Arc<Vec<u8>>, (u32, u32))>>, // Last frame and its size
+    views: Arc<Mutex<Vec<RenderView>>>,  // Extra views drawn from the same frame
 }
 
 impl VideoPlayer {
@@ -175,9 +276,8 @@ impl VideoPlayer {
             Self::set_option_string(handle, "idle", "yes"); // Keep mpv running
             Self::set_option_string(handle, "keep-open", "yes"); // Keep file open at end
 
-            // Default video dimensions (will be updated when window is set)
-            let video_width = 960;
-            let video_height = 540;
+            // Default render surface size (see `set_render_size`)
+            let (video_width, video_height) = DEFAULT_RENDER_SIZE;
             let buffer_size = (video_width * video_height * 4) as usize; // RGBA
 
             Self {
@@ -193,7 +293,12 @@ impl VideoPlayer {
                 texture_id: None,
                 video_width,
                 video_height,
-                frame_buffer: Arc::new(Mutex::new(Arc::new(vec![0u8; buffer_size]))),
+                render_size: Arc::new(Mutex::new(DEFAULT_RENDER_SIZE)),
+                frame_buffer: Arc::new(Mutex::new((
+                    Arc::new(vec![0u8; buffer_size]),
+                    DEFAULT_RENDER_SIZE,
+                ))),
+                views: Arc::new(Mutex::new(Vec::new())),
             }
         }
     }
@@ -221,14 +326,17 @@ impl VideoPlayer {
             self.observe_property("time-pos", mpv_format_MPV_FORMAT_DOUBLE)?;
             self.observe_property("duration", mpv_format_MPV_FORMAT_DOUBLE)?;
             self.observe_property("pause", mpv_format_MPV_FORMAT_FLAG)?;
+            self.observe_property("volume", mpv_format_MPV_FORMAT_DOUBLE)?;
+            self.observe_property("mute", mpv_format_MPV_FORMAT_FLAG)?;
 
             // Start event loop thread
             let handle = SendMpvHandle(self.mpv_handle.0);
             let state = Arc::clone(&self.state);
             let shutdown = Arc::clone(&self.shutdown);
+            let needs_render = Arc::clone(&self.needs_render);
 
             let event_thread = thread::spawn(move || {
-                Self::event_loop(handle, state, shutdown);
+                Self::event_loop(handle, state, shutdown, needs_render);
             });
 
             self.event_thread = Some(event_thread);
@@ -256,7 +364,12 @@ impl VideoPlayer {
     }
 
     /// Event loop that runs in a separate thread
-    fn event_loop(handle: SendMpvHandle, state: Arc<PlaybackState>, shutdown: Arc<AtomicBool>) {
+    fn event_loop(
+        handle: SendMpvHandle,
+        state: Arc<PlaybackState>,
+        shutdown: Arc<AtomicBool>,
+        needs_render: Arc<AtomicBool>,
+    ) {
         unsafe {
             loop {
                 if shutdown.load(Ordering::SeqCst) {
@@ -301,15 +414,39 @@ impl VideoPlayer {
                                         state.set_paused(paused);
                                     }
                                 }
+                                "volume" => {
+                                    if prop.format == mpv_format_MPV_FORMAT_DOUBLE
+                                        && !prop.data.is_null()
+                                    {
+                                        state.set_volume(*(prop.data as *const f64));
+                                    }
+                                }
+                                "mute" => {
+                                    if prop.format == mpv_format_MPV_FORMAT_FLAG
+                                        && !prop.data.is_null()
+                                    {
+                                        state.set_muted(*(prop.data as *const c_int) != 0);
+                                    }
+                                }
                                 _ => {}
                             }
                         }
                     }
+                    mpv_event_id_MPV_EVENT_PLAYBACK_RESTART => {
+                        // A seek finished. While paused mpv may not ask for a redraw,
+                        // so force one to show the frame at the new position
+                        state.record_restart();
+                        if state.is_paused() {
+                            needs_render.store(true, Ordering::SeqCst);
+                        }
+                    }
                     mpv_event_id_MPV_EVENT_SHUTDOWN => {
                         println!("VideoPlayer: Received shutdown event");
                         break;
                     }
                     mpv_event_id_MPV_EVENT_END_FILE => {
+                        // Not sent for `loop-file` restarts: mpv seeks back to the start
+                        // itself, so time-pos (and the scrubber) return to 0 on their own
                         println!("VideoPlayer: End of file");
                     }
                     _ => {}
@@ -613,6 +750,148 @@ impl VideoPlayer {
         }
     }
 
+    /// Set the native window handle and create OpenGL context (Linux/X11)
+    #[cfg(target_os = "linux")]
+    pub fn set_window_handle(&mut self, handle: usize) {
+        unsafe {
+            let window = handle as xlib::Window;
+            println!(
+                "VideoPlayer: Setting up OpenGL 3.2 context for X11 window: 0x{:x}",
+                window
+            );
+
+            // Use our own connection so the render thread doesn't share GPUI's
+            let display = xlib::XOpenDisplay(ptr::null());
+            if display.is_null() {
+                eprintln!("Failed to open X display");
+                return;
+            }
+            let screen = xlib::XDefaultScreen(display);
+
+            // Find a framebuffer config matching the window's visual
+            let fb_attrs: [i32; 13] = [
+                glx::GLX_X_RENDERABLE,
+                1,
+                glx::GLX_DRAWABLE_TYPE,
+                glx::GLX_WINDOW_BIT,
+                glx::GLX_RENDER_TYPE,
+                glx::GLX_RGBA_BIT,
+                glx::GLX_DOUBLEBUFFER,
+                1,
+                glx::GLX_DEPTH_SIZE,
+                24,
+                glx::GLX_ALPHA_SIZE,
+                8,
+                0, // Null terminator
+            ];
+            let mut config_count = 0;
+            let configs =
+                glx::glXChooseFBConfig(display, screen, fb_attrs.as_ptr(), &mut config_count);
+            if configs.is_null() || config_count == 0 {
+                eprintln!("No matching GLX framebuffer config");
+                xlib::XCloseDisplay(display);
+                return;
+            }
+
+            let mut window_attrs: xlib::XWindowAttributes = std::mem::zeroed();
+            xlib::XGetWindowAttributes(display, window, &mut window_attrs);
+            let window_visual = xlib::XVisualIDFromVisual(window_attrs.visual);
+
+            let configs_slice = std::slice::from_raw_parts(configs, config_count as usize);
+            let config = configs_slice
+                .iter()
+                .copied()
+                .find(|&config| {
+                    let mut visual_id = 0;
+                    glx::glXGetFBConfigAttrib(display, config, glx::GLX_VISUAL_ID, &mut visual_id);
+                    visual_id as xlib::VisualID == window_visual
+                })
+                .unwrap_or(configs_slice[0]);
+            xlib::XFree(configs as *mut _);
+
+            // Try to create OpenGL 3.2 Core Profile context if available
+            type GlxCreateContextAttribsARB = unsafe extern "C" fn(
+                display: *mut xlib::Display,
+                config: glx::GLXFBConfig,
+                share_context: glx::GLXContext,
+                direct: xlib::Bool,
+                attrib_list: *const i32,
+            ) -> glx::GLXContext;
+
+            let create_context_attribs = glx_proc_address(c"glXCreateContextAttribsARB");
+            let mut context: glx::GLXContext = ptr::null_mut();
+            if create_context_attribs.is_null() {
+                eprintln!("glXCreateContextAttribsARB not available - using legacy context");
+            } else {
+                let create_fn: GlxCreateContextAttribsARB =
+                    std::mem::transmute(create_context_attribs);
+                let attribs = [
+                    glx::arb::GLX_CONTEXT_MAJOR_VERSION_ARB,
+                    3,
+                    glx::arb::GLX_CONTEXT_MINOR_VERSION_ARB,
+                    2,
+                    glx::arb::GLX_CONTEXT_PROFILE_MASK_ARB,
+                    glx::arb::GLX_CONTEXT_CORE_PROFILE_BIT_ARB,
+                    0, // Null terminator
+                ];
+                context = create_fn(display, config, ptr::null_mut(), 1, attribs.as_ptr());
+                if context.is_null() {
+                    eprintln!("Failed to create OpenGL 3.2 context, falling back to legacy");
+                } else {
+                    println!("OpenGL 3.2 Core context created successfully");
+                }
+            }
+            if context.is_null() {
+                context = glx::glXCreateNewContext(
+                    display,
+                    config,
+                    glx::GLX_RGBA_TYPE,
+                    ptr::null_mut(),
+                    1,
+                );
+            }
+            if context.is_null() {
+                eprintln!("Failed to create GLX context");
+                xlib::XCloseDisplay(display);
+                return;
+            }
+
+            let gl_ctx = SendGLContext {
+                display: display as usize,
+                window,
+                context: context as usize,
+            };
+            if !gl_ctx.make_current() {
+                eprintln!("Failed to make OpenGL context current");
+                glx::glXDestroyContext(display, context);
+                xlib::XCloseDisplay(display);
+                return;
+            }
+
+            // Load OpenGL function pointers before releasing context
+            gl::load_with(|name| {
+                let name_c = CString::new(name).unwrap();
+                glx_proc_address(&name_c) as *const std::ffi::c_void
+            });
+
+            println!("VideoPlayer: OpenGL functions loaded");
+
+            // Create FBO and texture for off-screen rendering
+            self.create_fbo();
+
+            // Release the context from the main thread so the render thread can use it
+            if !gl_ctx.release() {
+                eprintln!("Warning: Failed to release OpenGL context from main thread");
+            }
+
+            self.gl_context = Some(gl_ctx);
+
+            println!("VideoPlayer: OpenGL context ready for mpv");
+
+            // Don't create render context here - wait until mpv is initialized in load_file()
+        }
+    }
+
     /// Create FBO and texture for off-screen rendering
     fn create_fbo(&mut self) {
         unsafe {
@@ -621,6 +900,20 @@ impl VideoPlayer {
                 self.video_width, self.video_height
             );
 
+            let (fbo, texture) = Self::create_render_target((self.video_width, self.video_height));
+            println!(
+                "VideoPlayer: FBO created successfully (ID: {}, Texture: {})",
+                fbo, texture
+            );
+
+            self.fbo_id = Some(fbo);
+            self.texture_id = Some(texture);
+        }
+    }
+
+    /// Create an FBO with a texture of the given size attached (context current)
+    unsafe fn create_render_target(size: (u32, u32)) -> (u32, u32) {
+        unsafe {
             // Generate framebuffer
             let mut fbo: u32 = 0;
             gl::GenFramebuffers(1, &mut fbo);
@@ -636,8 +929,8 @@ impl VideoPlayer {
                 gl::TEXTURE_2D,
                 0,
                 gl::RGBA as i32,
-                self.video_width as i32,
-                self.video_height as i32,
+                size.0 as i32,
+                size.1 as i32,
                 0,
                 gl::RGBA,
                 gl::UNSIGNED_BYTE,
@@ -662,18 +955,142 @@ impl VideoPlayer {
             let status = gl::CheckFramebufferStatus(gl::FRAMEBUFFER);
             if status != gl::FRAMEBUFFER_COMPLETE {
                 eprintln!("VideoPlayer: FBO is not complete! Status: 0x{:X}", status);
-            } else {
-                println!(
-                    "VideoPlayer: FBO created successfully (ID: {}, Texture: {})",
-                    fbo, texture
-                );
             }
 
             // Unbind FBO
             gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
 
-            self.fbo_id = Some(fbo);
-            self.texture_id = Some(texture);
+            (fbo, texture)
+        }
+    }
+
+    /// Size to draw the next frame at, reallocating the FBO texture first when
+    /// `set_render_size` asked for a different one (render thread, context current)
+    unsafe fn sync_fbo_size(
+        texture_id: u32,
+        render_size: &Mutex<(u32, u32)>,
+        allocated_size: &mut (u32, u32),
+    ) -> (u32, u32) {
+        let size = render_size.lock().map(|size| *size).unwrap_or(*allocated_size);
+        if size != *allocated_size {
+            unsafe { Self::allocate_texture(texture_id, size) };
+            println!("VideoPlayer: Render surface resized to {}x{}", size.0, size.1);
+            *allocated_size = size;
+        }
+        size
+    }
+
+    /// Reallocate an FBO texture at a new size (render thread, context current)
+    unsafe fn allocate_texture(texture_id: u32, size: (u32, u32)) {
+        unsafe {
+            gl::BindTexture(gl::TEXTURE_2D, texture_id);
+            gl::TexImage2D(
+                gl::TEXTURE_2D,
+                0,
+                gl::RGBA as i32,
+                size.0 as i32,
+                size.1 as i32,
+                0,
+                gl::RGBA,
+                gl::UNSIGNED_BYTE,
+                std::ptr::null(),
+            );
+            gl::BindTexture(gl::TEXTURE_2D, 0);
+        }
+    }
+
+    /// Draw the current frame into each added view's own FBO and read it back
+    /// (render thread, context current, after the main frame)
+    unsafe fn render_views(
+        render_ctx: &SendMpvRenderContext,
+        views: &Mutex<Vec<RenderView>>,
+        targets: &mut Vec<ViewTarget>,
+    ) {
+        let Ok(sizes) = views.lock().map(|views| {
+            views
+                .iter()
+                .map(|view| (view.surface, view.render_size))
+                .collect::<Vec<_>>()
+        }) else {
+            return;
+        };
+
+        // Free the targets of views that were removed
+        targets.retain(|target| {
+            let kept = sizes.iter().any(|(surface, _)| *surface == target.surface);
+            if !kept {
+                unsafe {
+                    gl::DeleteFramebuffers(1, &target.fbo_id);
+                    gl::DeleteTextures(1, &target.texture_id);
+                }
+            }
+            kept
+        });
+
+        for (surface, size) in sizes {
+            let index = match targets.iter().position(|target| target.surface == surface) {
+                Some(index) => index,
+                None => {
+                    let (fbo_id, texture_id) = unsafe { Self::create_render_target(size) };
+                    targets.push(ViewTarget {
+                        surface,
+                        fbo_id,
+                        texture_id,
+                        allocated_size: size,
+                    });
+                    targets.len() - 1
+                }
+            };
+            let target = &mut targets[index];
+            if target.allocated_size != size {
+                unsafe { Self::allocate_texture(target.texture_id, size) };
+                target.allocated_size = size;
+            }
+
+            let opengl_fbo = mpv_opengl_fbo {
+                fbo: target.fbo_id as i32,
+                w: size.0 as i32,
+                h: size.1 as i32,
+                internal_format: 0,
+            };
+            let mut render_params: Vec<mpv_render_param> = vec![
+                mpv_render_param {
+                    type_: mpv_render_param_type_MPV_RENDER_PARAM_OPENGL_FBO,
+                    data: &opengl_fbo as *const _ as *mut c_void,
+                },
+                mpv_render_param {
+                    type_: mpv_render_param_type_MPV_RENDER_PARAM_FLIP_Y,
+                    data: &0i32 as *const _ as *mut c_void, // Don't flip for FBO
+                },
+                mpv_render_param {
+                    type_: mpv_render_param_type_MPV_RENDER_PARAM_INVALID,
+                    data: ptr::null_mut(),
+                },
+            ];
+
+            let mut buffer = vec![0u8; (size.0 * size.1 * 4) as usize];
+            unsafe {
+                mpv_render_context_render(render_ctx.0, render_params.as_mut_ptr());
+
+                gl::BindFramebuffer(gl::FRAMEBUFFER, target.fbo_id);
+                gl::ReadPixels(
+                    0,
+                    0,
+                    size.0 as i32,
+                    size.1 as i32,
+                    gl::BGRA,
+                    gl::UNSIGNED_BYTE,
+                    buffer.as_mut_ptr() as *mut std::ffi::c_void,
+                );
+                gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+            }
+
+            let Ok(mut latest) = views.lock() else {
+                continue;
+            };
+            if let Some(view) = latest.iter_mut().find(|view| view.surface == surface) {
+                view.frame = (Arc::new(buffer), size);
+            }
         }
     }
 
@@ -741,9 +1158,9 @@ impl VideoPlayer {
                         ptr::null_mut()
                     }
 
-                    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+                    #[cfg(target_os = "linux")]
                     {
-                        ptr::null_mut()
+                        glx_proc_address(&symbol_name_cstring)
                     }
                 }
             }
@@ -795,6 +1212,17 @@ impl VideoPlayer {
                 println!("Temporarily made OpenGL context current for mpv initialization");
             }
 
+            // On Linux, the context was released in set_window_handle(), so make it current again
+            #[cfg(target_os = "linux")]
+            {
+                let gl_ctx = self.gl_context.as_ref().unwrap();
+                if !gl_ctx.make_current() {
+                    eprintln!("Failed to make context current for render context creation");
+                    return;
+                }
+                println!("Temporarily made OpenGL context current for mpv initialization");
+            }
+
             let mut render_context: *mut mpv_render_context = ptr::null_mut();
             let ret = mpv_render_context_create(
                 &mut render_context,
@@ -824,6 +1252,16 @@ impl VideoPlayer {
                 }
             }
 
+            // On Linux, release the context so the render thread can use it
+            #[cfg(target_os = "linux")]
+            {
+                if self.gl_context.as_ref().unwrap().release() {
+                    println!("Released OpenGL context after mpv initialization");
+                } else {
+                    eprintln!("Warning: Failed to release context after render context creation");
+                }
+            }
+
             if ret < 0 {
                 eprintln!(
                     "Failed to create render context: {}",
@@ -859,9 +1297,12 @@ impl VideoPlayer {
             let fbo_id = self
                 .fbo_id
                 .expect("FBO must be created before starting render thread");
+            let texture_id = self
+                .texture_id
+                .expect("FBO must be created before starting render thread");
             let frame_buffer = Arc::clone(&self.frame_buffer);
-            let video_width = self.video_width;
-            let video_height = self.video_height;
+            let render_size = Arc::clone(&self.render_size);
+            let views = Arc::clone(&self.views);
 
             let render_thread = thread::spawn(move || {
                 Self::render_loop(
@@ -870,9 +1311,10 @@ impl VideoPlayer {
                     shutdown,
                     needs_render,
                     fbo_id,
+                    texture_id,
                     frame_buffer,
-                    video_width,
-                    video_height,
+                    render_size,
+                    views,
                 );
             });
 
@@ -888,13 +1330,19 @@ impl VideoPlayer {
         shutdown: Arc<AtomicBool>,
         needs_render: Arc<AtomicBool>,
         fbo_id: u32,
-        frame_buffer: Arc<Mutex<Arc<Vec<u8>>>>,
-        video_width: u32,
-        video_height: u32,
+        texture_id: u32,
+        frame_buffer: Arc<Mutex<(Arc<Vec<u8>>, (u32, u32))>>,
+        render_size: Arc<Mutex<(u32, u32)>>,
+        views: Arc<Mutex<Vec<RenderView>>>,
     ) {
         unsafe {
             let _pool = NSAutoreleasePool::new(nil);
             let mut frame_count = 0u64;
+            let mut allocated_size = frame_buffer
+                .lock()
+                .map(|frame| frame.1)
+                .unwrap_or(DEFAULT_RENDER_SIZE);
+            let mut view_targets: Vec<ViewTarget> = Vec::new();
             println!("VideoPlayer: macOS render loop started");
 
             // Make context current on this render thread at the start
@@ -915,6 +1363,9 @@ impl VideoPlayer {
                 // Context is already current on this thread, but we can ensure it here
                 let () = msg_send![gl_context.0, makeCurrentContext];
 
+                let (video_width, video_height) =
+                    Self::sync_fbo_size(texture_id, &render_size, &mut allocated_size);
+
                 // Set up render parameters - render to our custom FBO
                 let mut render_params: Vec<mpv_render_param> = vec![
                     mpv_render_param {
@@ -958,12 +1409,14 @@ impl VideoPlayer {
                 );
 
                 // Replace the Arc in the mutex (cheap Arc clone by GPUI instead of Vec clone)
-                if let Ok(mut buffer_arc) = frame_buffer.lock() {
-                    *buffer_arc = Arc::new(new_buffer);
+                if let Ok(mut frame) = frame_buffer.lock() {
+                    *frame = (Arc::new(new_buffer), (video_width, video_height));
                 }
 
                 gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
 
+                Self::render_views(&render_ctx, &views, &mut view_targets);
+
                 // No buffer swap needed - we're not rendering to screen
 
                 frame_count += 1;
@@ -990,12 +1443,18 @@ impl VideoPlayer {
         shutdown: Arc<AtomicBool>,
         needs_render: Arc<AtomicBool>,
         fbo_id: u32,
-        frame_buffer: Arc<Mutex<Arc<Vec<u8>>>>,
-        video_width: u32,
-        video_height: u32,
+        texture_id: u32,
+        frame_buffer: Arc<Mutex<(Arc<Vec<u8>>, (u32, u32))>>,
+        render_size: Arc<Mutex<(u32, u32)>>,
+        views: Arc<Mutex<Vec<RenderView>>>,
     ) {
         unsafe {
             let mut frame_count = 0u64;
+            let mut allocated_size = frame_buffer
+                .lock()
+                .map(|frame| frame.1)
+                .unwrap_or(DEFAULT_RENDER_SIZE);
+            let mut view_targets: Vec<ViewTarget> = Vec::new();
             println!("VideoPlayer: Windows render loop started");
 
             loop {
@@ -1018,6 +1477,9 @@ impl VideoPlayer {
                     continue;
                 }
 
+                let (video_width, video_height) =
+                    Self::sync_fbo_size(texture_id, &render_size, &mut allocated_size);
+
                 // Set up render parameters - render to our custom FBO
                 let opengl_fbo = mpv_opengl_fbo {
                     fbo: fbo_id as i32,
@@ -1064,12 +1526,14 @@ impl VideoPlayer {
                 );
 
                 // Replace the Arc in the mutex (cheap Arc clone by GPUI instead of Vec clone)
-                if let Ok(mut buffer_arc) = frame_buffer.lock() {
-                    *buffer_arc = Arc::new(new_buffer);
+                if let Ok(mut frame) = frame_buffer.lock() {
+                    *frame = (Arc::new(new_buffer), (video_width, video_height));
                 }
 
                 gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
 
+                Self::render_views(&render_ctx, &views, &mut view_targets);
+
                 // No buffer swap needed - we're not rendering to screen
 
                 frame_count += 1;
@@ -1088,6 +1552,120 @@ impl VideoPlayer {
         }
     }
 
+    /// Render loop that runs in a separate thread (Linux)
+    #[cfg(target_os = "linux")]
+    fn render_loop(
+        render_ctx: SendMpvRenderContext,
+        gl_context: SendGLContext,
+        shutdown: Arc<AtomicBool>,
+        needs_render: Arc<AtomicBool>,
+        fbo_id: u32,
+        texture_id: u32,
+        frame_buffer: Arc<Mutex<(Arc<Vec<u8>>, (u32, u32))>>,
+        render_size: Arc<Mutex<(u32, u32)>>,
+        views: Arc<Mutex<Vec<RenderView>>>,
+    ) {
+        unsafe {
+            let mut frame_count = 0u64;
+            let mut allocated_size = frame_buffer
+                .lock()
+                .map(|frame| frame.1)
+                .unwrap_or(DEFAULT_RENDER_SIZE);
+            let mut view_targets: Vec<ViewTarget> = Vec::new();
+            println!("VideoPlayer: Linux render loop started");
+
+            // Make context current on this render thread at the start
+            if !gl_context.make_current() {
+                eprintln!("Failed to make GLX context current on render thread");
+                return;
+            }
+            println!("OpenGL context made current on render thread");
+
+            loop {
+                if shutdown.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                // Wait for render flag or timeout
+                if !needs_render.swap(false, Ordering::SeqCst) {
+                    std::thread::sleep(std::time::Duration::from_millis(16)); // ~60 FPS
+                    continue;
+                }
+
+                let (video_width, video_height) =
+                    Self::sync_fbo_size(texture_id, &render_size, &mut allocated_size);
+
+                // Set up render parameters - render to our custom FBO
+                let opengl_fbo = mpv_opengl_fbo {
+                    fbo: fbo_id as i32,
+                    w: video_width as i32,
+                    h: video_height as i32,
+                    internal_format: 0,
+                };
+                let flip_y: i32 = 0; // Don't flip for FBO
+
+                let mut render_params: Vec<mpv_render_param> = vec![
+                    mpv_render_param {
+                        type_: mpv_render_param_type_MPV_RENDER_PARAM_OPENGL_FBO,
+                        data: &opengl_fbo as *const _ as *mut c_void,
+                    },
+                    mpv_render_param {
+                        type_: mpv_render_param_type_MPV_RENDER_PARAM_FLIP_Y,
+                        data: &flip_y as *const _ as *mut c_void,
+                    },
+                    mpv_render_param {
+                        type_: mpv_render_param_type_MPV_RENDER_PARAM_INVALID,
+                        data: ptr::null_mut(),
+                    },
+                ];
+
+                // Render to FBO
+                mpv_render_context_render(render_ctx.0, render_params.as_mut_ptr());
+
+                // Read pixels from FBO into frame buffer
+                gl::BindFramebuffer(gl::FRAMEBUFFER, fbo_id);
+
+                // Create a new buffer and read pixels into it, then wrap in Arc
+                let buffer_size = (video_width * video_height * 4) as usize;
+                let mut new_buffer = vec![0u8; buffer_size];
+
+                // Use BGRA format to match video color ordering
+                gl::ReadPixels(
+                    0,
+                    0,
+                    video_width as i32,
+                    video_height as i32,
+                    gl::BGRA,
+                    gl::UNSIGNED_BYTE,
+                    new_buffer.as_mut_ptr() as *mut std::ffi::c_void,
+                );
+
+                // Replace the Arc in the mutex (cheap Arc clone by GPUI instead of Vec clone)
+                if let Ok(mut frame) = frame_buffer.lock() {
+                    *frame = (Arc::new(new_buffer), (video_width, video_height));
+                }
+
+                gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+
+                Self::render_views(&render_ctx, &views, &mut view_targets);
+
+                frame_count += 1;
+                if frame_count % 60 == 0 {
+                    println!(
+                        "VideoPlayer: Rendered {} frames ({}x{})",
+                        frame_count, video_width, video_height
+                    );
+                }
+            }
+
+            gl_context.release();
+            println!(
+                "VideoPlayer: Render loop exiting (rendered {} frames)",
+                frame_count
+            );
+        }
+    }
+
     /// Load a video file
     pub fn load_file(&mut self, file_path: &str) -> Result<(), VideoPlayerError> {
         if !std::path::Path::new(file_path).exists() {
@@ -1142,7 +1720,11 @@ impl VideoPlayer {
         self.set_property_flag("pause", true)
     }
 
-    /// Stop playback
+    /// Stop playback and unload the file (mpv's `stop` command)
+    ///
+    /// With `idle=yes` mpv stays running with nothing loaded, so the position,
+    /// duration and tracks are gone until a file is loaded again. To rewind
+    /// and pause while keeping the file, use `ControlsWindow::stop` instead.
     pub fn stop(&self) -> Result<(), VideoPlayerError> {
         println!("VideoPlayer: Stopping playback");
         unsafe {
@@ -1156,6 +1738,34 @@ impl VideoPlayer {
         Ok(())
     }
 
+    /// Step forward one frame, leaving playback paused
+    pub fn frame_step_forward(&self) -> Result<(), VideoPlayerError> {
+        self.frame_step_command("frame-step")
+    }
+
+    /// Step back one frame, leaving playback paused
+    pub fn frame_step_backward(&self) -> Result<(), VideoPlayerError> {
+        self.frame_step_command("frame-back-step")
+    }
+
+    /// Run mpv's `frame-step` or `frame-back-step`; both pause playback
+    fn frame_step_command(&self, command: &str) -> Result<(), VideoPlayerError> {
+        println!("VideoPlayer: {}", command);
+        unsafe {
+            let cmd = CString::new(command).unwrap();
+            let mut args = [cmd.as_ptr(), ptr::null()];
+            let ret = mpv_command(self.mpv_handle.0, args.as_mut_ptr());
+            if ret < 0 {
+                return Err(VideoPlayerError::CommandError(Self::error_string(ret)));
+            }
+        }
+
+        // The new frame has to be drawn even though playback stays paused;
+        // time-pos updates through the property observer
+        self.needs_render.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
     /// Get current playback position and duration
     pub fn get_position_duration(&self) -> Option<(ClockTime, ClockTime)> {
         let position = ClockTime(self.state.get_position());
@@ -1168,15 +1778,119 @@ impl VideoPlayer {
         !self.state.is_paused()
     }
 
+    /// Set the playback volume (0-100)
+    pub fn set_volume(&self, volume: f64) -> Result<(), VideoPlayerError> {
+        let volume = volume.clamp(0.0, 100.0);
+        self.set_property_double("volume", volume)?;
+        self.state.set_volume(volume);
+        Ok(())
+    }
+
+    /// Set the playback brightness (-100..100, 0 = unchanged)
+    ///
+    /// Like the other picture adjustments this only changes playback; exports
+    /// are encoded from the source without it.
+    pub fn set_brightness(&self, value: i64) -> Result<(), VideoPlayerError> {
+        self.set_adjustment("brightness", value)
+    }
+
+    /// Set the playback contrast (-100..100, 0 = unchanged); not applied to exports
+    pub fn set_contrast(&self, value: i64) -> Result<(), VideoPlayerError> {
+        self.set_adjustment("contrast", value)
+    }
+
+    /// Set the playback saturation (-100..100, -100 = grayscale); not applied to exports
+    pub fn set_saturation(&self, value: i64) -> Result<(), VideoPlayerError> {
+        self.set_adjustment("saturation", value)
+    }
+
+    /// Set the playback gamma (-100..100, 0 = unchanged); not applied to exports
+    pub fn set_gamma(&self, value: i64) -> Result<(), VideoPlayerError> {
+        self.set_adjustment("gamma", value)
+    }
+
+    /// Set one of mpv's -100..100 picture adjustment properties
+    fn set_adjustment(&self, property: &str, value: i64) -> Result<(), VideoPlayerError> {
+        self.set_property_int(property, value.clamp(-100, 100))?;
+        // Show the change on a paused frame too
+        if self.state.is_paused() {
+            self.needs_render.store(true, Ordering::SeqCst);
+        }
+        Ok(())
+    }
+
+    /// Current playback volume (0-100), as last reported by mpv
+    pub fn volume(&self) -> f64 {
+        self.state.get_volume()
+    }
+
+    /// Mute or unmute audio
+    pub fn set_muted(&self, muted: bool) -> Result<(), VideoPlayerError> {
+        self.set_property_flag("mute", muted)?;
+        self.state.set_muted(muted);
+        Ok(())
+    }
+
+    /// Flip the mute state, returning whether audio is now muted
+    pub fn toggle_mute(&self) -> Result<bool, VideoPlayerError> {
+        let muted = !self.state.is_muted();
+        self.set_muted(muted)?;
+        Ok(muted)
+    }
+
+    /// Whether audio is muted, as last reported by mpv
+    pub fn is_muted(&self) -> bool {
+        self.state.is_muted()
+    }
+
+    /// Source video size in pixels (aspect corrected), once a video is loaded
+    pub fn source_dimensions(&self) -> Option<(u32, u32)> {
+        let width = self.get_property_int("dwidth").ok()?;
+        let height = self.get_property_int("dheight").ok()?;
+        (width > 0 && height > 0).then_some((width as u32, height as u32))
+    }
+
+    /// Zoom and pan the picture inside the render surface
+    ///
+    /// `zoom` is mpv's `video-zoom` (log2 scale, 0 = fit to the surface); the
+    /// pan values are fractions of the scaled video size.
+    pub fn set_video_zoom_pan(
+        &self,
+        zoom: f64,
+        pan_x: f64,
+        pan_y: f64,
+    ) -> Result<(), VideoPlayerError> {
+        self.set_property_double("video-zoom", zoom)?;
+        self.set_property_double("video-pan-x", pan_x)?;
+        self.set_property_double("video-pan-y", pan_y)?;
+        self.request_render();
+        Ok(())
+    }
+
+    /// Current playback speed (1.0 = normal)
+    pub fn speed(&self) -> f64 {
+        self.get_property_double("speed").unwrap_or(1.0)
+    }
+
     /// Seek to a specific position
     pub fn seek(&self, position: ClockTime) -> Result<(), VideoPlayerError> {
+        self.seek_with_flags(position, "absolute")
+    }
+
+    /// Seek to a position, always decoding up to that exact frame
+    pub fn seek_exact(&self, position: ClockTime) -> Result<(), VideoPlayerError> {
+        self.seek_with_flags(position, "absolute+exact")
+    }
+
+    /// Run mpv's `seek` command with the given flags
+    fn seek_with_flags(&self, position: ClockTime, flags: &str) -> Result<(), VideoPlayerError> {
         let pos_secs = position.seconds().unwrap_or(0.0);
         println!("VideoPlayer: Seeking to {:.2}s", pos_secs);
 
         unsafe {
             let cmd = CString::new("seek").unwrap();
             let pos = CString::new(format!("{}", pos_secs)).unwrap();
-            let absolute = CString::new("absolute").unwrap();
+            let absolute = CString::new(flags).unwrap();
             let mut args = [cmd.as_ptr(), pos.as_ptr(), absolute.as_ptr(), ptr::null()];
 
             let ret = mpv_command(self.mpv_handle.0, args.as_mut_ptr());
@@ -1184,9 +1898,125 @@ impl VideoPlayer {
                 return Err(VideoPlayerError::CommandError(Self::error_string(ret)));
             }
         }
+
+        // Request a render right away too; the playback-restart event renders
+        // again once the frame at the new position has been decoded
+        if self.state.is_paused() {
+            self.needs_render.store(true, Ordering::SeqCst);
+        }
+        Ok(())
+    }
+
+    /// Save the displayed frame as an image (format from the file extension)
+    ///
+    /// Uses mpv's `screenshot-to-file` in `subtitles` mode: the frame at the
+    /// source resolution, with subtitles only when they're being shown (`video`
+    /// mode would always leave them out).
+    pub fn screenshot(&self, output_path: &str) -> Result<(), VideoPlayerError> {
+        println!("VideoPlayer: Saving screenshot to {}", output_path);
+
+        unsafe {
+            let cmd = CString::new("screenshot-to-file").unwrap();
+            let path = CString::new(output_path)
+                .map_err(|_| VideoPlayerError::CommandError("Invalid output path".to_string()))?;
+            let mode = CString::new("subtitles").unwrap();
+            let mut args = [cmd.as_ptr(), path.as_ptr(), mode.as_ptr(), ptr::null()];
+
+            let ret = mpv_command(self.mpv_handle.0, args.as_mut_ptr());
+            if ret < 0 {
+                return Err(VideoPlayerError::CommandError(Self::error_string(ret)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Seek by `delta_secs` from the current position, staying within the video
+    pub fn seek_relative(&self, delta_secs: f64) -> Result<(), VideoPlayerError> {
+        let delta_secs = match self.get_position_duration() {
+            Some((position, duration)) => {
+                let position_secs = position.seconds().unwrap_or(0.0);
+                let duration_secs = duration.seconds().unwrap_or(0.0);
+                let max_secs = if duration_secs > 0.0 {
+                    duration_secs
+                } else {
+                    f64::MAX
+                };
+                (position_secs + delta_secs).clamp(0.0, max_secs) - position_secs
+            }
+            None => delta_secs,
+        };
+        println!("VideoPlayer: Seeking {:+.2}s", delta_secs);
+
+        unsafe {
+            let cmd = CString::new("seek").unwrap();
+            let delta = CString::new(format!("{}", delta_secs)).unwrap();
+            let relative = CString::new("relative").unwrap();
+            let mut args = [cmd.as_ptr(), delta.as_ptr(), relative.as_ptr(), ptr::null()];
+
+            let ret = mpv_command(self.mpv_handle.0, args.as_mut_ptr());
+            if ret < 0 {
+                return Err(VideoPlayerError::CommandError(Self::error_string(ret)));
+            }
+        }
+
+        if self.state.is_paused() {
+            self.needs_render.store(true, Ordering::SeqCst);
+        }
+        Ok(())
+    }
+
+    /// Chapters of the loaded file as (start time in seconds, title), from `chapter-list`
+    ///
+    /// Untitled chapters are labelled "Chapter N". Empty for files without chapters.
+    pub fn list_chapters(&self) -> Vec<(f64, String)> {
+        let count = self.get_property_int("chapter-list/count").unwrap_or(0);
+        (0..count)
+            .filter_map(|i| {
+                let time = self
+                    .get_property_double(&format!("chapter-list/{}/time", i))
+                    .ok()?;
+                let title = self
+                    .get_property_string(&format!("chapter-list/{}/title", i))
+                    .ok()
+                    .filter(|title| !title.is_empty())
+                    .unwrap_or_else(|| format!("Chapter {}", i + 1));
+                Some((time, title))
+            })
+            .collect()
+    }
+
+    /// Jump `delta` chapters forward (or backward when negative) via the `chapter` property
+    pub fn seek_chapter(&self, delta: i32) -> Result<(), VideoPlayerError> {
+        let count = self.get_property_int("chapter-list/count").unwrap_or(0);
+        if count == 0 {
+            return Err(VideoPlayerError::CommandError(
+                "File has no chapters".to_string(),
+            ));
+        }
+        // mpv reports -1 before the first chapter starts
+        let current = self.get_property_int("chapter").unwrap_or(-1);
+        let target = (current + delta as i64).clamp(0, count - 1);
+        println!("VideoPlayer: Seeking to chapter {}", target);
+        self.set_property_int("chapter", target)?;
+
+        if self.state.is_paused() {
+            self.needs_render.store(true, Ordering::SeqCst);
+        }
         Ok(())
     }
 
+    /// Restart the file from the beginning when it ends (mpv `loop-file`)
+    pub fn set_loop(&self, enabled: bool) -> Result<(), VideoPlayerError> {
+        println!("VideoPlayer: Looping {}", if enabled { "on" } else { "off" });
+        self.set_property_string("loop-file", if enabled { "inf" } else { "no" })
+    }
+
+    /// Turn mpv's deinterlacing filter on or off (for combed interlaced sources)
+    pub fn set_deinterlace(&self, enabled: bool) -> Result<(), VideoPlayerError> {
+        println!("VideoPlayer: Deinterlacing {}", if enabled { "on" } else { "off" });
+        self.set_property_string("deinterlace", if enabled { "yes" } else { "no" })
+    }
+
     /// Enable or disable subtitle display
     pub fn set_subtitle_display(
         &self,
@@ -1217,6 +2047,86 @@ impl VideoPlayer {
         }
     }
 
+    /// Audio tracks of the loaded file as (mpv track id, label), from `track-list`
+    ///
+    /// mpv numbers audio tracks from 1 in stream order. Empty until the file's
+    /// tracks have been read.
+    pub fn list_audio_tracks(&self) -> Vec<(i64, String)> {
+        let count = self.get_property_int("track-list/count").unwrap_or(0);
+        (0..count)
+            .filter(|i| {
+                self.get_property_string(&format!("track-list/{}/type", i))
+                    .is_ok_and(|track_type| track_type == "audio")
+            })
+            .filter_map(|i| {
+                let id = self.get_property_int(&format!("track-list/{}/id", i)).ok()?;
+                let title = self.get_property_string(&format!("track-list/{}/title", i)).ok();
+                let lang = self.get_property_string(&format!("track-list/{}/lang", i)).ok();
+                let label = match (title, lang) {
+                    (Some(title), Some(lang)) => format!("{}: {} ({})", id, title, lang),
+                    (Some(title), None) => format!("{}: {}", id, title),
+                    (None, Some(lang)) => format!("{}: {}", id, lang),
+                    (None, None) => format!("Track {}", id),
+                };
+                Some((id, label))
+            })
+            .collect()
+    }
+
+    /// Set audio track (mpv track id, 1-based)
+    pub fn set_audio_track(&self, track_index: i32) -> Result<(), VideoPlayerError> {
+        println!("VideoPlayer: Setting audio track to {}", track_index);
+        self.set_property_int("aid", track_index as i64)
+    }
+
+    /// mpv track id of a subtitle track, from `track-list`
+    ///
+    /// A file added with `sub-add` (or auto-loaded next to the video) is found by
+    /// its path; an embedded stream by its position among the file's own subtitle
+    /// tracks. None when mpv hasn't listed the track (yet).
+    pub fn subtitle_track_id(
+        &self,
+        embedded_index: usize,
+        external_path: Option<&str>,
+    ) -> Option<i64> {
+        let count = self.get_property_int("track-list/count").unwrap_or(0);
+        let same_file = |a: &str, b: &str| {
+            match (std::fs::canonicalize(a), std::fs::canonicalize(b)) {
+                (Ok(a), Ok(b)) => a == b,
+                _ => a == b,
+            }
+        };
+        let mut embedded_seen = 0;
+        for i in 0..count {
+            let is_sub = self
+                .get_property_string(&format!("track-list/{}/type", i))
+                .is_ok_and(|track_type| track_type == "sub");
+            if !is_sub {
+                continue;
+            }
+            let external = self
+                .get_property_string(&format!("track-list/{}/external", i))
+                .is_ok_and(|value| value == "yes");
+            let matches = match external_path {
+                Some(path) => {
+                    external
+                        && self
+                            .get_property_string(&format!("track-list/{}/external-filename", i))
+                            .is_ok_and(|filename| same_file(&filename, path))
+                }
+                None if external => false,
+                None => {
+                    embedded_seen += 1;
+                    embedded_seen == embedded_index + 1
+                }
+            };
+            if matches {
+                return self.get_property_int(&format!("track-list/{}/id", i)).ok();
+            }
+        }
+        None
+    }
+
     /// Set subtitle track
     pub fn set_subtitle_track(&self, track_index: i32) -> Result<(), VideoPlayerError> {
         println!("VideoPlayer: Setting subtitle track to {}", track_index);
@@ -1278,6 +2188,24 @@ impl VideoPlayer {
         Ok(track_count as i32)
     }
 
+    /// Add a subtitle file as a new track after the existing ones, without selecting it
+    pub fn add_subtitle_file(&self, file_path: &str) -> Result<(), VideoPlayerError> {
+        println!("VideoPlayer: Adding subtitle file {}", file_path);
+        unsafe {
+            let cmd = CString::new("sub-add").unwrap();
+            let path = CString::new(file_path)
+                .map_err(|e| VideoPlayerError::CommandError(e.to_string()))?;
+            let flags = CString::new("auto").unwrap();
+            let mut args = [cmd.as_ptr(), path.as_ptr(), flags.as_ptr(), ptr::null()];
+
+            let ret = mpv_command(self.mpv_handle.0, args.as_mut_ptr());
+            if ret < 0 {
+                return Err(VideoPlayerError::CommandError(Self::error_string(ret)));
+            }
+        }
+        Ok(())
+    }
+
     /// Remove all secondary subtitles (keeps only the original embedded subtitles)
     pub fn remove_custom_subtitles(&self) -> Result<(), VideoPlayerError> {
         println!("VideoPlayer: Removing custom subtitles");
@@ -1317,6 +2245,24 @@ impl VideoPlayer {
         }
     }
 
+    /// Get a double property value
+    fn get_property_double(&self, name: &str) -> Result<f64, VideoPlayerError> {
+        unsafe {
+            let name_c = CString::new(name).unwrap();
+            let mut value: f64 = 0.0;
+            let ret = mpv_get_property(
+                self.mpv_handle.0,
+                name_c.as_ptr(),
+                mpv_format_MPV_FORMAT_DOUBLE,
+                &mut value as *mut f64 as *mut c_void,
+            );
+            if ret < 0 {
+                return Err(VideoPlayerError::MpvError(Self::error_string(ret)));
+            }
+            Ok(value)
+        }
+    }
+
     /// Get a string property value
     fn get_property_string(&self, name: &str) -> Result<String, VideoPlayerError> {
         unsafe {
@@ -1340,6 +2286,19 @@ impl VideoPlayer {
         }
     }
 
+    /// Set subtitle delay in seconds (positive shows subtitles later)
+    pub fn set_subtitle_delay(&self, seconds: f64) -> Result<(), VideoPlayerError> {
+        println!("VideoPlayer: Setting subtitle delay to {}", seconds);
+        self.set_property_double("sub-delay", seconds)
+    }
+
+    /// Set the text encoding of subtitle files loaded from now on (None = auto-detect)
+    pub fn set_subtitle_codepage(&self, charset: Option<&str>) -> Result<(), VideoPlayerError> {
+        let codepage = charset.unwrap_or("auto");
+        println!("VideoPlayer: Setting subtitle codepage to {}", codepage);
+        self.set_property_string("sub-codepage", codepage)
+    }
+
     /// Set subtitle font family
     pub fn set_subtitle_font(&self, font_name: &str) -> Result<(), VideoPlayerError> {
         println!("VideoPlayer: Setting subtitle font to {}", font_name);
@@ -1370,6 +2329,97 @@ impl VideoPlayer {
         self.set_property_string("sub-color", color)
     }
 
+    /// Set the subtitle background box (opacity from 0.0 to 1.0)
+    pub fn set_subtitle_background(
+        &self,
+        enabled: bool,
+        opacity: f64,
+    ) -> Result<(), VideoPlayerError> {
+        println!(
+            "VideoPlayer: Setting subtitle background box to {} (opacity {:.2})",
+            enabled, opacity
+        );
+
+        // Older mpv versions don't have background-box and draw the box from sub-back-color alone
+        let border_style = if enabled {
+            "background-box"
+        } else {
+            "outline-and-shadow"
+        };
+        if let Err(e) = self.set_property_string("sub-border-style", border_style) {
+            eprintln!("VideoPlayer: sub-border-style not supported: {}", e);
+        }
+
+        // mpv colors are #AARRGGBB where FF alpha is opaque
+        let alpha = if enabled {
+            (opacity.clamp(0.0, 1.0) * 255.0).round() as u8
+        } else {
+            0
+        };
+        self.set_property_string("sub-back-color", &format!("#{:02X}000000", alpha))
+    }
+
+    /// Position subtitles on screen from a numpad-style ASS alignment
+    /// (7 8 9 top, 4 5 6 middle, 1 2 3 bottom)
+    pub fn set_subtitle_alignment(&self, alignment: u8) -> Result<(), VideoPlayerError> {
+        println!("VideoPlayer: Setting subtitle alignment to {}", alignment);
+        let alignment = alignment.clamp(1, 9);
+        let align_x = match alignment % 3 {
+            1 => "left",
+            2 => "center",
+            _ => "right",
+        };
+        let align_y = match alignment {
+            1..=3 => "bottom",
+            4..=6 => "center",
+            _ => "top",
+        };
+        self.set_property_string("sub-align-x", align_x)?;
+        self.set_property_string("sub-align-y", align_y)
+    }
+
+    /// Set the subtitle outline (hex color, thickness) and drop shadow
+    ///
+    /// The outline isn't drawn while the background box is on.
+    pub fn set_subtitle_outline(
+        &self,
+        color: &str,
+        width: f64,
+        shadow: bool,
+    ) -> Result<(), VideoPlayerError> {
+        println!(
+            "VideoPlayer: Setting subtitle outline to {} {:.1} (shadow {})",
+            color, width, shadow
+        );
+
+        // mpv 0.38 renamed sub-border-* to sub-outline-*; older versions only have the old names
+        if self.set_property_string("sub-outline-color", color).is_err() {
+            self.set_property_string("sub-border-color", color)?;
+        }
+        let width = width.max(0.0);
+        if self.set_property_double("sub-outline-size", width).is_err() {
+            self.set_property_double("sub-border-size", width)?;
+        }
+        self.set_property_double("sub-shadow-offset", if shadow { 2.0 } else { 0.0 })
+    }
+
+    /// Keep subtitles inside the title-safe region (inset from 0.0 to 0.2 of the frame)
+    pub fn set_subtitle_safe_area(
+        &self,
+        enabled: bool,
+        inset: f64,
+    ) -> Result<(), VideoPlayerError> {
+        println!(
+            "VideoPlayer: Setting subtitle title-safe region to {} (inset {:.2})",
+            enabled, inset
+        );
+
+        // mpv margins are in units of a 720p-high frame; 22/25 are mpv's defaults
+        let inset = if enabled { inset.clamp(0.0, 0.5) } else { 0.0 };
+        self.set_property_int("sub-margin-y", 22 + (720.0 * inset).round() as i64)?;
+        self.set_property_int("sub-margin-x", 25 + (1280.0 * inset).round() as i64)
+    }
+
     /// Get pipeline reference (compatibility - returns None for mpv)
     pub fn get_pipeline(&self) -> Option<()> {
         None
@@ -1459,13 +2509,112 @@ impl VideoPlayer {
     /// Get a reference to the frame buffer for rendering in GPUI
     pub fn get_frame_buffer(&self) -> Arc<Vec<u8>> {
         // Lock the mutex and clone the Arc (cheap), not the Vec (expensive)
+        self.frame_buffer.lock().unwrap().0.clone()
+    }
+
+    /// Get the last frame together with the size it was drawn at
+    pub fn get_frame(&self) -> (Arc<Vec<u8>>, (u32, u32)) {
         self.frame_buffer.lock().unwrap().clone()
     }
 
-    /// Get video dimensions
+    /// Get video dimensions (the size of the last frame drawn)
     pub fn get_video_dimensions(&self) -> (u32, u32) {
-        (self.video_width, self.video_height)
+        self.frame_buffer
+            .lock()
+            .map(|frame| frame.1)
+            .unwrap_or((self.video_width, self.video_height))
     }
+
+    /// Draw frames at this size from now on (redrawing the current one)
+    ///
+    /// The default is `DEFAULT_RENDER_SIZE`, scaled to fit when painted; the
+    /// 100% view renders at the screen size of the video area instead.
+    pub fn set_render_size(&self, width: u32, height: u32) {
+        let size = (width.max(1), height.max(1));
+        if let Ok(mut render_size) = self.render_size.lock() {
+            if *render_size != size {
+                *render_size = size;
+                self.needs_render.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+
+    /// Add a view drawn alongside the main frame, for another window's surface
+    ///
+    /// Views start at `DEFAULT_RENDER_SIZE`; `set_view_render_size` changes it.
+    pub fn add_view(&self, surface: usize) {
+        let (width, height) = DEFAULT_RENDER_SIZE;
+        let blank = Arc::new(vec![0u8; (width * height * 4) as usize]);
+        if let Ok(mut views) = self.views.lock() {
+            views.push(RenderView {
+                surface,
+                render_size: DEFAULT_RENDER_SIZE,
+                frame: (blank, DEFAULT_RENDER_SIZE),
+            });
+        }
+        self.needs_render.store(true, Ordering::SeqCst);
+    }
+
+    /// Stop drawing a view added with `add_view` (its FBO is freed on the next render)
+    pub fn remove_view(&self, surface: usize) {
+        if let Ok(mut views) = self.views.lock() {
+            views.retain(|view| view.surface != surface);
+        }
+        self.needs_render.store(true, Ordering::SeqCst);
+    }
+
+    /// Draw a view at this size from now on, like `set_render_size` for the main frame
+    pub fn set_view_render_size(&self, surface: usize, width: u32, height: u32) {
+        let size = (width.max(1), height.max(1));
+        let Ok(mut views) = self.views.lock() else {
+            return;
+        };
+        let Some(view) = views.iter_mut().find(|view| view.surface == surface) else {
+            return;
+        };
+        if view.render_size != size {
+            view.render_size = size;
+            self.needs_render.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// Get the last frame drawn for a view together with its size
+    pub fn get_view_frame(&self, surface: usize) -> Option<(Arc<Vec<u8>>, (u32, u32))> {
+        let views = self.views.lock().ok()?;
+        views
+            .iter()
+            .find(|view| view.surface == surface)
+            .map(|view| view.frame.clone())
+    }
+
+    /// Number of seeks (and loads) mpv has finished, for waiting on a seek to land
+    pub fn playback_restart_count(&self) -> u64 {
+        self.state.restart_count()
+    }
+
+    /// Ask the render thread to draw the current frame into the frame buffer again
+    pub fn request_render(&self) {
+        self.needs_render.store(true, Ordering::SeqCst);
+    }
+
+    /// Write the current frame buffer to an image file (format from the extension)
+    pub fn save_current_frame(&self, output_path: &str) -> Result<(), String> {
+        let (buffer, (width, height)) = self.get_frame();
+        let rgba = bgra_to_rgba(&buffer);
+        let image = image::RgbaImage::from_raw(width, height, rgba)
+            .ok_or_else(|| "Frame buffer does not match the video dimensions".to_string())?;
+        image
+            .save(output_path)
+            .map_err(|e| format!("Failed to write frame to {}: {}", output_path, e))
+    }
+}
+
+/// Convert the BGRA pixels read back from OpenGL into RGBA for image encoders
+fn bgra_to_rgba(buffer: &[u8]) -> Vec<u8> {
+    buffer
+        .chunks_exact(4)
+        .flat_map(|px| [px[2], px[1], px[0], px[3]])
+        .collect()
 }
 
 impl Drop for VideoPlayer {
@@ -1518,6 +2667,11 @@ impl Drop for VideoPlayer {
                     wglMakeCurrent(hdc, hglrc);
                 }
 
+                #[cfg(target_os = "linux")]
+                if let Some(ref gl_ctx) = self.gl_context {
+                    gl_ctx.make_current();
+                }
+
                 // Delete OpenGL resources
                 gl::DeleteFramebuffers(1, &fbo_id);
                 gl::DeleteTextures(1, &texture_id);
@@ -1551,6 +2705,16 @@ impl Drop for VideoPlayer {
                 ReleaseDC(Some(hwnd), hdc);
             }
         }
+
+        #[cfg(target_os = "linux")]
+        unsafe {
+            if let Some(gl_ctx) = self.gl_context.take() {
+                let display = gl_ctx.display as *mut xlib::Display;
+                gl_ctx.release();
+                glx::glXDestroyContext(display, gl_ctx.context as glx::GLXContext);
+                xlib::XCloseDisplay(display);
+            }
+        }
     }
 }
 